@@ -2,7 +2,7 @@
 
 use alloc::string::{String, ToString};
 use derive_more::derive::Display;
-use kona_derive::errors::{PipelineError, PipelineErrorKind};
+use kona_derive::errors::{PipelineError, PipelineErrorKind, ProviderErrorKind};
 use kona_mpt::OrderedListWalkerError;
 use kona_preimage::errors::PreimageOracleError;
 use op_alloy_protocol::{FromBlockError, OpBlockConversionError};
@@ -42,7 +42,16 @@ impl From<OracleProviderError> for PipelineErrorKind {
     fn from(val: OracleProviderError) -> Self {
         match val {
             OracleProviderError::BlockNumberPastHead(_, _) => PipelineError::EndOfSource.crit(),
-            _ => PipelineError::Provider(val.to_string()).crit(),
+            OracleProviderError::Rlp(_) |
+            OracleProviderError::SliceConversion(_) |
+            OracleProviderError::Serde(_) |
+            OracleProviderError::BlockInfo(_) |
+            OracleProviderError::OpBlockConversion(_) => {
+                PipelineError::Provider(ProviderErrorKind::Decode(val.to_string())).crit()
+            }
+            OracleProviderError::Preimage(_) | OracleProviderError::TrieWalker(_) => {
+                PipelineError::Provider(ProviderErrorKind::Other(val.to_string())).crit()
+            }
         }
     }
 }
@@ -85,14 +85,21 @@ fn main() -> Result<(), String> {
             io::exit(1);
         };
         let cfg = Arc::new(boot.rollup_config.clone());
-        let pipeline = OraclePipeline::new(
+        let pipeline = match OraclePipeline::new(
             cfg.clone(),
             cursor.clone(),
             oracle.clone(),
             beacon,
             l1_provider.clone(),
             l2_provider.clone(),
-        );
+        ) {
+            Ok(pipeline) => pipeline,
+            Err(e) => {
+                error!(target: "client", "Failed to build the derivation pipeline: {}", e);
+                io::print(&alloc::format!("Failed to build the derivation pipeline: {}\n", e));
+                io::exit(1);
+            }
+        };
         let executor = KonaExecutorConstructor::new(
             &cfg,
             l2_provider.clone(),
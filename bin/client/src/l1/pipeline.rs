@@ -1,11 +1,11 @@
 //! Contains an oracle-backed pipeline.
 
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{
     attributes::StatefulAttributesBuilder,
-    errors::PipelineErrorKind,
+    errors::{PipelineBuildError, PipelineErrorKind},
     pipeline::{DerivationPipeline, PipelineBuilder},
     sources::EthereumDataSource,
     stages::{
@@ -13,7 +13,7 @@ use kona_derive::{
         L1Retrieval, L1Traversal,
     },
     traits::{BlobProvider, OriginProvider, Pipeline, SignalReceiver},
-    types::{PipelineResult, Signal, StepResult},
+    types::{PipelineResult, PipelineStatus, Signal, SignalReceipt, StepResult},
 };
 use kona_driver::{DriverPipeline, PipelineCursor};
 use kona_preimage::CommsClient;
@@ -71,7 +71,7 @@ where
     O: CommsClient + FlushableCache + FlushableCache + Send + Sync + Debug,
     B: BlobProvider + Send + Sync + Debug + Clone,
 {
-    /// Constructs a new oracle-backed derivation pipeline.
+    /// Constructs a new oracle-backed derivation pipeline. Fails if the rollup config is invalid.
     pub fn new(
         cfg: Arc<RollupConfig>,
         sync_start: PipelineCursor,
@@ -79,7 +79,7 @@ where
         blob_provider: B,
         chain_provider: OracleL1ChainProvider<O>,
         l2_chain_provider: OracleL2ChainProvider<O>,
-    ) -> Self {
+    ) -> Result<Self, PipelineBuildError> {
         let attributes = StatefulAttributesBuilder::new(
             cfg.clone(),
             l2_chain_provider.clone(),
@@ -94,8 +94,8 @@ where
             .chain_provider(chain_provider)
             .builder(attributes)
             .origin(sync_start.origin())
-            .build();
-        Self { pipeline, caching_oracle }
+            .build()?;
+        Ok(Self { pipeline, caching_oracle })
     }
 }
 
@@ -117,7 +117,7 @@ where
     B: BlobProvider + Send + Sync + Debug + Clone,
 {
     /// Receives a signal from the driver.
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         self.pipeline.signal(signal).await
     }
 }
@@ -131,6 +131,16 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.pipeline.origin()
     }
+
+    /// Collects the L1 origins of the pipeline's stages.
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.pipeline.collect_stage_origins(origins);
+    }
+
+    /// Collects the buffered channels and batches of the pipeline's stages.
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.pipeline.collect_status(status);
+    }
 }
 
 impl<O, B> Iterator for OraclePipeline<O, B>
@@ -156,6 +166,26 @@ where
         self.pipeline.peek()
     }
 
+    /// Peeks at the [OpAttributesWithParent] `n` positions ahead in the pipeline.
+    fn peek_nth(&self, n: usize) -> Option<&OpAttributesWithParent> {
+        self.pipeline.peek_nth(n)
+    }
+
+    /// Returns the number of prepared [OpAttributesWithParent].
+    fn prepared_len(&self) -> usize {
+        self.pipeline.prepared_len()
+    }
+
+    /// Returns the L1 origin that the next [OpAttributesWithParent] were derived from.
+    fn derived_from(&self) -> Option<BlockInfo> {
+        self.pipeline.derived_from()
+    }
+
+    /// Returns the number of L1 blocks the pipeline origin is behind `l1_head`.
+    fn origin_lag(&self, l1_head: BlockInfo) -> u64 {
+        self.pipeline.origin_lag(l1_head)
+    }
+
     /// Attempts to progress the pipeline.
     async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
         self.pipeline.step(cursor).await
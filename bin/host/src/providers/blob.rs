@@ -99,22 +99,13 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
         // Calculate the slot for the given timestamp.
         let slot = Self::slot(genesis, interval, block_ref.timestamp)?;
 
-        // Fetch blob sidecars for the slot using the given blob hashes.
+        // Fetch all blob sidecars of the slot in a single request.
         let sidecars = self.fetch_sidecars(slot, blob_hashes).await?;
 
-        // Filter blob sidecars that match the indicies in the specified list.
-        let blob_hash_indicies = blob_hashes.iter().map(|b| b.index).collect::<Vec<usize>>();
-        let filtered = sidecars
-            .into_iter()
-            .filter(|s| blob_hash_indicies.contains(&(s.index as usize)))
-            .collect::<Vec<_>>();
-
-        // Validate the correct number of blob sidecars were retrieved.
-        if blob_hashes.len() != filtered.len() {
-            return Err(BlobProviderError::SidecarLengthMismatch(blob_hashes.len(), filtered.len()));
-        }
+        // Order the sidecars like the specified list, regardless of the response order.
+        let ordered = order_sidecars(sidecars, blob_hashes, |s| s.index)?;
 
-        Ok(filtered
+        Ok(ordered
             .into_iter()
             .map(|s| BlobTransactionSidecarItem {
                 index: s.index,
@@ -170,6 +161,30 @@ where
     }
 }
 
+/// Selects the sidecars matching the indices of `blob_hashes` and returns them in the order of
+/// `blob_hashes`.
+///
+/// Blob sidecar APIs may return the sidecars of a slot in any order, but the blobs must be
+/// validated against, and their frames processed in, the order of the blob hashes in the block.
+fn order_sidecars<T>(
+    mut sidecars: Vec<T>,
+    blob_hashes: &[IndexedBlobHash],
+    index: impl Fn(&T) -> u64,
+) -> Result<Vec<T>, BlobProviderError> {
+    let requested = |s: &T| blob_hashes.iter().any(|hash| hash.index as u64 == index(s));
+    let found = sidecars.iter().filter(|s| requested(s)).count();
+    let mismatch = BlobProviderError::SidecarLengthMismatch(blob_hashes.len(), found);
+
+    let mut ordered = Vec::with_capacity(blob_hashes.len());
+    for hash in blob_hashes {
+        let Some(position) = sidecars.iter().position(|s| index(s) == hash.index as u64) else {
+            return Err(mismatch);
+        };
+        ordered.push(sidecars.swap_remove(position));
+    }
+    Ok(ordered)
+}
+
 /// The minimal interface required to fetch sidecars from a remote blob store.
 #[async_trait]
 pub trait BlobSidecarProvider {
@@ -249,19 +264,10 @@ impl<B: BeaconClient, F: BlobSidecarProvider> OnlineBlobProviderWithFallback<B,
         // Fetch blob sidecars for the given block reference and blob hashes.
         let sidecars = fallback.beacon_blob_side_cars(slot, blob_hashes).await?;
 
-        // Filter blob sidecars that match the indicies in the specified list.
-        let blob_hash_indicies = blob_hashes.iter().map(|b| b.index).collect::<Vec<_>>();
-        let filtered = sidecars
-            .into_iter()
-            .filter(|s| blob_hash_indicies.contains(&(s.index as usize)))
-            .collect::<Vec<_>>();
-
-        // Validate the correct number of blob sidecars were retrieved.
-        if blob_hashes.len() != filtered.len() {
-            return Err(BlobProviderError::SidecarLengthMismatch(blob_hashes.len(), filtered.len()));
-        }
+        // Order the sidecars like the specified list, regardless of the response order.
+        let ordered = order_sidecars(sidecars, blob_hashes, |s| s.index)?;
 
-        Ok(filtered
+        Ok(ordered
             .into_iter()
             .map(|s| BlobTransactionSidecarItem {
                 index: s.index,
@@ -418,3 +424,132 @@ impl<B: BeaconClient, F: BlobSidecarProvider> From<OnlineBlobProviderBuilder<B,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::beacon::{APIConfigResponse, APIGenesisResponse};
+    use alloy_primitives::B256;
+    use std::sync::Mutex;
+
+    fn indexed_hashes(indices: &[usize]) -> Vec<IndexedBlobHash> {
+        indices
+            .iter()
+            .map(|&index| IndexedBlobHash { hash: B256::with_last_byte(index as u8), index })
+            .collect()
+    }
+
+    /// A [BeaconClient] with a genesis time of 100 and 12 second slots, which serves no blob
+    /// sidecars and records the slots they were requested for.
+    #[derive(Debug, Default)]
+    struct MockBeaconClient {
+        /// Whether requests for blob sidecars fail, e.g. because the blobs expired.
+        unavailable: bool,
+        /// The slots blob sidecars were requested for.
+        requested_slots: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl BeaconClient for MockBeaconClient {
+        type Error = String;
+
+        async fn config_spec(&self) -> Result<APIConfigResponse, Self::Error> {
+            Ok(APIConfigResponse::new(12))
+        }
+
+        async fn beacon_genesis(&self) -> Result<APIGenesisResponse, Self::Error> {
+            Ok(APIGenesisResponse::new(100))
+        }
+
+        async fn beacon_blob_side_cars(
+            &self,
+            slot: u64,
+            _: &[IndexedBlobHash],
+        ) -> Result<Vec<BlobData>, Self::Error> {
+            self.requested_slots.lock().unwrap().push(slot);
+            if self.unavailable {
+                return Err("blobs unavailable".to_string());
+            }
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_slot() {
+        type Provider = OnlineBlobProvider<MockBeaconClient>;
+        assert_eq!(Provider::slot(100, 12, 100).unwrap(), 0);
+        assert_eq!(Provider::slot(100, 12, 111).unwrap(), 0);
+        assert_eq!(Provider::slot(100, 12, 160).unwrap(), 5);
+        assert!(matches!(Provider::slot(100, 12, 99), Err(BlobProviderError::SlotDerivation)));
+    }
+
+    #[tokio::test]
+    async fn test_load_configs() {
+        let mut provider = OnlineBlobProvider::new(MockBeaconClient::default(), None, None);
+        provider.load_configs().await.unwrap();
+        assert_eq!((provider.genesis_time, provider.slot_interval), (Some(100), Some(12)));
+
+        // Provided values are not overridden by the beacon node.
+        let mut provider = OnlineBlobProvider::new(MockBeaconClient::default(), Some(5), Some(2));
+        provider.load_configs().await.unwrap();
+        assert_eq!((provider.genesis_time, provider.slot_interval), (Some(5), Some(2)));
+    }
+
+    #[tokio::test]
+    async fn test_get_blobs_missing() {
+        let mut provider = OnlineBlobProvider::new(MockBeaconClient::default(), None, None);
+        let block_ref = BlockInfo { timestamp: 160, ..Default::default() };
+        let err = provider.get_blobs(&block_ref, &indexed_hashes(&[0, 1])).await.unwrap_err();
+        assert!(matches!(err, BlobProviderError::SidecarLengthMismatch(2, 0)));
+        assert_eq!(*provider.beacon_client.requested_slots.lock().unwrap(), vec![5]);
+    }
+
+    #[tokio::test]
+    async fn test_get_blobs_archive_fallback() {
+        let primary = MockBeaconClient { unavailable: true, ..Default::default() };
+        let mut provider = OnlineBlobProviderBuilder::new()
+            .with_beacon_client(primary)
+            .with_fallback_provider(MockBeaconClient::default())
+            .build();
+        let block_ref = BlockInfo { timestamp: 160, ..Default::default() };
+
+        // The archive is queried for the same slot once the primary fails.
+        let err = provider.get_blobs(&block_ref, &indexed_hashes(&[0])).await.unwrap_err();
+        assert!(matches!(err, BlobProviderError::SidecarLengthMismatch(1, 0)));
+        let fallback = provider.fallback.as_ref().unwrap();
+        assert_eq!(*fallback.requested_slots.lock().unwrap(), vec![5]);
+    }
+
+    #[tokio::test]
+    async fn test_get_blobs_without_fallback() {
+        let primary = MockBeaconClient { unavailable: true, ..Default::default() };
+        let mut provider = OnlineBlobProviderBuilder::<_, MockBeaconClient>::new()
+            .with_beacon_client(primary)
+            .build();
+        let block_ref = BlockInfo { timestamp: 160, ..Default::default() };
+        let err = provider.get_blobs(&block_ref, &indexed_hashes(&[0])).await.unwrap_err();
+        assert!(matches!(err, BlobProviderError::Backend(_)));
+    }
+
+    #[test]
+    fn test_order_sidecars_out_of_order_response() {
+        let hashes = indexed_hashes(&[1, 2, 4, 5]);
+        let response = vec![(5, "e"), (0, "z"), (2, "b"), (4, "d"), (1, "a")];
+        let ordered = order_sidecars(response, &hashes, |s| s.0).unwrap();
+        assert_eq!(ordered, vec![(1, "a"), (2, "b"), (4, "d"), (5, "e")]);
+
+        // The assembled order matches the order of an in-order response.
+        let in_order = vec![(1, "a"), (2, "b"), (4, "d"), (5, "e")];
+        assert_eq!(order_sidecars(in_order, &hashes, |s| s.0).unwrap(), ordered);
+    }
+
+    #[test]
+    fn test_order_sidecars_missing_sidecar() {
+        let hashes = indexed_hashes(&[1, 2, 3]);
+        let response = vec![(3, "c"), (1, "a")];
+        assert!(matches!(
+            order_sidecars(response, &hashes, |s| s.0),
+            Err(BlobProviderError::SidecarLengthMismatch(3, 2))
+        ));
+    }
+}
@@ -4,3 +4,9 @@
 
 mod stateful;
 pub use stateful::StatefulAttributesBuilder;
+
+mod upgrades;
+pub use upgrades::upgrade_transactions;
+
+mod validate;
+pub use validate::validate_attributes;
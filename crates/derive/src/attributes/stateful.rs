@@ -1,21 +1,23 @@
 //! The [`AttributesBuilder`] and it's default implementation.
 
 use crate::{
+    attributes::upgrade_transactions,
     errors::{BuilderError, PipelineEncodingError, PipelineError, PipelineErrorKind},
-    traits::{AttributesBuilder, ChainProvider, L2ChainProvider},
-    types::PipelineResult,
+    traits::{AttributesBuilder, ChainProvider, DepositFilter, L2ChainProvider, SequencerPolicy},
+    types::{EpochCache, PipelineResult, SequencerOverrides},
 };
 use alloc::{boxed::Box, fmt::Debug, string::ToString, sync::Arc, vec, vec::Vec};
-use alloy_consensus::{Eip658Value, Receipt};
+use alloy_consensus::{Eip658Value, Header, Receipt};
 use alloy_eips::{eip2718::Encodable2718, BlockNumHash};
 use alloy_primitives::{address, Address, Bytes, B256};
-use alloy_rlp::Encodable;
+use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_engine::PayloadAttributes;
 use async_trait::async_trait;
-use op_alloy_consensus::Hardforks;
+use op_alloy_consensus::{OpTxType, TxDeposit};
 use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::{decode_deposit, L1BlockInfoTx, L2BlockInfo, DEPOSIT_EVENT_ABI_HASH};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use tracing::info;
 
 /// The sequencer fee vault address.
 const SEQUENCER_FEE_VAULT_ADDRESS: Address = address!("4200000000000000000000000000000000000011");
@@ -33,6 +35,12 @@ where
     config_fetcher: L2P,
     /// The L1 receipts fetcher.
     receipts_fetcher: L1P,
+    /// The shared cache of epoch headers.
+    epoch_cache: Option<EpochCache>,
+    /// The filter applied to derived user deposits.
+    deposit_filter: Option<Arc<dyn DepositFilter>>,
+    /// The policy applied to the overridable fields of the attributes.
+    sequencer_policy: Option<Arc<dyn SequencerPolicy>>,
 }
 
 impl<L1P, L2P> StatefulAttributesBuilder<L1P, L2P>
@@ -42,7 +50,45 @@ where
 {
     /// Create a new [StatefulAttributesBuilder] with the given epoch.
     pub const fn new(rcfg: Arc<RollupConfig>, sys_cfg_fetcher: L2P, receipts: L1P) -> Self {
-        Self { rollup_cfg: rcfg, config_fetcher: sys_cfg_fetcher, receipts_fetcher: receipts }
+        Self {
+            rollup_cfg: rcfg,
+            config_fetcher: sys_cfg_fetcher,
+            receipts_fetcher: receipts,
+            epoch_cache: None,
+            deposit_filter: None,
+            sequencer_policy: None,
+        }
+    }
+
+    /// Sets the [EpochCache] used to look up epoch headers before fetching them.
+    pub fn with_epoch_cache(mut self, epoch_cache: EpochCache) -> Self {
+        self.epoch_cache = Some(epoch_cache);
+        self
+    }
+
+    /// Sets the [DepositFilter] applied to the user deposits derived from each epoch.
+    pub fn with_deposit_filter(mut self, deposit_filter: Arc<dyn DepositFilter>) -> Self {
+        self.deposit_filter = Some(deposit_filter);
+        self
+    }
+
+    /// Sets the [SequencerPolicy] applied to the attributes after they are built. Without a
+    /// policy, the attributes are left as derived by a verifier.
+    pub fn with_sequencer_policy(mut self, sequencer_policy: Arc<dyn SequencerPolicy>) -> Self {
+        self.sequencer_policy = Some(sequencer_policy);
+        self
+    }
+
+    /// Returns the L1 header of the epoch, from the [EpochCache] if possible.
+    async fn epoch_header(&mut self, epoch: BlockNumHash) -> PipelineResult<Header> {
+        if let Some(header) = self.epoch_cache.as_ref().and_then(|c| c.get(epoch)) {
+            return Ok(header);
+        }
+        let header = self.receipts_fetcher.header_by_hash(epoch.hash).await.map_err(Into::into)?;
+        if let Some(cache) = self.epoch_cache.as_ref() {
+            cache.insert(epoch, header.clone());
+        }
+        Ok(header)
     }
 }
 
@@ -70,8 +116,7 @@ where
         // In this case we need to fetch all transaction receipts from the L1 origin block so
         // we can scan for user deposits.
         let sequence_number = if l2_parent.l1_origin.number != epoch.number {
-            let header =
-                self.receipts_fetcher.header_by_hash(epoch.hash).await.map_err(Into::into)?;
+            let header = self.epoch_header(epoch).await?;
             if l2_parent.l1_origin.hash != header.parent_hash {
                 return Err(PipelineErrorKind::Reset(
                     BuilderError::BlockMismatchEpochReset(
@@ -84,10 +129,14 @@ where
             }
             let receipts =
                 self.receipts_fetcher.receipts_by_hash(epoch.hash).await.map_err(Into::into)?;
-            let deposits =
+            let mut deposits =
                 derive_deposits(epoch.hash, &receipts, self.rollup_cfg.deposit_contract_address)
                     .await
                     .map_err(|e| PipelineError::BadEncoding(e).crit())?;
+            if let Some(filter) = &self.deposit_filter {
+                deposits = filter_deposits(deposits, filter.as_ref())
+                    .map_err(|e| PipelineError::BadEncoding(e).crit())?;
+            }
             sys_config
                 .update_with_receipts(
                     &receipts,
@@ -106,8 +155,7 @@ where
                 ));
             }
 
-            let header =
-                self.receipts_fetcher.header_by_hash(epoch.hash).await.map_err(Into::into)?;
+            let header = self.epoch_header(epoch).await?;
             l1_header = header;
             deposit_transactions = vec![];
             l2_parent.seq_num + 1
@@ -128,17 +176,8 @@ where
             ));
         }
 
-        let mut upgrade_transactions: Vec<Bytes> = vec![];
-        if self.rollup_cfg.is_ecotone_active(next_l2_time) &&
-            !self.rollup_cfg.is_ecotone_active(l2_parent.block_info.timestamp)
-        {
-            upgrade_transactions = Hardforks::ecotone_txs();
-        }
-        if self.rollup_cfg.is_fjord_active(next_l2_time) &&
-            !self.rollup_cfg.is_fjord_active(l2_parent.block_info.timestamp)
-        {
-            upgrade_transactions.append(&mut Hardforks::fjord_txs());
-        }
+        let upgrade_transactions =
+            upgrade_transactions(&self.rollup_cfg, l2_parent.block_info.timestamp, next_l2_time);
 
         // Build and encode the L1 info transaction for the current payload.
         let (_, l1_info_tx_envelope) = L1BlockInfoTx::try_new_with_deposit_tx(
@@ -171,19 +210,28 @@ where
             parent_beacon_root = Some(l1_header.parent_beacon_block_root.unwrap_or_default());
         }
 
+        let mut overrides = SequencerOverrides {
+            no_tx_pool: true,
+            gas_limit: u64::from_be_bytes(
+                alloy_primitives::U64::from(sys_config.gas_limit).to_be_bytes(),
+            ),
+            suggested_fee_recipient: SEQUENCER_FEE_VAULT_ADDRESS,
+        };
+        if let Some(policy) = &self.sequencer_policy {
+            policy.apply(&mut overrides);
+        }
+
         Ok(OpPayloadAttributes {
             payload_attributes: PayloadAttributes {
                 timestamp: next_l2_time,
                 prev_randao: l1_header.mix_hash,
-                suggested_fee_recipient: SEQUENCER_FEE_VAULT_ADDRESS,
+                suggested_fee_recipient: overrides.suggested_fee_recipient,
                 parent_beacon_block_root: parent_beacon_root,
                 withdrawals,
             },
             transactions: Some(txs),
-            no_tx_pool: Some(true),
-            gas_limit: Some(u64::from_be_bytes(
-                alloy_primitives::U64::from(sys_config.gas_limit).to_be_bytes(),
-            )),
+            no_tx_pool: Some(overrides.no_tx_pool),
+            gas_limit: Some(overrides.gas_limit),
             eip_1559_params: sys_config.eip_1559_params(
                 &self.rollup_cfg,
                 l2_parent.block_info.timestamp,
@@ -225,17 +273,47 @@ async fn derive_deposits(
     Ok(res)
 }
 
+/// Applies the [DepositFilter] to the encoded user deposits, logging each dropped deposit.
+fn filter_deposits(
+    deposits: Vec<Bytes>,
+    filter: &dyn DepositFilter,
+) -> Result<Vec<Bytes>, PipelineEncodingError> {
+    let mut res = Vec::with_capacity(deposits.len());
+    for encoded in deposits {
+        let deposit =
+            TxDeposit::decode(&mut &encoded[1..]).map_err(PipelineEncodingError::AlloyRlpError)?;
+        let source_hash = deposit.source_hash;
+        let Some(deposit) = filter.filter(deposit) else {
+            info!(target: "attributes-builder", "Dropped deposit with source hash {source_hash}");
+            continue;
+        };
+        let mut buf = Vec::with_capacity(encoded.len());
+        buf.push(OpTxType::Deposit as u8);
+        deposit.encode(&mut buf);
+        res.push(buf.into());
+    }
+    Ok(res)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
+        attributes::upgrades::tests::{assert_ecotone_transactions, assert_fjord_transactions},
         errors::ResetError,
-        test_utils::{TestChainProvider, TestSystemConfigL2Fetcher},
+        sources::RecordingChainProvider,
+        test_utils::{
+            TestChainProvider, TestChainProviderBuilder, TestReplaySink, TestSystemConfigL2Fetcher,
+        },
+        traits::{PassthroughDepositFilter, VerifierPolicy},
+        types::{BlocklistDepositFilter, ChainRequest},
     };
     use alloc::vec;
-    use alloy_consensus::Header;
-    use alloy_primitives::{Log, LogData, B256, U256, U64};
-    use op_alloy_genesis::SystemConfig;
+    use alloy_primitives::{Log, LogData, TxKind, B256, B64, U256, U64};
+    use op_alloy_genesis::{
+        system::{CONFIG_UPDATE_EVENT_VERSION_0, CONFIG_UPDATE_TOPIC},
+        SystemConfig,
+    };
     use op_alloy_protocol::{BlockInfo, DepositError};
 
     fn generate_valid_log() -> Log {
@@ -336,6 +414,82 @@ mod tests {
         assert_eq!(result.unwrap().len(), 4);
     }
 
+    #[tokio::test]
+    async fn test_derive_deposits_from_chain_builder() {
+        let deposit_contract = address!("1111111111111111111111111111111111111111");
+        let deposit = TxDeposit {
+            from: address!("2222222222222222222222222222222222222222"),
+            to: TxKind::Call(address!("3333333333333333333333333333333333333333")),
+            mint: Some(10),
+            value: U256::from(100),
+            gas_limit: 1000,
+            input: Bytes::from_static(&[0xAB; 40]),
+            ..Default::default()
+        };
+        let builder = TestChainProviderBuilder::new(2)
+            .with_deposit_contract(deposit_contract)
+            .with_deposit(1, deposit.clone());
+        let block = builder.blocks()[1];
+        let mut provider = builder.build();
+
+        let receipts = provider.receipts_by_hash(block.hash).await.unwrap();
+        let deposits = derive_deposits(block.hash, &receipts, deposit_contract).await.unwrap();
+        let [encoded] = deposits.as_slice() else { panic!("expected a single deposit") };
+        let decoded = TxDeposit::decode(&mut &encoded[1..]).unwrap();
+        assert_eq!(decoded, TxDeposit { source_hash: decoded.source_hash, ..deposit });
+    }
+
+    #[tokio::test]
+    async fn test_filter_deposits() {
+        let deposit_contract = address!("1111111111111111111111111111111111111111");
+        let receipts = vec![generate_valid_receipt()];
+        let deposits = derive_deposits(B256::default(), &receipts, deposit_contract).await.unwrap();
+        let decode = |tx: &Bytes| TxDeposit::decode(&mut &tx[1..]).unwrap();
+
+        let passed = filter_deposits(deposits.clone(), &PassthroughDepositFilter).unwrap();
+        assert_eq!(passed, deposits);
+
+        let blocked = decode(&deposits[0]);
+        let filter = BlocklistDepositFilter::new().with_source_hash(blocked.source_hash);
+        let filtered = filter_deposits(deposits.clone(), &filter).unwrap();
+        assert_eq!(filtered, deposits[1..]);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_filtered_deposit() {
+        let deposit_contract = address!("1111111111111111111111111111111111111111");
+        let cfg = Arc::new(RollupConfig {
+            block_time: 2,
+            deposit_contract_address: deposit_contract,
+            ..Default::default()
+        });
+        let fetcher = || {
+            let mut fetcher = TestSystemConfigL2Fetcher::default();
+            fetcher.insert(0, SystemConfig::default());
+            fetcher
+        };
+        let prev_epoch = BlockNumHash { hash: B256::with_last_byte(1), number: 0 };
+        let header = Header { number: 1, parent_hash: prev_epoch.hash, ..Default::default() };
+        let hash = header.hash_slow();
+        let mut provider = TestChainProvider::default();
+        provider.insert_header(hash, header);
+        provider.insert_receipts(hash, vec![generate_valid_receipt()]);
+        let epoch = BlockNumHash { hash, number: 1 };
+        let l2_parent = L2BlockInfo { l1_origin: prev_epoch, ..Default::default() };
+
+        let mut builder = StatefulAttributesBuilder::new(cfg.clone(), fetcher(), provider.clone());
+        let txs = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap().transactions;
+        let txs = txs.unwrap();
+        assert_eq!(txs.len(), 3);
+
+        let blocked = TxDeposit::decode(&mut &txs[1][1..]).unwrap();
+        let filter = BlocklistDepositFilter::new().with_source_hash(blocked.source_hash);
+        let mut builder = StatefulAttributesBuilder::new(cfg, fetcher(), provider)
+            .with_deposit_filter(Arc::new(filter));
+        let filtered = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        assert_eq!(filtered.transactions.unwrap(), [txs[0].clone(), txs[2].clone()]);
+    }
+
     #[tokio::test]
     async fn test_prepare_payload_block_mismatch_epoch_reset() {
         let cfg = Arc::new(RollupConfig::default());
@@ -462,6 +616,60 @@ mod tests {
         assert_eq!(payload.transactions.unwrap().len(), 1);
     }
 
+    /// A [SequencerPolicy] that includes pool transactions and overrides the gas limit.
+    #[derive(Debug)]
+    struct TestSequencerPolicy;
+
+    impl SequencerPolicy for TestSequencerPolicy {
+        fn apply(&self, overrides: &mut SequencerOverrides) {
+            overrides.no_tx_pool = false;
+            overrides.gas_limit = 60_000_000;
+            overrides.suggested_fee_recipient = Address::with_last_byte(0xFE);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_sequencer_policy() {
+        let cfg = Arc::new(RollupConfig { block_time: 2, ..Default::default() });
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(1, SystemConfig { gas_limit: 30_000_000, ..Default::default() });
+        let mut provider = TestChainProvider::default();
+        let header = Header { timestamp: 100, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.insert_header(hash, header);
+        let epoch = BlockNumHash { hash, number: 1 };
+        let l2_parent = L2BlockInfo {
+            block_info: BlockInfo { number: 1, timestamp: 100, ..Default::default() },
+            l1_origin: epoch,
+            seq_num: 0,
+        };
+        let builder =
+            || StatefulAttributesBuilder::new(cfg.clone(), fetcher.clone(), provider.clone());
+
+        // Without a policy, and with the verifier policy, the attributes are left as derived.
+        let derived = builder().prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        assert_eq!(derived.no_tx_pool, Some(true));
+        assert_eq!(derived.gas_limit, Some(30_000_000));
+        assert_eq!(derived.payload_attributes.suggested_fee_recipient, SEQUENCER_FEE_VAULT_ADDRESS);
+        let mut verifier = builder().with_sequencer_policy(Arc::new(VerifierPolicy));
+        let payload = verifier.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        assert_eq!(payload, derived);
+
+        // A sequencer policy only changes the overridable fields.
+        let mut sequencer = builder().with_sequencer_policy(Arc::new(TestSequencerPolicy));
+        let payload = sequencer.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        let expected = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                suggested_fee_recipient: Address::with_last_byte(0xFE),
+                ..derived.payload_attributes.clone()
+            },
+            no_tx_pool: Some(false),
+            gas_limit: Some(60_000_000),
+            ..derived
+        };
+        assert_eq!(payload, expected);
+    }
+
     #[tokio::test]
     async fn test_prepare_payload_with_canyon() {
         let block_time = 10;
@@ -553,7 +761,7 @@ mod tests {
             eip_1559_params: None,
         };
         assert_eq!(payload, expected);
-        assert_eq!(payload.transactions.unwrap().len(), 7);
+        assert_ecotone_transactions(&payload.transactions.unwrap()[1..]);
     }
 
     #[tokio::test]
@@ -599,7 +807,150 @@ mod tests {
             )),
             eip_1559_params: None,
         };
-        assert_eq!(payload.transactions.as_ref().unwrap().len(), 10);
+        // Fjord activation implies Ecotone activation, so both sets of upgrade transactions are
+        // included.
+        let txs = payload.transactions.as_ref().unwrap();
+        assert_ecotone_transactions(&txs[1..7]);
+        assert_fjord_transactions(&txs[7..]);
         assert_eq!(payload, expected);
     }
+
+    /// Returns a receipt with a system config update of the EIP-1559 parameters.
+    fn eip_1559_update_receipt(cfg: &RollupConfig, denominator: u32, elasticity: u32) -> Receipt {
+        let mut data = vec![0u8; 96];
+        data[31] = 0x20;
+        data[63] = 0x20;
+        data[88..92].copy_from_slice(&denominator.to_be_bytes());
+        data[92..96].copy_from_slice(&elasticity.to_be_bytes());
+        let topics =
+            vec![CONFIG_UPDATE_TOPIC, CONFIG_UPDATE_EVENT_VERSION_0, B256::with_last_byte(4)];
+        Receipt {
+            status: Eip658Value::Eip658(true),
+            logs: vec![Log {
+                address: cfg.l1_system_config_address,
+                data: LogData::new_unchecked(topics, data.into()),
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Prepares the attributes for the L2 block after `parent_time` and returns their EIP-1559
+    /// parameters. The parent has the given system config, and if `epoch_receipts` are given,
+    /// the next block starts a new epoch with those receipts.
+    async fn prepared_eip_1559_params(
+        cfg: RollupConfig,
+        parent_time: u64,
+        sys_config: SystemConfig,
+        epoch_receipts: Option<Vec<Receipt>>,
+    ) -> Option<B64> {
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(1, sys_config);
+        let mut provider = TestChainProvider::default();
+        let header = Header { number: 1, timestamp: parent_time, ..Default::default() };
+        let hash = header.hash_slow();
+        let epoch = BlockNumHash { hash, number: 1 };
+        let l1_origin = match epoch_receipts {
+            Some(receipts) => {
+                provider.insert_receipts(hash, receipts);
+                BlockNumHash { hash: header.parent_hash, number: 0 }
+            }
+            None => epoch,
+        };
+        provider.insert_header(hash, header);
+        let l2_parent = L2BlockInfo {
+            block_info: BlockInfo { number: 1, timestamp: parent_time, ..Default::default() },
+            l1_origin,
+            seq_num: 0,
+        };
+        let mut builder = StatefulAttributesBuilder::new(Arc::new(cfg), fetcher, provider);
+        builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap().eip_1559_params
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_eip_1559_params_across_holocene() {
+        let cfg = RollupConfig { block_time: 2, holocene_time: Some(102), ..Default::default() };
+        let sys_config = SystemConfig {
+            eip1559_denominator: Some(250),
+            eip1559_elasticity: Some(6),
+            ..Default::default()
+        };
+        let params = |parent_time, sys_config| {
+            prepared_eip_1559_params(cfg.clone(), parent_time, sys_config, None)
+        };
+
+        assert_eq!(params(98, sys_config).await, None);
+        // The activation block uses the parameters of its parent.
+        assert_eq!(params(100, sys_config).await, Some(B64::ZERO));
+        let encoded = B64::from(((250u64 << 32) | 6).to_be_bytes());
+        assert_eq!(params(102, sys_config).await, Some(encoded));
+        // Zero parameters in the system config select the chain defaults.
+        assert_eq!(params(102, SystemConfig::default()).await, Some(B64::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_eip_1559_params_config_update() {
+        let cfg = RollupConfig {
+            block_time: 2,
+            holocene_time: Some(0),
+            l1_system_config_address: address!("1337000000000000000000000000000000000000"),
+            ..Default::default()
+        };
+        let receipts = vec![eip_1559_update_receipt(&cfg, 100, 4)];
+        let sys_config = SystemConfig {
+            eip1559_denominator: Some(250),
+            eip1559_elasticity: Some(6),
+            ..Default::default()
+        };
+
+        // The update in the new epoch's receipts applies to its first block.
+        let params = prepared_eip_1559_params(cfg, 102, sys_config, Some(receipts)).await;
+        assert_eq!(params, Some(B64::from(((100u64 << 32) | 4).to_be_bytes())));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_epoch_cache() {
+        let block_time = 2;
+        let cfg = Arc::new(RollupConfig { block_time, ..Default::default() });
+        let fetcher = || {
+            let mut fetcher = TestSystemConfigL2Fetcher::default();
+            (0..4).for_each(|number| fetcher.insert(number, SystemConfig::default()));
+            fetcher
+        };
+        let prev_epoch = BlockNumHash { hash: B256::with_last_byte(1), number: 0 };
+        let header = Header { number: 1, parent_hash: prev_epoch.hash, ..Default::default() };
+        let hash = header.hash_slow();
+        let mut provider = TestChainProvider::default();
+        provider.insert_header(hash, header);
+        provider.insert_receipts(hash, vec![]);
+        let sink = TestReplaySink::default();
+        let header_fetches = || {
+            let entries = sink.entries();
+            entries.iter().filter(|e| matches!(e.request, ChainRequest::HeaderByHash(_))).count()
+        };
+
+        let cache = EpochCache::default();
+        let recorder = RecordingChainProvider::new(provider.clone(), Arc::new(sink.clone()));
+        let mut builder = StatefulAttributesBuilder::new(cfg.clone(), fetcher(), recorder)
+            .with_epoch_cache(cache.clone());
+        let epoch = BlockNumHash { hash, number: 1 };
+        let l2_block = |number: u64, l1_origin| L2BlockInfo {
+            block_info: BlockInfo { number, timestamp: number * block_time, ..Default::default() },
+            l1_origin,
+            seq_num: number.saturating_sub(1),
+        };
+
+        // A span of three L2 blocks in the same epoch fetches the epoch header once.
+        builder.prepare_payload_attributes(l2_block(0, prev_epoch), epoch).await.unwrap();
+        builder.prepare_payload_attributes(l2_block(1, epoch), epoch).await.unwrap();
+        builder.prepare_payload_attributes(l2_block(2, epoch), epoch).await.unwrap();
+        assert_eq!(header_fetches(), 1);
+        assert_eq!(cache.len(), 1);
+
+        // Another component sharing the cache does not fetch the header again.
+        let recorder = RecordingChainProvider::new(provider, Arc::new(sink.clone()));
+        let mut other =
+            StatefulAttributesBuilder::new(cfg, fetcher(), recorder).with_epoch_cache(cache);
+        other.prepare_payload_attributes(l2_block(3, epoch), epoch).await.unwrap();
+        assert_eq!(header_fetches(), 1);
+    }
 }
@@ -0,0 +1,286 @@
+//! Contains the network upgrade deposit transactions included in hardfork activation blocks.
+
+use alloc::{string::String, vec::Vec};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{address, Address, Bytes, TxKind, U256};
+use op_alloy_consensus::{Hardforks, OpTxEnvelope, TxDeposit, UpgradeDepositSource};
+use op_alloy_genesis::RollupConfig;
+
+/// The account sending the L1 info deposits, which may call the Gas Price Oracle setters.
+const L1_INFO_DEPOSITOR_ADDRESS: Address = address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001");
+
+/// The L1 Block predeploy proxy.
+const L1_BLOCK_PROXY_ADDRESS: Address = address!("4200000000000000000000000000000000000015");
+
+/// The Gas Price Oracle predeploy proxy.
+const GAS_PRICE_ORACLE_PROXY_ADDRESS: Address =
+    address!("420000000000000000000000000000000000000f");
+
+/// The L1 Block implementation deployed by the Ecotone upgrade.
+const ECOTONE_L1_BLOCK_ADDRESS: Address = address!("07dbe8500fc591d1852b76fee44d5a05e13097ff");
+
+/// The Gas Price Oracle implementation deployed by the Ecotone upgrade.
+const ECOTONE_GAS_PRICE_ORACLE_ADDRESS: Address =
+    address!("b528d11cc114e026f138fe568744c6d45ce6da7a");
+
+/// The Gas Price Oracle implementation deployed by the Fjord upgrade.
+const FJORD_GAS_PRICE_ORACLE_ADDRESS: Address =
+    address!("a919894851548179a0750865e7974da599c0fac7");
+
+/// The selector of `upgradeTo(address)`.
+const UPGRADE_TO_SELECTOR: [u8; 4] = [0x36, 0x59, 0xcf, 0xe6];
+
+/// The selector of `setEcotone()`.
+const SET_ECOTONE_SELECTOR: &[u8] = &[0x22, 0xb9, 0x0a, 0xb3];
+
+/// The selector of `setFjord()`.
+const SET_FJORD_SELECTOR: &[u8] = &[0x8e, 0x98, 0xb1, 0x06];
+
+/// Returns the network upgrade deposit transactions to include in the L2 block at `next_l2_time`,
+/// on top of a parent block at `parent_time`.
+///
+/// The upgrade transactions of a hardfork are included in its activation block, the first L2
+/// block with a timestamp at or after the activation time. If several hardforks activate in the
+/// same block, their upgrade transactions are included in activation order.
+pub fn upgrade_transactions(cfg: &RollupConfig, parent_time: u64, next_l2_time: u64) -> Vec<Bytes> {
+    let mut upgrade_transactions = Vec::new();
+    if cfg.is_ecotone_active(next_l2_time) && !cfg.is_ecotone_active(parent_time) {
+        upgrade_transactions.append(&mut ecotone_transactions());
+    }
+    if cfg.is_fjord_active(next_l2_time) && !cfg.is_fjord_active(parent_time) {
+        upgrade_transactions.append(&mut fjord_transactions());
+    }
+    upgrade_transactions
+}
+
+/// Returns the [Ecotone upgrade transactions].
+///
+/// The contract deployments are taken from [Hardforks::ecotone_txs]. Its calls to the upgraded
+/// contracts do not match the spec: they target the deployers instead of the proxies, and use
+/// unpadded `upgradeTo` arguments and a wrong `setEcotone` selector. Those calls are built here.
+///
+/// [Ecotone upgrade transactions]: https://specs.optimism.io/protocol/ecotone/derivation.html#network-upgrade-automation-transactions
+fn ecotone_transactions() -> Vec<Bytes> {
+    let mut txs = Hardforks::ecotone_txs();
+    txs[2] = upgrade_deposit(
+        "Ecotone: L1 Block Proxy Update",
+        Address::ZERO,
+        L1_BLOCK_PROXY_ADDRESS,
+        50_000,
+        upgrade_to_calldata(ECOTONE_L1_BLOCK_ADDRESS),
+    );
+    txs[3] = upgrade_deposit(
+        "Ecotone: Gas Price Oracle Proxy Update",
+        Address::ZERO,
+        GAS_PRICE_ORACLE_PROXY_ADDRESS,
+        50_000,
+        upgrade_to_calldata(ECOTONE_GAS_PRICE_ORACLE_ADDRESS),
+    );
+    txs[4] = upgrade_deposit(
+        "Ecotone: Gas Price Oracle Set Ecotone",
+        L1_INFO_DEPOSITOR_ADDRESS,
+        GAS_PRICE_ORACLE_PROXY_ADDRESS,
+        80_000,
+        Bytes::from_static(SET_ECOTONE_SELECTOR),
+    );
+    txs
+}
+
+/// Returns the [Fjord upgrade transactions].
+///
+/// The Gas Price Oracle deployment is taken from [Hardforks::fjord_txs], whose calls to the
+/// upgraded contract target its Ecotone implementation instead of its proxy. Those calls are
+/// built here.
+///
+/// [Fjord upgrade transactions]: https://specs.optimism.io/protocol/fjord/derivation.html#network-upgrade-automation-transactions
+fn fjord_transactions() -> Vec<Bytes> {
+    let mut txs = Hardforks::fjord_txs();
+    txs[1] = upgrade_deposit(
+        "Fjord: Gas Price Oracle Proxy Update",
+        Address::ZERO,
+        GAS_PRICE_ORACLE_PROXY_ADDRESS,
+        50_000,
+        upgrade_to_calldata(FJORD_GAS_PRICE_ORACLE_ADDRESS),
+    );
+    txs[2] = upgrade_deposit(
+        "Fjord: Gas Price Oracle Set Fjord",
+        L1_INFO_DEPOSITOR_ADDRESS,
+        GAS_PRICE_ORACLE_PROXY_ADDRESS,
+        90_000,
+        Bytes::from_static(SET_FJORD_SELECTOR),
+    );
+    txs
+}
+
+/// Returns the encoded upgrade deposit with the given intent, calling `to`.
+fn upgrade_deposit(
+    intent: &str,
+    from: Address,
+    to: Address,
+    gas_limit: u64,
+    input: Bytes,
+) -> Bytes {
+    let deposit = TxDeposit {
+        source_hash: UpgradeDepositSource { intent: String::from(intent) }.source_hash(),
+        from,
+        to: TxKind::Call(to),
+        mint: None,
+        value: U256::ZERO,
+        gas_limit,
+        is_system_transaction: false,
+        input,
+    };
+    let mut buf = Vec::new();
+    OpTxEnvelope::Deposit(deposit).encode_2718(&mut buf);
+    buf.into()
+}
+
+/// Returns the ABI-encoded calldata of `upgradeTo(implementation)`.
+fn upgrade_to_calldata(implementation: Address) -> Bytes {
+    let mut calldata = UPGRADE_TO_SELECTOR.to_vec();
+    calldata.extend_from_slice(implementation.into_word().as_slice());
+    calldata.into()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{b256, hex, B256};
+
+    /// A network upgrade contract deployment, as specified.
+    struct Deployment {
+        source_hash: B256,
+        from: Address,
+        gas_limit: u64,
+        deployed: Address,
+    }
+
+    /// Asserts that `tx` is the specified contract deployment.
+    fn assert_deployment(tx: &[u8], expected: Deployment) {
+        let Ok(OpTxEnvelope::Deposit(deposit)) = OpTxEnvelope::decode_2718(&mut &tx[..]) else {
+            panic!("Expected a deposit transaction");
+        };
+        assert_eq!(deposit.source_hash, expected.source_hash);
+        assert_eq!(deposit.from, expected.from);
+        assert_eq!(deposit.to, TxKind::Create);
+        assert_eq!(deposit.mint.unwrap_or_default(), 0);
+        assert_eq!(deposit.value, U256::ZERO);
+        assert_eq!(deposit.gas_limit, expected.gas_limit);
+        assert!(!deposit.is_system_transaction);
+        assert!(!deposit.input.is_empty());
+        assert_eq!(deposit.from.create(0), expected.deployed);
+    }
+
+    /// Asserts that `txs` are the Ecotone upgrade transactions of the spec. The calls are
+    /// compared byte for byte. The deployments are compared field by field, except for the
+    /// deployed bytecode.
+    pub(crate) fn assert_ecotone_transactions(txs: &[Bytes]) {
+        assert_eq!(txs.len(), 6);
+        assert_deployment(
+            &txs[0],
+            Deployment {
+                source_hash: b256!(
+                    "877a6077205782ea15a6dc8699fa5ebcec5e0f4389f09cb8eda09488231346f8"
+                ),
+                from: address!("4210000000000000000000000000000000000000"),
+                gas_limit: 375_000,
+                deployed: ECOTONE_L1_BLOCK_ADDRESS,
+            },
+        );
+        assert_deployment(
+            &txs[1],
+            Deployment {
+                source_hash: b256!(
+                    "a312b4510adf943510f05fcc8f15f86995a5066bd83ce11384688ae20e6ecf42"
+                ),
+                from: address!("4210000000000000000000000000000000000001"),
+                gas_limit: 1_000_000,
+                deployed: ECOTONE_GAS_PRICE_ORACLE_ADDRESS,
+            },
+        );
+        assert_eq!(
+            txs[2][..],
+            hex!("7ef876a018acb38c5ff1c238a7460ebc1b421fa49ec4874bdf1e0a530d234104e5e67dbc940000000000000000000000000000000000000000944200000000000000000000000000000000000015808082c35080a43659cfe600000000000000000000000007dbe8500fc591d1852b76fee44d5a05e13097ff")
+        );
+        assert_eq!(
+            txs[3][..],
+            hex!("7ef876a0ee4f9385eceef498af0be7ec5862229f426dec41c8d42397c7257a5117d9230a94000000000000000000000000000000000000000094420000000000000000000000000000000000000f808082c35080a43659cfe6000000000000000000000000b528d11cc114e026f138fe568744c6d45ce6da7a")
+        );
+        assert_eq!(
+            txs[4][..],
+            hex!("7ef857a00c1cb38e99dbc9cbfab3bb80863380b0905290b37eb3d6ab18dc01c1f3e75f9394deaddeaddeaddeaddeaddeaddeaddeaddead000194420000000000000000000000000000000000000f808083013880808422b90ab3")
+        );
+        assert_deployment(
+            &txs[5],
+            Deployment {
+                source_hash: b256!(
+                    "69b763c48478b9dc2f65ada09b3d92133ec592ea715ec65ad6e7f3dc519dc00c"
+                ),
+                from: address!("0b799c86a49deeb90402691f1041aa3af2d3c875"),
+                gas_limit: 250_000,
+                deployed: address!("000f3df6d732807ef1319fb7b8bb8522d0beac02"),
+            },
+        );
+    }
+
+    /// Asserts that `txs` are the Fjord upgrade transactions of the spec. The calls are compared
+    /// byte for byte. The deployment is compared field by field, except for the deployed
+    /// bytecode.
+    pub(crate) fn assert_fjord_transactions(txs: &[Bytes]) {
+        assert_eq!(txs.len(), 3);
+        assert_deployment(
+            &txs[0],
+            Deployment {
+                source_hash: b256!(
+                    "86122c533fdcb89b16d8713174625e44578a89751d96c098ec19ab40a51a8ea3"
+                ),
+                from: address!("4210000000000000000000000000000000000002"),
+                gas_limit: 1_450_000,
+                deployed: FJORD_GAS_PRICE_ORACLE_ADDRESS,
+            },
+        );
+        assert_eq!(
+            txs[1][..],
+            hex!("7ef876a01e6bb0c28bfab3dc9b36ffb0f721f00d6937f33577606325692db0965a7d58c694000000000000000000000000000000000000000094420000000000000000000000000000000000000f808082c35080a43659cfe6000000000000000000000000a919894851548179a0750865e7974da599c0fac7")
+        );
+        assert_eq!(
+            txs[2][..],
+            hex!("7ef857a0bac7bb0d5961cad209a345408b0280a0d4686b1b20665e1b0f9cdafd73b19b6b94deaddeaddeaddeaddeaddeaddeaddeaddead000194420000000000000000000000000000000000000f808083015f9080848e98b106")
+        );
+    }
+
+    #[test]
+    fn test_ecotone_transactions() {
+        assert_ecotone_transactions(&ecotone_transactions());
+    }
+
+    #[test]
+    fn test_fjord_transactions() {
+        assert_fjord_transactions(&fjord_transactions());
+    }
+
+    #[test]
+    fn test_upgrade_transactions_in_activation_block() {
+        let cfg =
+            RollupConfig { ecotone_time: Some(101), fjord_time: Some(105), ..Default::default() };
+        assert!(upgrade_transactions(&cfg, 98, 100).is_empty());
+        assert_ecotone_transactions(&upgrade_transactions(&cfg, 100, 102));
+        assert!(upgrade_transactions(&cfg, 102, 104).is_empty());
+        assert_fjord_transactions(&upgrade_transactions(&cfg, 104, 106));
+        assert!(upgrade_transactions(&cfg, 106, 108).is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_transactions_same_activation_block() {
+        let cfg =
+            RollupConfig { ecotone_time: Some(102), fjord_time: Some(102), ..Default::default() };
+        let txs = upgrade_transactions(&cfg, 100, 102);
+        assert_ecotone_transactions(&txs[..6]);
+        assert_fjord_transactions(&txs[6..]);
+
+        // Hardforks active from genesis have no activation block.
+        let cfg = RollupConfig { ecotone_time: Some(0), fjord_time: Some(0), ..Default::default() };
+        assert!(upgrade_transactions(&cfg, 0, 2).is_empty());
+    }
+}
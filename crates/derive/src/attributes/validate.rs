@@ -0,0 +1,268 @@
+//! Contains [validate_attributes], which checks derived attributes against an existing L2 block.
+
+use crate::errors::AttributesMismatch;
+use alloc::vec::Vec;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::B64;
+use alloy_trie::root::ordered_trie_root;
+use op_alloy_consensus::OpBlock;
+use op_alloy_rpc_types_engine::OpAttributesWithParent;
+
+/// Checks that the derived attributes produce the given L2 block, as when consolidating the
+/// derived attributes against an unsafe block instead of building a new one.
+///
+/// Compares the parent hash, timestamp, `prev_randao`, fee recipient, gas limit, parent beacon
+/// block root, the byte encoding of every transaction, the withdrawals root implied by the
+/// attributes' withdrawals and, after Holocene, the EIP-1559 parameters in the block's extra data.
+/// Returns the first [AttributesMismatch] found, in that order.
+///
+/// Zero EIP-1559 parameters tell the execution layer to use the chain's canonical parameters,
+/// which are not known here, so only the format of the extra data is checked in that case.
+pub fn validate_attributes(
+    attributes: &OpAttributesWithParent,
+    block: &OpBlock,
+) -> Result<(), AttributesMismatch> {
+    let header = &block.header;
+    let payload = &attributes.attributes.payload_attributes;
+
+    let parent_hash = attributes.parent.block_info.hash;
+    if parent_hash != header.parent_hash {
+        return Err(AttributesMismatch::ParentHash(parent_hash, header.parent_hash));
+    }
+    if payload.timestamp != header.timestamp {
+        return Err(AttributesMismatch::Timestamp(payload.timestamp, header.timestamp));
+    }
+    if payload.prev_randao != header.mix_hash {
+        return Err(AttributesMismatch::PrevRandao(payload.prev_randao, header.mix_hash));
+    }
+    if payload.suggested_fee_recipient != header.beneficiary {
+        return Err(AttributesMismatch::FeeRecipient(
+            payload.suggested_fee_recipient,
+            header.beneficiary,
+        ));
+    }
+    let gas_limit = attributes.attributes.gas_limit;
+    if gas_limit != Some(header.gas_limit) {
+        return Err(AttributesMismatch::GasLimit(gas_limit, header.gas_limit));
+    }
+    if payload.parent_beacon_block_root != header.parent_beacon_block_root {
+        return Err(AttributesMismatch::ParentBeaconBlockRoot(
+            payload.parent_beacon_block_root,
+            header.parent_beacon_block_root,
+        ));
+    }
+
+    let txs = attributes.attributes.transactions.as_deref().unwrap_or_default();
+    let block_txs = &block.body.transactions;
+    if txs.len() != block_txs.len() {
+        return Err(AttributesMismatch::TransactionCount(txs.len(), block_txs.len()));
+    }
+    let mut encoded = Vec::new();
+    for (i, (tx, block_tx)) in txs.iter().zip(block_txs).enumerate() {
+        encoded.clear();
+        block_tx.encode_2718(&mut encoded);
+        if tx.as_ref() != encoded.as_slice() {
+            return Err(AttributesMismatch::Transaction(i));
+        }
+    }
+
+    let withdrawals_root = payload.withdrawals.as_deref().map(ordered_trie_root);
+    if withdrawals_root != header.withdrawals_root {
+        return Err(AttributesMismatch::WithdrawalsRoot(withdrawals_root, header.withdrawals_root));
+    }
+
+    // The attributes carry EIP-1559 parameters if and only if Holocene is active.
+    if let Some(params) = attributes.attributes.eip_1559_params {
+        let extra_data = &header.extra_data;
+        let valid_format = extra_data.len() == HOLOCENE_EXTRA_DATA_LEN && extra_data[0] == 0;
+        if !valid_format || (params != B64::ZERO && extra_data[1..] != params[..]) {
+            return Err(AttributesMismatch::Eip1559Params(params, extra_data.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// The length of the Holocene extra data: a zero version byte, followed by the EIP-1559
+/// denominator and elasticity as big-endian `u32`s.
+const HOLOCENE_EXTRA_DATA_LEN: usize = 9;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+    use alloy_consensus::{BlockBody, Header, EMPTY_ROOT_HASH};
+    use alloy_eips::{eip2718::Decodable2718, eip4895::Withdrawal};
+    use alloy_primitives::{b64, bytes, Address, Bytes, B256};
+    use alloy_rlp::Encodable;
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use op_alloy_consensus::{OpTxEnvelope, OpTxType, TxDeposit};
+    use op_alloy_protocol::{BlockInfo, L2BlockInfo};
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    fn deposit(gas_limit: u64) -> Bytes {
+        let mut encoded = vec![OpTxType::Deposit as u8];
+        TxDeposit { gas_limit, ..Default::default() }.encode(&mut encoded);
+        encoded.into()
+    }
+
+    /// Returns attributes and the L2 block they produce.
+    fn attributes_and_block() -> (OpAttributesWithParent, OpBlock) {
+        let txs = vec![deposit(1), deposit(2)];
+        let parent = L2BlockInfo {
+            block_info: BlockInfo { hash: B256::repeat_byte(0x01), ..Default::default() },
+            ..Default::default()
+        };
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: 2,
+                prev_randao: B256::repeat_byte(0x02),
+                suggested_fee_recipient: Address::repeat_byte(0x03),
+                withdrawals: Some(Vec::new()),
+                parent_beacon_block_root: Some(B256::repeat_byte(0x04)),
+            },
+            transactions: Some(txs.clone()),
+            no_tx_pool: Some(true),
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+        };
+        let block = OpBlock {
+            header: Header {
+                parent_hash: parent.block_info.hash,
+                timestamp: 2,
+                mix_hash: B256::repeat_byte(0x02),
+                beneficiary: Address::repeat_byte(0x03),
+                gas_limit: 30_000_000,
+                parent_beacon_block_root: Some(B256::repeat_byte(0x04)),
+                withdrawals_root: Some(EMPTY_ROOT_HASH),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: txs
+                    .iter()
+                    .map(|tx| OpTxEnvelope::decode_2718(&mut tx.as_ref()).unwrap())
+                    .collect(),
+                ommers: Vec::new(),
+                withdrawals: None,
+            },
+        };
+        (OpAttributesWithParent { attributes, parent, is_last_in_span: false }, block)
+    }
+
+    /// Applies `modify` to a matching pair of attributes and block, and validates them.
+    fn validate_modified(
+        modify: impl FnOnce(&mut OpAttributesWithParent, &mut OpBlock),
+    ) -> Result<(), AttributesMismatch> {
+        let (mut attributes, mut block) = attributes_and_block();
+        modify(&mut attributes, &mut block);
+        validate_attributes(&attributes, &block)
+    }
+
+    #[test]
+    fn test_validate_attributes_match() {
+        assert_eq!(validate_modified(|_, _| {}), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_attributes_parent_hash() {
+        let result = validate_modified(|_, block| block.header.parent_hash = B256::ZERO);
+        let expected = AttributesMismatch::ParentHash(B256::repeat_byte(0x01), B256::ZERO);
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_validate_attributes_timestamp() {
+        let result = validate_modified(|_, block| block.header.timestamp = 4);
+        assert_eq!(result, Err(AttributesMismatch::Timestamp(2, 4)));
+    }
+
+    #[test]
+    fn test_validate_attributes_prev_randao() {
+        let result = validate_modified(|_, block| block.header.mix_hash = B256::ZERO);
+        let expected = AttributesMismatch::PrevRandao(B256::repeat_byte(0x02), B256::ZERO);
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_validate_attributes_fee_recipient() {
+        let result = validate_modified(|_, block| block.header.beneficiary = Address::ZERO);
+        let expected = AttributesMismatch::FeeRecipient(Address::repeat_byte(0x03), Address::ZERO);
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_validate_attributes_gas_limit() {
+        let result = validate_modified(|attributes, _| attributes.attributes.gas_limit = None);
+        assert_eq!(result, Err(AttributesMismatch::GasLimit(None, 30_000_000)));
+    }
+
+    #[test]
+    fn test_validate_attributes_parent_beacon_block_root() {
+        let result = validate_modified(|_, block| block.header.parent_beacon_block_root = None);
+        let expected =
+            AttributesMismatch::ParentBeaconBlockRoot(Some(B256::repeat_byte(0x04)), None);
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_validate_attributes_transaction_count() {
+        let result = validate_modified(|_, block| {
+            block.body.transactions.pop();
+        });
+        assert_eq!(result, Err(AttributesMismatch::TransactionCount(2, 1)));
+    }
+
+    #[test]
+    fn test_validate_attributes_transaction() {
+        let result = validate_modified(|attributes, _| {
+            attributes.attributes.transactions = Some(vec![deposit(1), deposit(3)]);
+        });
+        assert_eq!(result, Err(AttributesMismatch::Transaction(1)));
+    }
+
+    #[test]
+    fn test_validate_attributes_withdrawals_root() {
+        let result = validate_modified(|attributes, _| {
+            let withdrawals = vec![Withdrawal { amount: 1, ..Default::default() }];
+            attributes.attributes.payload_attributes.withdrawals = Some(withdrawals);
+        });
+        let Err(AttributesMismatch::WithdrawalsRoot(Some(root), Some(EMPTY_ROOT_HASH))) = result
+        else {
+            panic!("expected a withdrawals root mismatch, got {result:?}");
+        };
+        assert_ne!(root, EMPTY_ROOT_HASH);
+
+        // Pre-Canyon blocks have no withdrawals root.
+        let result = validate_modified(|attributes, _| {
+            attributes.attributes.payload_attributes.withdrawals = None;
+        });
+        assert_eq!(result, Err(AttributesMismatch::WithdrawalsRoot(None, Some(EMPTY_ROOT_HASH))));
+    }
+
+    #[test]
+    fn test_validate_attributes_eip_1559_params() {
+        let params = b64!("000000fa00000006");
+        let result = validate_modified(|attributes, block| {
+            attributes.attributes.eip_1559_params = Some(params);
+            block.header.extra_data = bytes!("00000000fa00000006");
+        });
+        assert_eq!(result, Ok(()));
+
+        let result = validate_modified(|attributes, block| {
+            attributes.attributes.eip_1559_params = Some(params);
+            block.header.extra_data = bytes!("00000000fa00000008");
+        });
+        let expected = AttributesMismatch::Eip1559Params(params, bytes!("00000000fa00000008"));
+        assert_eq!(result, Err(expected));
+
+        // Zero parameters select the canonical parameters, but the format is still checked.
+        let result = validate_modified(|attributes, block| {
+            attributes.attributes.eip_1559_params = Some(B64::ZERO);
+            block.header.extra_data = bytes!("00000000fa00000008");
+        });
+        assert_eq!(result, Ok(()));
+        let result = validate_modified(|attributes, _| {
+            attributes.attributes.eip_1559_params = Some(B64::ZERO);
+        });
+        assert_eq!(result, Err(AttributesMismatch::Eip1559Params(B64::ZERO, Bytes::new())));
+    }
+}
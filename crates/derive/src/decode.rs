@@ -0,0 +1,446 @@
+//! Synchronous building blocks for decoding batcher data without a derivation pipeline.
+//!
+//! These functions perform the same frame parsing, channel assembly and batch decoding as the
+//! [FrameQueue], [ChannelAssembler] and [ChannelReader] stages, but operate on in-memory data
+//! and do not require any providers. They are intended for light clients and other `no_std`
+//! consumers that already have the batcher transactions at hand.
+//!
+//! [encode_frames] performs the inverse of [decode_frames], and [encode_batch] encodes a batch as
+//! it appears in a decompressed channel, for batcher simulations and fixture generators.
+//!
+//! All allocations are bounded by the input and the [DerivationLimits]:
+//! - [decode_frames] allocates at most the length of the transaction data.
+//! - [assemble_single_channel] allocates at most the sum of the frame data lengths.
+//! - [decode_batches] allocates at most the RLP byte limit for the decompressed channel, plus the
+//!   decoded batches themselves.
+//!
+//! [FrameQueue]: crate::stages::FrameQueue
+//! [ChannelAssembler]: crate::stages::ChannelAssembler
+//! [ChannelReader]: crate::stages::ChannelReader
+
+use crate::{
+    errors::{BatchDecodeError, ChannelAssemblyError, FrameEncodingError, FrameValidationError},
+    stages::{parse_frames, BatchReader},
+    types::DerivationLimits,
+};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::Bytes;
+use alloy_rlp::Encodable;
+use op_alloy_genesis::RollupConfig;
+use op_alloy_protocol::{
+    Batch, BlockInfo, Channel, Frame, RawSpanBatch, SpanBatchError, DERIVATION_VERSION_0,
+    SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE,
+};
+
+/// Decodes the frames of a single batcher transaction.
+///
+/// The data must begin with the derivation version byte. Frames are validated strictly against
+/// the spec frame length limit, and a single malformed frame rejects the whole transaction.
+pub fn decode_frames(data: &[u8]) -> Result<Vec<Frame>, FrameValidationError> {
+    parse_frames(data, DerivationLimits::SPEC.frame_len)
+}
+
+/// Encodes the frames of a single channel as the data of a batcher transaction.
+///
+/// The data begins with the derivation version byte, followed by the encoded frames in the given
+/// order, exactly as the batcher posts them. The frames must all belong to the same channel, and
+/// their data must fit within the spec frame length limit so that [decode_frames] accepts it.
+pub fn encode_frames(frames: &[Frame]) -> Result<Vec<u8>, FrameEncodingError> {
+    let first = frames.first().ok_or(FrameEncodingError::NoFrames)?;
+    let max_frame_len = DerivationLimits::SPEC.frame_len;
+    let mut data = vec![DERIVATION_VERSION_0];
+    for frame in frames {
+        if frame.id != first.id {
+            return Err(FrameEncodingError::MixedChannels(frame.number));
+        }
+        if frame.data.len() > max_frame_len {
+            return Err(FrameEncodingError::DataTooLong(
+                frame.number,
+                frame.data.len(),
+                max_frame_len,
+            ));
+        }
+        data.extend_from_slice(&frame.encode());
+    }
+    Ok(data)
+}
+
+/// Encodes a batch, appending it to `out`.
+///
+/// The batch type prefix byte is followed by the RLP encoding of a [SingleBatch], or by the span
+/// batch binary format of a [SpanBatch]. This is the inverse of [Batch::decode], and the format of
+/// each RLP string in a decompressed channel.
+///
+/// Returns a [SpanBatchError] if the span batch cannot be converted to its raw form, e.g. if one
+/// of its transactions cannot be decoded.
+///
+/// [SingleBatch]: op_alloy_protocol::SingleBatch
+/// [SpanBatch]: op_alloy_protocol::SpanBatch
+pub fn encode_batch(batch: &Batch, out: &mut Vec<u8>) -> Result<(), SpanBatchError> {
+    match batch {
+        Batch::Single(batch) => {
+            out.push(SINGLE_BATCH_TYPE);
+            batch.encode(out);
+        }
+        Batch::Span(batch) => {
+            out.push(SPAN_BATCH_TYPE);
+            RawSpanBatch::try_from(batch.clone())?.encode(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Assembles the frames of a single channel into the raw, compressed channel data.
+///
+/// Frames may be passed in any order, but must all belong to the same channel and include the
+/// closing frame. Unlike the [ChannelBank], channel timeouts are not checked.
+///
+/// [ChannelBank]: crate::stages::ChannelBank
+pub fn assemble_single_channel(
+    frames: impl IntoIterator<Item = Frame>,
+) -> Result<Bytes, ChannelAssemblyError> {
+    let mut frames = frames.into_iter();
+    let first = frames.next().ok_or(ChannelAssemblyError::NoFrames)?;
+    let mut channel = Channel::new(first.id, BlockInfo::default());
+
+    for frame in core::iter::once(first).chain(frames) {
+        if frame.id != channel.id() {
+            return Err(ChannelAssemblyError::MixedChannels(frame.number));
+        }
+        let number = frame.number;
+        channel
+            .add_frame(frame, BlockInfo::default())
+            .map_err(|_| ChannelAssemblyError::FrameRejected(number))?;
+    }
+
+    if !channel.is_ready() {
+        return Err(ChannelAssemblyError::Incomplete);
+    }
+    channel.frame_data().ok_or(ChannelAssemblyError::Incomplete)
+}
+
+/// Decompresses an assembled channel and decodes all of its batches.
+///
+/// The `l1_timestamp` is the timestamp of the L1 block that completed the channel, and selects
+/// the hardfork-dependent RLP byte limit and compression algorithms. Batches are not validated
+/// against L2 state.
+///
+/// Empty channels and channels without batches decode to no batches.
+pub fn decode_batches(
+    channel: &[u8],
+    limits: &DerivationLimits,
+    cfg: &RollupConfig,
+    l1_timestamp: u64,
+) -> Result<Vec<Batch>, BatchDecodeError> {
+    let max_rlp_bytes_per_channel = limits.max_rlp_bytes_per_channel(cfg, l1_timestamp);
+    let mut reader = BatchReader::new(channel, max_rlp_bytes_per_channel, l1_timestamp);
+    let mut batches = Vec::new();
+    while let Some(batch) = reader.next_batch(cfg) {
+        if let Batch::Span(span) = &batch {
+            if span.batches.len() as u64 > limits.span_batch_elements {
+                return Err(BatchDecodeError::SpanBatchTooLarge(
+                    span.batches.len(),
+                    limits.span_batch_elements,
+                ));
+            }
+        }
+        batches.push(batch);
+    }
+
+    if !reader.is_exhausted() && !reader.is_batchless() {
+        return Err(BatchDecodeError::Malformed(batches.len()));
+    }
+    Ok(batches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxEip1559, TxEip2930, TxEnvelope, TxLegacy};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Address, Parity, Signature, TxKind, B256, U256};
+    use miniz_oxide::deflate::compress_to_vec_zlib;
+    use op_alloy_protocol::{L2BlockInfo, SingleBatch, SpanBatch};
+    use proptest::prelude::*;
+
+    fn compressed_channel(timestamps: &[u64]) -> Vec<u8> {
+        let mut rlp = Vec::new();
+        for &timestamp in timestamps {
+            let mut data = vec![0u8];
+            SingleBatch { timestamp, ..Default::default() }.encode(&mut data);
+            Bytes::from(data).encode(&mut rlp);
+        }
+        compress_to_vec_zlib(&rlp, 9)
+    }
+
+    #[test]
+    fn test_decode_raw_bytes_to_batches() {
+        let compressed = compressed_channel(&[2, 4, 6]);
+        let (head, tail) = compressed.split_at(compressed.len() / 2);
+        let first = Frame { id: [0xAA; 16], number: 0, data: head.to_vec(), is_last: false };
+        let second = Frame { id: [0xAA; 16], number: 1, data: tail.to_vec(), is_last: true };
+
+        // Frames of a channel may be spread over several batcher transactions.
+        let mut frames = decode_frames(&encode_frames(&[second]).unwrap()).unwrap();
+        frames.extend(decode_frames(&encode_frames(&[first]).unwrap()).unwrap());
+
+        let channel = assemble_single_channel(frames).unwrap();
+        assert_eq!(channel.as_ref(), compressed.as_slice());
+
+        let cfg = RollupConfig::default();
+        let batches = decode_batches(&channel, &DerivationLimits::SPEC, &cfg, 0).unwrap();
+        let timestamps = batches.iter().map(|b| b.timestamp()).collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![2, 4, 6]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_encode_frames_round_trip(
+            id in any::<[u8; 16]>(),
+            datas in prop::collection::vec(prop::collection::vec(any::<u8>(), 0..256), 1..16),
+        ) {
+            let last = datas.len() - 1;
+            let frames = datas
+                .into_iter()
+                .enumerate()
+                .map(|(i, data)| Frame { id, number: i as u16, data, is_last: i == last })
+                .collect::<Vec<_>>();
+            let encoded = encode_frames(&frames).unwrap();
+            prop_assert_eq!(decode_frames(&encoded).unwrap(), frames);
+        }
+
+        #[test]
+        fn test_decode_frames_arbitrary_input(data in prop::collection::vec(any::<u8>(), 0..512)) {
+            // Batcher data is attacker controlled: malformed frames must be rejected, not panic.
+            let mut tx = vec![DERIVATION_VERSION_0];
+            tx.extend_from_slice(&data);
+            let _ = decode_frames(&data);
+            let _ = decode_frames(&tx);
+        }
+    }
+
+    #[test]
+    fn test_encode_frames_errors() {
+        let frame = |id: u8, number: u16, len: usize| Frame {
+            id: [id; 16],
+            number,
+            data: vec![0; len],
+            is_last: false,
+        };
+        let max = DerivationLimits::SPEC.frame_len;
+        assert_eq!(encode_frames(&[]), Err(FrameEncodingError::NoFrames));
+        assert_eq!(
+            encode_frames(&[frame(1, 0, 1), frame(2, 1, 1)]),
+            Err(FrameEncodingError::MixedChannels(1))
+        );
+        assert_eq!(
+            encode_frames(&[frame(1, 0, 1), frame(1, 1, max + 1)]),
+            Err(FrameEncodingError::DataTooLong(1, max + 1, max))
+        );
+        assert!(encode_frames(&[frame(1, 0, max)]).is_ok());
+    }
+
+    #[test]
+    fn test_assemble_single_channel_errors() {
+        let frame = |id: u8, number: u16, is_last: bool| Frame {
+            id: [id; 16],
+            number,
+            data: vec![1],
+            is_last,
+        };
+        assert_eq!(assemble_single_channel(Vec::new()), Err(ChannelAssemblyError::NoFrames));
+        assert_eq!(
+            assemble_single_channel([frame(1, 0, false), frame(2, 1, true)]),
+            Err(ChannelAssemblyError::MixedChannels(1))
+        );
+        assert_eq!(
+            assemble_single_channel([frame(1, 0, false), frame(1, 0, true)]),
+            Err(ChannelAssemblyError::FrameRejected(0))
+        );
+        assert_eq!(
+            assemble_single_channel([frame(1, 1, true)]),
+            Err(ChannelAssemblyError::Incomplete)
+        );
+    }
+
+    #[test]
+    fn test_decode_batches_limits() {
+        let cfg = RollupConfig::default();
+        let channel = compressed_channel(&[2]);
+        let limits = DerivationLimits { rlp_bytes_per_channel: 1, ..Default::default() };
+        assert_eq!(decode_batches(&channel, &limits, &cfg, 0), Err(BatchDecodeError::Malformed(0)));
+        assert_eq!(
+            decode_batches(&[0xFF], &DerivationLimits::SPEC, &cfg, 0),
+            Err(BatchDecodeError::Malformed(0))
+        );
+    }
+
+    fn origins(count: u64) -> Vec<BlockInfo> {
+        (0..count)
+            .map(|number| BlockInfo {
+                number,
+                hash: B256::with_last_byte(number as u8 + 1),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_span_batch_singular_batches_round_trip() {
+        let cfg = RollupConfig { block_time: 2, l2_chain_id: 10, ..Default::default() };
+        let origins = origins(3);
+        let singles = [(0, 2), (1, 4), (1, 6), (2, 8)].map(|(epoch_num, timestamp)| SingleBatch {
+            epoch_num,
+            epoch_hash: origins[epoch_num as usize].hash,
+            timestamp,
+            ..Default::default()
+        });
+
+        let mut span = SpanBatch { chain_id: cfg.l2_chain_id, ..Default::default() };
+        for (seq_num, batch) in singles.iter().enumerate() {
+            span.append_singular_batch(batch.clone(), seq_num as u64).unwrap();
+        }
+        let mut data = Vec::new();
+        encode_batch(&Batch::Span(span), &mut data).unwrap();
+        let Batch::Span(decoded) = Batch::decode(&mut data.as_slice(), &cfg).unwrap() else {
+            panic!("expected a span batch");
+        };
+
+        let batches = decoded.get_singular_batches(&origins, L2BlockInfo::default()).unwrap();
+        assert_eq!(batches, singles);
+    }
+
+    /// The L2 chain id of the batch encoding round trip tests.
+    const CHAIN_ID: u64 = 10;
+
+    /// Builds a signed transaction of the given kind: an unprotected legacy, a protected legacy,
+    /// an EIP-2930 or an EIP-1559 transaction.
+    fn signed_tx(kind: u8, nonce: u64, value: u64, to: Address) -> Bytes {
+        let (to, value) = (TxKind::Call(to), U256::from(value));
+        let sig = Signature::test_signature();
+        let tx = match kind {
+            0 | 1 => {
+                let chain_id = (kind == 1).then_some(CHAIN_ID);
+                let tx = TxLegacy { chain_id, nonce, to, value, ..Default::default() };
+                // Legacy signatures carry `v` as 27/28, or as the EIP-155 value when protected.
+                let sig = match chain_id {
+                    Some(chain_id) => sig.with_chain_id(chain_id),
+                    None => sig.with_parity(Parity::NonEip155(false)),
+                };
+                TxEnvelope::Legacy(Signed::new_unchecked(tx, sig, Default::default()))
+            }
+            2 => {
+                let tx = TxEip2930 { chain_id: CHAIN_ID, nonce, to, value, ..Default::default() };
+                TxEnvelope::Eip2930(Signed::new_unchecked(tx, sig, Default::default()))
+            }
+            _ => {
+                let tx = TxEip1559 { chain_id: CHAIN_ID, nonce, to, value, ..Default::default() };
+                TxEnvelope::Eip1559(Signed::new_unchecked(tx, sig, Default::default()))
+            }
+        };
+        tx.encoded_2718().into()
+    }
+
+    /// A strategy for the transactions of a block, of mixed types.
+    fn block_txs() -> impl Strategy<Value = Vec<Bytes>> {
+        prop::collection::vec((0..4u8, any::<u64>(), any::<u64>(), any::<[u8; 20]>()), 0..4)
+            .prop_map(|txs| {
+                txs.into_iter()
+                    .map(|(kind, nonce, value, to)| signed_tx(kind, nonce, value, to.into()))
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_encode_single_batch_round_trip(
+            parent_hash in any::<[u8; 32]>(),
+            epoch_num in any::<u64>(),
+            epoch_hash in any::<[u8; 32]>(),
+            timestamp in any::<u64>(),
+            transactions in block_txs(),
+        ) {
+            let batch = Batch::Single(SingleBatch {
+                parent_hash: parent_hash.into(),
+                epoch_num,
+                epoch_hash: epoch_hash.into(),
+                timestamp,
+                transactions,
+            });
+            let mut data = Vec::new();
+            encode_batch(&batch, &mut data).unwrap();
+            let decoded = Batch::decode(&mut data.as_slice(), &RollupConfig::default()).unwrap();
+            prop_assert_eq!(decoded, batch);
+        }
+
+        #[test]
+        fn test_encode_span_batch_round_trip(
+            first_epoch in 0..u32::MAX as u64,
+            blocks in prop::collection::vec((any::<bool>(), block_txs()), 1..8),
+        ) {
+            let cfg = RollupConfig { block_time: 2, l2_chain_id: CHAIN_ID, ..Default::default() };
+            let mut span = SpanBatch { chain_id: CHAIN_ID, ..Default::default() };
+            let mut epoch_num = first_epoch;
+            for (i, (advance_epoch, transactions)) in blocks.into_iter().enumerate() {
+                epoch_num += (i > 0 && advance_epoch) as u64;
+                let batch = SingleBatch {
+                    epoch_num,
+                    timestamp: 2 * (i as u64 + 1),
+                    transactions,
+                    ..Default::default()
+                };
+                span.append_singular_batch(batch, i as u64).unwrap();
+            }
+
+            let mut data = Vec::new();
+            encode_batch(&Batch::Span(span.clone()), &mut data).unwrap();
+            let Batch::Span(decoded) = Batch::decode(&mut data.as_slice(), &cfg).unwrap() else {
+                panic!("expected a span batch");
+            };
+            prop_assert_eq!(decoded.batches, span.batches);
+        }
+    }
+
+    /// Encodes a raw span batch with the given block count and block transaction counts, but
+    /// without any transactions.
+    fn adversarial_span_channel(block_count: u64, tx_counts: &[u64]) -> Vec<u8> {
+        fn uvarint(mut value: u64, out: &mut Vec<u8>) {
+            while value >= 0x80 {
+                out.push(value as u8 | 0x80);
+                value >>= 7;
+            }
+            out.push(value as u8);
+        }
+
+        // The batch type, relative timestamp, L1 origin number, parent and L1 origin checks.
+        let mut span = vec![1u8, 0, 0];
+        span.extend_from_slice(&[0u8; 40]);
+        uvarint(block_count, &mut span);
+        span.resize(span.len() + block_count.min(64).div_ceil(8) as usize, 0xFF);
+        tx_counts.iter().for_each(|&count| uvarint(count, &mut span));
+
+        let mut rlp = Vec::new();
+        Bytes::from(span).encode(&mut rlp);
+        compress_to_vec_zlib(&rlp, 9)
+    }
+
+    #[test]
+    fn test_decode_batches_adversarial_span_lengths() {
+        let cfg = RollupConfig::default();
+        let over = op_alloy_protocol::MAX_SPAN_BATCH_ELEMENTS + 1;
+        let channels = [
+            adversarial_span_channel(u64::MAX, &[]),
+            adversarial_span_channel(over, &[]),
+            adversarial_span_channel(1, &[u64::MAX]),
+            adversarial_span_channel(1, &[over]),
+        ];
+        // Length prefixes are bounded before anything is allocated for them, so decoding fails
+        // fast instead of exhausting memory.
+        for channel in channels {
+            assert_eq!(
+                decode_batches(&channel, &DerivationLimits::SPEC, &cfg, 0),
+                Err(BatchDecodeError::Malformed(0))
+            );
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use alloc::string::String;
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{Address, Bytes, B256, B64};
 
 /// An [AttributesBuilder] Error.
 ///
@@ -32,3 +32,43 @@ pub enum BuilderError {
 }
 
 impl core::error::Error for BuilderError {}
+
+/// The first field in which derived payload attributes differ from an existing L2 block.
+///
+/// Each variant holds the value from the attributes first, and the value from the block second.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum AttributesMismatch {
+    /// The block does not build on the parent of the attributes.
+    #[display("Parent hash mismatch. Attributes: {_0}, block: {_1}")]
+    ParentHash(B256, B256),
+    /// The timestamps differ.
+    #[display("Timestamp mismatch. Attributes: {_0}, block: {_1}")]
+    Timestamp(u64, u64),
+    /// The `prev_randao` of the attributes differs from the block's mix hash.
+    #[display("Prev randao mismatch. Attributes: {_0}, block: {_1}")]
+    PrevRandao(B256, B256),
+    /// The suggested fee recipient differs from the block's beneficiary.
+    #[display("Fee recipient mismatch. Attributes: {_0}, block: {_1}")]
+    FeeRecipient(Address, Address),
+    /// The gas limits differ, or the attributes have none.
+    #[display("Gas limit mismatch. Attributes: {_0:?}, block: {_1}")]
+    GasLimit(Option<u64>, u64),
+    /// The parent beacon block roots differ.
+    #[display("Parent beacon block root mismatch. Attributes: {_0:?}, block: {_1:?}")]
+    ParentBeaconBlockRoot(Option<B256>, Option<B256>),
+    /// The number of transactions differs.
+    #[display("Transaction count mismatch. Attributes: {_0}, block: {_1}")]
+    TransactionCount(usize, usize),
+    /// The encoding of the transaction at the index differs.
+    #[display("Transaction mismatch at index {_0}")]
+    Transaction(usize),
+    /// The withdrawals root implied by the attributes differs from the block's.
+    #[display("Withdrawals root mismatch. Attributes: {_0:?}, block: {_1:?}")]
+    WithdrawalsRoot(Option<B256>, Option<B256>),
+    /// The Holocene EIP-1559 parameters differ from those in the block's extra data, or the
+    /// extra data is not in the Holocene format.
+    #[display("EIP-1559 parameters mismatch. Attributes: {_0}, block extra data: {_1}")]
+    Eip1559Params(B64, Bytes),
+}
+
+impl core::error::Error for AttributesMismatch {}
@@ -0,0 +1,64 @@
+//! Error types for rollup config validation and pipeline construction.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// An inconsistency found when validating a [RollupConfig].
+///
+/// [RollupConfig]: op_alloy_genesis::RollupConfig
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The block time is zero.
+    #[display("block_time must be non-zero, got 0")]
+    ZeroBlockTime,
+    /// The sequencing window size is zero.
+    #[display("seq_window_size must be non-zero, got 0")]
+    ZeroSeqWindowSize,
+    /// The channel timeout is zero.
+    #[display("channel_timeout must be non-zero, got 0")]
+    ZeroChannelTimeout,
+    /// The sequencing window is smaller than the channel timeout.
+    #[display("seq_window_size ({_0}) must not be smaller than channel_timeout ({_1})")]
+    SeqWindowSmallerThanChannelTimeout(u64, u64),
+    /// The L1 chain id is zero.
+    #[display("l1_chain_id must be non-zero, got 0")]
+    ZeroL1ChainId,
+    /// The L2 chain id is zero.
+    #[display("l2_chain_id must be non-zero, got 0")]
+    ZeroL2ChainId,
+    /// The genesis system config is missing.
+    #[display("genesis.system_config must be set")]
+    MissingGenesisSystemConfig,
+    /// The batch inbox address is the zero address.
+    #[display("batch_inbox_address must be non-zero")]
+    ZeroBatchInboxAddress,
+    /// A hardfork is scheduled without its predecessor.
+    #[display("{_0} is set ({_1}) but its predecessor {_2} is not")]
+    MissingPredecessorFork(String, u64, String),
+    /// A hardfork is scheduled before its predecessor.
+    #[display("{_0} ({_1}) must not be before {_2} ({_3})")]
+    ForkOutOfOrder(String, u64, String, u64),
+}
+
+impl core::error::Error for ConfigError {}
+
+/// An error returned when building a [DerivationPipeline] with the [PipelineBuilder].
+///
+/// [DerivationPipeline]: crate::pipeline::DerivationPipeline
+/// [PipelineBuilder]: crate::pipeline::PipelineBuilder
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum PipelineBuildError {
+    /// A required component was not set.
+    #[display("{_0} must be set")]
+    MissingComponent(&'static str),
+    /// The rollup config failed validation.
+    #[display(
+        "invalid rollup config: {}",
+        _0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    InvalidConfig(Vec<ConfigError>),
+}
+
+impl core::error::Error for PipelineBuildError {}
@@ -1,13 +1,25 @@
 //! Error types for the kona derivation pipeline.
 
 mod attributes;
-pub use attributes::BuilderError;
+pub use attributes::{AttributesMismatch, BuilderError};
 
 mod stages;
-pub use stages::BatchDecompressionError;
+pub use stages::{
+    BatchDecodeError, BatchDecompressionError, ChannelAssemblyError, ChannelOutError,
+    FrameEncodingError, FrameValidationError,
+};
 
 mod pipeline;
-pub use pipeline::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError};
+pub use pipeline::{
+    PipelineEncodingError, PipelineError, PipelineErrorKind, ProviderErrorKind, ResetError,
+    Severity,
+};
 
 mod sources;
-pub use sources::{BlobDecodingError, BlobProviderError};
+pub use sources::{AltDaError, BlobDecodingError, BlobProviderError, SyntheticChainError};
+
+mod config;
+pub use config::{ConfigError, PipelineBuildError};
+
+mod replay;
+pub use replay::ReplayError;
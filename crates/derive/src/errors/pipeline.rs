@@ -30,6 +30,58 @@ pub enum PipelineErrorKind {
     Reset(ResetError),
 }
 
+impl PipelineErrorKind {
+    /// Returns the [Severity] of the error.
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Self::Temporary(_) => Severity::Temporary,
+            Self::Reset(_) => Severity::Reset,
+            Self::Critical(_) => Severity::Critical,
+        }
+    }
+
+    /// Returns whether the step may be retried without intervention.
+    pub const fn is_temporary(&self) -> bool {
+        matches!(self, Self::Temporary(_))
+    }
+
+    /// Returns whether the pipeline must be reset before it can make progress.
+    pub const fn requires_reset(&self) -> bool {
+        matches!(self, Self::Reset(_))
+    }
+
+    /// Returns whether derivation cannot continue.
+    pub const fn is_critical(&self) -> bool {
+        matches!(self, Self::Critical(_))
+    }
+
+    /// Escalates a temporary [PipelineError::Provider] error for data that was not found to a
+    /// [ResetError::Provider], for errors raised while resetting. Retrying a reset whose target
+    /// data is missing cannot succeed, so the driver has to pick a new target instead.
+    pub fn escalate_not_found(self) -> Self {
+        match self {
+            Self::Temporary(PipelineError::Provider(kind)) if kind.is_not_found() => {
+                ResetError::Provider(kind).reset()
+            }
+            err => err,
+        }
+    }
+}
+
+/// How a driver should react to a [PipelineErrorKind], ordered from least to most severe.
+#[derive(derive_more::Display, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The step may be retried as-is, possibly after more L1 data becomes available.
+    #[display("temporary")]
+    Temporary,
+    /// The pipeline must be reset before it can make progress.
+    #[display("reset")]
+    Reset,
+    /// Derivation cannot continue.
+    #[display("critical")]
+    Critical,
+}
+
 impl From<ResetError> for PipelineErrorKind {
     fn from(err: ResetError) -> Self {
         Self::Reset(err)
@@ -85,6 +137,9 @@ pub enum PipelineError {
     /// Missing L1 origin.
     #[display("Missing L1 origin from previous stage")]
     MissingOrigin,
+    /// An L1 block was provided out of order, with the expected and the provided block numbers.
+    #[display("Non-contiguous L1 block: expected #{_0}, got #{_1}")]
+    NonContiguousBlock(u64, u64),
     /// Missing data from [L1Retrieval].
     ///
     /// [L1Retrieval]: crate::stages::L1Retrieval
@@ -110,9 +165,12 @@ pub enum PipelineError {
     /// The data source can no longer provide any more data.
     #[display("Data source exhausted")]
     EndOfSource,
-    /// Provider error variant.
-    #[display("Blob provider error: {_0}")]
-    Provider(String),
+    /// A provider failed to serve a request, with the [ProviderErrorKind] of the failure.
+    #[display("Provider error: {_0}")]
+    Provider(ProviderErrorKind),
+    /// Data was dropped due to a spec violation while the pipeline runs in strict mode.
+    #[display("Strict mode violation: {_0}")]
+    StrictViolation(String),
 }
 
 impl From<BuilderError> for PipelineError {
@@ -138,6 +196,44 @@ impl core::error::Error for PipelineError {
 }
 
 impl PipelineError {
+    /// Returns the [Severity] the error is raised with by default, which [PipelineError::classify]
+    /// applies.
+    ///
+    /// [PipelineError::Provider] errors are temporary by default, but providers whose failure is
+    /// known to be deterministic raise them as critical errors instead. Data that is not found
+    /// while resetting raises a [ResetError::Provider] instead, as the reset target itself may be
+    /// gone.
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Self::Eof |
+            Self::NotEnoughData |
+            Self::ChannelReaderEmpty |
+            Self::MissingL1Data |
+            Self::Provider(_) => Severity::Temporary,
+            Self::ChannelProviderEmpty |
+            Self::ChannelAlreadyBuilt |
+            Self::ChannelNotFound |
+            Self::BatchQueueEmpty |
+            Self::MissingOrigin |
+            Self::NonContiguousBlock(_, _) |
+            Self::InvalidBatchType |
+            Self::InvalidBatchValidity |
+            Self::SystemConfigUpdate(_) |
+            Self::AttributesBuilder(_) |
+            Self::BadEncoding(_) |
+            Self::EndOfSource |
+            Self::StrictViolation(_) => Severity::Critical,
+        }
+    }
+
+    /// Wraps the [PipelineError] in a [PipelineErrorKind] of its default [Severity].
+    pub const fn classify(self) -> PipelineErrorKind {
+        match self.severity() {
+            Severity::Temporary => self.temp(),
+            Severity::Reset | Severity::Critical => self.crit(),
+        }
+    }
+
     /// Wrap [PipelineError] as a [PipelineErrorKind::Critical].
     pub const fn crit(self) -> PipelineErrorKind {
         PipelineErrorKind::Critical(self)
@@ -149,6 +245,52 @@ impl PipelineError {
     }
 }
 
+/// The kind of failure behind a [PipelineError::Provider] error, so that drivers can tell data
+/// that does not exist apart from a request that failed to go through.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum ProviderErrorKind {
+    /// The block with the given number was not found.
+    #[display("Block #{_0} not found")]
+    BlockNotFound(u64),
+    /// The block with the given hash was not found.
+    #[display("Block {_0} not found")]
+    BlockHashNotFound(B256),
+    /// The receipts of the block with the given hash were not found.
+    #[display("Receipts of block {_0} not found")]
+    ReceiptsNotFound(B256),
+    /// The system config of the L2 block with the given number was not found.
+    #[display("System config of L2 block #{_0} not found")]
+    SystemConfigNotFound(u64),
+    /// The request failed to reach the backend or to return, e.g. it timed out.
+    #[display("Transport error: {_0}")]
+    Transport(String),
+    /// The response could not be decoded.
+    #[display("Decode error: {_0}")]
+    Decode(String),
+    /// Any other provider failure.
+    #[display("{_0}")]
+    Other(String),
+}
+
+impl ProviderErrorKind {
+    /// Returns whether the requested data was not found.
+    pub const fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            Self::BlockNotFound(_) |
+                Self::BlockHashNotFound(_) |
+                Self::ReceiptsNotFound(_) |
+                Self::SystemConfigNotFound(_)
+        )
+    }
+}
+
+impl From<ProviderErrorKind> for PipelineError {
+    fn from(kind: ProviderErrorKind) -> Self {
+        Self::Provider(kind)
+    }
+}
+
 /// A reset error
 #[derive(derive_more::Display, Clone, Debug, Eq, PartialEq)]
 pub enum ResetError {
@@ -176,6 +318,14 @@ pub enum ResetError {
     /// A Holocene activation temporary error.
     #[display("Holocene activation reset")]
     HoloceneActivation,
+    /// The pipeline was stepped with a cursor behind one it already stepped on.
+    /// The first argument is the cursor's L2 block number, and the second argument is the highest
+    /// L2 block number stepped on.
+    #[display("Stale cursor: L2 block #{_0} is behind #{_1}")]
+    StaleCursor(u64, u64),
+    /// Data needed to reset the pipeline was not found by a provider.
+    #[display("Provider error during reset: {_0}")]
+    Provider(ProviderErrorKind),
 }
 
 impl From<BuilderError> for ResetError {
@@ -235,7 +385,9 @@ impl From<DepositError> for PipelineEncodingError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
     use core::error::Error;
+    use op_alloy_genesis::system::LogProcessingError;
 
     #[test]
     fn test_pipeline_error_kind_source() {
@@ -293,10 +445,99 @@ mod tests {
                 Default::default(),
             )),
             ResetError::HoloceneActivation,
+            ResetError::Provider(ProviderErrorKind::BlockNotFound(0)),
         ];
         for error in reset_errors.into_iter() {
             let expected = PipelineErrorKind::Reset(error.clone());
             assert_eq!(error.reset(), expected);
         }
     }
+
+    #[test]
+    fn test_pipeline_error_severity() {
+        let errors = [
+            (PipelineError::Eof, Severity::Temporary),
+            (PipelineError::NotEnoughData, Severity::Temporary),
+            (PipelineError::ChannelProviderEmpty, Severity::Critical),
+            (PipelineError::ChannelAlreadyBuilt, Severity::Critical),
+            (PipelineError::ChannelNotFound, Severity::Critical),
+            (PipelineError::ChannelReaderEmpty, Severity::Temporary),
+            (PipelineError::BatchQueueEmpty, Severity::Critical),
+            (PipelineError::MissingOrigin, Severity::Critical),
+            (PipelineError::NonContiguousBlock(2, 3), Severity::Critical),
+            (PipelineError::MissingL1Data, Severity::Temporary),
+            (PipelineError::InvalidBatchType, Severity::Critical),
+            (PipelineError::InvalidBatchValidity, Severity::Critical),
+            (
+                PipelineError::SystemConfigUpdate(SystemConfigUpdateError::LogProcessing(
+                    LogProcessingError::InvalidTopicLen(0),
+                )),
+                Severity::Critical,
+            ),
+            (
+                PipelineError::AttributesBuilder(BuilderError::BlockMismatch(
+                    Default::default(),
+                    Default::default(),
+                )),
+                Severity::Critical,
+            ),
+            (
+                PipelineError::BadEncoding(PipelineEncodingError::AlloyRlpError(
+                    alloy_rlp::Error::Overflow,
+                )),
+                Severity::Critical,
+            ),
+            (PipelineError::EndOfSource, Severity::Critical),
+            (
+                PipelineError::Provider(ProviderErrorKind::Transport("timeout".to_string())),
+                Severity::Temporary,
+            ),
+            (PipelineError::StrictViolation("frame".to_string()), Severity::Critical),
+        ];
+        for (error, severity) in errors {
+            assert_eq!(error.severity(), severity, "{error}");
+            let kind = error.classify();
+            assert_eq!(kind.severity(), severity);
+            assert_eq!(kind.is_temporary(), severity == Severity::Temporary);
+            assert_eq!(kind.is_critical(), severity == Severity::Critical);
+            assert!(!kind.requires_reset());
+        }
+    }
+
+    #[test]
+    fn test_reset_error_severity() {
+        let err = ResetError::ReorgDetected(Default::default(), Default::default()).reset();
+        assert_eq!(err.severity(), Severity::Reset);
+        assert!(err.requires_reset());
+        assert!(!err.is_temporary() && !err.is_critical());
+        assert!(Severity::Temporary < Severity::Reset && Severity::Reset < Severity::Critical);
+    }
+
+    #[test]
+    fn test_escalate_not_found() {
+        let not_found = ProviderErrorKind::SystemConfigNotFound(1);
+        let err = PipelineError::Provider(not_found.clone()).temp().escalate_not_found();
+        assert_eq!(err, ResetError::Provider(not_found.clone()).reset());
+
+        // Transport failures stay temporary, and critical errors are left as they are.
+        let transport = || ProviderErrorKind::Transport("timeout".to_string());
+        let err = PipelineError::Provider(transport()).temp().escalate_not_found();
+        assert_eq!(err, PipelineError::Provider(transport()).temp());
+        let err = PipelineError::Provider(not_found.clone()).crit().escalate_not_found();
+        assert_eq!(err, PipelineError::Provider(not_found).crit());
+    }
+
+    #[test]
+    fn test_provider_error_kind_is_not_found() {
+        assert!(ProviderErrorKind::BlockNotFound(1).is_not_found());
+        assert!(ProviderErrorKind::BlockHashNotFound(B256::ZERO).is_not_found());
+        assert!(ProviderErrorKind::ReceiptsNotFound(B256::ZERO).is_not_found());
+        assert!(ProviderErrorKind::SystemConfigNotFound(1).is_not_found());
+        assert!(!ProviderErrorKind::Transport("timeout".to_string()).is_not_found());
+        assert!(!ProviderErrorKind::Decode("rlp".to_string()).is_not_found());
+        assert!(!ProviderErrorKind::Other("other".to_string()).is_not_found());
+
+        let err = PipelineError::from(ProviderErrorKind::BlockNotFound(1));
+        assert_eq!(err.to_string(), "Provider error: Block #1 not found");
+    }
 }
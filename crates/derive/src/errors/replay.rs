@@ -0,0 +1,51 @@
+//! Error types for replaying recorded provider traffic.
+
+use super::{PipelineError, PipelineErrorKind, ProviderErrorKind};
+use crate::types::ChainRequest;
+use alloc::string::ToString;
+
+/// An error returned by the [ReplayChainProvider].
+///
+/// [ReplayChainProvider]: crate::sources::ReplayChainProvider
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The request was never recorded in the replay log.
+    #[display("Request not in replay log: {_0}")]
+    NotInLog(ChainRequest),
+    /// The recorded response does not match the kind of the request.
+    #[display("Recorded response has the wrong kind for request: {_0}")]
+    ResponseMismatch(ChainRequest),
+    /// The recorded response could not be decoded.
+    #[display("Recorded response could not be decoded for request: {_0}")]
+    InvalidResponse(ChainRequest),
+}
+
+impl core::error::Error for ReplayError {}
+
+impl From<ReplayError> for PipelineErrorKind {
+    fn from(val: ReplayError) -> Self {
+        // A request that is missing from the log means the replayed run diverged from the
+        // recorded one, so retrying can never succeed.
+        PipelineError::Provider(ProviderErrorKind::Other(val.to_string())).crit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_replay_error() {
+        let err: PipelineErrorKind =
+            ReplayError::NotInLog(ChainRequest::BlockInfoByNumber(1)).into();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+
+        let err: PipelineErrorKind =
+            ReplayError::ResponseMismatch(ChainRequest::BlockInfoByNumber(1)).into();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+
+        let err: PipelineErrorKind =
+            ReplayError::InvalidResponse(ChainRequest::L2BlockByNumber(1)).into();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+    }
+}
@@ -1,7 +1,9 @@
 //! Error types for sources.
 
-use super::{PipelineError, PipelineErrorKind};
+use super::{PipelineError, PipelineErrorKind, ProviderErrorKind};
+use crate::traits::TransientError;
 use alloc::string::{String, ToString};
+use alloy_primitives::B256;
 
 /// Blob Decoding Error
 #[derive(derive_more::Display, Debug, PartialEq, Eq)]
@@ -18,6 +20,9 @@ pub enum BlobDecodingError {
     /// Missing Data
     #[display("Missing data")]
     MissingData,
+    /// The blob does not match the versioned hash it was requested by.
+    #[display("Blob does not match its versioned hash")]
+    InvalidCommitment,
 }
 
 impl core::error::Error for BlobDecodingError {}
@@ -42,16 +47,25 @@ pub enum BlobProviderError {
 impl From<BlobProviderError> for PipelineErrorKind {
     fn from(val: BlobProviderError) -> Self {
         match val {
-            BlobProviderError::SidecarLengthMismatch(_, _) => {
-                PipelineError::Provider(val.to_string()).crit()
+            BlobProviderError::SidecarLengthMismatch(_, _) | BlobProviderError::SlotDerivation => {
+                PipelineError::Provider(ProviderErrorKind::Other(val.to_string())).crit()
+            }
+            BlobProviderError::BlobDecoding(err) => {
+                PipelineError::Provider(ProviderErrorKind::Decode(err.to_string())).crit()
+            }
+            BlobProviderError::Backend(err) => {
+                PipelineError::Provider(ProviderErrorKind::Transport(err)).temp()
             }
-            BlobProviderError::SlotDerivation => PipelineError::Provider(val.to_string()).crit(),
-            BlobProviderError::BlobDecoding(_) => PipelineError::Provider(val.to_string()).crit(),
-            BlobProviderError::Backend(_) => PipelineError::Provider(val.to_string()).temp(),
         }
     }
 }
 
+impl TransientError for BlobProviderError {
+    fn is_transient(&self) -> bool {
+        matches!(self, Self::Backend(_))
+    }
+}
+
 impl From<BlobDecodingError> for BlobProviderError {
     fn from(err: BlobDecodingError) -> Self {
         Self::BlobDecoding(err)
@@ -67,6 +81,92 @@ impl core::error::Error for BlobProviderError {
     }
 }
 
+/// An error returned by the [SyntheticChainProvider].
+///
+/// [SyntheticChainProvider]: crate::sources::SyntheticChainProvider
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum SyntheticChainError {
+    /// The block number is outside of the synthetic chain.
+    #[display("Block #{_0} is outside of the synthetic chain")]
+    BlockNotFound(u64),
+    /// The block hash is not part of the synthetic chain.
+    #[display("Block {_0} is not part of the synthetic chain")]
+    HashNotFound(B256),
+}
+
+impl core::error::Error for SyntheticChainError {}
+
+impl TransientError for SyntheticChainError {
+    fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+impl From<SyntheticChainError> for PipelineErrorKind {
+    fn from(val: SyntheticChainError) -> Self {
+        let kind = match val {
+            SyntheticChainError::BlockNotFound(number) => ProviderErrorKind::BlockNotFound(number),
+            SyntheticChainError::HashNotFound(hash) => ProviderErrorKind::BlockHashNotFound(hash),
+        };
+        PipelineError::Provider(kind).temp()
+    }
+}
+
+/// An error returned by an [AltDaProvider].
+///
+/// [AltDaProvider]: crate::traits::AltDaProvider
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum AltDaError {
+    /// The commitment could not be decoded.
+    #[display("Invalid alt-DA commitment")]
+    InvalidCommitment,
+    /// The input was challenged and not resolved within the resolve window. The commitment is
+    /// skipped.
+    #[display("Alt-DA challenge expired")]
+    ChallengeExpired,
+    /// The input is missing, and the challenge window for the commitment has passed.
+    #[display("Alt-DA input missing past the challenge window")]
+    MissingPastWindow,
+    /// The input does not match its commitment.
+    #[display("Alt-DA input does not match its commitment")]
+    CommitmentMismatch,
+    /// The input exceeds the maximum input size. The commitment is skipped.
+    #[display("Alt-DA input too large: {_0} bytes")]
+    InputTooLarge(usize),
+    /// Error pertaining to the backend transport.
+    #[display("{_0}")]
+    Backend(String),
+}
+
+impl core::error::Error for AltDaError {}
+
+impl TransientError for AltDaError {
+    fn is_transient(&self) -> bool {
+        matches!(self, Self::Backend(_))
+    }
+}
+
+impl From<AltDaError> for PipelineErrorKind {
+    fn from(val: AltDaError) -> Self {
+        match val {
+            AltDaError::MissingPastWindow => {
+                PipelineError::Provider(ProviderErrorKind::Other(val.to_string())).crit()
+            }
+            AltDaError::InvalidCommitment => {
+                PipelineError::Provider(ProviderErrorKind::Decode(val.to_string())).temp()
+            }
+            AltDaError::ChallengeExpired |
+            AltDaError::CommitmentMismatch |
+            AltDaError::InputTooLarge(_) => {
+                PipelineError::Provider(ProviderErrorKind::Other(val.to_string())).temp()
+            }
+            AltDaError::Backend(err) => {
+                PipelineError::Provider(ProviderErrorKind::Transport(err)).temp()
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,5 +189,23 @@ mod tests {
         let err: PipelineErrorKind =
             BlobProviderError::BlobDecoding(BlobDecodingError::InvalidFieldElement).into();
         assert!(matches!(err, PipelineErrorKind::Critical(_)));
+
+        let err: PipelineErrorKind =
+            BlobProviderError::BlobDecoding(BlobDecodingError::InvalidCommitment).into();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+
+        let err: PipelineErrorKind = BlobProviderError::Backend("timeout".to_string()).into();
+        let expected = ProviderErrorKind::Transport("timeout".to_string());
+        assert_eq!(err, PipelineError::Provider(expected).temp());
+    }
+
+    #[test]
+    fn test_from_synthetic_chain_error() {
+        let err: PipelineErrorKind = SyntheticChainError::BlockNotFound(3).into();
+        assert_eq!(err, PipelineError::Provider(ProviderErrorKind::BlockNotFound(3)).temp());
+
+        let err: PipelineErrorKind = SyntheticChainError::HashNotFound(B256::ZERO).into();
+        let expected = ProviderErrorKind::BlockHashNotFound(B256::ZERO);
+        assert_eq!(err, PipelineError::Provider(expected).temp());
     }
 }
@@ -1,13 +1,115 @@
 //! Error types for derivation pipeline stages.
 
-use op_alloy_protocol::MAX_SPAN_BATCH_ELEMENTS;
+use op_alloy_protocol::{FrameParseError, SpanBatchError};
 
-/// A frame decompression error.
-#[derive(derive_more::Display, Debug, PartialEq, Eq)]
+/// A channel decompression error.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
 pub enum BatchDecompressionError {
-    /// The buffer exceeds the [MAX_SPAN_BATCH_ELEMENTS] protocol parameter.
-    #[display("The batch exceeds the maximum number of elements: {max_size}", max_size = MAX_SPAN_BATCH_ELEMENTS)]
+    /// The decompressed channel data exceeds the maximum number of RLP bytes per channel.
+    #[display("Decompressed channel data exceeds the maximum number of RLP bytes")]
     BatchTooLarge,
+    /// The channel data is not a valid compressed stream.
+    #[display("Channel data is not a valid compressed stream")]
+    InvalidData,
+    /// The compression algorithm of the channel is not active at its L1 origin.
+    #[display("{_0} compression is not active at the channel's L1 origin")]
+    InactiveCompression(&'static str),
 }
 
 impl core::error::Error for BatchDecompressionError {}
+
+/// An integrity error found while parsing the frames of a batcher transaction.
+///
+/// Per the spec, if any frame in a batcher transaction fails to parse, all frames in the
+/// transaction are rejected.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum FrameValidationError {
+    /// The transaction data could not be parsed into frames.
+    #[display("Failed to parse frames: {_0}")]
+    Parse(FrameParseError),
+    /// The frame at the given index has more data than the maximum frame length.
+    #[display("Frame {_0} has {_1} bytes of data, max is {_2}")]
+    DataTooLong(usize, usize, usize),
+}
+
+impl core::error::Error for FrameValidationError {}
+
+/// An error encoding frames into batcher transaction data.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum FrameEncodingError {
+    /// No frames were provided.
+    #[display("No frames to encode")]
+    NoFrames,
+    /// A frame belongs to a different channel than the first frame.
+    #[display("Frame #{_0} belongs to a different channel")]
+    MixedChannels(u16),
+    /// The frame data is longer than the maximum frame length.
+    #[display("Frame #{_0} has {_1} bytes of data, max is {_2}")]
+    DataTooLong(u16, usize, usize),
+}
+
+impl core::error::Error for FrameEncodingError {}
+
+/// An error building a channel with a [ChannelOut].
+///
+/// [ChannelOut]: crate::types::ChannelOut
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum ChannelOutError {
+    /// A span batch could not be converted to its raw encoding.
+    #[display("Failed to encode span batch: {_0}")]
+    SpanBatch(SpanBatchError),
+    /// The maximum frame size does not leave room for any frame data.
+    #[display("Max frame size {_0} does not exceed the frame overhead")]
+    FrameSizeTooSmall(usize),
+    /// The channel needs more frames than a frame number can represent.
+    #[display("Channel needs more than 65536 frames")]
+    TooManyFrames,
+}
+
+impl From<SpanBatchError> for ChannelOutError {
+    fn from(err: SpanBatchError) -> Self {
+        Self::SpanBatch(err)
+    }
+}
+
+impl core::error::Error for ChannelOutError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::SpanBatch(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// An error assembling a single channel from its frames.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum ChannelAssemblyError {
+    /// No frames were provided.
+    #[display("No frames to assemble")]
+    NoFrames,
+    /// A frame belongs to a different channel than the first frame.
+    #[display("Frame #{_0} belongs to a different channel")]
+    MixedChannels(u16),
+    /// The channel rejected a frame, e.g. a duplicate frame number or a frame after the last.
+    #[display("Channel rejected frame #{_0}")]
+    FrameRejected(u16),
+    /// The channel is missing frames or its closing frame.
+    #[display("Channel is incomplete")]
+    Incomplete,
+}
+
+impl core::error::Error for ChannelAssemblyError {}
+
+/// An error decoding the batches of an assembled channel.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum BatchDecodeError {
+    /// The channel data failed to decompress, exceeded the RLP byte limit, or contained a
+    /// malformed batch after the given number of successfully decoded batches.
+    #[display("Malformed channel data after {_0} batches")]
+    Malformed(usize),
+    /// A span batch exceeds the maximum number of elements.
+    #[display("Span batch has {_0} elements, max is {_1}")]
+    SpanBatchTooLarge(usize, u64),
+}
+
+impl core::error::Error for BatchDecodeError {}
@@ -0,0 +1,680 @@
+//! End-to-end tests that run the full default [DerivationPipeline] against in-memory chains.
+//!
+//! The [Harness] fabricates a miniature L1 chain, the expected L2 chain, and the batcher
+//! transactions posted for it, so that every derived payload can be checked against attributes
+//! computed independently of the pipeline.
+//!
+//! [DerivationPipeline]: crate::pipeline::DerivationPipeline
+
+use crate::{
+    attributes::StatefulAttributesBuilder,
+    decode::encode_frames,
+    errors::{PipelineErrorKind, ResetError},
+    pipeline::{DerivationPipeline, PipelineBuilder, StandardPipeline},
+    sources::{
+        AltDaCommitment, AltDaSource, LocalFrameSource, RecordingChainProvider,
+        ReplayChainProvider, SyntheticChainProvider, ALT_DA_DERIVATION_VERSION, EIGEN_DA_LAYER,
+    },
+    stages::{
+        AttributesQueue, BatchProvider, BatchStream, ChannelProvider, ChannelReader, FrameQueue,
+        L1Retrieval, L1Traversal, FRAME_OVERHEAD_LEN,
+    },
+    test_utils::{TestAltDaProvider, TestL2ChainProvider, TestReplaySink},
+    traits::{
+        ChainProvider, DataAvailabilityProvider, DerivationMetrics, L2ChainProvider,
+        OriginProvider, Pipeline, SignalReceiver,
+    },
+    types::{
+        ActivationSignal, ChannelId, ChannelOut, CompressionAlgo, CountingMetrics, MetricsSummary,
+        Signal, StepResult,
+    },
+};
+use alloc::{sync::Arc, vec, vec::Vec};
+use alloy_consensus::{Signed, TxEip1559, TxEnvelope};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{address, keccak256, Address, Bytes, Signature, TxKind, B256};
+use alloy_rlp::Decodable;
+use core::fmt::Debug;
+use op_alloy_consensus::TxDeposit;
+use op_alloy_genesis::{ChainGenesis, RollupConfig, SystemConfig};
+use op_alloy_protocol::{
+    Batch, BlockInfo, L1BlockInfoTx, L2BlockInfo, SingleBatch, SpanBatch, FRAME_OVERHEAD,
+};
+use op_alloy_rpc_types_engine::OpAttributesWithParent;
+
+/// The L1 block time of the harness chains.
+const L1_BLOCK_TIME: u64 = 12;
+
+/// The gas limit of the harness system config.
+const GAS_LIMIT: u64 = 30_000_000;
+
+/// The sequencer fee vault, which receives the fees of every derived block.
+const SEQUENCER_FEE_VAULT: Address = address!("4200000000000000000000000000000000000011");
+
+/// The maximum number of pipeline steps before a test is considered stuck.
+const MAX_STEPS: usize = 1_000;
+
+/// The attributes builder used by the harness pipeline.
+type HarnessAttributesBuilder =
+    StatefulAttributesBuilder<SyntheticChainProvider, TestL2ChainProvider>;
+
+/// The [ChannelReader] stage of the harness pipeline.
+type HarnessChannelReader<D = LocalFrameSource> =
+    ChannelReader<ChannelProvider<FrameQueue<L1Retrieval<D, L1Traversal<SyntheticChainProvider>>>>>;
+
+/// The [BatchProvider] stage of the harness pipeline.
+type HarnessBatchProvider<D = LocalFrameSource> =
+    BatchProvider<BatchStream<HarnessChannelReader<D>, TestL2ChainProvider>, TestL2ChainProvider>;
+
+/// The default pipeline, composed over the harness providers.
+type HarnessPipeline<D = LocalFrameSource> = DerivationPipeline<
+    AttributesQueue<HarnessBatchProvider<D>, HarnessAttributesBuilder>,
+    TestL2ChainProvider,
+>;
+
+/// A miniature L1 chain with posted batcher data for an expected L2 chain.
+#[derive(Debug)]
+struct Harness {
+    /// The rollup config.
+    cfg: Arc<RollupConfig>,
+    /// The L1 chain.
+    l1: SyntheticChainProvider,
+    /// The expected L2 chain, starting with the genesis block.
+    l2_chain: Vec<L2BlockInfo>,
+    /// The user transactions of each L2 block, by number.
+    l2_txs: Vec<Vec<Bytes>>,
+    /// The batcher transactions posted in each L1 block, by number.
+    batcher_txs: Vec<Vec<Bytes>>,
+    /// The metrics recorder handed to the pipeline, if any.
+    metrics: Option<Arc<dyn DerivationMetrics>>,
+}
+
+impl Harness {
+    /// Creates a harness with `l1_blocks` L1 blocks and a rollup config produced by `cfg`, which
+    /// receives the config with the harness genesis already set.
+    fn new(l1_blocks: u64, cfg: impl FnOnce(RollupConfig) -> RollupConfig) -> Self {
+        let l1 = SyntheticChainProvider::new(0, 0, L1_BLOCK_TIME, l1_blocks);
+        let system_config = SystemConfig {
+            batcher_address: address!("000000000000000000000000000000000000ba7c"),
+            gas_limit: GAS_LIMIT,
+            ..Default::default()
+        };
+        let l2_genesis = L2BlockInfo {
+            block_info: BlockInfo { hash: l2_hash(0), ..Default::default() },
+            l1_origin: l1.block(0).expect("L1 genesis").id(),
+            seq_num: 0,
+        };
+        let cfg = cfg(RollupConfig {
+            genesis: ChainGenesis {
+                l1: l2_genesis.l1_origin,
+                l2: l2_genesis.block_info.id(),
+                l2_time: 0,
+                system_config: Some(system_config),
+            },
+            block_time: 2,
+            max_sequencer_drift: 600,
+            seq_window_size: 100,
+            channel_timeout: 50,
+            l1_chain_id: 1,
+            l2_chain_id: 10,
+            batch_inbox_address: address!("ff00000000000000000000000000000000000010"),
+            ..Default::default()
+        });
+        Self {
+            cfg: Arc::new(cfg),
+            l2_chain: vec![l2_genesis],
+            l2_txs: vec![Vec::new()],
+            batcher_txs: vec![Vec::new(); l1_blocks as usize],
+            l1,
+            metrics: None,
+        }
+    }
+
+    /// Returns the L1 block with the given number.
+    fn l1_block(&self, number: u64) -> BlockInfo {
+        self.l1.block(number).expect("L1 block")
+    }
+
+    /// Appends an L2 block with the given user transactions to the expected L2 chain. The block
+    /// adopts the next epoch if its timestamp has reached it.
+    fn push_l2_block(&mut self, txs: Vec<Bytes>) -> SingleBatch {
+        let parent = self.l2_chain[self.l2_chain.len() - 1];
+        let number = parent.block_info.number + 1;
+        let timestamp = parent.block_info.timestamp + self.cfg.block_time;
+        let next_epoch = self.l1_block(parent.l1_origin.number + 1);
+        let (l1_origin, seq_num) = if timestamp >= next_epoch.timestamp {
+            (next_epoch.id(), 0)
+        } else {
+            (parent.l1_origin, parent.seq_num + 1)
+        };
+        self.l2_chain.push(L2BlockInfo {
+            block_info: BlockInfo {
+                hash: l2_hash(number),
+                number,
+                parent_hash: parent.block_info.hash,
+                timestamp,
+            },
+            l1_origin,
+            seq_num,
+        });
+        self.l2_txs.push(txs.clone());
+        SingleBatch {
+            parent_hash: parent.block_info.hash,
+            epoch_num: l1_origin.number,
+            epoch_hash: l1_origin.hash,
+            timestamp,
+            transactions: txs,
+        }
+    }
+
+    /// Posts a zlib channel with the given batches, split into one frame per entry of
+    /// `inclusions`, each posted in the L1 block with that number.
+    fn post_channel(&mut self, id: u8, batches: Vec<Batch>, inclusions: &[u64]) {
+        let mut channel_out = ChannelOut::new(ChannelId::new([id; 16]), CompressionAlgo::Zlib);
+        batches.into_iter().for_each(|batch| channel_out.add_batch(batch).unwrap());
+        let frame_len = channel_out.ready_bytes().div_ceil(inclusions.len());
+        let frames = channel_out.output_frames(frame_len + FRAME_OVERHEAD_LEN).unwrap();
+        for (frame, &l1_block) in frames.iter().zip(inclusions) {
+            let tx = encode_frames(core::slice::from_ref(frame)).unwrap();
+            self.batcher_txs[l1_block as usize].push(tx.into());
+        }
+    }
+
+    /// Builds the default pipeline over the harness chains, starting at the L2 genesis.
+    fn pipeline(&self) -> HarnessPipeline {
+        self.pipeline_with(LocalFrameSource::new(0, self.batcher_txs.clone()))
+    }
+
+    /// Builds the default pipeline over the harness chains with the given data source, starting
+    /// at the L2 genesis.
+    fn pipeline_with<D>(&self, dap: D) -> HarnessPipeline<D>
+    where
+        D: DataAvailabilityProvider + Send + Sync + Debug,
+    {
+        self.pipeline_with_providers(self.l1.clone(), self.l2_provider(), dap)
+    }
+
+    /// Returns an L2 chain provider serving the expected L2 chain.
+    fn l2_provider(&self) -> TestL2ChainProvider {
+        let mut l2_provider =
+            TestL2ChainProvider { blocks: self.l2_chain.clone(), ..Default::default() };
+        for block in &self.l2_chain {
+            let system_config = self.cfg.genesis.system_config.expect("genesis system config");
+            l2_provider.system_configs.insert(block.block_info.number, system_config);
+        }
+        l2_provider
+    }
+
+    /// Builds the default pipeline with the given providers, which must serve the harness chains,
+    /// starting at the L2 genesis.
+    fn pipeline_with_providers<P, T, D>(
+        &self,
+        l1_provider: P,
+        l2_provider: T,
+        dap: D,
+    ) -> StandardPipeline<StatefulAttributesBuilder<P, T>, P, T, D>
+    where
+        P: ChainProvider + Clone + Send + Sync + Debug,
+        T: L2ChainProvider + Clone + Send + Sync + Debug,
+        D: DataAvailabilityProvider + Send + Sync + Debug,
+    {
+        let builder = StatefulAttributesBuilder::new(
+            self.cfg.clone(),
+            l2_provider.clone(),
+            l1_provider.clone(),
+        );
+        let mut builder = PipelineBuilder::new()
+            .rollup_config(self.cfg.clone())
+            .origin(self.l1_block(0))
+            .dap_source(dap)
+            .builder(builder)
+            .chain_provider(l1_provider)
+            .l2_chain_provider(l2_provider);
+        if let Some(metrics) = &self.metrics {
+            builder = builder.metrics(Arc::clone(metrics));
+        }
+        builder.build().expect("valid harness pipeline")
+    }
+
+    /// Runs the pipeline until it derives the full expected L2 chain, advancing the cursor along
+    /// the expected chain after each derived payload. Like the driver, a Holocene activation is
+    /// handled by signalling it to the pipeline rather than resetting.
+    async fn derive<P>(&self, pipeline: &mut P) -> Vec<OpAttributesWithParent>
+    where
+        P: Pipeline + SignalReceiver + Send,
+    {
+        let target = self.l2_chain.len() - 1;
+        let mut derived = Vec::new();
+        for _ in 0..MAX_STEPS {
+            if derived.len() == target {
+                return derived;
+            }
+            match pipeline.step(self.l2_chain[derived.len()]).await {
+                StepResult::PreparedAttributes => {
+                    derived.push(pipeline.next().expect("prepared attributes"));
+                }
+                StepResult::AdvancedOrigin => {}
+                StepResult::OriginAdvanceErr(PipelineErrorKind::Reset(
+                    ResetError::HoloceneActivation,
+                )) => {
+                    let signal = ActivationSignal {
+                        l2_safe_head: self.l2_chain[derived.len()],
+                        l1_origin: pipeline.origin().expect("pipeline origin"),
+                        system_config: None,
+                    };
+                    pipeline.signal(signal.signal()).await.expect("activation signal");
+                }
+                StepResult::OriginAdvanceErr(PipelineErrorKind::Temporary(_)) |
+                StepResult::StepFailed(PipelineErrorKind::Temporary(_)) => {}
+                result => panic!("unexpected step result: {result}"),
+            }
+        }
+        panic!("derived {} of {target} blocks after {MAX_STEPS} steps", derived.len());
+    }
+
+    /// Asserts that the derived attributes match the expected L2 chain.
+    fn assert_derived(&self, derived: &[OpAttributesWithParent], last_in_span: &[bool]) {
+        assert_eq!(derived.len(), self.l2_chain.len() - 1);
+        for (i, attrs) in derived.iter().enumerate() {
+            let block = self.l2_chain[i + 1];
+            let epoch = &self.l1.headers()[block.l1_origin.number as usize];
+            assert_eq!(attrs.parent, self.l2_chain[i], "parent of block #{}", i + 1);
+            assert_eq!(attrs.is_last_in_span, last_in_span[i], "span end at block #{}", i + 1);
+
+            let payload = &attrs.attributes.payload_attributes;
+            assert_eq!(payload.timestamp, block.block_info.timestamp);
+            assert_eq!(payload.prev_randao, epoch.mix_hash);
+            assert_eq!(payload.suggested_fee_recipient, SEQUENCER_FEE_VAULT);
+            assert_eq!(payload.withdrawals, Some(Vec::new()));
+            let parent_beacon_root =
+                self.cfg.is_ecotone_active(payload.timestamp).then_some(B256::ZERO);
+            assert_eq!(payload.parent_beacon_block_root, parent_beacon_root);
+            assert_eq!(attrs.attributes.no_tx_pool, Some(true));
+            assert_eq!(attrs.attributes.gas_limit, Some(GAS_LIMIT));
+
+            // The L1 info deposit comes first, followed by the batched user transactions.
+            let txs = attrs.attributes.transactions.as_ref().expect("transactions");
+            assert_eq!(txs[0][0], 0x7E, "L1 info deposit of block #{}", i + 1);
+            let deposit = TxDeposit::decode(&mut &txs[0][1..]).unwrap();
+            let l1_info = L1BlockInfoTx::decode_calldata(deposit.input.as_ref()).unwrap();
+            assert_eq!(l1_info.id(), block.l1_origin);
+            assert_eq!(l1_info.sequence_number(), block.seq_num);
+            assert_eq!(&txs[1..], self.l2_txs[i + 1].as_slice());
+        }
+    }
+}
+
+/// Returns the hash of the expected L2 block with the given number.
+fn l2_hash(number: u64) -> B256 {
+    keccak256([b"l2".as_slice(), &number.to_be_bytes()].concat())
+}
+
+/// Returns an encoded, signed EIP-1559 transaction for the given chain.
+fn user_tx(chain_id: u64) -> Bytes {
+    let to = TxKind::Call(Address::ZERO);
+    let tx = TxEip1559 { chain_id, nonce: 1, to, ..Default::default() };
+    let signed = Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO);
+    TxEnvelope::Eip1559(signed).encoded_2718().into()
+}
+
+/// Combines the given consecutive [SingleBatch]es into a span batch.
+fn span_batch(cfg: &RollupConfig, batches: &[(SingleBatch, u64)]) -> Batch {
+    let mut span = SpanBatch {
+        genesis_timestamp: cfg.genesis.l2_time,
+        chain_id: cfg.l2_chain_id,
+        ..Default::default()
+    };
+    for (batch, seq_num) in batches {
+        span.append_singular_batch(batch.clone(), *seq_num).unwrap();
+    }
+    Batch::Span(span)
+}
+
+#[tokio::test]
+async fn test_default_pipeline_derives_chain() {
+    let mut harness = Harness::new(5, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        ..cfg
+    });
+
+    // Blocks #1-#5 belong to epoch 0 and are posted as single batches in a channel split over
+    // two frames in L1 blocks #1 and #2. Block #3 carries a user transaction.
+    let singles = (1..=5)
+        .map(|n| {
+            let txs = if n == 3 { vec![Bytes::from_static(&[0x02, 0xAA])] } else { Vec::new() };
+            Batch::Single(harness.push_l2_block(txs))
+        })
+        .collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 2]);
+
+    // Blocks #6-#10 adopt epoch 1 and are posted as a single span batch in L1 block #3. Block #8
+    // carries a user transaction, which the span batch encodes in its own format.
+    let span = (6..=10)
+        .map(|n| {
+            let txs = if n == 8 { vec![user_tx(harness.cfg.l2_chain_id)] } else { Vec::new() };
+            let batch = harness.push_l2_block(txs);
+            (batch, harness.l2_chain[harness.l2_chain.len() - 1].seq_num)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(span[0].0.epoch_num, 1);
+    harness.post_channel(0xBB, vec![span_batch(&harness.cfg, &span)], &[3]);
+
+    let mut pipeline = harness.pipeline();
+    let derived = harness.derive(&mut pipeline).await;
+    let last_in_span = [true, true, true, true, true, false, false, false, false, true];
+    harness.assert_derived(&derived, &last_in_span);
+}
+
+#[tokio::test]
+async fn test_alt_da_pipeline_derives_chain() {
+    let mut harness = Harness::new(4, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        ..cfg
+    });
+    let singles =
+        (1..=3).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 2]);
+
+    // Post an EigenDA commitment in place of every batcher transaction, and serve the frames
+    // from the alt-DA provider.
+    let mut alt_da = TestAltDaProvider::default();
+    for tx in harness.batcher_txs.iter_mut().flatten() {
+        let commitment = AltDaCommitment::Generic {
+            da_layer: EIGEN_DA_LAYER,
+            payload: Bytes::copy_from_slice(keccak256(&tx[..]).as_slice()),
+        };
+        alt_da.insert_input(commitment.clone(), tx.clone());
+        let mut data = vec![ALT_DA_DERIVATION_VERSION];
+        data.extend_from_slice(&commitment.encode());
+        *tx = data.into();
+    }
+    let dap = AltDaSource::new(LocalFrameSource::new(0, harness.batcher_txs.clone()), alt_da);
+
+    let mut pipeline = harness.pipeline_with(dap);
+    let derived = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&derived, &[true, true, true]);
+}
+
+#[tokio::test]
+async fn test_dry_run_assembles_channel_across_blocks() {
+    let mut harness = Harness::new(5, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        channel_timeout: 2,
+        ..cfg
+    });
+    let singles =
+        (1..=3).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 3]);
+
+    // The second frame lands in the last L1 block before the channel times out.
+    let mut pipeline = harness.pipeline();
+    let derived = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&derived, &[true, true, true]);
+}
+
+#[tokio::test]
+async fn test_dry_run_channel_timeout() {
+    let mut harness = Harness::new(5, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        channel_timeout: 1,
+        ..cfg
+    });
+    let singles =
+        (1..=3).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 3]);
+
+    // The channel times out before its second frame lands, so the pipeline traverses the whole
+    // L1 chain without deriving any attributes.
+    let mut pipeline = harness.pipeline();
+    for _ in 0..MAX_STEPS {
+        if let StepResult::PreparedAttributes = pipeline.step(harness.l2_chain[0]).await {
+            panic!("derived attributes from a timed out channel");
+        }
+    }
+    assert_eq!(pipeline.origin(), Some(harness.l1_block(4)));
+}
+
+#[tokio::test]
+async fn test_pipeline_record_and_replay() {
+    let mut harness = Harness::new(4, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        ..cfg
+    });
+    let singles =
+        (1..=3).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 2]);
+
+    // Record the L1 and L2 provider traffic of a run into a single log.
+    let sink = Arc::new(TestReplaySink::default());
+    let l1 = RecordingChainProvider::new(harness.l1.clone(), sink.clone());
+    let l2 = RecordingChainProvider::new(harness.l2_provider(), sink.clone());
+    let dap = LocalFrameSource::new(0, harness.batcher_txs.clone());
+    let mut pipeline = harness.pipeline_with_providers(l1, l2, dap);
+    let recorded = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&recorded, &[true, true, true]);
+
+    // Replaying the log alone derives identical attributes.
+    let replay = ReplayChainProvider::new(sink.entries());
+    let dap = LocalFrameSource::new(0, harness.batcher_txs.clone());
+    let mut pipeline = harness.pipeline_with_providers(replay.clone(), replay, dap);
+    let replayed = harness.derive(&mut pipeline).await;
+    assert_eq!(recorded, replayed);
+}
+
+#[tokio::test]
+async fn test_pipeline_records_metrics() {
+    let mut harness = Harness::new(4, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        ..cfg
+    });
+    let singles =
+        (1..=3).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 2]);
+    let metrics = CountingMetrics::new();
+    harness.metrics = Some(Arc::new(metrics.clone()));
+
+    let mut pipeline = harness.pipeline();
+    let derived = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&derived, &[true, true, true]);
+
+    // Every batcher transaction carries a single frame, behind the version byte and the 23 bytes
+    // of frame overhead.
+    let frame_bytes = harness.batcher_txs.iter().flatten().map(|tx| tx.len() as u64 - 24).sum();
+    let expected = MetricsSummary {
+        frames: 2,
+        frame_bytes,
+        channels_opened: 1,
+        channels_ready: 1,
+        batches_accepted: 3,
+        attributes_prepared: 3,
+        ..Default::default()
+    };
+    let summary = metrics.summary();
+    let summary_without_distance =
+        MetricsSummary { safe_origin_distance: 0, max_safe_origin_distance: 0, ..summary };
+    assert_eq!(summary_without_distance, expected);
+    assert!(summary.max_safe_origin_distance >= 2);
+}
+
+#[tokio::test]
+async fn test_pipeline_derives_across_holocene_activation() {
+    // Holocene activates at the timestamp of L1 block #2.
+    let mut harness = Harness::new(4, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        ecotone_time: Some(0),
+        fjord_time: Some(0),
+        granite_time: Some(0),
+        holocene_time: Some(2 * L1_BLOCK_TIME),
+        ..cfg
+    });
+
+    // Blocks #1-#5 belong to epoch 0 and are posted before the activation, in L1 block #1.
+    let singles =
+        (1..=5).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1]);
+
+    // Blocks #6-#8 adopt epoch 1 and are posted after the activation, in L1 block #3.
+    let singles =
+        (6..=8).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    assert_eq!(harness.l2_chain[6].l1_origin.number, 1);
+    harness.post_channel(0xBB, singles, &[3]);
+
+    let mut pipeline = harness.pipeline();
+    let derived = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&derived, &[true; 8]);
+
+    // The same pipeline instance switched to the Holocene stages.
+    let stages = pipeline.stage_origins().into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+    assert!(stages.contains(&"channel-assembler") && stages.contains(&"batch-validator"));
+    assert!(!stages.contains(&"channel-bank") && !stages.contains(&"batch-queue"));
+    assert_eq!(pipeline.origin().map(|origin| origin.number), Some(3));
+}
+
+#[tokio::test]
+async fn test_pipeline_signal_advance_origin_keeps_channels() {
+    let mut harness = Harness::new(4, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        ..cfg
+    });
+    let singles =
+        (1..=3).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 2]);
+    let metrics = CountingMetrics::new();
+    harness.metrics = Some(Arc::new(metrics.clone()));
+
+    // Step until the first frame of the channel, posted in L1 block #1, is in the channel bank.
+    let mut pipeline = harness.pipeline();
+    for _ in 0..MAX_STEPS {
+        if metrics.summary().frames == 1 {
+            break;
+        }
+        let _ = pipeline.step(harness.l2_chain[0]).await;
+    }
+    assert_eq!(metrics.summary().frames, 1);
+    assert_eq!(pipeline.origin(), Some(harness.l1_block(1)));
+
+    // Force the pipeline onto L1 block #2. The pending channel survives and is completed by the
+    // frame posted there, which it would not be after a reset.
+    pipeline.signal(Signal::AdvanceOrigin).await.unwrap();
+    assert_eq!(pipeline.origin(), Some(harness.l1_block(2)));
+    let derived = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&derived, &[true, true, true]);
+    assert_eq!(metrics.summary().channels_ready, 1);
+}
+
+#[tokio::test]
+async fn test_pipeline_fills_expired_sequencer_windows() {
+    // With a sequencer window of two L1 blocks, epoch N expires once the pipeline reaches L1
+    // block #N+3, or block #N+2 once it has no more data.
+    let mut harness = Harness::new(6, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        seq_window_size: 2,
+        channel_timeout: 2,
+        ..cfg
+    });
+
+    // The batcher is offline for epochs 0-2, so every block is a deposit-only block. The epoch
+    // advances as soon as the L2 time reaches the time of the next L1 block.
+    for _ in 1..=17 {
+        harness.push_l2_block(Vec::new());
+    }
+    assert_eq!(harness.l2_chain[6].l1_origin.number, 1);
+    assert_eq!(harness.l2_chain[17].l1_origin.number, 2);
+
+    let mut pipeline = harness.pipeline();
+    let derived = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&derived, &[true; 17]);
+}
+
+#[tokio::test]
+async fn test_pipeline_resumes_batches_after_expired_sequencer_windows() {
+    let mut harness = Harness::new(6, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        seq_window_size: 2,
+        channel_timeout: 2,
+        ..cfg
+    });
+
+    // The batcher is offline for epochs 0 and 1, whose windows expire at L1 blocks #3 and #4.
+    for _ in 1..=11 {
+        harness.push_l2_block(Vec::new());
+    }
+
+    // It comes back for epoch 2, posting its blocks in L1 block #3. They are buffered until
+    // the deposit-only blocks of epoch 1 reach their parent. Block #12 carries a user
+    // transaction.
+    let singles = (12..=17)
+        .map(|n| {
+            let txs = if n == 12 { vec![Bytes::from_static(&[0x02, 0xAA])] } else { Vec::new() };
+            Batch::Single(harness.push_l2_block(txs))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(harness.l2_chain[12].l1_origin.number, 2);
+    harness.post_channel(0xAA, singles, &[3]);
+
+    let mut pipeline = harness.pipeline();
+    let derived = harness.derive(&mut pipeline).await;
+    harness.assert_derived(&derived, &[true; 17]);
+}
+
+#[tokio::test]
+#[cfg(feature = "serde")]
+async fn test_pipeline_status_snapshot() {
+    let mut harness = Harness::new(4, |cfg| RollupConfig {
+        regolith_time: Some(0),
+        canyon_time: Some(0),
+        delta_time: Some(0),
+        ..cfg
+    });
+    let singles =
+        (1..=3).map(|_| Batch::Single(harness.push_l2_block(Vec::new()))).collect::<Vec<_>>();
+    harness.post_channel(0xAA, singles, &[1, 2]);
+    let metrics = CountingMetrics::new();
+    harness.metrics = Some(Arc::new(metrics.clone()));
+
+    // Step until the first frame of the channel, posted in L1 block #1, is in the channel bank.
+    let mut pipeline = harness.pipeline();
+    for _ in 0..MAX_STEPS {
+        if metrics.summary().frames == 1 {
+            break;
+        }
+        let _ = pipeline.step(harness.l2_chain[0]).await;
+    }
+
+    let frame_bytes = harness.batcher_txs[1][0].len() - 24;
+    let status = serde_json::to_value(pipeline.status()).unwrap();
+    let expected = serde_json::json!({
+        "origin": harness.l1_block(1),
+        "last_prepared_cursor": null,
+        "prepared_len": 0,
+        "stage_origins": pipeline.stage_origins(),
+        "channels": 1,
+        "channel_bytes": frame_bytes + FRAME_OVERHEAD,
+        "buffered_batches": 0,
+    });
+    assert_eq!(status, expected);
+
+    // The status names every stage of the pre-Holocene pipeline.
+    let stages = pipeline.stage_origins().into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+    assert!(stages.contains(&"channel-bank") && stages.contains(&"batch-queue"));
+}
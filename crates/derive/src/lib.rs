@@ -5,18 +5,20 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 extern crate alloc;
 
 /// Required types and traits for kona's derivation pipeline.
 pub mod prelude {
     pub use crate::{
-        attributes::*, errors::*, pipeline::*, sources::*, stages::*, traits::*, types::*,
+        attributes::*, decode::*, errors::*, pipeline::*, sources::*, stages::*, traits::*,
+        types::*,
     };
 }
 
 pub mod attributes;
+pub mod decode;
 pub mod errors;
 pub mod pipeline;
 pub mod sources;
@@ -26,3 +28,6 @@ pub mod types;
 
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
+
+#[cfg(test)]
+mod integration_tests;
@@ -0,0 +1,198 @@
+//! Contains the [BatchStreamReader], a standalone facade over the batch decoding stages.
+
+use crate::{
+    errors::{PipelineError, PipelineErrorKind, ResetError},
+    stages::{
+        BatchStreamProvider, ChannelProvider, ChannelReader, FrameQueue, L1Retrieval, L1Traversal,
+    },
+    traits::{ChainProvider, DataAvailabilityProvider, OriginAdvancer, OriginProvider},
+    types::PipelineResult,
+};
+use alloc::sync::Arc;
+use core::fmt::Debug;
+use op_alloy_genesis::RollupConfig;
+use op_alloy_protocol::{BatchWithInclusionBlock, BlockInfo};
+use tracing::{debug, trace};
+
+type ChannelReaderStage<P, D> =
+    ChannelReader<ChannelProvider<FrameQueue<L1Retrieval<D, L1Traversal<P>>>>>;
+
+/// Metadata about where a decoded batch was included on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InclusionMeta {
+    /// The L1 block in which the channel containing the batch was completed.
+    pub inclusion_block: BlockInfo,
+    /// The index of the batch among all batches decoded at the inclusion block.
+    pub index: usize,
+}
+
+/// The [BatchStreamReader] decodes batches from L1 without running the full derivation pipeline.
+///
+/// It wires the [L1Traversal], [L1Retrieval], [FrameQueue], [ChannelProvider] and
+/// [ChannelReader] stages together and yields every decoded batch for a range of L1 blocks.
+/// Batches are not validated against L2 state, so no L2 chain provider or attributes builder is
+/// required. This is useful for indexers and batch explorers.
+#[derive(Debug)]
+pub struct BatchStreamReader<P, D>
+where
+    P: ChainProvider + Send + Debug,
+    D: DataAvailabilityProvider + Send + Debug,
+{
+    /// The channel reader at the top of the decoding stage stack.
+    reader: ChannelReaderStage<P, D>,
+    /// The last L1 block number (inclusive) to read batches from.
+    end: u64,
+    /// The number of batches decoded at the current origin.
+    index: usize,
+}
+
+impl<P, D> BatchStreamReader<P, D>
+where
+    P: ChainProvider + Send + Debug,
+    D: DataAvailabilityProvider + Send + Debug,
+{
+    /// Creates a new [BatchStreamReader] that reads batches from the L1 blocks `start` through
+    /// `end` (inclusive).
+    pub fn new(
+        chain_provider: P,
+        dap_source: D,
+        rollup_config: Arc<RollupConfig>,
+        start: BlockInfo,
+        end: u64,
+    ) -> Self {
+        let mut l1_traversal = L1Traversal::new(chain_provider, Arc::clone(&rollup_config));
+        l1_traversal.block = Some(start);
+        l1_traversal.system_config =
+            rollup_config.genesis.system_config.as_ref().cloned().unwrap_or_default();
+        let l1_retrieval = L1Retrieval::new(l1_traversal, dap_source);
+        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config));
+        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue);
+        let reader = ChannelReader::new(channel_provider, rollup_config);
+        Self { reader, end, index: 0 }
+    }
+
+    /// Returns the current L1 origin of the reader.
+    pub fn origin(&self) -> Option<BlockInfo> {
+        self.reader.origin()
+    }
+
+    /// Returns the next decoded batch along with its [InclusionMeta].
+    ///
+    /// Returns `Ok(None)` once all batches up to and including the `end` L1 block have been read.
+    /// Temporary errors, such as [PipelineError::NotEnoughData] while a channel is being
+    /// assembled or a provider failure, are returned to the caller, which may call this again to
+    /// retry.
+    pub async fn next(
+        &mut self,
+    ) -> PipelineResult<Option<(BatchWithInclusionBlock, InclusionMeta)>> {
+        loop {
+            let origin = self.reader.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+            if origin.number > self.end {
+                return Ok(None);
+            }
+
+            match self.reader.next_batch().await {
+                Ok(batch) => {
+                    let meta = InclusionMeta { inclusion_block: origin, index: self.index };
+                    self.index += 1;
+                    trace!(target: "batch-stream-reader", "Decoded batch at L1 block #{}", origin.number);
+                    return Ok(Some((BatchWithInclusionBlock::new(origin, batch), meta)));
+                }
+                Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => {
+                    if origin.number >= self.end {
+                        return Ok(None);
+                    }
+                    match self.reader.advance_origin().await {
+                        // The origin has been advanced regardless of the Holocene activation, and
+                        // the stages switch to Holocene rules based on the new origin.
+                        Ok(()) | Err(PipelineErrorKind::Reset(ResetError::HoloceneActivation)) => {
+                            debug!(target: "batch-stream-reader", "Advanced origin past L1 block #{}", origin.number);
+                            self.index = 0;
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestChainProviderBuilder;
+    use alloc::{boxed::Box, vec, vec::Vec};
+    use alloy_primitives::{map::HashMap, Bytes};
+    use alloy_rlp::Encodable;
+    use async_trait::async_trait;
+    use miniz_oxide::deflate::compress_to_vec_zlib;
+    use op_alloy_protocol::{Batch, Frame, SingleBatch, DERIVATION_VERSION_0};
+
+    /// A [DataAvailabilityProvider] that serves data per L1 block number.
+    #[derive(Debug, Default)]
+    struct BlockDAP {
+        data: HashMap<u64, Vec<Bytes>>,
+    }
+
+    #[async_trait]
+    impl DataAvailabilityProvider for BlockDAP {
+        type Item = Bytes;
+
+        async fn next(&mut self, block: &BlockInfo) -> PipelineResult<Self::Item> {
+            self.data.get_mut(&block.number).and_then(|d| d.pop()).ok_or(PipelineError::Eof.temp())
+        }
+
+        fn clear(&mut self) {}
+    }
+
+    fn channel(id: u8, batches: &[SingleBatch]) -> Bytes {
+        let mut rlp = Vec::new();
+        for batch in batches {
+            let mut data = vec![0u8];
+            batch.encode(&mut data);
+            Bytes::from(data).encode(&mut rlp);
+        }
+        let frame =
+            Frame { id: [id; 16], number: 0, data: compress_to_vec_zlib(&rlp, 9), is_last: true };
+        let mut bytes = vec![DERIVATION_VERSION_0];
+        bytes.extend_from_slice(&frame.encode());
+        bytes.into()
+    }
+
+    #[tokio::test]
+    async fn test_batch_stream_reader_three_blocks() {
+        let builder = TestChainProviderBuilder::new(3);
+        let blocks = builder.blocks();
+
+        let batch = |timestamp| SingleBatch { timestamp, ..Default::default() };
+        let mut dap = BlockDAP::default();
+        dap.data.insert(0, vec![channel(1, &[batch(2), batch(4)])]);
+        dap.data.insert(2, vec![channel(3, &[batch(10)]), channel(2, &[batch(6), batch(8)])]);
+
+        let cfg = Arc::new(RollupConfig::default());
+        let mut reader = BatchStreamReader::new(builder.build(), dap, cfg, blocks[0], 2);
+
+        let mut decoded = Vec::new();
+        let mut temporary = 0;
+        loop {
+            let (batch, meta) = match reader.next().await {
+                Ok(Some(next)) => next,
+                Ok(None) => break,
+                Err(PipelineErrorKind::Temporary(_)) => {
+                    temporary += 1;
+                    continue;
+                }
+                Err(e) => panic!("unexpected error: {e}"),
+            };
+            assert_eq!(batch.inclusion_block, meta.inclusion_block);
+            let Batch::Single(single) = batch.batch else { panic!("expected single batch") };
+            decoded.push((single.timestamp, meta.inclusion_block.number, meta.index));
+        }
+
+        assert_eq!(decoded, vec![(2, 0, 0), (4, 0, 1), (6, 2, 0), (8, 2, 1), (10, 2, 2)]);
+        // Temporary errors while loading each channel were returned rather than retried.
+        assert!(temporary > 0);
+        assert_eq!(reader.origin(), Some(blocks[2]));
+    }
+}
@@ -1,17 +1,23 @@
 //! Contains the `PipelineBuilder` object that is used to build a `DerivationPipeline`.
 
 use crate::{
+    errors::PipelineBuildError,
     pipeline::DerivationPipeline,
     stages::{
         AttributesQueue, BatchProvider, BatchStream, ChannelProvider, ChannelReader, FrameQueue,
-        L1Retrieval, L1Traversal,
+        HoloceneSpanPolicy, L1Retrieval, L1Traversal,
     },
-    traits::{AttributesBuilder, ChainProvider, DataAvailabilityProvider, L2ChainProvider},
+    traits::{
+        AttributesBuilder, ChainProvider, DataAvailabilityProvider, DerivationMetrics,
+        L2ChainProvider, ValidateRollupConfig,
+    },
+    types::{DerivationLimits, EpochCache},
 };
 use alloc::sync::Arc;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::BlockInfo;
+use tracing::info;
 
 type L1TraversalStage<P> = L1Traversal<P>;
 type L1RetrievalStage<DAP, P> = L1Retrieval<DAP, L1TraversalStage<P>>;
@@ -22,7 +28,53 @@ type BatchStreamStage<DAP, P, T> = BatchStream<ChannelReaderStage<DAP, P>, T>;
 type BatchProviderStage<DAP, P, T> = BatchProvider<BatchStreamStage<DAP, P, T>, T>;
 type AttributesQueueStage<DAP, P, T, B> = AttributesQueue<BatchProviderStage<DAP, P, T>, B>;
 
+/// The [DerivationPipeline] over the standard stage stack built by the [PipelineBuilder], from
+/// the [L1Traversal] to the [AttributesQueue].
+///
+/// The type parameters are those of the [PipelineBuilder]: the attributes builder, the chain
+/// provider, the L2 chain provider and the data availability provider.
+pub type StandardPipeline<B, P, T, D> = DerivationPipeline<AttributesQueueStage<D, P, T, B>, T>;
+
 /// The `PipelineBuilder` constructs a [DerivationPipeline] using a builder pattern.
+///
+/// The rollup config, origin, data availability provider, attributes builder, chain provider and
+/// L2 chain provider must be set; [PipelineBuilder::build] fails naming the first one missing.
+///
+/// ```
+/// use core::fmt::Debug;
+/// use kona_derive::{
+///     errors::PipelineBuildError,
+///     pipeline::{PipelineBuilder, StandardPipeline},
+///     traits::{AttributesBuilder, ChainProvider, DataAvailabilityProvider, L2ChainProvider},
+/// };
+/// use op_alloy_genesis::RollupConfig;
+/// use op_alloy_protocol::BlockInfo;
+/// use std::sync::Arc;
+///
+/// fn new_pipeline<B, P, T, D>(
+///     cfg: Arc<RollupConfig>,
+///     origin: BlockInfo,
+///     builder: B,
+///     chain_provider: P,
+///     l2_chain_provider: T,
+///     dap: D,
+/// ) -> Result<StandardPipeline<B, P, T, D>, PipelineBuildError>
+/// where
+///     B: AttributesBuilder + Send + Debug,
+///     P: ChainProvider + Send + Sync + Debug,
+///     T: L2ChainProvider + Clone + Send + Sync + Debug,
+///     D: DataAvailabilityProvider + Send + Sync + Debug,
+/// {
+///     PipelineBuilder::new()
+///         .rollup_config(cfg)
+///         .origin(origin)
+///         .dap_source(dap)
+///         .builder(builder)
+///         .chain_provider(chain_provider)
+///         .l2_chain_provider(l2_chain_provider)
+///         .build()
+/// }
+/// ```
 #[derive(Debug)]
 pub struct PipelineBuilder<B, P, T, D>
 where
@@ -37,6 +89,17 @@ where
     builder: Option<B>,
     origin: Option<BlockInfo>,
     rollup_config: Option<Arc<RollupConfig>>,
+    validate_config: bool,
+    limits: DerivationLimits,
+    l1_end: Option<BlockInfo>,
+    strict: bool,
+    holocene_span_policy: HoloceneSpanPolicy,
+    epoch_cache: Option<EpochCache>,
+    warm_start: bool,
+    max_prepared: Option<usize>,
+    stall_threshold: Option<u64>,
+    skip_empty_origins: bool,
+    metrics: Option<Arc<dyn DerivationMetrics>>,
 }
 
 impl<B, P, T, D> Default for PipelineBuilder<B, P, T, D>
@@ -54,6 +117,17 @@ where
             builder: None,
             origin: None,
             rollup_config: None,
+            validate_config: true,
+            limits: DerivationLimits::SPEC,
+            l1_end: None,
+            strict: false,
+            holocene_span_policy: HoloceneSpanPolicy::Keep,
+            epoch_cache: None,
+            warm_start: false,
+            max_prepared: None,
+            stall_threshold: None,
+            skip_empty_origins: false,
+            metrics: None,
         }
     }
 }
@@ -76,12 +150,96 @@ where
         self
     }
 
+    /// Sets whether the rollup config is validated when the pipeline is built. Enabled by
+    /// default; disable only for devnets with deliberately unusual configs.
+    pub const fn validate_config(mut self, validate_config: bool) -> Self {
+        self.validate_config = validate_config;
+        self
+    }
+
+    /// Overrides the [DerivationLimits] enforced by the pipeline. Defaults to the spec values;
+    /// only chains with non-standard parameters should override them.
+    pub const fn limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether frames, channels and batches dropped due to a spec violation fail the
+    /// pipeline with a critical error instead of being skipped. Disabled by default; intended for
+    /// validating derivation test vectors.
+    ///
+    /// Strict mode covers malformed batcher transactions, frames dropped by the Holocene ordering
+    /// rules, invalid or late frames, frames for closed channels, timed out, oversized and
+    /// malformed channels, and invalid or (post-Holocene) future batches. Past batches and
+    /// channels evicted from a full channel bank are still dropped, as they are not spec
+    /// violations of the batcher.
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [HoloceneSpanPolicy] applied to span batches whose blocks straddle the Holocene
+    /// activation. Defaults to [HoloceneSpanPolicy::Keep], as specified.
+    pub const fn holocene_span_policy(mut self, policy: HoloceneSpanPolicy) -> Self {
+        self.holocene_span_policy = policy;
+        self
+    }
+
+    /// Sets the [EpochCache] shared with the attributes builder. The pipeline invalidates the
+    /// cache whenever it is reset.
+    pub fn epoch_cache(mut self, epoch_cache: EpochCache) -> Self {
+        self.epoch_cache = Some(epoch_cache);
+        self
+    }
+
+    /// Sets whether, after a reset, batches for blocks up to the reset safe head are
+    /// fast-forwarded by comparing only their timestamps to the safe head. Disabled by default.
+    pub const fn warm_start(mut self, warm_start: bool) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
+    /// Bounds the number of prepared attributes held by the pipeline. Unbounded by default.
+    pub const fn max_prepared(mut self, max_prepared: usize) -> Self {
+        self.max_prepared = Some(max_prepared);
+        self
+    }
+
+    /// Reports a [StepResult::Stalled] once more than `threshold` consecutive origins were
+    /// advanced past without preparing attributes. Stalls are not reported by default.
+    ///
+    /// [StepResult::Stalled]: crate::types::StepResult::Stalled
+    pub const fn stall_threshold(mut self, threshold: u64) -> Self {
+        self.stall_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets whether L1 blocks without derivation data are skipped while the origin is behind the
+    /// L1 origin of the cursor, as after a reset. Disabled by default.
+    pub const fn skip_empty_origins(mut self, skip_empty_origins: bool) -> Self {
+        self.skip_empty_origins = skip_empty_origins;
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recorder, shared by the pipeline and its stages.
+    pub fn metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Sets the origin L1 block for the pipeline.
     pub const fn origin(mut self, origin: BlockInfo) -> Self {
         self.origin = Some(origin);
         self
     }
 
+    /// Pins the pipeline to the given L1 block. Derivation consumes L1 data up to and including
+    /// this block, and treats all later blocks as nonexistent.
+    pub const fn l1_end(mut self, l1_end: BlockInfo) -> Self {
+        self.l1_end = Some(l1_end);
+        self
+    }
+
     /// Sets the data availability provider for the pipeline.
     pub fn dap_source(mut self, dap_source: D) -> Self {
         self.dap_source = Some(dap_source);
@@ -107,42 +265,155 @@ where
     }
 
     /// Builds the pipeline.
-    pub fn build(self) -> DerivationPipeline<AttributesQueueStage<D, P, T, B>, T> {
-        self.into()
+    ///
+    /// Fails if a required component is missing, or if config validation is enabled and the
+    /// rollup config is inconsistent. See [ValidateRollupConfig].
+    pub fn build(self) -> Result<StandardPipeline<B, P, T, D>, PipelineBuildError> {
+        self.try_into()
     }
 }
 
-impl<B, P, T, D> From<PipelineBuilder<B, P, T, D>>
-    for DerivationPipeline<AttributesQueueStage<D, P, T, B>, T>
+impl<B, P, T, D> TryFrom<PipelineBuilder<B, P, T, D>> for StandardPipeline<B, P, T, D>
 where
     B: AttributesBuilder + Send + Debug,
     P: ChainProvider + Send + Sync + Debug,
     T: L2ChainProvider + Clone + Send + Sync + Debug,
     D: DataAvailabilityProvider + Send + Sync + Debug,
 {
-    fn from(builder: PipelineBuilder<B, P, T, D>) -> Self {
+    type Error = PipelineBuildError;
+
+    fn try_from(builder: PipelineBuilder<B, P, T, D>) -> Result<Self, Self::Error> {
+        use PipelineBuildError::MissingComponent;
+
         // Extract the builder fields.
-        let rollup_config = builder.rollup_config.expect("rollup_config must be set");
-        let chain_provider = builder.chain_provider.expect("chain_provider must be set");
-        let l2_chain_provider = builder.l2_chain_provider.expect("chain_provider must be set");
-        let dap_source = builder.dap_source.expect("dap_source must be set");
-        let attributes_builder = builder.builder.expect("builder must be set");
+        let rollup_config = builder.rollup_config.ok_or(MissingComponent("rollup_config"))?;
+        if builder.validate_config {
+            rollup_config.validate().map_err(PipelineBuildError::InvalidConfig)?;
+        }
+        let origin = builder.origin.ok_or(MissingComponent("origin"))?;
+        let chain_provider = builder.chain_provider.ok_or(MissingComponent("chain_provider"))?;
+        let l2_chain_provider =
+            builder.l2_chain_provider.ok_or(MissingComponent("l2_chain_provider"))?;
+        let dap_source = builder.dap_source.ok_or(MissingComponent("dap_source"))?;
+        let attributes_builder = builder.builder.ok_or(MissingComponent("builder"))?;
+        let limits = builder.limits;
+        if limits.is_overridden() {
+            info!(target: "pipeline", "Overriding derivation limits: {:?}", limits);
+        }
+        let strict = builder.strict;
+        if strict {
+            info!(target: "pipeline", "Running derivation in strict mode");
+        }
 
         // Compose the stage stack.
         let mut l1_traversal = L1Traversal::new(chain_provider, Arc::clone(&rollup_config));
-        l1_traversal.block = Some(builder.origin.expect("origin must be set"));
+        l1_traversal.block = Some(origin);
+        l1_traversal.l1_end = builder.l1_end;
         let l1_retrieval = L1Retrieval::new(l1_traversal, dap_source);
-        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config));
-        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue);
-        let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config));
+        let mut frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config))
+            .with_limits(limits)
+            .with_strict(strict);
+        if let Some(metrics) = &builder.metrics {
+            frame_queue = frame_queue.with_metrics(Arc::clone(metrics));
+        }
+        let mut channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue)
+            .with_limits(limits)
+            .with_strict(strict);
+        if let Some(metrics) = &builder.metrics {
+            channel_provider = channel_provider.with_metrics(Arc::clone(metrics));
+        }
+        let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config))
+            .with_limits(limits)
+            .with_strict(strict);
         let batch_stream =
-            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone());
-        let batch_provider =
-            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone());
+            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone())
+                .with_strict(strict)
+                .with_holocene_span_policy(builder.holocene_span_policy);
+        let mut batch_provider =
+            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone())
+                .with_warm_start(builder.warm_start)
+                .with_limits(limits)
+                .with_strict(strict);
+        if let Some(epoch_cache) = builder.epoch_cache {
+            batch_provider = batch_provider.with_epoch_cache(epoch_cache);
+        }
+        if let Some(metrics) = &builder.metrics {
+            batch_provider = batch_provider.with_metrics(Arc::clone(metrics));
+        }
         let attributes =
             AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder);
 
         // Create the pipeline.
-        Self::new(attributes, rollup_config, l2_chain_provider)
+        let mut pipeline = Self::new(attributes, rollup_config, l2_chain_provider);
+        if let Some(max_prepared) = builder.max_prepared {
+            pipeline = pipeline.with_max_prepared(max_prepared);
+        }
+        if let Some(threshold) = builder.stall_threshold {
+            pipeline = pipeline.with_stall_threshold(threshold);
+        }
+        if let Some(metrics) = builder.metrics {
+            pipeline = pipeline.with_metrics(metrics);
+        }
+        Ok(pipeline.with_skip_empty_origins(builder.skip_empty_origins))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::{ConfigError, PipelineBuildError},
+        test_utils::*,
+    };
+    use alloc::{string::ToString, sync::Arc, vec};
+    use op_alloy_genesis::{RollupConfig, SystemConfig, OP_MAINNET_CONFIG};
+    use op_alloy_protocol::BlockInfo;
+
+    #[test]
+    fn test_build_validates_rollup_config() {
+        let err = super::PipelineBuilder::<_, _, _, TestDAP>::new()
+            .rollup_config(Arc::new(RollupConfig { block_time: 0, ..test_rollup_config() }))
+            .origin(BlockInfo::default())
+            .dap_source(TestDAP::default())
+            .builder(TestAttributesBuilder::default())
+            .chain_provider(TestChainProvider::default())
+            .l2_chain_provider(TestL2ChainProvider::default())
+            .build()
+            .unwrap_err();
+        assert_eq!(err, PipelineBuildError::InvalidConfig(vec![ConfigError::ZeroBlockTime]));
+        assert_eq!(err.to_string(), "invalid rollup config: block_time must be non-zero, got 0");
+    }
+
+    #[test]
+    fn test_build_requires_l2_chain_provider() {
+        let err = super::PipelineBuilder::<_, _, TestL2ChainProvider, TestDAP>::new()
+            .rollup_config(Arc::new(RollupConfig::default()))
+            .validate_config(false)
+            .origin(BlockInfo::default())
+            .dap_source(TestDAP::default())
+            .builder(TestAttributesBuilder::default())
+            .chain_provider(TestChainProvider::default())
+            .build()
+            .unwrap_err();
+        assert_eq!(err, PipelineBuildError::MissingComponent("l2_chain_provider"));
+    }
+
+    #[test]
+    fn test_build_valid_rollup_config() {
+        let pipeline = super::PipelineBuilder::<_, _, _, TestDAP>::new()
+            .rollup_config(Arc::new(test_rollup_config()))
+            .origin(BlockInfo::default())
+            .dap_source(TestDAP::default())
+            .builder(TestAttributesBuilder::default())
+            .chain_provider(TestChainProvider::default())
+            .l2_chain_provider(TestL2ChainProvider::default())
+            .build();
+        assert!(pipeline.is_ok());
+    }
+
+    /// Returns a rollup config that passes validation.
+    fn test_rollup_config() -> RollupConfig {
+        let mut cfg = OP_MAINNET_CONFIG;
+        cfg.genesis.system_config = Some(SystemConfig::default());
+        cfg
     }
 }
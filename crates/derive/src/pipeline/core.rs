@@ -1,19 +1,37 @@
 //! Contains the core derivation pipeline.
 
 use crate::{
-    errors::{PipelineError, PipelineErrorKind},
+    errors::{PipelineError, PipelineErrorKind, ResetError},
     traits::{
-        L2ChainProvider, NextAttributes, OriginAdvancer, OriginProvider, Pipeline, SignalReceiver,
+        DerivationMetrics, EventSink, L2ChainProvider, NextAttributes, OriginAdvancer,
+        OriginProvider, Pipeline, SignalReceiver,
+    },
+    types::{
+        ActivationSignal, AttributesDerivedEvent, ChannelFlushedEvent, OriginAdvancedEvent,
+        PipelineEvent, PipelineResult, PipelineStatus, PreparedAttributes, PreparedEntry,
+        ResetEvent, ResetSignal, Signal, SignalReceipt, StepResult,
     },
-    types::{ActivationSignal, PipelineResult, ResetSignal, Signal, StepResult},
 };
-use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+use alloy_primitives::B256;
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::{RollupConfig, SystemConfig};
 use op_alloy_protocol::{BlockInfo, L2BlockInfo};
 use op_alloy_rpc_types_engine::OpAttributesWithParent;
-use tracing::{error, trace, warn};
+use tracing::{debug, error, trace, warn};
+
+/// How the [DerivationPipeline] handles a step with a cursor that is behind a cursor it already
+/// stepped on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StaleCursorPolicy {
+    /// Return [StepResult::StaleCursor] without touching the stages.
+    #[default]
+    Reject,
+    /// Fail the step with [ResetError::StaleCursor], requiring the pipeline to be reset before it
+    /// makes further progress.
+    RequireReset,
+}
 
 /// The derivation pipeline is responsible for deriving L2 inputs from L1 data.
 #[derive(Debug)]
@@ -26,12 +44,33 @@ where
     pub attributes: S,
     /// Reset provider for the pipeline.
     /// A list of prepared [OpAttributesWithParent] to be used by the derivation pipeline
-    /// consumer.
-    pub prepared: VecDeque<OpAttributesWithParent>,
+    /// consumer, each with the L1 origin it was derived from.
+    pub prepared: VecDeque<PreparedAttributes>,
     /// The rollup config.
     pub rollup_config: Arc<RollupConfig>,
     /// The L2 Chain Provider used to fetch the system config on reset.
     pub l2_chain_provider: P,
+    /// An optional sink notified of pipeline events.
+    pub event_sink: Option<Box<dyn EventSink>>,
+    /// The highest cursor the pipeline was stepped on since the last reset.
+    pub highest_cursor: Option<L2BlockInfo>,
+    /// How a step with a stale cursor is handled.
+    pub stale_cursor_policy: StaleCursorPolicy,
+    /// The maximum number of prepared attributes, or [None] if unbounded.
+    pub max_prepared: Option<usize>,
+    /// An optional recorder of the pipeline metrics.
+    pub metrics: Option<Arc<dyn DerivationMetrics>>,
+    /// The number of consecutive origins after which the pipeline reports a
+    /// [StepResult::Stalled], or [None] if stalls are not reported.
+    pub stall_threshold: Option<u64>,
+    /// The number of origins advanced since attributes were last prepared.
+    pub origins_since_attributes: u64,
+    /// Whether L1 blocks without derivation data are skipped while the origin is behind the L1
+    /// origin of the cursor.
+    pub skip_empty_origins: bool,
+    /// The parent hashes and timestamps of the attributes restored with
+    /// [DerivationPipeline::restore_prepared] that the stages have not derived again yet.
+    pub restored: Vec<(B256, u64)>,
 }
 
 impl<S, P> DerivationPipeline<S, P>
@@ -45,7 +84,121 @@ where
         rollup_config: Arc<RollupConfig>,
         l2_chain_provider: P,
     ) -> Self {
-        Self { attributes, prepared: VecDeque::new(), rollup_config, l2_chain_provider }
+        Self {
+            attributes,
+            prepared: VecDeque::new(),
+            rollup_config,
+            l2_chain_provider,
+            event_sink: None,
+            highest_cursor: None,
+            stale_cursor_policy: StaleCursorPolicy::Reject,
+            max_prepared: None,
+            metrics: None,
+            stall_threshold: None,
+            origins_since_attributes: 0,
+            skip_empty_origins: false,
+            restored: Vec::new(),
+        }
+    }
+
+    /// Bounds the number of prepared attributes. Once the bound is reached, steps return
+    /// [StepResult::PreparedQueueFull] until the consumer pops prepared attributes.
+    pub const fn with_max_prepared(mut self, max_prepared: usize) -> Self {
+        self.max_prepared = Some(max_prepared);
+        self
+    }
+
+    /// Reports a [StepResult::Stalled] instead of a [StepResult::AdvancedOrigin] once more than
+    /// `threshold` consecutive origins were advanced past without preparing attributes.
+    pub const fn with_stall_threshold(mut self, threshold: u64) -> Self {
+        self.stall_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets whether L1 blocks without derivation data are skipped with
+    /// [OriginAdvancer::advance_origin_to] while the origin is behind the L1 origin of the
+    /// cursor. Batches for blocks up to the cursor are dropped anyway in that range, so only
+    /// the L1 blocks carrying batcher data or system config updates are walked.
+    pub const fn with_skip_empty_origins(mut self, skip_empty_origins: bool) -> Self {
+        self.skip_empty_origins = skip_empty_origins;
+        self
+    }
+
+    /// Sets the [StaleCursorPolicy] of the pipeline.
+    pub const fn with_stale_cursor_policy(mut self, policy: StaleCursorPolicy) -> Self {
+        self.stale_cursor_policy = policy;
+        self
+    }
+
+    /// Registers an [EventSink] to be notified of pipeline events.
+    pub fn with_event_sink(mut self, sink: impl EventSink + 'static) -> Self {
+        self.event_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording prepared attributes and the safe origin distance.
+    /// The stages record their own metrics, and are handed the recorder by the [PipelineBuilder].
+    ///
+    /// [PipelineBuilder]: crate::pipeline::PipelineBuilder
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the L1 origin that the most recently prepared [OpAttributesWithParent] were
+    /// derived from, if any are prepared.
+    pub fn last_prepared_origin(&self) -> Option<BlockInfo> {
+        self.prepared.back().and_then(|prepared| prepared.derived_from)
+    }
+
+    /// Returns a snapshot of the prepared attributes, in order, for persisting across restarts.
+    pub fn snapshot_prepared(&self) -> Vec<PreparedEntry> {
+        self.prepared.iter().cloned().map(PreparedEntry::from).collect()
+    }
+
+    /// Replaces the prepared attributes with a snapshot taken by
+    /// [DerivationPipeline::snapshot_prepared]. The stages derive the restored attributes again
+    /// after a restart, and those are skipped with a [StepResult::SkippedRestoredAttributes] rather
+    /// than prepared twice.
+    ///
+    /// The entries must form a chain on top of `cursor`: the first entry's parent must be the
+    /// `cursor`, and each following entry's parent must be the child of the previous entry's
+    /// parent. Otherwise the snapshot is stale, the prepared attributes are left untouched, and a
+    /// [ResetError::BadParentHash] is returned.
+    pub fn restore_prepared(
+        &mut self,
+        cursor: L2BlockInfo,
+        entries: Vec<PreparedEntry>,
+    ) -> PipelineResult<()> {
+        let mut expected = cursor.block_info;
+        for (i, entry) in entries.iter().enumerate() {
+            let parent = entry.parent.block_info;
+            let linked = if i == 0 {
+                parent.hash == expected.hash
+            } else {
+                parent.parent_hash == expected.hash && parent.number == expected.number + 1
+            };
+            if !linked {
+                let actual = if i == 0 { parent.hash } else { parent.parent_hash };
+                return Err(ResetError::BadParentHash(expected.hash, actual).reset());
+            }
+            expected = parent;
+        }
+        self.restored = entries
+            .iter()
+            .map(|entry| {
+                (entry.parent.block_info.hash, entry.attributes.payload_attributes.timestamp)
+            })
+            .collect();
+        self.prepared = entries.into_iter().map(PreparedAttributes::from).collect();
+        Ok(())
+    }
+
+    /// Emits an event to the registered [EventSink], if any.
+    fn emit(&mut self, event: PipelineEvent) {
+        if let Some(sink) = self.event_sink.as_mut() {
+            sink.emit(event);
+        }
     }
 }
 
@@ -57,6 +210,14 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.attributes.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.attributes.collect_stage_origins(origins);
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.attributes.collect_status(status);
+    }
 }
 
 impl<S, P> Iterator for DerivationPipeline<S, P>
@@ -67,7 +228,7 @@ where
     type Item = OpAttributesWithParent;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.prepared.pop_front()
+        self.prepared.pop_front().map(|prepared| prepared.attributes)
     }
 }
 
@@ -87,13 +248,32 @@ where
     /// [`Signal::Activation`] does a similar thing to the reset, with different
     /// holocene-specific reset rules.
     ///
+    /// Both discard any prepared attributes, as they were derived from the chain prior to the
+    /// reset.
+    ///
+    /// [`Signal::AdvanceOrigin`] advances the stages to the next L1 origin directly, keeping their
+    /// state and the prepared attributes. If there is no next origin yet, it does nothing.
+    ///
+    /// The returned [SignalReceipt] counts the prepared attributes and channels that were
+    /// discarded, e.g. for drivers to meter the work thrown away by a reorg.
+    ///
     /// ### Parameters
     ///
     /// The `signal` is contains the signal variant with any necessary parameters.
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         match signal {
-            mut s @ Signal::Reset(ResetSignal { l2_safe_head, .. }) |
-            mut s @ Signal::Activation(ActivationSignal { l2_safe_head, .. }) => {
+            mut s @ Signal::Reset(ResetSignal { l2_safe_head, l1_origin, .. }) |
+            mut s @ Signal::Activation(ActivationSignal { l2_safe_head, l1_origin, .. }) => {
+                let prepared_discarded = self.prepared.len();
+                if prepared_discarded > 0 {
+                    trace!(
+                        target: "pipeline",
+                        "Discarding {} prepared attributes on reset", prepared_discarded
+                    );
+                    self.prepared.clear();
+                }
+                self.restored.clear();
+                self.l2_chain_provider.invalidate_l2_above(l2_safe_head.block_info.number);
                 let system_config = self
                     .l2_chain_provider
                     .system_config_by_number(
@@ -101,25 +281,56 @@ where
                         Arc::clone(&self.rollup_config),
                     )
                     .await
-                    .map_err(Into::into)?;
+                    .map_err(|err| {
+                        let err: PipelineErrorKind = err.into();
+                        err.escalate_not_found()
+                    })?;
                 s = s.with_system_config(system_config);
-                match self.attributes.signal(s).await {
-                    Ok(()) => trace!(target: "pipeline", "Stages reset"),
+                let mut receipt = match self.attributes.signal(s).await {
+                    Ok(receipt) => {
+                        trace!(target: "pipeline", "Stages reset");
+                        receipt
+                    }
                     Err(err) => {
                         if let PipelineErrorKind::Temporary(PipelineError::Eof) = err {
                             trace!(target: "pipeline", "Stages reset with EOF");
+                            SignalReceipt { stages_reset: true, ..Default::default() }
                         } else {
                             error!(target: "pipeline", "Stage reset errored: {:?}", err);
-                            return Err(err);
+                            return Err(err.escalate_not_found());
                         }
                     }
-                }
+                };
+                receipt.prepared_discarded = prepared_discarded;
+                self.highest_cursor = None;
+                self.origins_since_attributes = 0;
+                self.emit(PipelineEvent::Reset(ResetEvent {
+                    l2_safe_head,
+                    l1_origin,
+                    activation: matches!(s, Signal::Activation(_)),
+                }));
+                Ok(receipt)
             }
             Signal::FlushChannel => {
-                self.attributes.signal(signal).await?;
+                let receipt = self.attributes.signal(signal).await?;
+                let origin = self.attributes.origin();
+                self.emit(PipelineEvent::ChannelFlushed(ChannelFlushedEvent { origin }));
+                Ok(receipt)
+            }
+            Signal::AdvanceOrigin => {
+                match self.attributes.advance_origin().await {
+                    Ok(()) => {
+                        let origin = self.attributes.origin();
+                        self.emit(PipelineEvent::OriginAdvanced(OriginAdvancedEvent { origin }));
+                    }
+                    Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => {
+                        trace!(target: "pipeline", "No origin to advance to");
+                    }
+                    Err(err) => return Err(err),
+                }
+                Ok(SignalReceipt::default())
             }
         }
-        Ok(())
     }
 }
 
@@ -131,7 +342,22 @@ where
 {
     /// Peeks at the next prepared [OpAttributesWithParent] from the pipeline.
     fn peek(&self) -> Option<&OpAttributesWithParent> {
-        self.prepared.front()
+        self.prepared.front().map(|prepared| &prepared.attributes)
+    }
+
+    /// Peeks at the prepared [OpAttributesWithParent] `n` positions ahead in the pipeline.
+    fn peek_nth(&self, n: usize) -> Option<&OpAttributesWithParent> {
+        self.prepared.get(n).map(|prepared| &prepared.attributes)
+    }
+
+    /// Returns the number of prepared [OpAttributesWithParent].
+    fn prepared_len(&self) -> usize {
+        self.prepared.len()
+    }
+
+    /// Returns the L1 origin that the next prepared [OpAttributesWithParent] were derived from.
+    fn derived_from(&self) -> Option<BlockInfo> {
+        self.prepared.front().and_then(|prepared| prepared.derived_from)
     }
 
     /// Returns the rollup config.
@@ -161,20 +387,112 @@ where
     /// When [DerivationPipeline::step] returns [Ok(())], it should be called again, to continue the
     /// derivation process.
     ///
+    /// If the cursor is behind a cursor that was already stepped on since the last reset, the
+    /// stages are not touched and the step is handled according to the [StaleCursorPolicy].
+    /// Likewise, if the prepared attributes are at the configured capacity, the stages are not
+    /// touched and [StepResult::PreparedQueueFull] is returned.
+    ///
+    /// With the `tracing-spans` feature, the step runs in a `pipeline_step` span carrying the
+    /// cursor and origin block numbers, which parents the spans of the stages it drives.
+    ///
     /// [PipelineError]: crate::errors::PipelineError
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(
+            name = "pipeline_step",
+            level = "debug",
+            skip_all,
+            fields(cursor = cursor.block_info.number, origin = tracing::field::Empty)
+        )
+    )]
     async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+        #[cfg(feature = "tracing-spans")]
+        if let Some(origin) = self.attributes.origin() {
+            tracing::Span::current().record("origin", origin.number);
+        }
+        if let Some(highest) = self.highest_cursor {
+            if cursor.block_info.number < highest.block_info.number {
+                let (stale, highest) = (cursor.block_info.number, highest.block_info.number);
+                warn!(
+                    target: "pipeline",
+                    "Stepped with stale cursor #{} behind #{}", stale, highest
+                );
+                return match self.stale_cursor_policy {
+                    StaleCursorPolicy::Reject => StepResult::StaleCursor(stale, highest),
+                    StaleCursorPolicy::RequireReset => {
+                        StepResult::StepFailed(ResetError::StaleCursor(stale, highest).reset())
+                    }
+                };
+            }
+        }
+        if self.max_prepared.is_some_and(|max| self.prepared.len() >= max) {
+            trace!(target: "pipeline", "Prepared attributes queue is full");
+            return StepResult::PreparedQueueFull;
+        }
+        self.highest_cursor = Some(cursor);
+        if let (Some(metrics), Some(origin)) = (&self.metrics, self.attributes.origin()) {
+            let distance = origin.number.saturating_sub(cursor.l1_origin.number);
+            metrics.record_safe_origin_distance(distance);
+        }
+
         match self.attributes.next_attributes(cursor).await {
             Ok(a) => {
+                // Attributes restored from a snapshot are derived again after a restart, as the
+                // stages restart from the safe head. They were already prepared, so are skipped.
+                let key = (a.parent.block_info.hash, a.attributes.payload_attributes.timestamp);
+                if let Some(i) = self.restored.iter().position(|restored| *restored == key) {
+                    self.restored.remove(i);
+                    debug!(
+                        target: "pipeline",
+                        "Skipping restored L2 attributes for timestamp {}", key.1
+                    );
+                    return StepResult::SkippedRestoredAttributes;
+                }
                 trace!(target: "pipeline", "Prepared L2 attributes: {:?}", a);
-                self.prepared.push_back(a);
+                let event = AttributesDerivedEvent {
+                    parent: a.parent,
+                    timestamp: a.attributes.payload_attributes.timestamp,
+                    derived_from: self.attributes.derived_from(),
+                };
+                self.prepared.push_back(PreparedAttributes::new(a, event.derived_from));
+                self.origins_since_attributes = 0;
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_attributes_prepared();
+                }
+                self.emit(PipelineEvent::AttributesDerived(event));
                 StepResult::PreparedAttributes
             }
             Err(err) => match err {
                 PipelineErrorKind::Temporary(PipelineError::Eof) => {
                     trace!(target: "pipeline", "Pipeline advancing origin");
-                    if let Err(e) = self.attributes.advance_origin().await {
+                    // Only the number and hash of the cursor's L1 origin are known, and only
+                    // those are checked once the origin reaches it.
+                    let target = BlockInfo {
+                        number: cursor.l1_origin.number,
+                        hash: cursor.l1_origin.hash,
+                        ..Default::default()
+                    };
+                    let skip = self.skip_empty_origins &&
+                        self.attributes.origin().is_some_and(|o| o.number < target.number);
+                    let advanced = if skip {
+                        self.attributes.advance_origin_to(target).await
+                    } else {
+                        self.attributes.advance_origin().await
+                    };
+                    if let Err(e) = advanced {
                         return StepResult::OriginAdvanceErr(e);
                     }
+                    let origin = self.attributes.origin();
+                    self.emit(PipelineEvent::OriginAdvanced(OriginAdvancedEvent { origin }));
+                    self.origins_since_attributes += 1;
+                    let stalled = self.origins_since_attributes;
+                    if self.stall_threshold.is_some_and(|threshold| stalled > threshold) {
+                        warn!(
+                            target: "pipeline",
+                            "No attributes prepared for {} consecutive origins", stalled
+                        );
+                        return StepResult::Stalled(stalled);
+                    }
                     StepResult::AdvancedOrigin
                 }
                 _ => {
@@ -189,11 +507,17 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{pipeline::DerivationPipeline, test_utils::*};
-    use alloc::{string::ToString, sync::Arc};
+    use crate::{
+        errors::ProviderErrorKind, pipeline::DerivationPipeline, test_utils::*,
+        types::CountingMetrics,
+    };
+    use alloc::{sync::Arc, vec, vec::Vec};
+    use alloy_consensus::{BlockBody, Header};
+    use alloy_primitives::B256;
     use alloy_rpc_types_engine::PayloadAttributes;
+    use op_alloy_consensus::{Hardforks, OpBlock, OpTxType};
     use op_alloy_genesis::{RollupConfig, SystemConfig};
-    use op_alloy_protocol::L2BlockInfo;
+    use op_alloy_protocol::{BatchValidationProvider, L2BlockInfo};
     use op_alloy_rpc_types_engine::{OpAttributesWithParent, OpPayloadAttributes};
 
     fn default_test_payload_attributes() -> OpAttributesWithParent {
@@ -227,7 +551,7 @@ mod tests {
     fn test_pipeline_next_attributes_with_peek() {
         let mut pipeline = new_test_pipeline();
         let expected = default_test_payload_attributes();
-        pipeline.prepared.push_back(expected.clone());
+        pipeline.prepared.push_back(PreparedAttributes::new(expected.clone(), None));
 
         let result = pipeline.peek();
         assert_eq!(result, Some(&expected));
@@ -236,6 +560,146 @@ mod tests {
         assert_eq!(result, Some(expected));
     }
 
+    /// Returns the cursor the snapshot test entries are chained on.
+    fn snapshot_cursor() -> L2BlockInfo {
+        let block_info =
+            BlockInfo { hash: B256::repeat_byte(0xAA), number: 10, ..Default::default() };
+        L2BlockInfo { block_info, ..Default::default() }
+    }
+
+    /// Returns `count` prepared entries chained on top of `cursor`, each carrying the Fjord
+    /// upgrade deposit transactions.
+    fn chained_prepared_entries(cursor: L2BlockInfo, count: u64) -> Vec<PreparedEntry> {
+        let mut parent = cursor;
+        (0..count)
+            .map(|i| {
+                let mut attributes = default_test_payload_attributes();
+                attributes.attributes.transactions = Some(Hardforks::fjord_txs());
+                attributes.parent = parent;
+                attributes.is_last_in_span = i + 1 == count;
+                parent.block_info = BlockInfo {
+                    hash: B256::repeat_byte(i as u8 + 1),
+                    number: parent.block_info.number + 1,
+                    parent_hash: parent.block_info.hash,
+                    timestamp: parent.block_info.timestamp + 2,
+                };
+                let derived_from = BlockInfo { number: 5, ..Default::default() };
+                PreparedEntry::new(attributes, Some(derived_from))
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_pipeline_snapshot_prepared_roundtrip() {
+        let cursor = snapshot_cursor();
+        let mut pipeline = new_test_pipeline();
+        let entries = chained_prepared_entries(cursor, 3);
+        pipeline.prepared.extend(entries.into_iter().map(PreparedAttributes::from));
+
+        let json = serde_json::to_string(&pipeline.snapshot_prepared()).unwrap();
+        let snapshot: Vec<PreparedEntry> = serde_json::from_str(&json).unwrap();
+        let mut restored = new_test_pipeline();
+        restored.restore_prepared(cursor, snapshot).unwrap();
+
+        assert_eq!(restored.prepared, pipeline.prepared);
+        let txs = restored.peek().unwrap().attributes.transactions.as_ref().unwrap();
+        assert!(txs.iter().all(|tx| tx[0] == OpTxType::Deposit as u8));
+    }
+
+    #[test]
+    fn test_pipeline_restore_prepared_rejects_discontinuity() {
+        let cursor = snapshot_cursor();
+        let mut pipeline = new_test_pipeline();
+        let entries = chained_prepared_entries(cursor, 3);
+
+        // The snapshot does not extend a different cursor.
+        let other = L2BlockInfo { block_info: BlockInfo::default(), ..cursor };
+        let err = pipeline.restore_prepared(other, entries.clone()).unwrap_err();
+        let expected = ResetError::BadParentHash(B256::ZERO, cursor.block_info.hash).reset();
+        assert_eq!(err, expected);
+
+        // A gap in the snapshot is detected.
+        let gapped = vec![entries[0].clone(), entries[2].clone()];
+        assert!(pipeline.restore_prepared(cursor, gapped).is_err());
+        assert!(pipeline.prepared.is_empty());
+
+        pipeline.restore_prepared(cursor, entries).unwrap();
+        assert_eq!(pipeline.prepared_len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_restore_prepared_skips_rederived() {
+        let cursor = snapshot_cursor();
+        let entries = chained_prepared_entries(cursor, 3);
+        let rederived = PreparedAttributes::from(entries[0].clone()).attributes;
+        let mut next = default_test_payload_attributes();
+        next.attributes.payload_attributes.timestamp = 6;
+        next.parent = entries[2].parent;
+        let attributes = TestNextAttributes::with_queue(vec![rederived, next.clone()]);
+        let mut pipeline = DerivationPipeline::new(
+            attributes,
+            Arc::new(RollupConfig::default()),
+            TestL2ChainProvider::default(),
+        );
+        pipeline.restore_prepared(cursor, entries).unwrap();
+
+        // After a restart, the stages derive the restored attributes again. They are skipped
+        // without touching the prepared attributes.
+        assert_eq!(pipeline.step(cursor).await, StepResult::SkippedRestoredAttributes);
+        assert_eq!(pipeline.prepared_len(), 3);
+        assert_eq!(pipeline.step(cursor).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.prepared_len(), 4);
+        assert_eq!(pipeline.prepared.back().unwrap().attributes, next);
+    }
+
+    #[test]
+    fn test_pipeline_stage_origins() {
+        let pipeline = new_test_pipeline();
+        let origins = pipeline.stage_origins();
+        let names = origins.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+
+        // The channel and batch stages are only listed once the pipeline has selected them.
+        assert_eq!(
+            names,
+            [
+                "l1-traversal",
+                "l1-retrieval",
+                "frame-queue",
+                "channel-reader",
+                "batch-stream",
+                "attributes-queue"
+            ]
+        );
+        assert!(origins.iter().all(|(_, origin)| *origin == Some(BlockInfo::default())));
+    }
+
+    #[test]
+    fn test_pipeline_peek_nth() {
+        let mut pipeline = new_test_pipeline();
+        let attributes = (0..3)
+            .map(|timestamp| {
+                let mut attributes = default_test_payload_attributes();
+                attributes.attributes.payload_attributes.timestamp = timestamp;
+                attributes
+            })
+            .collect::<Vec<_>>();
+        let prepared = attributes.iter().cloned().map(|a| PreparedAttributes::new(a, None));
+        pipeline.prepared.extend(prepared);
+
+        assert_eq!(pipeline.prepared_len(), 3);
+        assert_eq!(pipeline.peek_nth(0), pipeline.peek());
+        assert_eq!(pipeline.peek_nth(2), Some(&attributes[2]));
+        assert_eq!(pipeline.peek_nth(3), None);
+
+        // Popping the front shifts the look-ahead.
+        assert_eq!(pipeline.next(), Some(attributes[0].clone()));
+        assert_eq!(pipeline.prepared_len(), 2);
+        assert_eq!(pipeline.peek_nth(0), Some(&attributes[1]));
+        assert_eq!(pipeline.peek_nth(1), Some(&attributes[2]));
+        assert_eq!(pipeline.peek_nth(2), None);
+    }
+
     #[tokio::test]
     async fn test_derivation_pipeline_missing_block() {
         let mut pipeline = new_test_pipeline();
@@ -244,7 +708,7 @@ mod tests {
         assert_eq!(
             result,
             StepResult::OriginAdvanceErr(
-                PipelineError::Provider("Block not found".to_string()).temp()
+                PipelineError::Provider(ProviderErrorKind::BlockNotFound(1)).temp()
             )
         );
     }
@@ -254,7 +718,8 @@ mod tests {
         let rollup_config = Arc::new(RollupConfig::default());
         let l2_chain_provider = TestL2ChainProvider::default();
         let expected = default_test_payload_attributes();
-        let attributes = TestNextAttributes { next_attributes: Some(expected) };
+        let attributes =
+            TestNextAttributes { next_attributes: Some(expected), ..Default::default() };
         let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider);
 
         // Step on the pipeline and expect the result.
@@ -263,6 +728,87 @@ mod tests {
         assert_eq!(result, StepResult::PreparedAttributes);
     }
 
+    #[tokio::test]
+    async fn test_derivation_pipeline_prepared_attributes_derived_from() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let l2_chain_provider = TestL2ChainProvider::default();
+        let expected = default_test_payload_attributes();
+        let attributes =
+            TestNextAttributes { next_attributes: Some(expected.clone()), ..Default::default() };
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider);
+        assert_eq!(pipeline.derived_from(), None);
+
+        let result = pipeline.step(L2BlockInfo::default()).await;
+        assert_eq!(result, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.derived_from(), Some(BlockInfo::default()));
+        assert_eq!(pipeline.next(), Some(expected));
+        assert_eq!(pipeline.derived_from(), None);
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_last_prepared_origin() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let attributes = TestNextAttributes {
+            next_attributes: Some(default_test_payload_attributes()),
+            ..Default::default()
+        };
+        let metrics = CountingMetrics::new();
+        let mut pipeline =
+            DerivationPipeline::new(attributes, rollup_config, TestL2ChainProvider::default())
+                .with_metrics(Arc::new(metrics.clone()));
+        assert_eq!(pipeline.last_prepared_origin(), None);
+        let head = BlockInfo { number: 5, ..Default::default() };
+        assert_eq!(pipeline.origin_lag(head), 5);
+
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.last_prepared_origin().unwrap().number, 0);
+
+        // Attributes prepared after an origin advance are tracked with the new origin.
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::AdvancedOrigin);
+        pipeline.attributes.queue.push_back(default_test_payload_attributes());
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.last_prepared_origin().unwrap().number, 1);
+        assert_eq!(pipeline.derived_from().unwrap().number, 0);
+
+        // Advancing without preparing attributes leaves the last prepared origin behind.
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.origin().unwrap().number, 3);
+        assert_eq!(pipeline.last_prepared_origin().unwrap().number, 1);
+        assert_eq!(pipeline.origin_lag(head), 2);
+        // Reading the lag does not record it.
+        assert_eq!(metrics.summary().l1_head_lag, 0);
+
+        pipeline.next();
+        assert_eq!(pipeline.last_prepared_origin().unwrap().number, 1);
+        pipeline.next();
+        assert_eq!(pipeline.last_prepared_origin(), None);
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_stalled() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let mut pipeline = DerivationPipeline::new(
+            TestNextAttributes::default(),
+            rollup_config,
+            TestL2ChainProvider::default(),
+        )
+        .with_stall_threshold(2);
+
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::Stalled(3));
+        // The pipeline keeps advancing while stalled.
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::Stalled(4));
+        assert_eq!(pipeline.origin().unwrap().number, 4);
+
+        // Preparing attributes resets the watchdog.
+        pipeline.attributes.queue.push_back(default_test_payload_attributes());
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.origins_since_attributes, 1);
+    }
+
     #[tokio::test]
     async fn test_derivation_pipeline_advance_origin() {
         let rollup_config = Arc::new(RollupConfig::default());
@@ -310,7 +856,8 @@ mod tests {
 
         // Signal the pipeline to reset.
         let result = pipeline.signal(ResetSignal::default().signal()).await.unwrap_err();
-        assert_eq!(result, PipelineError::Provider("System config not found".to_string()).temp());
+        let expected = ResetError::Provider(ProviderErrorKind::SystemConfigNotFound(0));
+        assert_eq!(result, expected.reset());
     }
 
     #[tokio::test]
@@ -325,4 +872,404 @@ mod tests {
         let result = pipeline.signal(ResetSignal::default().signal()).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_reset_to_fetched_safe_head() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let safe_head = L2BlockInfo {
+            block_info: BlockInfo { number: 5, hash: B256::repeat_byte(5), ..Default::default() },
+            ..Default::default()
+        };
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(5, SystemConfig::default());
+        fetcher.insert_l2_block_info(safe_head);
+        fetcher.insert_block(OpBlock {
+            header: Header { number: 5, ..Default::default() },
+            body: BlockBody { transactions: Vec::new(), ommers: Vec::new(), withdrawals: None },
+        });
+        let attributes = TestNextAttributes::default();
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, fetcher);
+
+        // Like a driver resetting onto its safe head, fetch the head by number before resetting.
+        let provider = &mut pipeline.l2_chain_provider;
+        let l2_safe_head = provider.l2_block_info_by_number(5).await.unwrap();
+        assert_eq!(l2_safe_head, safe_head);
+        assert_eq!(provider.block_by_number(5).await.unwrap().header.number, 5);
+        pipeline.signal(ResetSignal { l2_safe_head, ..Default::default() }.signal()).await.unwrap();
+        assert_eq!(pipeline.attributes.resets, 1);
+
+        // Missing L2 blocks and system configs surface as typed errors.
+        let provider = &mut pipeline.l2_chain_provider;
+        assert_eq!(
+            provider.l2_block_info_by_number(6).await,
+            Err(TestSystemConfigL2FetcherError::L2BlockInfoNotFound(6))
+        );
+        assert_eq!(
+            provider.block_by_number(6).await.unwrap_err(),
+            TestSystemConfigL2FetcherError::BlockNotFound(6)
+        );
+        let l2_safe_head = L2BlockInfo {
+            block_info: BlockInfo { number: 6, ..Default::default() },
+            ..Default::default()
+        };
+        let err = pipeline
+            .signal(ResetSignal { l2_safe_head, ..Default::default() }.signal())
+            .await
+            .unwrap_err();
+        assert_eq!(err, ResetError::Provider(ProviderErrorKind::SystemConfigNotFound(6)).reset());
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_reset_clears_prepared() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let mut l2_chain_provider = TestL2ChainProvider::default();
+        l2_chain_provider.system_configs.insert(0, SystemConfig::default());
+        let stale = default_test_payload_attributes();
+        let mut fresh = default_test_payload_attributes();
+        fresh.attributes.payload_attributes.timestamp = 2;
+        let attributes = TestNextAttributes::with_queue(vec![stale.clone(), fresh.clone()]);
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider);
+
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.peek(), Some(&stale));
+
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::PreparedAttributes);
+        pipeline.attributes.queue.push_back(fresh.clone());
+
+        let receipt = pipeline.signal(ResetSignal::default().signal()).await.unwrap();
+        let expected =
+            SignalReceipt { prepared_discarded: 2, stages_reset: true, flushed_channels: 0 };
+        assert_eq!(receipt, expected);
+        assert_eq!(pipeline.peek(), None);
+        assert_eq!(pipeline.derived_from(), None);
+
+        // Only attributes derived after the reset are yielded.
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.next(), Some(fresh));
+        assert_eq!(pipeline.next(), None);
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_activation_clears_prepared() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let mut l2_chain_provider = TestL2ChainProvider::default();
+        l2_chain_provider.system_configs.insert(0, SystemConfig::default());
+        let attributes = TestNextAttributes::default();
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider);
+        let attributes = default_test_payload_attributes();
+        pipeline.prepared.push_back(PreparedAttributes::new(attributes, None));
+
+        pipeline.signal(ActivationSignal::default().signal()).await.unwrap();
+        assert_eq!(pipeline.prepared_len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_events() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let mut l2_chain_provider = TestL2ChainProvider::default();
+        l2_chain_provider.system_configs.insert(0, SystemConfig::default());
+        let attributes = TestNextAttributes::with_queue(vec![default_test_payload_attributes()]);
+        let sink = BoundedEventSink::new(16);
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider)
+            .with_event_sink(sink.clone());
+        let cursor = L2BlockInfo::default();
+        let origin = |number| BlockInfo { number, ..Default::default() };
+
+        // Derive attributes at the first origin, then advance across two origins.
+        assert_eq!(pipeline.step(cursor).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.step(cursor).await, StepResult::AdvancedOrigin);
+        pipeline.attributes.queue.push_back(default_test_payload_attributes());
+        assert_eq!(pipeline.step(cursor).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.step(cursor).await, StepResult::AdvancedOrigin);
+
+        // Flush, then reset.
+        pipeline.signal(Signal::FlushChannel).await.unwrap();
+        let reset = ResetSignal { l1_origin: origin(2), ..Default::default() };
+        pipeline.signal(reset.signal()).await.unwrap();
+
+        // A failed reset must not be reported.
+        pipeline.l2_chain_provider.system_configs.clear();
+        assert!(pipeline.signal(ResetSignal::default().signal()).await.is_err());
+
+        let derived = |number| {
+            PipelineEvent::AttributesDerived(AttributesDerivedEvent {
+                parent: L2BlockInfo::default(),
+                timestamp: 0,
+                derived_from: Some(origin(number)),
+            })
+        };
+        let advanced = |number| {
+            PipelineEvent::OriginAdvanced(OriginAdvancedEvent { origin: Some(origin(number)) })
+        };
+        assert_eq!(
+            sink.events(),
+            vec![
+                derived(0),
+                advanced(1),
+                derived(1),
+                advanced(2),
+                PipelineEvent::ChannelFlushed(ChannelFlushedEvent { origin: Some(origin(2)) }),
+                PipelineEvent::Reset(ResetEvent {
+                    l2_safe_head: L2BlockInfo::default(),
+                    l1_origin: origin(2),
+                    activation: false,
+                }),
+            ]
+        );
+    }
+
+    fn cursor(number: u64) -> L2BlockInfo {
+        L2BlockInfo { block_info: BlockInfo { number, ..Default::default() }, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_skip_empty_origins() {
+        let mut cursor = cursor(0);
+        cursor.l1_origin.number = 5;
+
+        // By default, the origin advances a single L1 block per step.
+        let mut pipeline = DerivationPipeline::new(
+            TestNextAttributes::default(),
+            Arc::new(RollupConfig::default()),
+            TestL2ChainProvider::default(),
+        );
+        assert_eq!(pipeline.step(cursor).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.origin().map(|origin| origin.number), Some(1));
+
+        // Skipping empty origins, it advances straight to the L1 origin of the cursor, and one
+        // block at a time from there.
+        let mut pipeline = DerivationPipeline::new(
+            TestNextAttributes::default(),
+            Arc::new(RollupConfig::default()),
+            TestL2ChainProvider::default(),
+        )
+        .with_skip_empty_origins(true);
+        assert_eq!(pipeline.step(cursor).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.origin().map(|origin| origin.number), Some(5));
+        assert_eq!(pipeline.step(cursor).await, StepResult::AdvancedOrigin);
+        assert_eq!(pipeline.origin().map(|origin| origin.number), Some(6));
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_stale_cursor_rejected() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let l2_chain_provider = TestL2ChainProvider::default();
+        let attributes = TestNextAttributes::with_queue(vec![
+            default_test_payload_attributes(),
+            default_test_payload_attributes(),
+        ]);
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider);
+
+        assert_eq!(pipeline.step(cursor(1)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.step(cursor(2)).await, StepResult::PreparedAttributes);
+
+        // A backwards cursor is rejected without touching the stages.
+        pipeline.attributes.queue.push_back(default_test_payload_attributes());
+        assert_eq!(pipeline.step(cursor(1)).await, StepResult::StaleCursor(1, 2));
+        assert_eq!(pipeline.attributes.queue.len(), 1);
+        assert_eq!(pipeline.prepared.len(), 2);
+
+        // Stepping with the same cursor again is fine.
+        assert_eq!(pipeline.step(cursor(2)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.highest_cursor, Some(cursor(2)));
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_stale_cursor_require_reset() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let mut l2_chain_provider = TestL2ChainProvider::default();
+        l2_chain_provider.system_configs.insert(0, SystemConfig::default());
+        let attributes = TestNextAttributes::with_queue(vec![default_test_payload_attributes()]);
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider)
+            .with_stale_cursor_policy(StaleCursorPolicy::RequireReset);
+
+        assert_eq!(pipeline.step(cursor(5)).await, StepResult::PreparedAttributes);
+        assert_eq!(
+            pipeline.step(cursor(3)).await,
+            StepResult::StepFailed(ResetError::StaleCursor(3, 5).reset())
+        );
+        // The guard holds until the pipeline is reset.
+        assert_eq!(
+            pipeline.step(cursor(4)).await,
+            StepResult::StepFailed(ResetError::StaleCursor(4, 5).reset())
+        );
+
+        // After a reset, the lower cursor is accepted.
+        pipeline.signal(ResetSignal::default().signal()).await.unwrap();
+        assert_eq!(pipeline.highest_cursor, None);
+        pipeline.attributes.queue.push_back(default_test_payload_attributes());
+        assert_eq!(pipeline.step(cursor(3)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.highest_cursor, Some(cursor(3)));
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_max_prepared() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let l2_chain_provider = TestL2ChainProvider::default();
+        let attributes = TestNextAttributes::with_queue(vec![default_test_payload_attributes(); 3]);
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider)
+            .with_max_prepared(2);
+
+        assert_eq!(pipeline.step(cursor(1)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.step(cursor(1)).await, StepResult::PreparedAttributes);
+
+        // A full queue rejects the step without touching the stages.
+        assert_eq!(pipeline.step(cursor(1)).await, StepResult::PreparedQueueFull);
+        assert_eq!(pipeline.attributes.queue.len(), 1);
+        assert_eq!(pipeline.prepared_len(), 2);
+
+        // Popping an item resumes stepping.
+        assert!(pipeline.next().is_some());
+        assert_eq!(pipeline.step(cursor(1)).await, StepResult::PreparedAttributes);
+        assert_eq!(pipeline.prepared_len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_unbounded_by_default() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let l2_chain_provider = TestL2ChainProvider::default();
+        let attributes =
+            TestNextAttributes::with_queue(vec![default_test_payload_attributes(); 64]);
+        let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider);
+        for _ in 0..64 {
+            assert_eq!(pipeline.step(cursor(1)).await, StepResult::PreparedAttributes);
+        }
+        assert_eq!(pipeline.prepared_len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_advance_to_origin() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let attributes = TestNextAttributes::with_queue(vec![
+            default_test_payload_attributes(),
+            default_test_payload_attributes(),
+        ]);
+        let mut pipeline =
+            DerivationPipeline::new(attributes, rollup_config, TestL2ChainProvider::default());
+
+        let mut drained = Vec::new();
+        let origin =
+            pipeline.advance_to_origin(|| cursor(0), 2, |a| drained.push(a)).await.unwrap();
+        assert_eq!(origin.number, 2);
+        assert_eq!(drained, vec![default_test_payload_attributes(); 2]);
+        assert!(pipeline.prepared.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_advance_to_origin_behind() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let mut attributes =
+            TestNextAttributes::with_queue(vec![default_test_payload_attributes()]);
+        attributes.origin.number = 5;
+        let mut pipeline =
+            DerivationPipeline::new(attributes, rollup_config, TestL2ChainProvider::default());
+
+        let mut drained = Vec::new();
+        let origin =
+            pipeline.advance_to_origin(|| cursor(0), 3, |a| drained.push(a)).await.unwrap();
+        assert_eq!(origin.number, 5);
+        assert!(drained.is_empty());
+        // The pipeline was not stepped.
+        assert_eq!(pipeline.attributes.queue.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_advance_to_origin_not_enough_data() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let attributes = TestNextAttributes {
+            not_enough_data: 16,
+            ..TestNextAttributes::with_queue(vec![default_test_payload_attributes()])
+        };
+        let mut pipeline =
+            DerivationPipeline::new(attributes, rollup_config, TestL2ChainProvider::default());
+
+        // Steps that need more data are stepped through.
+        let mut drained = Vec::new();
+        let origin =
+            pipeline.advance_to_origin(|| cursor(0), 2, |a| drained.push(a)).await.unwrap();
+        assert_eq!(origin.number, 2);
+        assert_eq!(drained, vec![default_test_payload_attributes()]);
+    }
+
+    #[tokio::test]
+    async fn test_advance_to_origin_temporary_error() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let err = || PipelineError::Provider(ProviderErrorKind::BlockNotFound(1)).temp();
+        let attributes = TestNextAttributes {
+            step_error: Some(err()),
+            ..TestNextAttributes::with_queue(vec![default_test_payload_attributes()])
+        };
+        let mut pipeline =
+            DerivationPipeline::new(attributes, rollup_config, TestL2ChainProvider::default());
+
+        // The temporary error is returned without retrying, so the caller can back off.
+        assert_eq!(pipeline.advance_to_origin(|| cursor(0), 2, |_| {}).await.unwrap_err(), err());
+        assert_eq!(pipeline.origin().unwrap().number, 0);
+        assert_eq!(pipeline.attributes.queue.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_signal_advance_origin() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let attributes = TestNextAttributes {
+            next_attributes: Some(default_test_payload_attributes()),
+            origin_limit: Some(1),
+            ..Default::default()
+        };
+        let sink = BoundedEventSink::new(16);
+        let mut pipeline =
+            DerivationPipeline::new(attributes, rollup_config, TestL2ChainProvider::default())
+                .with_event_sink(sink.clone());
+        assert_eq!(pipeline.step(cursor(0)).await, StepResult::PreparedAttributes);
+
+        // The origin advances without resetting the stages or the prepared attributes.
+        pipeline.signal(Signal::AdvanceOrigin).await.unwrap();
+        assert_eq!(pipeline.origin().unwrap().number, 1);
+        assert_eq!(pipeline.attributes.resets, 0);
+        assert_eq!(pipeline.prepared_len(), 1);
+
+        // At the tip of the source, the signal does nothing.
+        pipeline.signal(Signal::AdvanceOrigin).await.unwrap();
+        assert_eq!(pipeline.origin().unwrap().number, 1);
+
+        let events = sink.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[1],
+            PipelineEvent::OriginAdvanced(OriginAdvancedEvent {
+                origin: Some(BlockInfo { number: 1, ..Default::default() }),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_advance_to_origin_exhausted() {
+        let rollup_config = Arc::new(RollupConfig::default());
+        let attributes = TestNextAttributes { origin_limit: Some(1), ..Default::default() };
+        let mut pipeline =
+            DerivationPipeline::new(attributes, rollup_config, TestL2ChainProvider::default());
+
+        let err = pipeline.advance_to_origin(|| cursor(0), 3, |_| {}).await.unwrap_err();
+        assert_eq!(err, PipelineError::Eof.temp());
+        assert_eq!(pipeline.origin().unwrap().number, 1);
+    }
+
+    #[cfg(feature = "tracing-spans")]
+    #[tokio::test]
+    async fn test_derivation_pipeline_step_spans() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let storage = SpanStorage::default();
+        let layer = SpanCollectingLayer::new(storage.clone());
+        let subscriber = tracing_subscriber::Registry::default().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut pipeline = new_test_pipeline();
+        let _ = pipeline.step(L2BlockInfo::default()).await;
+
+        // The stages driven by the step are nested under its span.
+        assert_eq!(storage.parent_of("pipeline_step"), Some(None));
+        assert_eq!(storage.parent_of("attributes_queue"), Some(Some("pipeline_step")));
+        assert_eq!(storage.parent_of("batch_queue"), Some(Some("attributes_queue")));
+    }
 }
@@ -1,7 +1,10 @@
 //! Module containing the derivation pipeline.
 
 mod builder;
-pub use builder::PipelineBuilder;
+pub use builder::{PipelineBuilder, StandardPipeline};
 
 mod core;
-pub use core::DerivationPipeline;
+pub use core::{DerivationPipeline, StaleCursorPolicy};
+
+mod batch_stream_reader;
+pub use batch_stream_reader::{BatchStreamReader, InclusionMeta};
@@ -0,0 +1,328 @@
+//! Contains the [AltDaSource], which resolves alt-DA commitments posted by the batcher through
+//! an [AltDaProvider].
+
+use crate::{
+    errors::AltDaError,
+    traits::{AltDaProvider, DataAvailabilityProvider},
+    types::PipelineResult,
+};
+use alloc::{boxed::Box, vec::Vec};
+use alloy_primitives::{keccak256, Bytes, B256};
+use async_trait::async_trait;
+use op_alloy_protocol::BlockInfo;
+use tracing::warn;
+
+/// The batcher transaction version byte marking an alt-DA commitment.
+pub const ALT_DA_DERIVATION_VERSION: u8 = 0x01;
+
+/// The commitment type byte of a [AltDaCommitment::Keccak256] commitment.
+pub const KECCAK256_COMMITMENT_TYPE: u8 = 0x00;
+
+/// The commitment type byte of a [AltDaCommitment::Generic] commitment.
+pub const GENERIC_COMMITMENT_TYPE: u8 = 0x01;
+
+/// The DA layer byte of a [AltDaCommitment::Generic] commitment to EigenDA.
+pub const EIGEN_DA_LAYER: u8 = 0x00;
+
+/// The maximum size of an alt-DA input, matching the maximum size of a calldata frame.
+pub const MAX_ALT_DA_INPUT_SIZE: usize = 130_672;
+
+/// A commitment to input data posted to an alternative data availability layer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AltDaCommitment {
+    /// A keccak256 commitment to the input, used with a DA server storing preimages.
+    Keccak256(B256),
+    /// A commitment whose format is defined by the DA layer.
+    Generic {
+        /// The DA layer byte.
+        da_layer: u8,
+        /// The DA layer specific payload.
+        payload: Bytes,
+    },
+}
+
+impl AltDaCommitment {
+    /// Decodes a commitment from batcher transaction data, following the
+    /// [ALT_DA_DERIVATION_VERSION] byte.
+    pub fn decode(data: &[u8]) -> Result<Self, AltDaError> {
+        let (&commitment_type, data) = data.split_first().ok_or(AltDaError::InvalidCommitment)?;
+        match commitment_type {
+            KECCAK256_COMMITMENT_TYPE => {
+                let hash = <[u8; 32]>::try_from(data).map_err(|_| AltDaError::InvalidCommitment)?;
+                Ok(Self::Keccak256(hash.into()))
+            }
+            GENERIC_COMMITMENT_TYPE => {
+                let (&da_layer, payload) =
+                    data.split_first().ok_or(AltDaError::InvalidCommitment)?;
+                if payload.is_empty() {
+                    return Err(AltDaError::InvalidCommitment);
+                }
+                Ok(Self::Generic { da_layer, payload: Bytes::copy_from_slice(payload) })
+            }
+            _ => Err(AltDaError::InvalidCommitment),
+        }
+    }
+
+    /// Verifies that the input matches the commitment. [AltDaCommitment::Keccak256] commitments
+    /// are checked against the hash of the input, while [AltDaCommitment::Generic] commitments
+    /// are verified by the DA layer.
+    pub fn verify(&self, input: &[u8]) -> Result<(), AltDaError> {
+        if input.len() > MAX_ALT_DA_INPUT_SIZE {
+            return Err(AltDaError::InputTooLarge(input.len()));
+        }
+        match self {
+            Self::Keccak256(hash) if keccak256(input) != *hash => {
+                Err(AltDaError::CommitmentMismatch)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Encodes the commitment, without the [ALT_DA_DERIVATION_VERSION] byte.
+    pub fn encode(&self) -> Bytes {
+        let mut data = Vec::new();
+        match self {
+            Self::Keccak256(hash) => {
+                data.push(KECCAK256_COMMITMENT_TYPE);
+                data.extend_from_slice(hash.as_slice());
+            }
+            Self::Generic { da_layer, payload } => {
+                data.extend_from_slice(&[GENERIC_COMMITMENT_TYPE, *da_layer]);
+                data.extend_from_slice(payload);
+            }
+        }
+        data.into()
+    }
+}
+
+/// A [DataAvailabilityProvider] that resolves the alt-DA commitments served by an inner
+/// [DataAvailabilityProvider] through an [AltDaProvider].
+///
+/// Batcher transactions starting with the [ALT_DA_DERIVATION_VERSION] byte are replaced by the
+/// input they commit to, all other data is passed through unchanged. Commitments that cannot be
+/// decoded, whose challenge expired, or whose input exceeds [MAX_ALT_DA_INPUT_SIZE] are skipped.
+/// A commitment whose input could not be fetched or does not match the commitment is retried on
+/// the next call, before any further data is pulled from the inner source.
+#[derive(Debug, Clone)]
+pub struct AltDaSource<D, A>
+where
+    D: DataAvailabilityProvider + Send,
+    A: AltDaProvider + Send,
+{
+    /// The inner data source serving batcher transactions.
+    pub inner: D,
+    /// The alt-DA provider resolving commitments.
+    pub alt_da: A,
+    /// The commitment whose input could not be fetched yet.
+    pub pending: Option<AltDaCommitment>,
+}
+
+impl<D, A> AltDaSource<D, A>
+where
+    D: DataAvailabilityProvider + Send,
+    A: AltDaProvider + Send,
+{
+    /// Creates a new [AltDaSource] resolving the commitments served by `inner`.
+    pub const fn new(inner: D, alt_da: A) -> Self {
+        Self { inner, alt_da, pending: None }
+    }
+
+    /// Returns the next commitment to resolve, or the data to pass through unchanged.
+    async fn next_commitment(
+        &mut self,
+        block_ref: &BlockInfo,
+    ) -> PipelineResult<Result<AltDaCommitment, Bytes>> {
+        if let Some(commitment) = self.pending.take() {
+            return Ok(Ok(commitment));
+        }
+        loop {
+            let data: Bytes = self.inner.next(block_ref).await?.into();
+            if data.first() != Some(&ALT_DA_DERIVATION_VERSION) {
+                return Ok(Err(data));
+            }
+            match AltDaCommitment::decode(&data[1..]) {
+                Ok(commitment) => return Ok(Ok(commitment)),
+                Err(e) => warn!(target: "alt-da-source", "Skipping alt-DA commitment: {e}"),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<D, A> DataAvailabilityProvider for AltDaSource<D, A>
+where
+    D: DataAvailabilityProvider + Send + Sync,
+    A: AltDaProvider + Send + Sync,
+{
+    type Item = Bytes;
+
+    async fn next(&mut self, block_ref: &BlockInfo) -> PipelineResult<Self::Item> {
+        loop {
+            let commitment = match self.next_commitment(block_ref).await? {
+                Ok(commitment) => commitment,
+                Err(data) => return Ok(data),
+            };
+            let input = self.alt_da.get_input(block_ref, &commitment).await;
+            match input.and_then(|input| commitment.verify(&input).map(|_| input)) {
+                Ok(input) => return Ok(input),
+                Err(e @ (AltDaError::ChallengeExpired | AltDaError::InputTooLarge(_))) => {
+                    warn!(target: "alt-da-source", "Skipping alt-DA commitment: {e}");
+                }
+                Err(e) => {
+                    self.pending = Some(commitment);
+                    return Err(e.into());
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::{PipelineError, PipelineErrorKind},
+        sources::LocalFrameSource,
+        test_utils::TestAltDaProvider,
+    };
+    use alloc::vec;
+
+    fn eigen_da_commitment(payload: &[u8]) -> AltDaCommitment {
+        AltDaCommitment::Generic { da_layer: EIGEN_DA_LAYER, payload: payload.to_vec().into() }
+    }
+
+    fn commitment_tx(commitment: &AltDaCommitment) -> Bytes {
+        let mut data = vec![ALT_DA_DERIVATION_VERSION];
+        data.extend_from_slice(&commitment.encode());
+        data.into()
+    }
+
+    #[test]
+    fn test_alt_da_commitment_roundtrip() {
+        let commitments =
+            [AltDaCommitment::Keccak256(B256::repeat_byte(0xAA)), eigen_da_commitment(&[1, 2, 3])];
+        for commitment in commitments {
+            assert_eq!(AltDaCommitment::decode(&commitment.encode()).unwrap(), commitment);
+        }
+    }
+
+    #[test]
+    fn test_alt_da_commitment_decode_invalid() {
+        for data in [&[][..], &[0x00, 0xAA][..], &[0x01, EIGEN_DA_LAYER][..], &[0x02, 0xAA][..]] {
+            assert_eq!(AltDaCommitment::decode(data), Err(AltDaError::InvalidCommitment));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alt_da_source_resolves_commitments() {
+        let commitment = eigen_da_commitment(&[0xEE]);
+        let frames = Bytes::from_static(&[0x00, 0xAA]);
+        let calldata = Bytes::from_static(&[0x00, 0xBB]);
+        let txs = vec![commitment_tx(&commitment), calldata.clone()];
+        let inner = LocalFrameSource::new(0, vec![txs]);
+        let mut alt_da = TestAltDaProvider::default();
+        alt_da.insert_input(commitment, frames.clone());
+        let mut source = AltDaSource::new(inner, alt_da);
+
+        let block = BlockInfo::default();
+        assert_eq!(source.next(&block).await.unwrap(), frames);
+        assert_eq!(source.next(&block).await.unwrap(), calldata);
+        assert_eq!(source.next(&block).await.unwrap_err(), PipelineError::Eof.temp());
+    }
+
+    #[tokio::test]
+    async fn test_alt_da_source_skips_expired_and_invalid_commitments() {
+        let expired = eigen_da_commitment(&[0xEE]);
+        let calldata = Bytes::from_static(&[0x00, 0xBB]);
+        let txs = vec![
+            commitment_tx(&expired),
+            Bytes::from_static(&[ALT_DA_DERIVATION_VERSION, 0x02]),
+            calldata.clone(),
+        ];
+        let mut alt_da = TestAltDaProvider::default();
+        alt_da.insert_error(expired, AltDaError::ChallengeExpired);
+        let mut source = AltDaSource::new(LocalFrameSource::new(0, vec![txs]), alt_da);
+
+        assert_eq!(source.next(&BlockInfo::default()).await.unwrap(), calldata);
+    }
+
+    #[tokio::test]
+    async fn test_alt_da_source_retries_missing_input() {
+        let commitment = eigen_da_commitment(&[0xEE]);
+        let frames = Bytes::from_static(&[0x00, 0xAA]);
+        let inner = LocalFrameSource::new(0, vec![vec![commitment_tx(&commitment)]]);
+        let mut source = AltDaSource::new(inner, TestAltDaProvider::default());
+
+        let block = BlockInfo::default();
+        let err = source.next(&block).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Provider(_))));
+        assert_eq!(source.pending, Some(commitment.clone()));
+
+        // Once the input is available, the pending commitment is resolved.
+        source.alt_da.insert_input(commitment, frames.clone());
+        assert_eq!(source.next(&block).await.unwrap(), frames);
+        assert_eq!(source.next(&block).await.unwrap_err(), PipelineError::Eof.temp());
+    }
+
+    #[test]
+    fn test_alt_da_commitment_verify() {
+        let input = [0xAA; 8];
+        let commitment = AltDaCommitment::Keccak256(keccak256(input));
+        assert_eq!(commitment.verify(&input), Ok(()));
+        assert_eq!(commitment.verify(&[0xBB; 8]), Err(AltDaError::CommitmentMismatch));
+        let large = vec![0; MAX_ALT_DA_INPUT_SIZE + 1];
+        assert_eq!(
+            eigen_da_commitment(&[0xEE]).verify(&large),
+            Err(AltDaError::InputTooLarge(MAX_ALT_DA_INPUT_SIZE + 1))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_alt_da_source_rejects_mismatched_input() {
+        let frames = Bytes::from_static(&[0x00, 0xAA]);
+        let commitment = AltDaCommitment::Keccak256(keccak256(&frames));
+        let inner = LocalFrameSource::new(0, vec![vec![commitment_tx(&commitment)]]);
+        let mut alt_da = TestAltDaProvider::default();
+        alt_da.insert_input(commitment.clone(), Bytes::from_static(&[0x00, 0xBB]));
+        let mut source = AltDaSource::new(inner, alt_da);
+
+        // A mismatched input is an error, and the commitment is retried.
+        let block = BlockInfo::default();
+        let err = source.next(&block).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Provider(_))));
+        assert_eq!(source.pending, Some(commitment.clone()));
+
+        source.alt_da.insert_input(commitment, frames.clone());
+        assert_eq!(source.next(&block).await.unwrap(), frames);
+    }
+
+    #[tokio::test]
+    async fn test_alt_da_source_skips_oversized_input() {
+        let commitment = eigen_da_commitment(&[0xEE]);
+        let calldata = Bytes::from_static(&[0x00, 0xBB]);
+        let txs = vec![commitment_tx(&commitment), calldata.clone()];
+        let mut alt_da = TestAltDaProvider::default();
+        alt_da.insert_input(commitment, vec![0; MAX_ALT_DA_INPUT_SIZE + 1].into());
+        let mut source = AltDaSource::new(LocalFrameSource::new(0, vec![txs]), alt_da);
+
+        assert_eq!(source.next(&BlockInfo::default()).await.unwrap(), calldata);
+        assert_eq!(source.pending, None);
+    }
+
+    #[tokio::test]
+    async fn test_alt_da_source_missing_past_window_critical() {
+        let commitment = eigen_da_commitment(&[0xEE]);
+        let inner = LocalFrameSource::new(0, vec![vec![commitment_tx(&commitment)]]);
+        let mut alt_da = TestAltDaProvider::default();
+        alt_da.insert_error(commitment, AltDaError::MissingPastWindow);
+        let mut source = AltDaSource::new(inner, alt_da);
+
+        let err = source.next(&BlockInfo::default()).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Critical(PipelineError::Provider(_))));
+    }
+}
@@ -1,10 +1,12 @@
 //! Blob Data Source
 
+#[cfg(feature = "kzg")]
+use crate::errors::BlobDecodingError;
 use crate::{
     errors::{BlobProviderError, PipelineError},
     sources::{BlobData, IndexedBlobHash},
     traits::{BlobProvider, ChainProvider, DataAvailabilityProvider},
-    types::PipelineResult,
+    types::{PipelineResult, SignerCache},
 };
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use alloy_consensus::{Transaction, TxEip4844Variant, TxEnvelope, TxType};
@@ -32,6 +34,8 @@ where
     pub data: Vec<BlobData>,
     /// Whether the source is open.
     pub open: bool,
+    /// The cache of recovered transaction signers, if any.
+    pub signer_cache: Option<SignerCache>,
 }
 
 impl<F, B> BlobSource<F, B>
@@ -53,9 +57,16 @@ where
             signer,
             data: Vec::new(),
             open: false,
+            signer_cache: None,
         }
     }
 
+    /// Sets the [SignerCache] used to look up the signers of batcher transactions.
+    pub fn with_signer_cache(mut self, signer_cache: SignerCache) -> Self {
+        self.signer_cache = Some(signer_cache);
+        self
+    }
+
     fn extract_blob_data(&self, txs: Vec<TxEnvelope>) -> (Vec<BlobData>, Vec<IndexedBlobHash>) {
         let mut number: u64 = 0;
         let mut data = Vec::new();
@@ -82,7 +93,11 @@ where
                 number += blob_hashes.map_or(0, |h| h.len() as u64);
                 continue;
             }
-            if tx.recover_signer().unwrap_or_default() != self.signer {
+            let signer = match &self.signer_cache {
+                Some(cache) => cache.recover_signer(&tx),
+                None => tx.recover_signer().ok(),
+            };
+            if signer.unwrap_or_default() != self.signer {
                 number += blob_hashes.map_or(0, |h| h.len() as u64);
                 continue;
             }
@@ -141,6 +156,17 @@ where
             warn!(target: "blob-source", "Failed to fetch blobs: {e}");
             BlobProviderError::Backend(e.to_string())
         })?;
+        if blobs.len() != blob_hashes.len() {
+            warn!(
+                target: "blob-source",
+                "Expected {} blobs, the blob provider returned {}", blob_hashes.len(), blobs.len()
+            );
+            return Err(BlobProviderError::SidecarLengthMismatch(blob_hashes.len(), blobs.len()));
+        }
+
+        // Don't trust the blob provider, check the blobs against the batcher's versioned hashes.
+        #[cfg(feature = "kzg")]
+        verify_blobs(&blobs, &blob_hashes)?;
 
         // Fill the blob pointers.
         let mut blob_index = 0;
@@ -172,6 +198,32 @@ where
     }
 }
 
+/// Verifies that each blob commits to the versioned hash it was requested by.
+#[cfg(feature = "kzg")]
+fn verify_blobs(
+    blobs: &[Box<alloy_eips::eip4844::Blob>],
+    hashes: &[IndexedBlobHash],
+) -> Result<(), BlobDecodingError> {
+    use alloy_eips::eip4844::{env_settings::EnvKzgSettings, kzg_to_versioned_hash};
+
+    let settings = EnvKzgSettings::Default;
+    for (blob, hash) in blobs.iter().zip(hashes) {
+        let blob = c_kzg::Blob::from_bytes(blob.as_slice())
+            .map_err(|_| BlobDecodingError::InvalidCommitment)?;
+        let commitment = c_kzg::KzgCommitment::blob_to_kzg_commitment(&blob, settings.get())
+            .map_err(|_| BlobDecodingError::InvalidCommitment)?;
+        let versioned_hash = kzg_to_versioned_hash(&commitment.to_bytes().into_inner());
+        if versioned_hash != hash.hash {
+            warn!(
+                target: "blob-source",
+                "Blob #{} does not match versioned hash {}", hash.index, hash.hash
+            );
+            return Err(BlobDecodingError::InvalidCommitment);
+        }
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl<F, B> DataAvailabilityProvider for BlobSource<F, B>
 where
@@ -215,6 +267,9 @@ pub(crate) mod tests {
         errors::PipelineErrorKind,
         test_utils::{TestBlobProvider, TestChainProvider},
     };
+    use alloc::vec;
+    use alloy_consensus::{Signed, TxEip4844};
+    use alloy_primitives::Signature;
     use alloy_rlp::Decodable;
 
     pub(crate) fn default_test_blob_source() -> BlobSource<TestChainProvider, TestBlobProvider> {
@@ -277,38 +332,43 @@ pub(crate) mod tests {
 
     #[tokio::test]
     async fn test_load_blobs_chain_provider_4844_txs_succeeds() {
-        use alloy_consensus::Blob;
+        let (blob, hash) = committed_blob();
+        let mut source = default_test_blob_source();
+        source.batcher_address =
+            alloy_primitives::address!("11E9CA82A3a762b4B5bd264d4173a242e7a77064");
+        let tx = TxEnvelope::Eip4844(Signed::new_unchecked(
+            TxEip4844Variant::TxEip4844(TxEip4844 {
+                to: source.batcher_address,
+                blob_versioned_hashes: vec![hash.hash],
+                ..Default::default()
+            }),
+            Signature::test_signature(),
+            Default::default(),
+        ));
+        source.signer = tx.recover_signer().unwrap();
+        source.chain_provider.insert_block_with_transactions(1, BlockInfo::default(), vec![tx]);
+        source.blob_fetcher.insert_blob(hash.hash, *blob);
+        source.load_blobs(&BlockInfo::default()).await.unwrap();
+        assert!(source.open);
+        assert_eq!(source.data.len(), 1);
+    }
 
+    #[tokio::test]
+    async fn test_load_blobs_missing_blob() {
         let mut source = default_test_blob_source();
-        let block_info = BlockInfo::default();
         source.signer = alloy_primitives::address!("A83C816D4f9b2783761a22BA6FADB0eB0606D7B2");
         source.batcher_address =
             alloy_primitives::address!("11E9CA82A3a762b4B5bd264d4173a242e7a77064");
         let txs = valid_blob_txs();
-        source.chain_provider.insert_block_with_transactions(1, block_info, txs);
-        let hashes = [
-            alloy_primitives::b256!(
-                "012ec3d6f66766bedb002a190126b3549fce0047de0d4c25cffce0dc1c57921a"
-            ),
-            alloy_primitives::b256!(
-                "0152d8e24762ff22b1cfd9f8c0683786a7ca63ba49973818b3d1e9512cd2cec4"
-            ),
-            alloy_primitives::b256!(
-                "013b98c6c83e066d5b14af2b85199e3d4fc7d1e778dd53130d180f5077e2d1c7"
-            ),
-            alloy_primitives::b256!(
-                "01148b495d6e859114e670ca54fb6e2657f0cbae5b08063605093a4b3dc9f8f1"
-            ),
-            alloy_primitives::b256!(
-                "011ac212f13c5dff2b2c6b600a79635103d6f580a4221079951181b25c7e6549"
-            ),
-        ];
-        for hash in hashes {
-            source.blob_fetcher.insert_blob(hash, Blob::with_last_byte(1u8));
-        }
-        source.load_blobs(&BlockInfo::default()).await.unwrap();
-        assert!(source.open);
-        assert!(!source.data.is_empty());
+        source.chain_provider.insert_block_with_transactions(1, BlockInfo::default(), txs);
+        let hash = alloy_primitives::b256!(
+            "012ec3d6f66766bedb002a190126b3549fce0047de0d4c25cffce0dc1c57921a"
+        );
+        source.blob_fetcher.insert_blob(hash, alloy_consensus::Blob::with_last_byte(1u8));
+
+        let err = source.load_blobs(&BlockInfo::default()).await.unwrap_err();
+        assert_eq!(err, BlobProviderError::SidecarLengthMismatch(5, 1));
+        assert!(!source.open);
     }
 
     #[tokio::test]
@@ -346,4 +406,33 @@ pub(crate) mod tests {
         let err = source.next(&BlockInfo::default()).await.unwrap_err();
         assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Provider(_))));
     }
+
+    /// Returns a blob and the versioned hash of its KZG commitment.
+    fn committed_blob() -> (Box<alloy_eips::eip4844::Blob>, IndexedBlobHash) {
+        let blob = Box::new(alloy_eips::eip4844::Blob::with_last_byte(1u8));
+        let hash = alloy_primitives::b256!(
+            "01de636576994ce669a2974044daa5209956dfb6f3fe524758bf7ded9609d41e"
+        );
+        (blob, IndexedBlobHash { hash, index: 0 })
+    }
+
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn test_verify_blobs_valid_commitment() {
+        let (blob, hash) = committed_blob();
+        assert!(verify_blobs(&[blob], &[hash]).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn test_verify_blobs_tampered_blob() {
+        let (mut blob, hash) = committed_blob();
+        blob[31] = 2;
+        assert_eq!(verify_blobs(&[blob], &[hash]), Err(BlobDecodingError::InvalidCommitment));
+
+        // The error halts the pipeline rather than being retried.
+        let err: PipelineErrorKind =
+            BlobProviderError::from(BlobDecodingError::InvalidCommitment).into();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+    }
 }
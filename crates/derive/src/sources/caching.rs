@@ -0,0 +1,431 @@
+//! Contains the [CachingChainProvider], which memoizes the responses of an inner chain provider.
+
+use crate::{
+    errors::PipelineErrorKind,
+    traits::{ChainProvider, L2ChainProvider},
+};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_primitives::{map::HashMap, B256};
+use async_trait::async_trait;
+use core::hash::Hash;
+use op_alloy_consensus::OpBlock;
+use op_alloy_genesis::{RollupConfig, SystemConfig};
+use op_alloy_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo};
+use spin::Mutex;
+
+/// The default number of entries retained per method by the [CachingChainProvider].
+pub const DEFAULT_PROVIDER_CACHE_SIZE: usize = 256;
+
+/// A bounded least-recently-used cache.
+#[derive(Debug)]
+struct LruCache<K, V> {
+    /// The cached entries, along with the tick of their last use.
+    entries: HashMap<K, (V, u64)>,
+    /// The keys of the cached entries by the tick of their last use, least recently used first.
+    order: BTreeMap<u64, K>,
+    /// The tick of the most recent use.
+    tick: u64,
+    /// The maximum number of cached entries.
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    /// Creates a new [LruCache] that retains at most `capacity` entries.
+    fn new(capacity: usize) -> Self {
+        Self { entries: HashMap::default(), order: BTreeMap::new(), tick: 0, capacity }
+    }
+
+    /// Returns the cached value for `key`, marking it as most recently used.
+    fn get(&mut self, key: &K) -> Option<V> {
+        let (value, used) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        self.tick += 1;
+        *used = self.tick;
+        self.order.insert(self.tick, key.clone());
+        Some(value.clone())
+    }
+
+    /// Inserts the value for `key`, evicting the least recently used entry if the cache is full.
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((_, used)) = self.entries.remove(&key) {
+            self.order.remove(&used);
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, evicted)) = self.order.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.tick += 1;
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    /// Retains only the entries whose key satisfies `f`.
+    fn retain(&mut self, mut f: impl FnMut(&K) -> bool) {
+        let entries = &mut self.entries;
+        self.order.retain(|_, key| {
+            let keep = f(key);
+            if !keep {
+                entries.remove(key);
+            }
+            keep
+        });
+    }
+}
+
+/// The per-method caches of a [CachingChainProvider].
+#[derive(Debug)]
+struct ProviderCaches {
+    /// L1 headers, by hash.
+    headers: LruCache<B256, Header>,
+    /// L1 blocks, by number.
+    block_infos: LruCache<u64, BlockInfo>,
+    /// L1 receipts, by block hash.
+    receipts: LruCache<B256, Vec<Receipt>>,
+    /// L1 blocks and their transactions, by block hash.
+    transactions: LruCache<B256, (BlockInfo, Vec<TxEnvelope>)>,
+    /// L2 blocks, by number.
+    l2_block_infos: LruCache<u64, L2BlockInfo>,
+    /// Full L2 blocks, by number.
+    l2_blocks: LruCache<u64, OpBlock>,
+    /// L2 system configs, by block number.
+    system_configs: LruCache<u64, SystemConfig>,
+    /// The number of requests served from the caches.
+    hits: u64,
+    /// The number of requests forwarded to the inner provider.
+    misses: u64,
+}
+
+impl ProviderCaches {
+    /// Creates new caches that retain at most `capacity` entries each.
+    fn new(capacity: usize) -> Self {
+        Self {
+            headers: LruCache::new(capacity),
+            block_infos: LruCache::new(capacity),
+            receipts: LruCache::new(capacity),
+            transactions: LruCache::new(capacity),
+            l2_block_infos: LruCache::new(capacity),
+            l2_blocks: LruCache::new(capacity),
+            system_configs: LruCache::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+/// A [ChainProvider] and [L2ChainProvider] that memoizes the responses of an inner provider in
+/// per-method least-recently-used caches.
+///
+/// Responses requested by hash are immutable, so they are never invalidated. Responses requested
+/// by block number change when the chain reorgs, so they are invalidated through
+/// [ChainProvider::invalidate_l1_above] and [L2ChainProvider::invalidate_l2_above], which the
+/// pipeline calls when it is reset. Errors are not cached. Clones share the same caches.
+#[derive(Debug, Clone)]
+pub struct CachingChainProvider<P> {
+    /// The inner provider that serves cache misses.
+    inner: P,
+    /// The caches, shared between clones.
+    caches: Arc<Mutex<ProviderCaches>>,
+}
+
+impl<P> CachingChainProvider<P> {
+    /// Creates a new [CachingChainProvider] wrapping `inner`, retaining at most `capacity`
+    /// entries per method.
+    pub fn new(inner: P, capacity: usize) -> Self {
+        Self { inner, caches: Arc::new(Mutex::new(ProviderCaches::new(capacity))) }
+    }
+
+    /// Returns a reference to the inner provider.
+    pub const fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner provider.
+    pub fn inner_mut(&mut self) -> &mut P {
+        &mut self.inner
+    }
+
+    /// Consumes the [CachingChainProvider] and returns the inner provider.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Returns the number of requests served from the caches.
+    pub fn hits(&self) -> u64 {
+        self.caches.lock().hits
+    }
+
+    /// Returns the number of requests forwarded to the inner provider.
+    pub fn misses(&self) -> u64 {
+        self.caches.lock().misses
+    }
+}
+
+/// Serves the request from the given cache of a [CachingChainProvider], or forwards it to the
+/// inner provider and caches the response.
+macro_rules! cached {
+    ($self:ident, $cache:ident, $key:expr, $request:expr) => {{
+        let cached = {
+            let mut caches = $self.caches.lock();
+            let value = caches.$cache.get(&$key);
+            if value.is_some() {
+                caches.hits += 1;
+            } else {
+                caches.misses += 1;
+            }
+            value
+        };
+        if let Some(value) = cached {
+            return Ok(value);
+        }
+        let value = $request.await?;
+        $self.caches.lock().$cache.insert($key, value.clone());
+        Ok(value)
+    }};
+}
+
+#[async_trait]
+impl<P: ChainProvider + Send> ChainProvider for CachingChainProvider<P> {
+    type Error = P::Error;
+
+    async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+        cached!(self, headers, hash, self.inner.header_by_hash(hash))
+    }
+
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        cached!(self, block_infos, number, self.inner.block_info_by_number(number))
+    }
+
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        cached!(self, receipts, hash, self.inner.receipts_by_hash(hash))
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        cached!(self, transactions, hash, self.inner.block_info_and_transactions_by_hash(hash))
+    }
+
+    fn invalidate_l1_above(&mut self, number: u64) {
+        self.caches.lock().block_infos.retain(|n| *n <= number);
+        self.inner.invalidate_l1_above(number);
+    }
+}
+
+#[async_trait]
+impl<P: BatchValidationProvider + Send> BatchValidationProvider for CachingChainProvider<P> {
+    type Error = <P as BatchValidationProvider>::Error;
+
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        cached!(self, l2_block_infos, number, self.inner.l2_block_info_by_number(number))
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        cached!(self, l2_blocks, number, self.inner.block_by_number(number))
+    }
+}
+
+#[async_trait]
+impl<P> L2ChainProvider for CachingChainProvider<P>
+where
+    P: L2ChainProvider + Send,
+    <P as BatchValidationProvider>::Error: Into<PipelineErrorKind>,
+{
+    type Error = <P as L2ChainProvider>::Error;
+
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+        cached!(
+            self,
+            system_configs,
+            number,
+            self.inner.system_config_by_number(number, rollup_config)
+        )
+    }
+
+    fn invalidate_l2_above(&mut self, number: u64) {
+        let mut caches = self.caches.lock();
+        caches.l2_block_infos.retain(|n| *n <= number);
+        caches.l2_blocks.retain(|n| *n <= number);
+        caches.system_configs.retain(|n| *n <= number);
+        drop(caches);
+        self.inner.invalidate_l2_above(number);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pipeline::DerivationPipeline,
+        stages::L1Traversal,
+        test_utils::{
+            TestChainProvider, TestChainProviderBuilder, TestL2ChainProviderBuilder,
+            TestNextAttributes,
+        },
+        traits::SignalReceiver,
+        types::ResetSignal,
+    };
+
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo { number, hash: B256::with_last_byte(number as u8), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_serves_repeated_queries() {
+        let mut inner = TestChainProvider::default();
+        inner.insert_block(1, block(1));
+        inner.insert_receipts(block(1).hash, Vec::new());
+        let mut provider = CachingChainProvider::new(inner, 4);
+
+        assert_eq!(provider.block_info_by_number(1).await.unwrap(), block(1));
+        assert!(provider.receipts_by_hash(block(1).hash).await.unwrap().is_empty());
+        assert_eq!((provider.hits(), provider.misses()), (0, 2));
+
+        // The inner provider is not called again for repeated queries.
+        provider.inner_mut().clear();
+        assert_eq!(provider.block_info_by_number(1).await.unwrap(), block(1));
+        assert!(provider.receipts_by_hash(block(1).hash).await.unwrap().is_empty());
+        assert_eq!((provider.hits(), provider.misses()), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_errors_not_cached() {
+        let mut provider = CachingChainProvider::new(TestChainProvider::default(), 4);
+        assert!(provider.block_info_by_number(1).await.is_err());
+
+        provider.inner_mut().insert_block(1, block(1));
+        assert_eq!(provider.block_info_by_number(1).await.unwrap(), block(1));
+        assert_eq!(provider.misses(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_evicts_least_recently_used() {
+        let mut inner = TestChainProvider::default();
+        (1..=3).for_each(|n| inner.insert_block(n, block(n)));
+        let mut provider = CachingChainProvider::new(inner, 2);
+
+        provider.block_info_by_number(1).await.unwrap();
+        provider.block_info_by_number(2).await.unwrap();
+        // Block #1 is used again, so block #2 is evicted when block #3 is cached.
+        provider.block_info_by_number(1).await.unwrap();
+        provider.block_info_by_number(3).await.unwrap();
+
+        provider.inner_mut().clear();
+        assert!(provider.block_info_by_number(1).await.is_ok());
+        assert!(provider.block_info_by_number(3).await.is_ok());
+        assert!(provider.block_info_by_number(2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_invalidate_l1_above() {
+        let mut inner = TestChainProvider::default();
+        (1..=3).for_each(|n| inner.insert_block(n, block(n)));
+        let mut provider = CachingChainProvider::new(inner, 4);
+        for n in 1..=3 {
+            provider.block_info_by_number(n).await.unwrap();
+        }
+
+        provider.invalidate_l1_above(1);
+        provider.inner_mut().clear();
+        assert!(provider.block_info_by_number(1).await.is_ok());
+        assert!(provider.block_info_by_number(2).await.is_err());
+        assert!(provider.block_info_by_number(3).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_invalidate_l2_above() {
+        let l1_chain = TestChainProviderBuilder::new(1).blocks();
+        let builder = TestL2ChainProviderBuilder::new(&l1_chain, 3);
+        let mut provider = CachingChainProvider::new(builder.build(), 4);
+        for n in 1..=2 {
+            provider.l2_block_info_by_number(n).await.unwrap();
+        }
+
+        provider.invalidate_l2_above(1);
+        provider.inner_mut().blocks.clear();
+        assert!(provider.l2_block_info_by_number(1).await.is_ok());
+        assert!(provider.l2_block_info_by_number(2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_invalidated_on_l1_reset() {
+        let mut inner = TestChainProvider::default();
+        (1..=2).for_each(|n| inner.insert_block(n, block(n)));
+        let mut provider = CachingChainProvider::new(inner, 4);
+        provider.block_info_by_number(2).await.unwrap();
+
+        let mut traversal = L1Traversal::new(provider.clone(), Arc::new(RollupConfig::default()));
+        let reset = ResetSignal {
+            l1_origin: block(1),
+            system_config: Some(SystemConfig::default()),
+            ..Default::default()
+        };
+        traversal.signal(reset.signal()).await.unwrap();
+
+        provider.inner_mut().clear();
+        assert!(provider.block_info_by_number(2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_invalidated_on_reset() {
+        let l1_chain = TestChainProviderBuilder::new(1).blocks();
+        let builder = TestL2ChainProviderBuilder::new(&l1_chain, 3)
+            .with_system_config(SystemConfig::default());
+        let mut provider = CachingChainProvider::new(builder.build(), 4);
+        provider.l2_block_info_by_number(2).await.unwrap();
+
+        let mut pipeline = DerivationPipeline::new(
+            TestNextAttributes::default(),
+            Arc::new(RollupConfig::default()),
+            provider.clone(),
+        );
+        let l2_safe_head = provider.l2_block_info_by_number(1).await.unwrap();
+        let reset = ResetSignal { l2_safe_head, ..Default::default() };
+        pipeline.signal(reset.signal()).await.unwrap();
+
+        provider.inner_mut().blocks.clear();
+        assert!(provider.l2_block_info_by_number(1).await.is_ok());
+        assert!(provider.l2_block_info_by_number(2).await.is_err());
+    }
+
+    #[test]
+    fn test_lru_cache_retain() {
+        let mut cache = LruCache::new(4);
+        (1..=4).for_each(|n| cache.insert(n, n));
+        cache.retain(|n| n % 2 == 0);
+        assert_eq!((cache.entries.len(), cache.order.len()), (2, 2));
+        assert_eq!(cache.get(&2), Some(2));
+        assert_eq!(cache.get(&3), None);
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_l2_queries() {
+        let l1_chain = TestChainProviderBuilder::new(1).blocks();
+        let builder = TestL2ChainProviderBuilder::new(&l1_chain, 2)
+            .with_system_config(SystemConfig::default());
+        let l2_block = builder.blocks()[1];
+        let mut provider = CachingChainProvider::new(builder.build(), 4);
+        let cfg = Arc::new(RollupConfig::default());
+
+        for _ in 0..2 {
+            assert_eq!(provider.l2_block_info_by_number(1).await.unwrap(), l2_block);
+            assert!(provider.system_config_by_number(1, cfg.clone()).await.is_ok());
+        }
+        assert_eq!((provider.hits(), provider.misses()), (2, 2));
+
+        // Clones share the caches.
+        let mut clone = provider.clone();
+        clone.inner_mut().blocks.clear();
+        assert_eq!(clone.l2_block_info_by_number(1).await.unwrap(), l2_block);
+        assert_eq!(provider.hits(), 3);
+    }
+}
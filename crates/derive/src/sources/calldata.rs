@@ -3,7 +3,7 @@
 use crate::{
     errors::PipelineError,
     traits::{ChainProvider, DataAvailabilityProvider},
-    types::PipelineResult,
+    types::{PipelineResult, SignerCache},
 };
 use alloc::{boxed::Box, collections::VecDeque};
 use alloy_consensus::{Transaction, TxEnvelope};
@@ -27,12 +27,27 @@ where
     pub calldata: VecDeque<Bytes>,
     /// Whether the calldata source is open.
     pub open: bool,
+    /// The cache of recovered transaction signers, if any.
+    pub signer_cache: Option<SignerCache>,
 }
 
 impl<CP: ChainProvider + Send> CalldataSource<CP> {
     /// Creates a new calldata source.
     pub const fn new(chain_provider: CP, batch_inbox_address: Address, signer: Address) -> Self {
-        Self { chain_provider, batch_inbox_address, signer, calldata: VecDeque::new(), open: false }
+        Self {
+            chain_provider,
+            batch_inbox_address,
+            signer,
+            calldata: VecDeque::new(),
+            open: false,
+            signer_cache: None,
+        }
+    }
+
+    /// Sets the [SignerCache] used to look up the signers of batch inbox transactions.
+    pub fn with_signer_cache(mut self, signer_cache: SignerCache) -> Self {
+        self.signer_cache = Some(signer_cache);
+        self
     }
 
     /// Loads the calldata into the source if it is not open.
@@ -58,7 +73,11 @@ impl<CP: ChainProvider + Send> CalldataSource<CP> {
                 if to != self.batch_inbox_address {
                     return None;
                 }
-                if tx.recover_signer().ok()? != self.signer {
+                let signer = match &self.signer_cache {
+                    Some(cache) => cache.recover_signer(tx)?,
+                    None => tx.recover_signer().ok()?,
+                };
+                if signer != self.signer {
                     return None;
                 }
                 Some(data.to_vec().into())
@@ -92,7 +111,7 @@ mod tests {
     use crate::{errors::PipelineErrorKind, test_utils::TestChainProvider};
     use alloc::{vec, vec::Vec};
     use alloy_consensus::{Signed, TxEip2930, TxEip4844, TxEip4844Variant, TxLegacy};
-    use alloy_primitives::{address, Address, Signature, TxKind};
+    use alloy_primitives::{address, Address, Signature, TxKind, B256};
 
     pub(crate) fn test_legacy_tx(to: Address) -> TxEnvelope {
         let sig = Signature::test_signature();
@@ -239,4 +258,37 @@ mod tests {
             Err(PipelineErrorKind::Temporary(_))
         ));
     }
+
+    #[tokio::test]
+    async fn test_signer_cache_across_reset() {
+        let batch_inbox_address = address!("0123456789012345678901234567890123456789");
+        let tx = |nonce: u8| {
+            let tx = TxLegacy {
+                nonce: nonce as u64,
+                to: TxKind::Call(batch_inbox_address),
+                ..Default::default()
+            };
+            TxEnvelope::Legacy(Signed::new_unchecked(
+                tx,
+                Signature::test_signature(),
+                B256::repeat_byte(nonce),
+            ))
+        };
+        let cache = SignerCache::default();
+        let mut source = default_test_calldata_source().with_signer_cache(cache.clone());
+        source.batch_inbox_address = batch_inbox_address;
+        source.signer = tx(1).recover_signer().unwrap();
+        let txs = vec![tx(1), tx(2)];
+        source.chain_provider.insert_block_with_transactions(0, BlockInfo::default(), txs);
+
+        // A reset replays the same origin, which must not recover the signers again. The
+        // transactions differ, so only the first one recovers to the batcher.
+        for _ in 0..2 {
+            source.load_calldata(&BlockInfo::default()).await.unwrap();
+            assert_eq!(source.calldata.len(), 1);
+            source.clear();
+        }
+        assert_eq!((cache.misses(), cache.hits()), (2, 2));
+        assert_eq!(cache.len(), 2);
+    }
 }
@@ -4,7 +4,7 @@
 use crate::{
     sources::{BlobSource, CalldataSource},
     traits::{BlobProvider, ChainProvider, DataAvailabilityProvider},
-    types::PipelineResult,
+    types::{PipelineResult, SignerCache},
 };
 use alloc::{boxed::Box, fmt::Debug};
 use alloy_primitives::Bytes;
@@ -42,13 +42,19 @@ where
     }
 
     /// Instantiates a new [EthereumDataSource] from parts.
+    ///
+    /// The blob and calldata sources share a single [SignerCache], so that batcher transactions
+    /// replayed after a reset are not recovered again.
     pub fn new_from_parts(provider: C, blobs: B, cfg: &RollupConfig) -> Self {
         let signer =
             cfg.genesis.system_config.as_ref().map(|sc| sc.batcher_address).unwrap_or_default();
+        let signer_cache = SignerCache::default();
         Self {
             ecotone_timestamp: cfg.ecotone_time,
-            blob_source: BlobSource::new(provider.clone(), blobs, cfg.batch_inbox_address, signer),
-            calldata_source: CalldataSource::new(provider, cfg.batch_inbox_address, signer),
+            blob_source: BlobSource::new(provider.clone(), blobs, cfg.batch_inbox_address, signer)
+                .with_signer_cache(signer_cache.clone()),
+            calldata_source: CalldataSource::new(provider, cfg.batch_inbox_address, signer)
+                .with_signer_cache(signer_cache),
         }
     }
 }
@@ -0,0 +1,260 @@
+//! Contains the [LocalFrameSource] and [SyntheticChainProvider], used to run derivation over
+//! batcher data that has not been posted to L1.
+
+use crate::{
+    errors::{PipelineError, SyntheticChainError},
+    traits::{ChainProvider, DataAvailabilityProvider},
+    types::PipelineResult,
+};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_primitives::{keccak256, Bytes, B256};
+use async_trait::async_trait;
+use op_alloy_protocol::BlockInfo;
+
+/// A [DataAvailabilityProvider] that serves batcher transactions from memory.
+///
+/// The batcher transactions are grouped per L1 block, starting at a configured L1 block number.
+/// With the `std` feature, the groups can be loaded from a directory with [Self::from_dir]. L1
+/// blocks without data yield no batcher transactions.
+#[derive(Debug, Clone)]
+pub struct LocalFrameSource {
+    /// The L1 block number of the first group of batcher transactions.
+    start: u64,
+    /// The batcher transactions, grouped per L1 block.
+    blocks: Vec<Vec<Bytes>>,
+    /// The batcher transactions of the current L1 block that have not been served yet.
+    data: VecDeque<Bytes>,
+    /// Whether the current L1 block has been loaded.
+    open: bool,
+}
+
+impl LocalFrameSource {
+    /// Creates a new [LocalFrameSource] serving `blocks[i]` at L1 block `start + i`.
+    pub fn new(start: u64, blocks: Vec<Vec<Bytes>>) -> Self {
+        Self { start, blocks, data: VecDeque::new(), open: false }
+    }
+
+    /// Loads a [LocalFrameSource] from a directory with one subdirectory per L1 block, named by
+    /// its block number, holding one file per batcher transaction. The files of a block are
+    /// served in the order of their names. Entries that are not named by a block number are
+    /// ignored.
+    #[cfg(any(test, feature = "std"))]
+    pub fn from_dir(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let mut groups = alloc::collections::BTreeMap::<u64, Vec<Bytes>>::new();
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let Some(number) = entry.file_name().to_str().and_then(|name| name.parse().ok()) else {
+                continue;
+            };
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let mut files = std::fs::read_dir(entry.path())?
+                .map(|file| file.map(|file| file.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            files.retain(|file| file.is_file());
+            files.sort();
+            let txs = files
+                .iter()
+                .map(|file| std::fs::read(file).map(Bytes::from))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            groups.insert(number, txs);
+        }
+
+        let start = groups.keys().next().copied().unwrap_or_default();
+        let len = groups.keys().next_back().map_or(0, |end| end - start + 1);
+        let mut blocks = alloc::vec![Vec::new(); len as usize];
+        for (number, txs) in groups {
+            blocks[(number - start) as usize] = txs;
+        }
+        Ok(Self::new(start, blocks))
+    }
+
+    /// Returns the L1 block number of the last group of batcher transactions, if any.
+    pub fn end(&self) -> Option<u64> {
+        self.blocks.len().checked_sub(1).map(|len| self.start + len as u64)
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityProvider for LocalFrameSource {
+    type Item = Bytes;
+
+    async fn next(&mut self, block_ref: &BlockInfo) -> PipelineResult<Self::Item> {
+        if !self.open {
+            let index = block_ref.number.checked_sub(self.start);
+            let txs = index.and_then(|i| self.blocks.get(i as usize)).cloned().unwrap_or_default();
+            self.data = txs.into();
+            self.open = true;
+        }
+        self.data.pop_front().ok_or(PipelineError::Eof.temp())
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.open = false;
+    }
+}
+
+/// A [ChainProvider] that fabricates a consistent, empty L1 chain.
+///
+/// Blocks are produced at a fixed block time, and each block links to its parent by the hash of
+/// its [Header]. Blocks carry no transactions or receipts, so the [SystemConfig] is never
+/// updated.
+///
+/// [SystemConfig]: op_alloy_genesis::SystemConfig
+#[derive(Debug, Clone)]
+pub struct SyntheticChainProvider {
+    /// The fabricated headers, starting with the first block.
+    headers: Vec<Header>,
+    /// The [BlockInfo] of each fabricated header.
+    blocks: Vec<BlockInfo>,
+}
+
+impl SyntheticChainProvider {
+    /// Creates a new [SyntheticChainProvider] with `len` blocks, starting at block number `start`
+    /// with the given timestamp and spaced `block_time` seconds apart. The mix hash of each
+    /// block is derived from its number.
+    pub fn new(start: u64, timestamp: u64, block_time: u64, len: u64) -> Self {
+        let headers = chain_headers(start, timestamp, block_time, len, |header| {
+            header.mix_hash = keccak256(header.number.to_be_bytes());
+        });
+        let blocks = headers.iter().map(header_block_info).collect();
+        Self { headers, blocks }
+    }
+
+    /// Returns the fabricated headers, starting with the first block.
+    pub fn headers(&self) -> &[Header] {
+        &self.headers
+    }
+
+    /// Returns the block at the given number.
+    pub fn block(&self, number: u64) -> Option<BlockInfo> {
+        let index = number.checked_sub(self.blocks.first()?.number)?;
+        self.blocks.get(index as usize).copied()
+    }
+
+    /// Looks up the index of the block with the given hash.
+    fn index_by_hash(&self, hash: B256) -> Result<usize, SyntheticChainError> {
+        self.blocks
+            .iter()
+            .position(|b| b.hash == hash)
+            .ok_or(SyntheticChainError::HashNotFound(hash))
+    }
+}
+
+#[async_trait]
+impl ChainProvider for SyntheticChainProvider {
+    type Error = SyntheticChainError;
+
+    async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+        self.index_by_hash(hash).map(|i| self.headers[i].clone())
+    }
+
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        self.block(number).ok_or(SyntheticChainError::BlockNotFound(number))
+    }
+
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        self.index_by_hash(hash).map(|_| Vec::new())
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        self.index_by_hash(hash).map(|i| (self.blocks[i], Vec::new()))
+    }
+}
+
+/// Generates `len` headers starting at block number `start` with the given timestamp, spaced
+/// `block_time` seconds apart. Each header links to its predecessor by hash, and is passed to
+/// `fill` before it is hashed.
+pub(crate) fn chain_headers(
+    start: u64,
+    timestamp: u64,
+    block_time: u64,
+    len: u64,
+    mut fill: impl FnMut(&mut Header),
+) -> Vec<Header> {
+    let mut headers = Vec::<Header>::with_capacity(len as usize);
+    for i in 0..len {
+        let mut header = Header {
+            parent_hash: headers.last().map(Header::hash_slow).unwrap_or_default(),
+            number: start + i,
+            timestamp: timestamp + i * block_time,
+            ..Default::default()
+        };
+        fill(&mut header);
+        headers.push(header);
+    }
+    headers
+}
+
+/// Returns the [BlockInfo] of the given header.
+pub(crate) fn header_block_info(header: &Header) -> BlockInfo {
+    BlockInfo {
+        hash: header.hash_slow(),
+        number: header.number,
+        parent_hash: header.parent_hash,
+        timestamp: header.timestamp,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[tokio::test]
+    async fn test_synthetic_chain_is_consistent() {
+        let mut provider = SyntheticChainProvider::new(5, 100, 12, 4);
+        for number in 6..=8 {
+            let parent = provider.block_info_by_number(number - 1).await.unwrap();
+            let block = provider.block_info_by_number(number).await.unwrap();
+            assert_eq!(block.parent_hash, parent.hash);
+            assert_eq!(block.timestamp, 100 + (number - 5) * 12);
+
+            let header = provider.header_by_hash(block.hash).await.unwrap();
+            assert_eq!(header.hash_slow(), block.hash);
+            assert_eq!(header.parent_hash, parent.hash);
+            assert_eq!(provider.receipts_by_hash(block.hash).await.unwrap(), vec![]);
+        }
+        assert_eq!(
+            provider.block_info_by_number(4).await.unwrap_err(),
+            SyntheticChainError::BlockNotFound(4)
+        );
+        assert_eq!(
+            provider.block_info_by_number(9).await.unwrap_err(),
+            SyntheticChainError::BlockNotFound(9)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_frame_source_from_dir() {
+        let dir = std::env::temp_dir().join(format!("kona-local-frames-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let files = [("3", "1.bin", [0x02]), ("3", "0.bin", [0x01]), ("5", "0", [0x03])];
+        for (block, file, data) in files {
+            std::fs::create_dir_all(dir.join(block)).unwrap();
+            std::fs::write(dir.join(block).join(file), data).unwrap();
+        }
+        std::fs::write(dir.join("README"), b"not a block").unwrap();
+
+        let mut source = LocalFrameSource::from_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(source.end(), Some(5));
+
+        let mut served = Vec::new();
+        for number in 2..=5 {
+            source.clear();
+            let block = BlockInfo { number, ..Default::default() };
+            while let Ok(tx) = source.next(&block).await {
+                served.push((number, tx));
+            }
+        }
+        let expected = [(3, [0x01]), (3, [0x02]), (5, [0x03])];
+        assert_eq!(served, expected.map(|(n, tx)| (n, Bytes::copy_from_slice(&tx))));
+    }
+}
@@ -21,3 +21,23 @@ pub use blobs::BlobSource;
 
 mod calldata;
 pub use calldata::CalldataSource;
+
+mod alt_da;
+pub use alt_da::{
+    AltDaCommitment, AltDaSource, ALT_DA_DERIVATION_VERSION, EIGEN_DA_LAYER,
+    GENERIC_COMMITMENT_TYPE, KECCAK256_COMMITMENT_TYPE, MAX_ALT_DA_INPUT_SIZE,
+};
+
+mod local;
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) use local::{chain_headers, header_block_info};
+pub use local::{LocalFrameSource, SyntheticChainProvider};
+
+mod replay;
+pub use replay::{RecordingChainProvider, ReplayChainProvider};
+
+mod retry;
+pub use retry::RetryProvider;
+
+mod caching;
+pub use caching::{CachingChainProvider, DEFAULT_PROVIDER_CACHE_SIZE};
@@ -0,0 +1,434 @@
+//! Contains the [RecordingChainProvider] and [ReplayChainProvider], used to record provider
+//! traffic during a derivation run and reproduce the run deterministically from the log.
+
+use crate::{
+    errors::{PipelineErrorKind, ReplayError},
+    sources::IndexedBlobHash,
+    traits::{BlobProvider, ChainProvider, L2ChainProvider, ReplaySink},
+    types::{ChainRequest, ChainResponse, ReplayEntry},
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::{map::HashMap, B256};
+use alloy_rlp::{Decodable, Encodable};
+use async_trait::async_trait;
+use op_alloy_consensus::OpBlock;
+use op_alloy_genesis::{RollupConfig, SystemConfig};
+use op_alloy_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo};
+
+/// A provider that forwards every request to an inner provider and appends each successful
+/// request/response pair to a [ReplaySink].
+///
+/// It implements [ChainProvider], [L2ChainProvider] and [BlobProvider] for inner providers that
+/// do, so every provider of the pipeline can be wrapped to record into the same sink. Failed
+/// requests are not recorded, so a later replay surfaces them as requests missing from the log.
+#[derive(Debug, Clone)]
+pub struct RecordingChainProvider<P> {
+    /// The inner provider that serves the requests.
+    inner: P,
+    /// The sink that recorded entries are appended to.
+    sink: Arc<dyn ReplaySink>,
+}
+
+impl<P> RecordingChainProvider<P> {
+    /// Creates a new [RecordingChainProvider] wrapping `inner`.
+    pub const fn new(inner: P, sink: Arc<dyn ReplaySink>) -> Self {
+        Self { inner, sink }
+    }
+
+    /// Returns a reference to the inner provider.
+    pub const fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Consumes the [RecordingChainProvider] and returns the inner provider.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Appends a request/response pair to the sink.
+    fn record(&self, request: ChainRequest, response: ChainResponse) {
+        self.sink.append(ReplayEntry::new(request, response));
+    }
+}
+
+#[async_trait]
+impl<P: ChainProvider + Send> ChainProvider for RecordingChainProvider<P> {
+    type Error = P::Error;
+
+    async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+        let header = self.inner.header_by_hash(hash).await?;
+        let response = ChainResponse::Header(Box::new(header.clone()));
+        self.record(ChainRequest::HeaderByHash(hash), response);
+        Ok(header)
+    }
+
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        let block = self.inner.block_info_by_number(number).await?;
+        self.record(ChainRequest::BlockInfoByNumber(number), ChainResponse::BlockInfo(block));
+        Ok(block)
+    }
+
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        let receipts = self.inner.receipts_by_hash(hash).await?;
+        self.record(ChainRequest::ReceiptsByHash(hash), ChainResponse::Receipts(receipts.clone()));
+        Ok(receipts)
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        let (block, txs) = self.inner.block_info_and_transactions_by_hash(hash).await?;
+        self.record(
+            ChainRequest::BlockInfoAndTransactionsByHash(hash),
+            ChainResponse::BlockInfoAndTransactions(block, txs.clone()),
+        );
+        Ok((block, txs))
+    }
+
+    fn invalidate_l1_above(&mut self, number: u64) {
+        self.inner.invalidate_l1_above(number);
+    }
+}
+
+#[async_trait]
+impl<P: BatchValidationProvider + Send> BatchValidationProvider for RecordingChainProvider<P> {
+    type Error = P::Error;
+
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        let block = self.inner.l2_block_info_by_number(number).await?;
+        self.record(ChainRequest::L2BlockInfoByNumber(number), ChainResponse::L2BlockInfo(block));
+        Ok(block)
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        let block = self.inner.block_by_number(number).await?;
+        let mut rlp = Vec::new();
+        block.encode(&mut rlp);
+        self.record(ChainRequest::L2BlockByNumber(number), ChainResponse::L2Block(rlp.into()));
+        Ok(block)
+    }
+}
+
+#[async_trait]
+impl<P> L2ChainProvider for RecordingChainProvider<P>
+where
+    P: L2ChainProvider + Send,
+    <P as BatchValidationProvider>::Error: Into<PipelineErrorKind>,
+{
+    type Error = <P as L2ChainProvider>::Error;
+
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+        let config = self.inner.system_config_by_number(number, rollup_config).await?;
+        let request = ChainRequest::SystemConfigByNumber(number);
+        self.record(request, ChainResponse::SystemConfig(config));
+        Ok(config)
+    }
+
+    fn invalidate_l2_above(&mut self, number: u64) {
+        self.inner.invalidate_l2_above(number);
+    }
+}
+
+#[async_trait]
+impl<P: BlobProvider + Send> BlobProvider for RecordingChainProvider<P> {
+    type Error = P::Error;
+
+    async fn get_blobs(
+        &mut self,
+        block_ref: &BlockInfo,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        let blobs = self.inner.get_blobs(block_ref, blob_hashes).await?;
+        for (hash, blob) in blob_hashes.iter().zip(&blobs) {
+            self.record(ChainRequest::BlobByHash(hash.hash), ChainResponse::Blob(blob.clone()));
+        }
+        Ok(blobs)
+    }
+}
+
+/// A provider that serves every request purely from a recorded replay log.
+///
+/// It implements [ChainProvider], [L2ChainProvider] and [BlobProvider], so a single log recorded
+/// by [RecordingChainProvider]s can stand in for every provider of the pipeline. Any request that
+/// is not present in the log fails with [ReplayError::NotInLog], which signals that the replayed
+/// run diverged from the recorded one.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayChainProvider {
+    /// The recorded responses, keyed by request.
+    responses: Arc<HashMap<ChainRequest, ChainResponse>>,
+}
+
+impl ReplayChainProvider {
+    /// Creates a new [ReplayChainProvider] from the recorded entries.
+    ///
+    /// If a request was recorded more than once, the latest response is served.
+    pub fn new(entries: impl IntoIterator<Item = ReplayEntry>) -> Self {
+        let responses = entries.into_iter().map(|e| (e.request, e.response)).collect();
+        Self { responses: Arc::new(responses) }
+    }
+
+    /// Returns the number of distinct requests in the log.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Returns whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Looks up the recorded response for the given request.
+    fn lookup(&self, request: ChainRequest) -> Result<&ChainResponse, ReplayError> {
+        self.responses.get(&request).ok_or(ReplayError::NotInLog(request))
+    }
+}
+
+#[async_trait]
+impl ChainProvider for ReplayChainProvider {
+    type Error = ReplayError;
+
+    async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+        let request = ChainRequest::HeaderByHash(hash);
+        match self.lookup(request)? {
+            ChainResponse::Header(header) => Ok(header.as_ref().clone()),
+            _ => Err(ReplayError::ResponseMismatch(request)),
+        }
+    }
+
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        let request = ChainRequest::BlockInfoByNumber(number);
+        match self.lookup(request)? {
+            ChainResponse::BlockInfo(block) => Ok(*block),
+            _ => Err(ReplayError::ResponseMismatch(request)),
+        }
+    }
+
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        let request = ChainRequest::ReceiptsByHash(hash);
+        match self.lookup(request)? {
+            ChainResponse::Receipts(receipts) => Ok(receipts.clone()),
+            _ => Err(ReplayError::ResponseMismatch(request)),
+        }
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        let request = ChainRequest::BlockInfoAndTransactionsByHash(hash);
+        match self.lookup(request)? {
+            ChainResponse::BlockInfoAndTransactions(block, txs) => Ok((*block, txs.clone())),
+            _ => Err(ReplayError::ResponseMismatch(request)),
+        }
+    }
+}
+
+#[async_trait]
+impl BatchValidationProvider for ReplayChainProvider {
+    type Error = ReplayError;
+
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        let request = ChainRequest::L2BlockInfoByNumber(number);
+        match self.lookup(request)? {
+            ChainResponse::L2BlockInfo(block) => Ok(*block),
+            _ => Err(ReplayError::ResponseMismatch(request)),
+        }
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        let request = ChainRequest::L2BlockByNumber(number);
+        match self.lookup(request)? {
+            ChainResponse::L2Block(rlp) => OpBlock::decode(&mut rlp.as_ref())
+                .map_err(|_| ReplayError::InvalidResponse(request)),
+            _ => Err(ReplayError::ResponseMismatch(request)),
+        }
+    }
+}
+
+#[async_trait]
+impl L2ChainProvider for ReplayChainProvider {
+    type Error = ReplayError;
+
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+        _: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+        let request = ChainRequest::SystemConfigByNumber(number);
+        match self.lookup(request)? {
+            ChainResponse::SystemConfig(config) => Ok(*config),
+            _ => Err(ReplayError::ResponseMismatch(request)),
+        }
+    }
+}
+
+#[async_trait]
+impl BlobProvider for ReplayChainProvider {
+    type Error = ReplayError;
+
+    async fn get_blobs(
+        &mut self,
+        _: &BlockInfo,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        blob_hashes
+            .iter()
+            .map(|hash| {
+                let request = ChainRequest::BlobByHash(hash.hash);
+                match self.lookup(request)? {
+                    ChainResponse::Blob(blob) => Ok(blob.clone()),
+                    _ => Err(ReplayError::ResponseMismatch(request)),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::PipelineErrorKind,
+        stages::L1Traversal,
+        test_utils::{TestBlobProvider, TestChainProvider, TestL2ChainProvider, TestReplaySink},
+        traits::{OriginAdvancer, OriginProvider},
+    };
+    use alloc::vec;
+    use alloy_consensus::BlockBody;
+
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo {
+            number,
+            hash: B256::with_last_byte(number as u8 + 1),
+            parent_hash: B256::with_last_byte(number as u8),
+            timestamp: number * 12,
+        }
+    }
+
+    fn chain_provider() -> TestChainProvider {
+        let mut provider = TestChainProvider::default();
+        for number in 0..4 {
+            provider.insert_block(number, block(number));
+            provider.insert_receipts(block(number).hash, vec![]);
+        }
+        provider
+    }
+
+    async fn walk<P: ChainProvider + Send>(provider: P, steps: usize) -> Vec<BlockInfo> {
+        let mut traversal = L1Traversal::new(provider, Arc::new(RollupConfig::default()));
+        traversal.block = Some(block(0));
+        let mut origins = Vec::new();
+        for _ in 0..steps {
+            traversal.advance_origin().await.unwrap();
+            origins.push(traversal.origin().unwrap());
+        }
+        origins
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let sink = TestReplaySink::default();
+        let recorder = RecordingChainProvider::new(chain_provider(), Arc::new(sink.clone()));
+        let recorded = walk(recorder, 3).await;
+
+        // Each advance fetches the next block and its receipts.
+        let entries = sink.entries();
+        assert_eq!(entries.len(), 6);
+        assert_eq!(entries[0].request, ChainRequest::BlockInfoByNumber(1));
+        assert_eq!(entries[1].request, ChainRequest::ReceiptsByHash(block(1).hash));
+
+        let replay = ReplayChainProvider::new(entries);
+        assert_eq!(replay.len(), 6);
+        let replayed = walk(replay, 3).await;
+        assert_eq!(recorded, replayed);
+        assert_eq!(replayed, vec![block(1), block(2), block(3)]);
+    }
+
+    #[tokio::test]
+    async fn test_replay_request_not_in_log() {
+        let sink = TestReplaySink::default();
+        let recorder = RecordingChainProvider::new(chain_provider(), Arc::new(sink.clone()));
+        walk(recorder, 1).await;
+
+        let mut traversal =
+            L1Traversal::new(ReplayChainProvider::new(sink.entries()), Default::default());
+        traversal.block = Some(block(1));
+        let err = traversal.advance_origin().await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+
+        let mut replay = ReplayChainProvider::new(sink.entries());
+        let err = replay.header_by_hash(block(1).hash).await.unwrap_err();
+        assert_eq!(err, ReplayError::NotInLog(ChainRequest::HeaderByHash(block(1).hash)));
+    }
+
+    #[tokio::test]
+    async fn test_replay_response_mismatch() {
+        let request = ChainRequest::ReceiptsByHash(block(1).hash);
+        let entry = ReplayEntry::new(request, ChainResponse::BlockInfo(block(1)));
+        let mut replay = ReplayChainProvider::new([entry]);
+        let err = replay.receipts_by_hash(block(1).hash).await.unwrap_err();
+        assert_eq!(err, ReplayError::ResponseMismatch(request));
+    }
+
+    #[tokio::test]
+    async fn test_recording_skips_failed_requests() {
+        let sink = TestReplaySink::default();
+        let mut recorder = RecordingChainProvider::new(chain_provider(), Arc::new(sink.clone()));
+        assert!(recorder.block_info_by_number(10).await.is_err());
+        assert!(sink.entries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_l2_and_blobs() {
+        let info = L2BlockInfo { block_info: block(1), ..Default::default() };
+        let header = Header { number: 1, ..Default::default() };
+        let body = BlockBody { transactions: Vec::new(), ommers: Vec::new(), withdrawals: None };
+        let op_block = OpBlock { header, body };
+        let config = SystemConfig { gas_limit: 30_000_000, ..Default::default() };
+        let mut l2 = TestL2ChainProvider::default();
+        l2.blocks.push(info);
+        l2.op_blocks.push(op_block.clone());
+        l2.system_configs.insert(1, config);
+        let blob_hash = IndexedBlobHash { index: 0, hash: B256::repeat_byte(1) };
+        let mut blobs = TestBlobProvider::default();
+        blobs.insert_blob(blob_hash.hash, Blob::repeat_byte(0xAB));
+
+        // Both providers record into the same log.
+        let sink = TestReplaySink::default();
+        let cfg = Arc::new(RollupConfig::default());
+        let mut l2 = RecordingChainProvider::new(l2, Arc::new(sink.clone()));
+        l2.l2_block_info_by_number(1).await.unwrap();
+        l2.block_by_number(1).await.unwrap();
+        l2.system_config_by_number(1, cfg.clone()).await.unwrap();
+        let mut blobs = RecordingChainProvider::new(blobs, Arc::new(sink.clone()));
+        let hashes = [blob_hash];
+        let recorded = blobs.get_blobs(&block(1), &hashes).await.unwrap();
+
+        let mut replay = ReplayChainProvider::new(sink.entries());
+        assert_eq!(replay.len(), 4);
+        assert_eq!(replay.l2_block_info_by_number(1).await.unwrap(), info);
+        assert_eq!(replay.block_by_number(1).await.unwrap(), op_block);
+        assert_eq!(replay.system_config_by_number(1, cfg).await.unwrap(), config);
+        assert_eq!(replay.get_blobs(&block(1), &hashes).await.unwrap(), recorded);
+        assert_eq!(
+            replay.block_by_number(2).await.unwrap_err(),
+            ReplayError::NotInLog(ChainRequest::L2BlockByNumber(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_invalid_l2_block() {
+        let request = ChainRequest::L2BlockByNumber(1);
+        let entry = ReplayEntry::new(request, ChainResponse::L2Block(vec![0xFF].into()));
+        let mut replay = ReplayChainProvider::new([entry]);
+        let err = replay.block_by_number(1).await.unwrap_err();
+        assert_eq!(err, ReplayError::InvalidResponse(request));
+    }
+}
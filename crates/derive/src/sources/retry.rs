@@ -0,0 +1,601 @@
+//! Contains the [RetryProvider], which retries transient provider errors with exponential
+//! backoff.
+
+use crate::{
+    errors::PipelineErrorKind,
+    sources::IndexedBlobHash,
+    traits::{BlobProvider, ChainProvider, L2ChainProvider, RetrySleep, TransientError},
+    types::RetryPolicy,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::{Address, B256};
+use async_trait::async_trait;
+use core::{
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use op_alloy_consensus::OpBlock;
+use op_alloy_genesis::{RollupConfig, SystemConfig};
+use op_alloy_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo};
+use tracing::warn;
+
+/// The jitter seed of the next [RetryProvider], so that instances do not share a jitter sequence.
+static NEXT_JITTER_SEED: AtomicUsize = AtomicUsize::new(0);
+
+/// A [ChainProvider], [L2ChainProvider] and [BlobProvider] that retries requests failing with a
+/// transient error, according to a [RetryPolicy].
+///
+/// Deterministic errors, as classified by [TransientError], are returned immediately, as is the
+/// last transient error once the policy's attempts are exhausted. Range lookups are retried as a
+/// whole, so that batched overrides of the inner provider are preserved, and cache invalidations
+/// are forwarded to the inner provider.
+#[derive(Debug, Clone)]
+pub struct RetryProvider<P, S> {
+    /// The inner provider that serves the requests.
+    inner: P,
+    /// The timer used to wait between attempts.
+    sleeper: S,
+    /// The retry policy.
+    policy: RetryPolicy,
+    /// The state of the generator selecting the jitter of each delay.
+    jitter_state: u64,
+    /// The total number of attempts made against the inner provider.
+    attempts: u64,
+}
+
+impl<P, S: RetrySleep> RetryProvider<P, S> {
+    /// Creates a new [RetryProvider] wrapping `inner`. Every instance is given a distinct jitter
+    /// seed, which can be replaced with [RetryProvider::with_jitter_seed].
+    pub fn new(inner: P, sleeper: S, policy: RetryPolicy) -> Self {
+        let jitter_state = NEXT_JITTER_SEED.fetch_add(1, Ordering::Relaxed) as u64;
+        Self { inner, sleeper, policy, jitter_state, attempts: 0 }
+    }
+
+    /// Seeds the generator selecting the jitter of each delay, e.g. with entropy from the host.
+    pub const fn with_jitter_seed(mut self, seed: u64) -> Self {
+        self.jitter_state = seed;
+        self
+    }
+
+    /// Returns a reference to the inner provider.
+    pub const fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Returns a reference to the timer.
+    pub const fn sleeper(&self) -> &S {
+        &self.sleeper
+    }
+
+    /// Returns the [RetryPolicy].
+    pub const fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Returns the total number of attempts made against the inner provider.
+    pub const fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// Consumes the [RetryProvider] and returns the inner provider.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Returns the next jitter entropy, using the SplitMix64 generator.
+    const fn next_entropy(&mut self) -> u64 {
+        self.jitter_state = self.jitter_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.jitter_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the delay to wait before retrying a request that failed on the given attempt,
+    /// or [None] if the error must be surfaced.
+    fn backoff<E>(&mut self, attempt: u32, err: &E) -> Option<Duration>
+    where
+        E: TransientError + Display,
+    {
+        if !err.is_transient() || attempt >= self.policy.max_attempts {
+            return None;
+        }
+        let entropy = self.next_entropy();
+        let delay = self.policy.delay(attempt, entropy);
+        warn!(
+            target: "retry-provider",
+            "Transient provider error (attempt {}/{}): {}. Retrying in {:?}",
+            attempt, self.policy.max_attempts, err, delay
+        );
+        Some(delay)
+    }
+}
+
+/// Runs the request against the inner provider of a [RetryProvider] until it succeeds, fails
+/// with a deterministic error, or the policy's attempts are exhausted.
+macro_rules! retry {
+    ($self:ident, $request:expr) => {{
+        let mut attempt = 1;
+        loop {
+            $self.attempts += 1;
+            match $request.await {
+                Ok(value) => break Ok(value),
+                Err(e) => match $self.backoff(attempt, &e) {
+                    Some(delay) => {
+                        $self.sleeper.sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => break Err(e),
+                },
+            }
+        }
+    }};
+}
+
+#[async_trait]
+impl<P, S> ChainProvider for RetryProvider<P, S>
+where
+    P: ChainProvider + Send,
+    P::Error: TransientError + Send,
+    S: RetrySleep,
+{
+    type Error = P::Error;
+
+    async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+        retry!(self, self.inner.header_by_hash(hash))
+    }
+
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        retry!(self, self.inner.block_info_by_number(number))
+    }
+
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        retry!(self, self.inner.receipts_by_hash(hash))
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        retry!(self, self.inner.block_info_and_transactions_by_hash(hash))
+    }
+
+    async fn block_has_batcher_tx(
+        &mut self,
+        hash: B256,
+        batch_inbox: Address,
+        system_config: Address,
+    ) -> Result<bool, Self::Error>
+    where
+        Self: Send,
+    {
+        retry!(self, self.inner.block_has_batcher_tx(hash, batch_inbox, system_config))
+    }
+
+    fn invalidate_l1_above(&mut self, number: u64) {
+        self.inner.invalidate_l1_above(number);
+    }
+}
+
+#[async_trait]
+impl<P, S> BatchValidationProvider for RetryProvider<P, S>
+where
+    P: BatchValidationProvider + Send,
+    <P as BatchValidationProvider>::Error: TransientError + Send,
+    S: RetrySleep,
+{
+    type Error = <P as BatchValidationProvider>::Error;
+
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        retry!(self, self.inner.l2_block_info_by_number(number))
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        retry!(self, self.inner.block_by_number(number))
+    }
+}
+
+#[async_trait]
+impl<P, S> L2ChainProvider for RetryProvider<P, S>
+where
+    P: L2ChainProvider + Send,
+    <P as BatchValidationProvider>::Error: TransientError + Into<PipelineErrorKind> + Send,
+    <P as L2ChainProvider>::Error: TransientError + Send,
+    S: RetrySleep,
+{
+    type Error = <P as L2ChainProvider>::Error;
+
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+        retry!(self, self.inner.system_config_by_number(number, rollup_config.clone()))
+    }
+
+    async fn block_info_by_range(
+        &mut self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<L2BlockInfo>, <Self as BatchValidationProvider>::Error>
+    where
+        Self: Send,
+    {
+        retry!(self, self.inner.block_info_by_range(start, end))
+    }
+
+    async fn blocks_by_range(
+        &mut self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<OpBlock>, <Self as BatchValidationProvider>::Error>
+    where
+        Self: Send,
+    {
+        retry!(self, self.inner.blocks_by_range(start, end))
+    }
+
+    async fn system_configs_by_range(
+        &mut self,
+        start: u64,
+        end: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<Vec<SystemConfig>, <Self as L2ChainProvider>::Error>
+    where
+        Self: Send,
+    {
+        retry!(self, self.inner.system_configs_by_range(start, end, rollup_config.clone()))
+    }
+
+    fn invalidate_l2_above(&mut self, number: u64) {
+        self.inner.invalidate_l2_above(number);
+    }
+}
+
+#[async_trait]
+impl<P, S> BlobProvider for RetryProvider<P, S>
+where
+    P: BlobProvider + Send,
+    P::Error: TransientError + Send,
+    S: RetrySleep,
+{
+    type Error = P::Error;
+
+    async fn get_blobs(
+        &mut self,
+        block_ref: &BlockInfo,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        retry!(self, self.inner.get_blobs(block_ref, blob_hashes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::{BlobProviderError, PipelineError, PipelineErrorKind, ProviderErrorKind},
+        sources::CachingChainProvider,
+        test_utils::{
+            TestBlobProvider, TestChainProvider, TestChainProviderBuilder, TestL2ChainProvider,
+            TestL2ChainProviderBuilder,
+        },
+    };
+    use alloc::{string::ToString, vec};
+
+    /// A [RetrySleep] that records the requested delays instead of waiting.
+    #[derive(Debug, Default)]
+    struct RecordingSleep(Vec<Duration>);
+
+    #[async_trait]
+    impl RetrySleep for RecordingSleep {
+        async fn sleep(&mut self, duration: Duration) {
+            self.0.push(duration);
+        }
+    }
+
+    #[derive(Debug, derive_more::Display)]
+    enum FlakyError {
+        #[display("Connection reset")]
+        Transient,
+        #[display("Block not found")]
+        NotFound,
+    }
+
+    impl From<FlakyError> for PipelineErrorKind {
+        fn from(val: FlakyError) -> Self {
+            PipelineError::Provider(ProviderErrorKind::Other(val.to_string())).temp()
+        }
+    }
+
+    impl TransientError for FlakyError {
+        fn is_transient(&self) -> bool {
+            matches!(self, Self::Transient)
+        }
+    }
+
+    /// A [ChainProvider] and [L2ChainProvider] that fails with a transient error a given number
+    /// of times before serving requests from a [TestChainProvider] and [TestL2ChainProvider].
+    #[derive(Debug, Default)]
+    struct FlakyChainProvider {
+        inner: TestChainProvider,
+        l2: TestL2ChainProvider,
+        failures: u32,
+    }
+
+    impl FlakyChainProvider {
+        /// Fails with a transient error if any failures are left.
+        fn flake(&mut self) -> Result<(), FlakyError> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(FlakyError::Transient);
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ChainProvider for FlakyChainProvider {
+        type Error = FlakyError;
+
+        async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+            self.inner.header_by_hash(hash).await.map_err(|_| FlakyError::NotFound)
+        }
+
+        async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+            self.flake()?;
+            self.inner.block_info_by_number(number).await.map_err(|_| FlakyError::NotFound)
+        }
+
+        async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+            self.inner.receipts_by_hash(hash).await.map_err(|_| FlakyError::NotFound)
+        }
+
+        async fn block_info_and_transactions_by_hash(
+            &mut self,
+            hash: B256,
+        ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+            self.inner
+                .block_info_and_transactions_by_hash(hash)
+                .await
+                .map_err(|_| FlakyError::NotFound)
+        }
+
+        async fn block_has_batcher_tx(
+            &mut self,
+            _: B256,
+            _: Address,
+            _: Address,
+        ) -> Result<bool, Self::Error>
+        where
+            Self: Send,
+        {
+            self.flake()?;
+            Ok(true)
+        }
+    }
+
+    #[async_trait]
+    impl BatchValidationProvider for FlakyChainProvider {
+        type Error = FlakyError;
+
+        async fn l2_block_info_by_number(
+            &mut self,
+            number: u64,
+        ) -> Result<L2BlockInfo, Self::Error> {
+            self.flake()?;
+            self.l2.l2_block_info_by_number(number).await.map_err(|_| FlakyError::NotFound)
+        }
+
+        async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+            self.l2.block_by_number(number).await.map_err(|_| FlakyError::NotFound)
+        }
+    }
+
+    #[async_trait]
+    impl L2ChainProvider for FlakyChainProvider {
+        type Error = FlakyError;
+
+        async fn system_config_by_number(
+            &mut self,
+            number: u64,
+            rollup_config: Arc<RollupConfig>,
+        ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+            self.l2
+                .system_config_by_number(number, rollup_config)
+                .await
+                .map_err(|_| FlakyError::NotFound)
+        }
+
+        /// Serves the whole range in a single request, as a provider backed by a remote node
+        /// would.
+        async fn block_info_by_range(
+            &mut self,
+            start: u64,
+            end: u64,
+        ) -> Result<Vec<L2BlockInfo>, <Self as BatchValidationProvider>::Error>
+        where
+            Self: Send,
+        {
+            self.flake()?;
+            self.l2.block_info_by_range(start, end).await.map_err(|_| FlakyError::NotFound)
+        }
+    }
+
+    fn flaky_provider(
+        failures: u32,
+        policy: RetryPolicy,
+    ) -> RetryProvider<FlakyChainProvider, RecordingSleep> {
+        let mut inner = TestChainProvider::default();
+        inner.insert_block(1, BlockInfo { number: 1, ..Default::default() });
+        let flaky = FlakyChainProvider { inner, failures };
+        RetryProvider::new(flaky, RecordingSleep::default(), policy)
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_recovers_from_transient_errors() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100));
+        let mut provider = flaky_provider(3, policy);
+
+        let block = provider.block_info_by_number(1).await.unwrap();
+        assert_eq!(block.number, 1);
+        assert_eq!(provider.attempts(), 4);
+        assert_eq!(
+            provider.sleeper().0,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_exhausts_attempts() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+        let mut provider = flaky_provider(5, policy);
+
+        let err = provider.block_info_by_number(1).await.unwrap_err();
+        assert!(matches!(err, FlakyError::Transient));
+        assert_eq!(provider.attempts(), 3);
+        assert_eq!(provider.sleeper().0.len(), 2);
+
+        // The next request starts with a fresh attempt budget.
+        assert_eq!(provider.block_info_by_number(1).await.unwrap().number, 1);
+        assert_eq!(provider.attempts(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_deterministic_error_not_retried() {
+        let mut provider = flaky_provider(0, RetryPolicy::default());
+
+        let err = provider.block_info_by_number(2).await.unwrap_err();
+        assert!(matches!(err, FlakyError::NotFound));
+        assert_eq!(provider.attempts(), 1);
+        assert!(provider.sleeper().0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_jitter_bounded() {
+        let policy =
+            RetryPolicy::new(4, Duration::from_millis(100)).with_jitter(Duration::from_millis(50));
+        let mut provider = flaky_provider(3, policy);
+
+        provider.block_info_by_number(1).await.unwrap();
+        for (retry, delay) in provider.sleeper().0.iter().enumerate() {
+            let backoff = Duration::from_millis(100 << retry);
+            assert!(*delay >= backoff && *delay <= backoff + Duration::from_millis(50));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_jitter_seeded_per_instance() {
+        let policy =
+            RetryPolicy::new(4, Duration::from_millis(100)).with_jitter(Duration::from_millis(50));
+        let mut first = flaky_provider(3, policy);
+        let mut second = flaky_provider(3, policy);
+        first.block_info_by_number(1).await.unwrap();
+        second.block_info_by_number(1).await.unwrap();
+        assert_ne!(first.sleeper().0, second.sleeper().0);
+
+        // Instances with the same seed share their jitter.
+        let mut first = flaky_provider(3, policy).with_jitter_seed(7);
+        let mut second = flaky_provider(3, policy).with_jitter_seed(7);
+        first.block_info_by_number(1).await.unwrap();
+        second.block_info_by_number(1).await.unwrap();
+        assert_eq!(first.sleeper().0, second.sleeper().0);
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_forwards_block_has_batcher_tx() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100));
+        let mut provider = flaky_provider(2, policy);
+
+        // The inner provider's override is retried, rather than the default implementation
+        // querying headers and transactions that the inner provider does not have.
+        let has_batcher_tx =
+            provider.block_has_batcher_tx(B256::ZERO, Address::ZERO, Address::ZERO).await;
+        assert!(has_batcher_tx.unwrap());
+        assert_eq!(provider.attempts(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_forwards_invalidate_l1_above() {
+        let mut inner = TestChainProvider::default();
+        (1..=2).for_each(|n| inner.insert_block(n, BlockInfo { number: n, ..Default::default() }));
+        let caching = CachingChainProvider::new(inner, 4);
+        let mut provider =
+            RetryProvider::new(caching, RecordingSleep::default(), Default::default());
+        provider.block_info_by_number(1).await.unwrap();
+        provider.block_info_by_number(2).await.unwrap();
+
+        // Block #2 is evicted from the cache, so it is fetched from the inner provider again.
+        provider.invalidate_l1_above(1);
+        provider.block_info_by_number(1).await.unwrap();
+        provider.block_info_by_number(2).await.unwrap();
+        assert_eq!((provider.inner().hits(), provider.inner().misses()), (1, 3));
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_forwards_l2_range_lookups() {
+        let l1_chain = TestChainProviderBuilder::new(1).blocks();
+        let builder = TestL2ChainProviderBuilder::new(&l1_chain, 3);
+        let flaky = FlakyChainProvider { l2: builder.build(), failures: 1, ..Default::default() };
+        let policy = RetryPolicy::new(5, Duration::from_millis(100));
+        let mut provider = RetryProvider::new(flaky, RecordingSleep::default(), policy);
+
+        // The range is retried as a single request, rather than block by block through the
+        // default implementation.
+        let blocks = provider.block_info_by_range(1, 2).await.unwrap();
+        assert_eq!(blocks, builder.blocks()[1..=2]);
+        assert_eq!(provider.attempts(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_retries_l2_lookups() {
+        let l1_chain = TestChainProviderBuilder::new(1).blocks();
+        let builder = TestL2ChainProviderBuilder::new(&l1_chain, 2);
+        let flaky = FlakyChainProvider { l2: builder.build(), failures: 2, ..Default::default() };
+        let policy = RetryPolicy::new(5, Duration::from_millis(100));
+        let mut provider = RetryProvider::new(flaky, RecordingSleep::default(), policy);
+
+        assert_eq!(provider.l2_block_info_by_number(1).await.unwrap(), builder.blocks()[1]);
+        assert_eq!(provider.attempts(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_forwards_invalidate_l2_above() {
+        let l1_chain = TestChainProviderBuilder::new(1).blocks();
+        let builder = TestL2ChainProviderBuilder::new(&l1_chain, 3);
+        let caching = CachingChainProvider::new(builder.build(), 4);
+        let mut provider =
+            RetryProvider::new(caching, RecordingSleep::default(), Default::default());
+        provider.l2_block_info_by_number(1).await.unwrap();
+        provider.l2_block_info_by_number(2).await.unwrap();
+
+        // Block #2 is evicted from the cache, so it is fetched from the inner provider again.
+        provider.invalidate_l2_above(1);
+        provider.l2_block_info_by_number(1).await.unwrap();
+        provider.l2_block_info_by_number(2).await.unwrap();
+        assert_eq!((provider.inner().hits(), provider.inner().misses()), (1, 3));
+    }
+
+    #[tokio::test]
+    async fn test_retry_provider_blobs_deterministic_error() {
+        let blobs = TestBlobProvider { should_error: true, ..Default::default() };
+        let mut provider = RetryProvider::new(blobs, RecordingSleep::default(), Default::default());
+
+        let err = provider.get_blobs(&BlockInfo::default(), &[]).await.unwrap_err();
+        assert_eq!(err, BlobProviderError::SlotDerivation);
+        assert_eq!(provider.attempts(), 1);
+    }
+
+    #[test]
+    fn test_blob_provider_error_transient() {
+        assert!(BlobProviderError::Backend("timeout".to_string()).is_transient());
+        assert!(!BlobProviderError::SlotDerivation.is_transient());
+    }
+}
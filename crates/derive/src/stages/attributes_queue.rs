@@ -6,9 +6,9 @@ use crate::{
         AttributesBuilder, AttributesProvider, NextAttributes, OriginAdvancer, OriginProvider,
         SignalReceiver,
     },
-    types::{PipelineResult, Signal},
+    types::{AttributesStats, PipelineResult, PipelineStatus, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
@@ -43,6 +43,12 @@ where
     is_last_in_span: bool,
     /// The current batch being processed.
     batch: Option<SingleBatch>,
+    /// The L1 origin at the time the current batch was loaded.
+    batch_origin: Option<BlockInfo>,
+    /// The L1 origin that the last emitted attributes were derived from.
+    derived_from: Option<BlockInfo>,
+    /// The transaction summary of the last emitted attributes.
+    stats: Option<AttributesStats>,
     /// The attributes builder.
     builder: AB,
 }
@@ -54,7 +60,21 @@ where
 {
     /// Create a new [AttributesQueue] stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P, builder: AB) -> Self {
-        Self { cfg, prev, is_last_in_span: false, batch: None, builder }
+        Self {
+            cfg,
+            prev,
+            is_last_in_span: false,
+            batch: None,
+            batch_origin: None,
+            derived_from: None,
+            stats: None,
+            builder,
+        }
+    }
+
+    /// Returns the [AttributesStats] of the last emitted attributes, if any.
+    pub const fn peek_stats(&self) -> Option<&AttributesStats> {
+        self.stats.as_ref()
     }
 
     /// Loads a [SingleBatch] from the [AttributesProvider] if needed.
@@ -62,12 +82,22 @@ where
         if self.batch.is_none() {
             let batch = self.prev.next_batch(parent).await?;
             self.batch = Some(batch);
+            self.batch_origin = self.prev.origin();
             self.is_last_in_span = self.prev.is_last_in_span();
         }
         self.batch.as_ref().cloned().ok_or(PipelineError::Eof.temp())
     }
 
     /// Returns the next [OpAttributesWithParent] from the current batch.
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(
+            name = "attributes_queue",
+            level = "debug",
+            skip_all,
+            fields(parent = parent.block_info.number)
+        )
+    )]
     pub async fn next_attributes(
         &mut self,
         parent: L2BlockInfo,
@@ -92,6 +122,8 @@ where
         // Clear out the local state once payload attributes are prepared.
         self.batch = None;
         self.is_last_in_span = false;
+        self.derived_from = self.batch_origin.take();
+        self.stats = Some(AttributesStats::from_attributes(&populated_attributes.attributes));
         Ok(populated_attributes)
     }
 
@@ -145,6 +177,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 #[async_trait]
@@ -159,6 +195,10 @@ where
     ) -> PipelineResult<OpAttributesWithParent> {
         self.next_attributes(parent).await
     }
+
+    fn derived_from(&self) -> Option<BlockInfo> {
+        self.derived_from
+    }
 }
 
 impl<P, AB> OriginProvider for AttributesQueue<P, AB>
@@ -169,6 +209,15 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("attributes-queue", self.origin()));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+    }
 }
 
 #[async_trait]
@@ -177,19 +226,25 @@ where
     P: AttributesProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
     AB: AttributesBuilder + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        match signal {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let receipt = match signal {
             s @ Signal::Reset(_) | s @ Signal::Activation(_) => {
-                self.prev.signal(s).await?;
+                let receipt = self.prev.signal(s).await?;
                 self.batch = None;
+                self.batch_origin = None;
+                self.derived_from = None;
+                self.stats = None;
                 self.is_last_in_span = false;
+                receipt
             }
             s @ Signal::FlushChannel => {
                 self.batch = None;
-                self.prev.signal(s).await?;
+                self.batch_origin = None;
+                self.prev.signal(s).await?
             }
-        }
-        Ok(())
+            s @ Signal::AdvanceOrigin => self.prev.signal(s).await?,
+        };
+        Ok(receipt)
     }
 }
 
@@ -388,4 +443,55 @@ mod tests {
         assert!(!aq.is_last_in_span);
         assert!(aq.batch.is_none());
     }
+
+    #[tokio::test]
+    async fn test_next_attributes_derived_from() {
+        let cfg = RollupConfig { block_time: 2, ..Default::default() };
+        let first = BlockInfo { number: 10, ..Default::default() };
+        let second = BlockInfo { number: 11, ..Default::default() };
+        let batches = vec![
+            Ok(SingleBatch { timestamp: 4, ..Default::default() }),
+            Ok(SingleBatch { timestamp: 2, ..Default::default() }),
+        ];
+        let mock = new_test_attributes_provider(Some(first), batches);
+        let pa = default_optimism_payload_attributes();
+        let mock_builder = TestAttributesBuilder { attributes: vec![Ok(pa.clone()), Ok(pa)] };
+        let mut aq = AttributesQueue::new(Arc::new(cfg), mock, mock_builder);
+        assert_eq!(NextAttributes::derived_from(&aq), None);
+
+        // The first batch is included at the first L1 block of the epoch.
+        aq.next_attributes(L2BlockInfo::default()).await.unwrap();
+        assert_eq!(NextAttributes::derived_from(&aq), Some(first));
+
+        // The second batch of the same epoch is included one L1 block later.
+        aq.prev.origin = Some(second);
+        let parent = L2BlockInfo {
+            block_info: BlockInfo { timestamp: 2, ..Default::default() },
+            ..Default::default()
+        };
+        aq.next_attributes(parent).await.unwrap();
+        assert_eq!(NextAttributes::derived_from(&aq), Some(second));
+    }
+
+    #[tokio::test]
+    async fn test_next_attributes_stats() {
+        let deposit = Bytes::from(vec![0x7E, 0x01]);
+        let batch = SingleBatch {
+            transactions: vec![Bytes::from(vec![0x02, 0x01, 0x02]), Bytes::from(vec![0xf8])],
+            ..Default::default()
+        };
+        let mock = new_test_attributes_provider(None, vec![Ok(batch)]);
+        let mut pa = default_optimism_payload_attributes();
+        pa.transactions = Some(vec![deposit]);
+        let mock_builder = TestAttributesBuilder { attributes: vec![Ok(pa)] };
+        let mut aq = AttributesQueue::new(Arc::new(RollupConfig::default()), mock, mock_builder);
+        assert_eq!(aq.peek_stats(), None);
+
+        aq.next_attributes(L2BlockInfo::default()).await.unwrap();
+        let expected = AttributesStats { deposits: 1, user_transactions: 2, total_bytes: 6 };
+        assert_eq!(aq.peek_stats(), Some(&expected));
+
+        aq.signal(ResetSignal::default().signal()).await.unwrap();
+        assert_eq!(aq.peek_stats(), None);
+    }
 }
@@ -4,10 +4,13 @@ use super::NextBatchProvider;
 use crate::{
     errors::PipelineError,
     stages::{BatchQueue, BatchValidator},
-    traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{
+        AttributesProvider, DerivationMetrics, L2ChainProvider, OriginAdvancer, OriginProvider,
+        SignalReceiver,
+    },
+    types::{DerivationLimits, EpochCache, PipelineResult, PipelineStatus, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
@@ -46,6 +49,16 @@ where
     ///
     /// Must be [None] if `prev` or `batch_queue` is [Some].
     batch_validator: Option<BatchValidator<P>>,
+    /// The epoch cache shared with the attributes builder, invalidated on reset.
+    epoch_cache: Option<EpochCache>,
+    /// Whether the [BatchQueue] fast-forwards already-applied blocks after a reset.
+    warm_start: bool,
+    /// The derivation resource limits enforced by the [BatchQueue].
+    limits: DerivationLimits,
+    /// Whether the active stage runs in strict mode.
+    strict: bool,
+    /// The metrics recorder passed on to the active stage, if any.
+    metrics: Option<Arc<dyn DerivationMetrics>>,
 }
 
 impl<P, F> BatchProvider<P, F>
@@ -55,7 +68,74 @@ where
 {
     /// Creates a new [BatchProvider] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P, provider: F) -> Self {
-        Self { cfg, provider, prev: Some(prev), batch_queue: None, batch_validator: None }
+        Self {
+            cfg,
+            provider,
+            prev: Some(prev),
+            batch_queue: None,
+            batch_validator: None,
+            epoch_cache: None,
+            warm_start: false,
+            limits: DerivationLimits::SPEC,
+            strict: false,
+            metrics: None,
+        }
+    }
+
+    /// Sets the [DerivationLimits] enforced by the [BatchQueue].
+    pub const fn with_limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether the [BatchQueue] fast-forwards batches for already-applied blocks after a
+    /// reset. The [BatchValidator] discards such batches as past batches without validation.
+    pub const fn with_warm_start(mut self, warm_start: bool) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
+    /// Sets whether the [BatchQueue] and [BatchValidator] fail with a critical error on invalid
+    /// batches instead of dropping them.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns the number of already-applied blocks skipped by the active [BatchQueue] during
+    /// warm starts.
+    pub fn warm_start_skipped(&self) -> u64 {
+        self.batch_queue.as_ref().map_or(0, |batch_queue| batch_queue.warm_start_skipped())
+    }
+
+    /// Sets the [EpochCache] that is invalidated when the pipeline is reset.
+    pub fn with_epoch_cache(mut self, epoch_cache: EpochCache) -> Self {
+        self.epoch_cache = Some(epoch_cache);
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording the batches of the [BatchQueue] and
+    /// [BatchValidator].
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Creates a [BatchQueue] on top of `prev`, configured like the provider.
+    fn new_batch_queue(&self, prev: P) -> BatchQueue<P, F> {
+        let mut bq = BatchQueue::new(self.cfg.clone(), prev, self.provider.clone())
+            .with_warm_start(self.warm_start)
+            .with_limits(self.limits)
+            .with_strict(self.strict);
+        bq.metrics = self.metrics.clone();
+        bq
+    }
+
+    /// Creates a [BatchValidator] on top of `prev`, configured like the provider.
+    fn new_batch_validator(&self, prev: P) -> BatchValidator<P> {
+        let mut bv = BatchValidator::new(self.cfg.clone(), prev).with_strict(self.strict);
+        bv.metrics = self.metrics.clone();
+        bv
     }
 
     /// Attempts to update the active stage of the mux.
@@ -65,16 +145,15 @@ where
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
             if self.cfg.is_holocene_active(origin.timestamp) {
-                self.batch_validator = Some(BatchValidator::new(self.cfg.clone(), prev));
+                self.batch_validator = Some(self.new_batch_validator(prev));
             } else {
-                self.batch_queue =
-                    Some(BatchQueue::new(self.cfg.clone(), prev, self.provider.clone()));
+                self.batch_queue = Some(self.new_batch_queue(prev));
             }
         } else if self.batch_queue.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
             // If the batch queue is active and Holocene is also active, transition to the batch
             // validator.
             let batch_queue = self.batch_queue.take().expect("Must have batch queue");
-            let mut bv = BatchValidator::new(self.cfg.clone(), batch_queue.prev);
+            let mut bv = self.new_batch_validator(batch_queue.prev);
             bv.l1_blocks = batch_queue.l1_blocks;
             self.batch_validator = Some(bv);
         } else if self.batch_validator.is_some() && !self.cfg.is_holocene_active(origin.timestamp) {
@@ -82,8 +161,7 @@ where
             // reorg around Holocene activation. Transition back to the batch queue
             // until Holocene re-activates.
             let batch_validator = self.batch_validator.take().expect("Must have batch validator");
-            let mut bq =
-                BatchQueue::new(self.cfg.clone(), batch_validator.prev, self.provider.clone());
+            let mut bq = self.new_batch_queue(batch_validator.prev);
             bq.l1_blocks = batch_validator.l1_blocks;
             self.batch_queue = Some(bq);
         }
@@ -108,6 +186,18 @@ where
             Err(PipelineError::NotEnoughData.temp())
         }
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.attempt_update()?;
+
+        if let Some(batch_validator) = self.batch_validator.as_mut() {
+            batch_validator.advance_origin_to(target).await
+        } else if let Some(batch_queue) = self.batch_queue.as_mut() {
+            batch_queue.advance_origin_to(target).await
+        } else {
+            Err(PipelineError::NotEnoughData.temp())
+        }
+    }
 }
 
 impl<P, F> OriginProvider for BatchProvider<P, F>
@@ -126,6 +216,26 @@ where
             |batch_validator| batch_validator.origin(),
         )
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        if let Some(batch_validator) = self.batch_validator.as_ref() {
+            batch_validator.collect_stage_origins(origins);
+        } else if let Some(batch_queue) = self.batch_queue.as_ref() {
+            batch_queue.collect_stage_origins(origins);
+        } else if let Some(prev) = self.prev.as_ref() {
+            prev.collect_stage_origins(origins);
+        }
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        if let Some(batch_validator) = self.batch_validator.as_ref() {
+            batch_validator.collect_status(status);
+        } else if let Some(batch_queue) = self.batch_queue.as_ref() {
+            batch_queue.collect_status(status);
+        } else if let Some(prev) = self.prev.as_ref() {
+            prev.collect_status(status);
+        }
+    }
 }
 
 #[async_trait]
@@ -134,9 +244,15 @@ where
     P: NextBatchProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
     F: L2ChainProvider + Clone + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         self.attempt_update()?;
 
+        if matches!(signal, Signal::Reset(_) | Signal::Activation(_)) {
+            if let Some(epoch_cache) = self.epoch_cache.as_ref() {
+                epoch_cache.clear();
+            }
+        }
+
         if let Some(batch_validator) = self.batch_validator.as_mut() {
             batch_validator.signal(signal).await
         } else if let Some(batch_queue) = self.batch_queue.as_mut() {
@@ -179,9 +295,11 @@ mod test {
     use crate::{
         test_utils::{TestL2ChainProvider, TestNextBatchProvider},
         traits::{OriginProvider, SignalReceiver},
-        types::ResetSignal,
+        types::{EpochCache, ResetSignal, Signal},
     };
     use alloc::{sync::Arc, vec};
+    use alloy_consensus::Header;
+    use alloy_eips::BlockNumHash;
     use op_alloy_genesis::RollupConfig;
     use op_alloy_protocol::BlockInfo;
 
@@ -296,4 +414,21 @@ mod test {
         };
         assert!(bv.l1_blocks.len() == 1);
     }
+
+    #[tokio::test]
+    async fn test_batch_provider_reset_clears_epoch_cache() {
+        let provider = TestNextBatchProvider::new(vec![]);
+        let l2_provider = TestL2ChainProvider::default();
+        let cfg = Arc::new(RollupConfig::default());
+        let cache = EpochCache::default();
+        let mut batch_provider =
+            BatchProvider::new(cfg, provider, l2_provider).with_epoch_cache(cache.clone());
+
+        cache.insert(BlockNumHash::default(), Header::default());
+        batch_provider.signal(Signal::FlushChannel).await.unwrap();
+        assert_eq!(cache.len(), 1);
+
+        batch_provider.signal(ResetSignal::default().signal()).await.unwrap();
+        assert!(cache.is_empty());
+    }
 }
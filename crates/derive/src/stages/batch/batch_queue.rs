@@ -3,17 +3,35 @@
 use super::NextBatchProvider;
 use crate::{
     errors::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError},
-    traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    traits::{
+        AttributesProvider, DerivationMetrics, L2ChainProvider, OriginAdvancer, OriginProvider,
+        SignalReceiver,
+    },
+    types::{DerivationLimits, PipelineResult, PipelineStatus, ResetSignal, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::{
     Batch, BatchValidity, BatchWithInclusionBlock, BlockInfo, L2BlockInfo, SingleBatch,
 };
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
+
+/// A batch buffered by the [BatchQueue].
+#[derive(Debug, Clone)]
+pub(crate) struct BufferedBatch {
+    /// The batch, along with the L1 block it was included in.
+    pub(crate) inner: BatchWithInclusionBlock,
+    /// Whether the batch was ahead of the next expected timestamp when it was buffered.
+    pub(crate) future: bool,
+}
+
+impl From<BatchWithInclusionBlock> for BufferedBatch {
+    fn from(inner: BatchWithInclusionBlock) -> Self {
+        Self { inner, future: false }
+    }
+}
 
 /// [BatchQueue] is responsible for o rdering unordered batches
 /// and gnerating empty batches when the sequence window has passed.
@@ -49,13 +67,25 @@ where
     /// push it to the list.
     pub(crate) l1_blocks: Vec<BlockInfo>,
     /// A set of batches in order from when we've seen them.
-    pub(crate) batches: Vec<BatchWithInclusionBlock>,
+    pub(crate) batches: Vec<BufferedBatch>,
     /// A set of cached [SingleBatch]es derived from [SpanBatch]es.
     ///
     /// [SpanBatch]: op_alloy_protocol::SpanBatch
     pub(crate) next_spans: Vec<SingleBatch>,
     /// Used to validate the batches.
     pub(crate) fetcher: BF,
+    /// Whether batches for already-applied blocks are fast-forwarded after a reset.
+    pub(crate) warm_start: bool,
+    /// The L2 safe head of the last reset, below which blocks were already applied.
+    pub(crate) warm_start_head: Option<L2BlockInfo>,
+    /// The number of already-applied blocks skipped during warm starts.
+    pub(crate) warm_start_skipped: u64,
+    /// The derivation resource limits.
+    pub(crate) limits: DerivationLimits,
+    /// Whether spec-violation drops are surfaced as critical errors.
+    pub(crate) strict: bool,
+    /// The metrics recorder, if any.
+    pub(crate) metrics: Option<Arc<dyn DerivationMetrics>>,
 }
 
 impl<P, BF> BatchQueue<P, BF>
@@ -74,9 +104,99 @@ where
             batches: Default::default(),
             next_spans: Default::default(),
             fetcher,
+            warm_start: false,
+            warm_start_head: None,
+            warm_start_skipped: 0,
+            limits: DerivationLimits::SPEC,
+            strict: false,
+            metrics: None,
         }
     }
 
+    /// Sets the [DerivationLimits] enforced by the [BatchQueue].
+    pub const fn with_limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether batches for blocks at or below the reset safe head are fast-forwarded by only
+    /// comparing their timestamps to the safe head.
+    pub const fn with_warm_start(mut self, warm_start: bool) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
+    /// Sets whether invalid batches, and future batches once Holocene is active, fail with a
+    /// critical error instead of being dropped.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording the accepted and discarded batches.
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Records the final validity of a batch with the [DerivationMetrics], if any.
+    fn record_batch(&self, validity: BatchValidity) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_batch(validity);
+        }
+    }
+
+    /// Returns a critical [PipelineError::StrictViolation] for a dropped batch, if the
+    /// [BatchQueue] runs in strict mode.
+    fn check_strict(&self, batch: &Batch, validity: BatchValidity) -> PipelineResult<()> {
+        if self.strict {
+            return Err(PipelineError::StrictViolation(format!(
+                "{:?} batch with timestamp {}",
+                validity,
+                batch.timestamp()
+            ))
+            .crit());
+        }
+        Ok(())
+    }
+
+    /// Returns the number of already-applied blocks skipped during warm starts.
+    pub const fn warm_start_skipped(&self) -> u64 {
+        self.warm_start_skipped
+    }
+
+    /// Returns whether the batch is a [SingleBatch] for a block at or below the warm start safe
+    /// head. Batches for blocks below the safe head were already applied, and are skipped by
+    /// their timestamp alone. The batch for the safe head itself is only skipped if it links to
+    /// the safe head's parent; a conflicting batch is left to the full batch checks, which drop
+    /// it.
+    fn skip_applied_batch(&mut self, batch: &Batch) -> bool {
+        let (Some(head), Batch::Single(single)) = (self.warm_start_head, batch) else {
+            return false;
+        };
+        if !self.warm_start || single.timestamp > head.block_info.timestamp {
+            return false;
+        }
+        if single.timestamp == head.block_info.timestamp &&
+            single.parent_hash != head.block_info.parent_hash
+        {
+            warn!(
+                target: "batch-queue",
+                "Batch for block #{} conflicts with the local chain",
+                head.block_info.number
+            );
+            return false;
+        }
+
+        debug!(
+            target: "batch-queue",
+            "Skipping already-applied batch with timestamp {}",
+            single.timestamp
+        );
+        self.warm_start_skipped += 1;
+        true
+    }
+
     /// Pops the next batch from the current queued up span-batch cache.
     /// The parent is used to set the parent hash of the batch.
     /// The parent is verified when the batch is later validated.
@@ -131,28 +251,42 @@ where
         let mut remaining = Vec::new();
         for i in 0..self.batches.len() {
             let batch = &self.batches[i];
-            let validity =
-                batch.check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+            let validity = batch
+                .inner
+                .check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher)
+                .await;
             match validity {
                 BatchValidity::Future => {
                     // Drop Future batches post-holocene.
                     //
                     // See: <https://specs.optimism.io/protocol/holocene/derivation.html#batch-queue>
                     if !self.cfg.is_holocene_active(origin.timestamp) {
-                        remaining.push(batch.clone());
+                        remaining.push(BufferedBatch { future: true, ..batch.clone() });
                     } else {
+                        self.check_strict(&batch.inner.batch, validity)?;
                         self.prev.flush();
+                        self.record_batch(validity);
                         warn!(target: "batch-queue", "[HOLOCENE] Dropping future batch with parent: {}", parent.block_info.number);
                     }
                 }
                 BatchValidity::Drop => {
                     // If we drop a batch, flush previous batches buffered in the BatchStream
                     // stage.
+                    self.check_strict(&batch.inner.batch, validity)?;
                     self.prev.flush();
+                    self.record_batch(validity);
                     warn!(target: "batch-queue", "Dropping batch with parent: {}", parent.block_info);
                     continue;
                 }
                 BatchValidity::Accept => {
+                    if batch.future {
+                        debug!(
+                            target: "batch-queue",
+                            "Consuming buffered future batch for timestamp {}",
+                            batch.inner.batch.timestamp()
+                        );
+                    }
+                    self.record_batch(validity);
                     next_batch = Some(batch.clone());
                     // Don't keep the current batch in the remaining items since we are processing
                     // it now, but retain every batch we didn't get to yet.
@@ -170,6 +304,7 @@ where
                         return Err(PipelineError::InvalidBatchValidity.crit());
                     }
 
+                    self.record_batch(validity);
                     warn!(target: "batch-queue", "[HOLOCENE] Dropping outdated batch with parent: {}", parent.block_info.number);
                     continue;
                 }
@@ -178,8 +313,8 @@ where
         self.batches = remaining;
 
         if let Some(nb) = next_batch {
-            info!(target: "batch-queue", "Next batch found for timestamp {}", nb.batch.timestamp());
-            return Ok(nb.batch);
+            info!(target: "batch-queue", "Next batch found for timestamp {}", nb.inner.batch.timestamp());
+            return Ok(nb.inner.batch);
         }
 
         // If the current epoch is too old compared to the L1 block we are at,
@@ -241,6 +376,9 @@ where
             panic!("Cannot add batch without an origin");
         }
         let origin = self.origin.ok_or(PipelineError::MissingOrigin.crit())?;
+        if self.skip_applied_batch(&batch) {
+            return Ok(());
+        }
         let data = BatchWithInclusionBlock { inclusion_block: origin, batch };
         // If we drop the batch, validation logs the drop reason with WARN level.
         let validity =
@@ -249,13 +387,39 @@ where
         let drop = validity.is_drop() ||
             (self.cfg.is_holocene_active(origin.timestamp) && validity.is_future());
         if drop {
+            self.check_strict(&data.batch, validity)?;
             self.prev.flush();
+            self.record_batch(validity);
             return Ok(());
         } else if validity.is_outdated() {
             // If the batch is outdated, we drop it without flushing the previous stage.
+            self.record_batch(validity);
             return Ok(());
         }
-        self.batches.push(data);
+        if validity.is_future() {
+            debug!(
+                target: "batch-queue",
+                "Buffering future batch for timestamp {}",
+                data.batch.timestamp()
+            );
+        }
+        self.batches.push(BufferedBatch { inner: data, future: validity.is_future() });
+
+        // If bounded, evict the batch furthest in the future, which is needed last.
+        if self.limits.buffered_batches.is_some_and(|max| self.batches.len() > max) {
+            let (index, _) = self
+                .batches
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, b)| b.inner.batch.timestamp())
+                .expect("batches cannot be empty");
+            let evicted = self.batches.remove(index);
+            warn!(
+                target: "batch-queue",
+                "Batch queue is full, evicting batch for timestamp {}",
+                evicted.inner.batch.timestamp()
+            );
+        }
         Ok(())
     }
 }
@@ -269,6 +433,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 #[async_trait]
@@ -279,6 +447,15 @@ where
 {
     /// Returns the next valid batch upon the given safe head.
     /// Also returns the boolean that indicates if the batch is the last block in the batch.
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(
+            name = "batch_queue",
+            level = "debug",
+            skip_all,
+            fields(parent = parent.block_info.number)
+        )
+    )]
     async fn next_batch(&mut self, parent: L2BlockInfo) -> PipelineResult<SingleBatch> {
         if !self.next_spans.is_empty() {
             // There are cached singular batches derived from the span batch.
@@ -427,6 +604,16 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("batch-queue", self.origin));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+        status.buffered_batches += self.batches.len();
+    }
 }
 
 #[async_trait]
@@ -435,11 +622,12 @@ where
     P: NextBatchProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
     BF: L2ChainProvider + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        match signal {
-            s @ Signal::Reset(ResetSignal { l1_origin, .. }) => {
-                self.prev.signal(s).await?;
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let receipt = match signal {
+            s @ Signal::Reset(ResetSignal { l1_origin, l2_safe_head, .. }) => {
+                let receipt = self.prev.signal(s).await?;
                 self.origin = Some(l1_origin);
+                self.warm_start_head = self.warm_start.then_some(l2_safe_head);
                 self.batches.clear();
                 // Include the new origin as an origin to build on.
                 // This is only for the initialization case.
@@ -447,14 +635,17 @@ where
                 self.l1_blocks.clear();
                 self.l1_blocks.push(l1_origin);
                 self.next_spans.clear();
+                receipt
             }
             s @ Signal::Activation(_) | s @ Signal::FlushChannel => {
-                self.prev.signal(s).await?;
+                let receipt = self.prev.signal(s).await?;
                 self.batches.clear();
                 self.next_spans.clear();
+                receipt
             }
-        }
-        Ok(())
+            s @ Signal::AdvanceOrigin => self.prev.signal(s).await?,
+        };
+        Ok(receipt)
     }
 }
 
@@ -482,7 +673,7 @@ mod tests {
         let file_contents = &(&*file_contents)[..file_contents.len() - 1];
         let data = alloy_primitives::hex::decode(file_contents).unwrap();
         let bytes: alloy_primitives::Bytes = data.into();
-        BatchReader::new(bytes, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize)
+        BatchReader::new(bytes, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize, 0)
     }
 
     #[test]
@@ -507,10 +698,13 @@ mod tests {
         let mut bq = BatchQueue::new(cfg.clone(), mock, fetcher);
         bq.l1_blocks.push(BlockInfo::default());
         bq.next_spans.push(SingleBatch::default());
-        bq.batches.push(BatchWithInclusionBlock {
-            inclusion_block: BlockInfo::default(),
-            batch: Batch::Single(SingleBatch::default()),
-        });
+        bq.batches.push(
+            BatchWithInclusionBlock {
+                inclusion_block: BlockInfo::default(),
+                batch: Batch::Single(SingleBatch::default()),
+            }
+            .into(),
+        );
         assert!(!bq.prev.reset);
         bq.signal(ResetSignal::default().signal()).await.unwrap();
         assert!(bq.prev.reset);
@@ -528,10 +722,13 @@ mod tests {
         let mut bq = BatchQueue::new(cfg.clone(), mock, fetcher);
         bq.l1_blocks.push(BlockInfo::default());
         bq.next_spans.push(SingleBatch::default());
-        bq.batches.push(BatchWithInclusionBlock {
-            inclusion_block: BlockInfo::default(),
-            batch: Batch::Single(SingleBatch::default()),
-        });
+        bq.batches.push(
+            BatchWithInclusionBlock {
+                inclusion_block: BlockInfo::default(),
+                batch: Batch::Single(SingleBatch::default()),
+            }
+            .into(),
+        );
         bq.signal(Signal::FlushChannel).await.unwrap();
         assert!(bq.prev.flushed);
         assert!(bq.batches.is_empty());
@@ -677,6 +874,85 @@ mod tests {
         assert!(bq.batches.is_empty());
     }
 
+    /// Adds a single batch with the given timestamp and transactions to a fresh batch queue, on
+    /// top of a parent in epoch #0 at L1 time 0, and returns whether the batch was kept. Epoch
+    /// #1 is known, at the given timestamp.
+    async fn add_drift_batch(
+        cfg: RollupConfig,
+        timestamp: u64,
+        transactions: Vec<Bytes>,
+        next_epoch_timestamp: u64,
+    ) -> bool {
+        let epoch = BlockInfo { hash: B256::repeat_byte(0x01), ..Default::default() };
+        let next_epoch = BlockInfo {
+            hash: B256::repeat_byte(0x02),
+            number: 1,
+            parent_hash: epoch.hash,
+            timestamp: next_epoch_timestamp,
+        };
+        let parent = L2BlockInfo {
+            block_info: BlockInfo {
+                hash: B256::repeat_byte(0x03),
+                timestamp: timestamp - cfg.block_time,
+                ..Default::default()
+            },
+            l1_origin: epoch.id(),
+            seq_num: 0,
+        };
+        let batch = SingleBatch {
+            parent_hash: parent.block_info.hash,
+            epoch_num: 0,
+            epoch_hash: epoch.hash,
+            timestamp,
+            transactions,
+        };
+        let mock = TestNextBatchProvider::new(Vec::new());
+        let mut bq = BatchQueue::new(Arc::new(cfg), mock, TestL2ChainProvider::default());
+        bq.origin = Some(epoch);
+        bq.l1_blocks = vec![epoch, next_epoch];
+        bq.add_batch(Batch::Single(batch), parent).await.unwrap();
+        !bq.batches.is_empty()
+    }
+
+    fn drift_cfg(fjord_time: Option<u64>) -> RollupConfig {
+        RollupConfig {
+            block_time: 2,
+            seq_window_size: 100,
+            max_sequencer_drift: 600,
+            fjord_time,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_batch_max_sequencer_drift_pre_fjord() {
+        let txs = || vec![Bytes::from_static(&[0x02, 0xAA])];
+        // The configured drift applies, inclusive of the limit.
+        assert!(add_drift_batch(drift_cfg(None), 600, txs(), 4_000).await);
+        assert!(!add_drift_batch(drift_cfg(None), 602, txs(), 4_000).await);
+    }
+
+    #[tokio::test]
+    async fn test_add_batch_max_sequencer_drift_fjord() {
+        let txs = || vec![Bytes::from_static(&[0x02, 0xAA])];
+        // Fjord replaces the configured drift with a constant 1800 seconds.
+        assert!(add_drift_batch(drift_cfg(Some(0)), 602, txs(), 4_000).await);
+        assert!(add_drift_batch(drift_cfg(Some(0)), 1_800, txs(), 4_000).await);
+        assert!(!add_drift_batch(drift_cfg(Some(0)), 1_802, txs(), 4_000).await);
+
+        // The drift is selected by the timestamp of the batch's epoch, which predates Fjord.
+        assert!(!add_drift_batch(drift_cfg(Some(1)), 602, txs(), 4_000).await);
+    }
+
+    #[tokio::test]
+    async fn test_add_batch_max_sequencer_drift_empty_batch() {
+        // An empty batch past the drift is kept while the next epoch is still ahead of it, so
+        // that the L2 time does not fall behind the L1 time.
+        assert!(add_drift_batch(drift_cfg(Some(0)), 1_802, Vec::new(), 1_804).await);
+        // Once the next epoch could have been adopted, the batch is dropped.
+        assert!(!add_drift_batch(drift_cfg(Some(0)), 1_802, Vec::new(), 1_802).await);
+    }
+
     #[tokio::test]
     async fn test_derive_next_batch_missing_origin() {
         let data = vec![Ok(Batch::Single(SingleBatch::default()))];
@@ -856,7 +1132,7 @@ mod tests {
             inclusion_block: parent.block_info,
             batch: Batch::Single(batch),
         };
-        bq.batches.push(data);
+        bq.batches.push(data.into());
         assert_eq!(bq.batches.len(), 1);
 
         // Derive next batch
@@ -872,6 +1148,110 @@ mod tests {
         assert!(logs[0].contains(warn_str));
     }
 
+    /// Returns a [BatchQueue] on a single epoch, along with a closure building the L2 block at a
+    /// timestamp.
+    fn future_batch_queue(
+    ) -> (BatchQueue<TestNextBatchProvider, TestL2ChainProvider>, impl Fn(u64) -> L2BlockInfo) {
+        let cfg = RollupConfig {
+            block_time: 2,
+            seq_window_size: 100,
+            max_sequencer_drift: 600,
+            ..Default::default()
+        };
+        let epoch = BlockInfo { hash: B256::repeat_byte(0xEE), ..Default::default() };
+        let block = move |timestamp: u64| L2BlockInfo {
+            block_info: BlockInfo {
+                hash: B256::repeat_byte(timestamp as u8),
+                number: timestamp / 2,
+                timestamp,
+                ..Default::default()
+            },
+            l1_origin: epoch.id(),
+            seq_num: timestamp / 2,
+        };
+        let mut mock = TestNextBatchProvider::new(vec![]);
+        mock.origin = Some(epoch);
+        let mut bq = BatchQueue::new(Arc::new(cfg), mock, TestL2ChainProvider::default());
+        bq.origin = Some(epoch);
+        bq.l1_blocks.push(epoch);
+        (bq, block)
+    }
+
+    /// Returns the batch building on the given L2 block.
+    fn batch_on(parent: L2BlockInfo) -> Batch {
+        Batch::Single(SingleBatch {
+            parent_hash: parent.block_info.hash,
+            epoch_num: 0,
+            epoch_hash: B256::repeat_byte(0xEE),
+            timestamp: parent.block_info.timestamp + 2,
+            transactions: Vec::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_derive_next_batch_buffers_future_batches() {
+        let trace_store: TraceStorage = Default::default();
+        let layer = CollectingLayer::new(trace_store.clone());
+        let subscriber = tracing_subscriber::Registry::default().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (mut bq, block) = future_batch_queue();
+        let safe_head = block(10);
+
+        // The batches for T + 2 and T + 4 arrive before the batch for T.
+        bq.add_batch(batch_on(block(12)), safe_head).await.unwrap();
+        bq.add_batch(batch_on(block(14)), safe_head).await.unwrap();
+        assert_eq!(bq.batches.len(), 2);
+        let err = bq.derive_next_batch(false, safe_head).await.unwrap_err();
+        assert_eq!(err, PipelineError::Eof.temp());
+        assert_eq!(bq.batches.len(), 2);
+
+        // Once the gap is filled, all three batches are derived in order.
+        bq.add_batch(batch_on(safe_head), safe_head).await.unwrap();
+        let mut parent = safe_head;
+        for timestamp in [12, 14, 16] {
+            let batch = bq.derive_next_batch(false, parent).await.unwrap();
+            assert_eq!(batch.timestamp(), timestamp);
+            parent = block(timestamp);
+        }
+        assert!(bq.batches.is_empty());
+        assert!(!bq.prev.flushed);
+
+        let logs = trace_store.get_by_level(Level::DEBUG);
+        let buffered = logs.iter().filter(|l| l.contains("Buffering future batch"));
+        assert_eq!(buffered.count(), 2);
+        let consumed = logs.iter().filter(|l| l.contains("Consuming buffered future batch"));
+        assert_eq!(consumed.count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_add_batch_bounded_buffer() {
+        let (bq, block) = future_batch_queue();
+        let limits = DerivationLimits { buffered_batches: Some(2), ..Default::default() };
+        let mut bq = bq.with_limits(limits);
+        let safe_head = block(10);
+
+        for timestamp in [14, 16, 12] {
+            bq.add_batch(batch_on(block(timestamp)), safe_head).await.unwrap();
+        }
+        // The batch furthest in the future is evicted.
+        let timestamps = bq.batches.iter().map(|b| b.inner.batch.timestamp()).collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![16, 14]);
+    }
+
+    #[tokio::test]
+    async fn test_add_batch_unbounded_by_default() {
+        let (mut bq, block) = future_batch_queue();
+        assert_eq!(bq.limits.buffered_batches, None);
+        let safe_head = block(10);
+
+        // Future batches sharing a timestamp are all buffered.
+        for timestamp in [14, 14, 16, 12] {
+            bq.add_batch(batch_on(block(timestamp)), safe_head).await.unwrap();
+        }
+        assert_eq!(bq.batches.len(), 4);
+    }
+
     #[tokio::test]
     async fn test_next_batch_cached_single_batch() {
         let mut reader = new_batch_reader();
@@ -1096,4 +1476,74 @@ mod tests {
         let batch = bq.next_batch(parent).await.unwrap();
         assert_eq!(batch, SingleBatch::default());
     }
+
+    /// Returns a warm-started [BatchQueue] reset to block #5 of a mock L2 chain, with the given
+    /// batches queued up in the previous stage.
+    async fn warm_started_queue(
+        warm_start: bool,
+        batches: Vec<SingleBatch>,
+    ) -> (BatchQueue<TestNextBatchProvider, TestL2ChainProvider>, L2BlockInfo) {
+        let chain = (0..=5)
+            .map(|number| L2BlockInfo {
+                block_info: BlockInfo {
+                    hash: B256::repeat_byte(number as u8 + 1),
+                    number,
+                    parent_hash: B256::repeat_byte(number as u8),
+                    timestamp: number * 2,
+                },
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let safe_head = chain[5];
+        let cfg =
+            Arc::new(RollupConfig { block_time: 2, seq_window_size: 100, ..Default::default() });
+        let mut batches = batches.into_iter().map(|b| Ok(Batch::Single(b))).collect::<Vec<_>>();
+        batches.reverse();
+        let mock = TestNextBatchProvider::new(batches);
+        let fetcher = TestL2ChainProvider { blocks: chain, ..Default::default() };
+        let mut bq = BatchQueue::new(cfg, mock, fetcher).with_warm_start(warm_start);
+        let reset = ResetSignal { l2_safe_head: safe_head, ..Default::default() };
+        bq.signal(reset.signal()).await.unwrap();
+        (bq, safe_head)
+    }
+
+    #[tokio::test]
+    async fn test_batch_queue_warm_start() {
+        let applied = |number: u64| SingleBatch {
+            parent_hash: B256::repeat_byte(number as u8),
+            timestamp: number * 2,
+            ..Default::default()
+        };
+        let conflicting = SingleBatch { parent_hash: B256::repeat_byte(0xEE), ..applied(5) };
+        let (mut bq, safe_head) =
+            warm_started_queue(true, vec![applied(3), applied(4), conflicting]).await;
+
+        // Batches for already-applied blocks are skipped without being validated.
+        for _ in 0..2 {
+            let err = bq.next_batch(safe_head).await.unwrap_err();
+            assert_eq!(err, PipelineError::NotEnoughData.temp());
+            assert!(!bq.prev.flushed);
+        }
+        assert_eq!(bq.warm_start_skipped(), 2);
+        assert!(bq.batches.is_empty());
+
+        // A batch conflicting with the local chain is dropped by the full batch checks.
+        let err = bq.next_batch(safe_head).await.unwrap_err();
+        assert_eq!(err, PipelineError::NotEnoughData.temp());
+        assert!(bq.prev.flushed);
+        assert_eq!(bq.warm_start_skipped(), 2);
+        assert!(bq.batches.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_queue_warm_start_disabled() {
+        let applied =
+            SingleBatch { parent_hash: B256::repeat_byte(4), timestamp: 8, ..Default::default() };
+        let (mut bq, safe_head) = warm_started_queue(false, vec![applied]).await;
+        let err = bq.next_batch(safe_head).await.unwrap_err();
+        assert_eq!(err, PipelineError::NotEnoughData.temp());
+        // Without a warm start, the batch is dropped by the full batch checks.
+        assert!(bq.prev.flushed);
+        assert_eq!(bq.warm_start_skipped(), 0);
+    }
 }
@@ -4,16 +4,16 @@ use crate::{
     errors::{PipelineEncodingError, PipelineError},
     stages::NextBatchProvider,
     traits::{L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, PipelineStatus, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use alloc::{boxed::Box, collections::VecDeque, format, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::{
     Batch, BatchValidity, BatchWithInclusionBlock, BlockInfo, L2BlockInfo, SingleBatch, SpanBatch,
 };
-use tracing::{error, trace};
+use tracing::{error, trace, warn};
 
 /// Provides [Batch]es for the [BatchStream] stage.
 #[async_trait]
@@ -25,6 +25,27 @@ pub trait BatchStreamProvider {
     fn flush(&mut self);
 }
 
+/// How the [BatchStream] handles a span batch whose blocks straddle the Holocene activation
+/// timestamp.
+///
+/// Holocene activates on the L1 origin, so the spec handles a straddling span as a whole, under
+/// the rules of the L1 block it was included in. The other policies are for chains that require
+/// every block to be derived under the rules of its own timestamp.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HoloceneSpanPolicy {
+    /// The span is handled as a whole, as specified.
+    #[default]
+    Keep,
+    /// The span is dropped.
+    Drop,
+    /// The span is truncated to the blocks before the activation timestamp, which are then
+    /// handled as usual. The blocks from the activation timestamp on must be batched again.
+    ///
+    /// The span keeps its L1 origin check, which commits to the L1 origin of its last block, so a
+    /// span truncated to an earlier epoch fails validation.
+    Truncate,
+}
+
 /// [BatchStream] stage in the derivation pipeline.
 ///
 /// This stage is introduced in the [Holocene] hardfork.
@@ -51,6 +72,10 @@ where
     config: Arc<RollupConfig>,
     /// Used to validate the batches.
     fetcher: BF,
+    /// Whether spec-violation drops are surfaced as critical errors.
+    strict: bool,
+    /// How span batches straddling the Holocene activation are handled.
+    holocene_span_policy: HoloceneSpanPolicy,
 }
 
 impl<P, BF> BatchStream<P, BF>
@@ -60,7 +85,27 @@ where
 {
     /// Create a new [BatchStream] stage.
     pub const fn new(prev: P, config: Arc<RollupConfig>, fetcher: BF) -> Self {
-        Self { prev, span: None, buffer: VecDeque::new(), config, fetcher }
+        Self {
+            prev,
+            span: None,
+            buffer: VecDeque::new(),
+            config,
+            fetcher,
+            strict: false,
+            holocene_span_policy: HoloceneSpanPolicy::Keep,
+        }
+    }
+
+    /// Sets whether invalid span batches fail with a critical error instead of being dropped.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [HoloceneSpanPolicy] applied to span batches straddling the Holocene activation.
+    pub const fn with_holocene_span_policy(mut self, policy: HoloceneSpanPolicy) -> Self {
+        self.holocene_span_policy = policy;
+        self
     }
 
     /// Returns if the [BatchStream] stage is active based on the
@@ -70,6 +115,39 @@ where
         Ok(self.config.is_holocene_active(origin.timestamp))
     }
 
+    /// Applies the [HoloceneSpanPolicy] to the span batch if its blocks straddle the Holocene
+    /// activation timestamp. Returns [None] if the span is dropped.
+    fn apply_holocene_span_policy(&self, mut span: SpanBatch) -> Option<SpanBatch> {
+        let Some(activation) = self.config.holocene_time else {
+            return Some(span);
+        };
+        let before_activation = span.batches.iter().filter(|b| b.timestamp < activation).count();
+        if before_activation == 0 || before_activation == span.batches.len() {
+            return Some(span);
+        }
+
+        match self.holocene_span_policy {
+            HoloceneSpanPolicy::Keep => Some(span),
+            HoloceneSpanPolicy::Drop => {
+                warn!(
+                    target: "batch-stream",
+                    "Dropping span batch with timestamp {}: its blocks straddle the Holocene activation at {}",
+                    span.starting_timestamp(), activation
+                );
+                None
+            }
+            HoloceneSpanPolicy::Truncate => {
+                warn!(
+                    target: "batch-stream",
+                    "Truncating span batch with timestamp {} to its {} blocks before the Holocene activation at {}",
+                    span.starting_timestamp(), before_activation, activation
+                );
+                span.batches.truncate(before_activation);
+                Some(span)
+            }
+        }
+    }
+
     /// Gets a [SingleBatch] from the in-memory buffer.
     pub fn get_single_batch(
         &mut self,
@@ -118,6 +196,15 @@ where
         self.buffer.len()
     }
 
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(
+            name = "batch_stream",
+            level = "debug",
+            skip_all,
+            fields(parent = parent.block_info.number)
+        )
+    )]
     async fn next_batch(
         &mut self,
         parent: L2BlockInfo,
@@ -127,7 +214,13 @@ where
         // through this stage to the BatchQueue stage.
         if !self.is_active()? {
             trace!(target: "batch_span", "BatchStream stage is inactive, pass-through.");
-            return self.prev.next_batch().await;
+            return match self.prev.next_batch().await? {
+                Batch::Span(b) => self
+                    .apply_holocene_span_policy(b)
+                    .map(Batch::Span)
+                    .ok_or_else(|| PipelineError::NotEnoughData.temp()),
+                batch => Ok(batch),
+            };
         }
 
         // If the buffer is empty, attempt to pull a batch from the previous stage.
@@ -144,6 +237,9 @@ where
             match batch_with_inclusion.batch {
                 Batch::Single(b) => return Ok(Batch::Single(b)),
                 Batch::Span(b) => {
+                    let Some(b) = self.apply_holocene_span_policy(b) else {
+                        return Err(PipelineError::NotEnoughData.temp());
+                    };
                     let (validity, _) = b
                         .check_batch_prefix(
                             self.config.as_ref(),
@@ -157,6 +253,14 @@ where
                     match validity {
                         BatchValidity::Accept => self.span = Some(b),
                         BatchValidity::Drop => {
+                            if self.strict {
+                                return Err(PipelineError::StrictViolation(format!(
+                                    "invalid span batch with timestamp {}",
+                                    b.starting_timestamp()
+                                ))
+                                .crit());
+                            }
+
                             // Flush the stage.
                             self.flush();
 
@@ -192,6 +296,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 impl<P, BF> OriginProvider for BatchStream<P, BF>
@@ -202,6 +310,16 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("batch-stream", self.origin()));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+        status.buffered_batches += self.buffer.len();
+    }
 }
 
 #[async_trait]
@@ -210,11 +328,11 @@ where
     P: BatchStreamProvider + OriginAdvancer + OriginProvider + SignalReceiver + Debug + Send,
     BF: L2ChainProvider + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        self.prev.signal(signal).await?;
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let receipt = self.prev.signal(signal).await?;
         self.buffer.clear();
         self.span.take();
-        Ok(())
+        Ok(receipt)
     }
 }
 
@@ -225,8 +343,11 @@ mod test {
         test_utils::{CollectingLayer, TestBatchStreamProvider, TestL2ChainProvider, TraceStorage},
         types::ResetSignal,
     };
-    use alloc::vec;
+    use alloc::{vec, vec::Vec};
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::{FixedBytes, B256};
     use op_alloy_protocol::{SingleBatch, SpanBatchElement};
+    use tracing::Level;
     use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
     #[tokio::test]
@@ -367,6 +488,96 @@ mod test {
         assert!(stream.span.is_none());
     }
 
+    /// Returns a three-block span batch whose middle block is the Holocene activation block, and
+    /// the rollup config activating Holocene at that block.
+    fn straddling_span() -> (SpanBatch, Arc<RollupConfig>) {
+        let span = SpanBatch {
+            batches: vec![
+                SpanBatchElement { epoch_num: 1, timestamp: 2, ..Default::default() },
+                SpanBatchElement { epoch_num: 1, timestamp: 4, ..Default::default() },
+                SpanBatchElement { epoch_num: 1, timestamp: 6, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let config = Arc::new(RollupConfig {
+            delta_time: Some(0),
+            holocene_time: Some(4),
+            block_time: 2,
+            ..RollupConfig::default()
+        });
+        (span, config)
+    }
+
+    #[tokio::test]
+    async fn test_span_straddling_holocene_activation_kept() {
+        let (span, config) = straddling_span();
+        let origins = [BlockInfo { number: 1, timestamp: 12, ..Default::default() }];
+
+        // Included before the activation, the span is passed through whole.
+        let prev = TestBatchStreamProvider::new(vec![Ok(Batch::Span(span.clone()))]);
+        let mut stream = BatchStream::new(prev, config.clone(), TestL2ChainProvider::default());
+        assert!(!stream.is_active().unwrap());
+        let batch = stream.next_batch(Default::default(), &origins).await.unwrap();
+        assert_eq!(batch, Batch::Span(span.clone()));
+
+        // Included after the activation, every block of the span is buffered and validated
+        // under the Holocene rules.
+        let mut prev = TestBatchStreamProvider::new(vec![Ok(Batch::Span(span))]);
+        prev.origin = Some(BlockInfo { timestamp: 12, ..Default::default() });
+        let mut stream = BatchStream::new(prev, config, TestL2ChainProvider::default());
+        assert!(stream.is_active().unwrap());
+        for timestamp in [2, 4, 6] {
+            let batch = stream.next_batch(Default::default(), &origins).await.unwrap();
+            assert!(matches!(batch, Batch::Single(single) if single.timestamp == timestamp));
+        }
+        let err = stream.next_batch(Default::default(), &origins).await.unwrap_err();
+        assert_eq!(err, PipelineError::Eof.temp());
+    }
+
+    #[tokio::test]
+    async fn test_span_straddling_holocene_activation_dropped() {
+        let (span, config) = straddling_span();
+        let origins = [BlockInfo { number: 1, timestamp: 12, ..Default::default() }];
+
+        for inclusion_time in [0, 12] {
+            let mut prev = TestBatchStreamProvider::new(vec![Ok(Batch::Span(span.clone()))]);
+            prev.origin = Some(BlockInfo { timestamp: inclusion_time, ..Default::default() });
+            let mut stream = BatchStream::new(prev, config.clone(), TestL2ChainProvider::default())
+                .with_holocene_span_policy(HoloceneSpanPolicy::Drop);
+
+            let err = stream.next_batch(Default::default(), &origins).await.unwrap_err();
+            assert_eq!(err, PipelineError::NotEnoughData.temp());
+            assert!(stream.span.is_none());
+            let err = stream.next_batch(Default::default(), &origins).await.unwrap_err();
+            assert_eq!(err, PipelineError::Eof.temp());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_span_straddling_holocene_activation_truncated() {
+        let (span, config) = straddling_span();
+        let origins = [BlockInfo { number: 1, timestamp: 12, ..Default::default() }];
+        let mut truncated = span.clone();
+        truncated.batches.truncate(1);
+
+        // Included before the activation, the truncated span is passed through.
+        let prev = TestBatchStreamProvider::new(vec![Ok(Batch::Span(span.clone()))]);
+        let mut stream = BatchStream::new(prev, config.clone(), TestL2ChainProvider::default())
+            .with_holocene_span_policy(HoloceneSpanPolicy::Truncate);
+        let batch = stream.next_batch(Default::default(), &origins).await.unwrap();
+        assert_eq!(batch, Batch::Span(truncated));
+
+        // Included after the activation, only the block before the activation is buffered.
+        let mut prev = TestBatchStreamProvider::new(vec![Ok(Batch::Span(span))]);
+        prev.origin = Some(BlockInfo { timestamp: 12, ..Default::default() });
+        let mut stream = BatchStream::new(prev, config, TestL2ChainProvider::default())
+            .with_holocene_span_policy(HoloceneSpanPolicy::Truncate);
+        let batch = stream.next_batch(Default::default(), &origins).await.unwrap();
+        assert!(matches!(batch, Batch::Single(single) if single.timestamp == 2));
+        let err = stream.next_batch(Default::default(), &origins).await.unwrap_err();
+        assert_eq!(err, PipelineError::Eof.temp());
+    }
+
     #[tokio::test]
     async fn test_single_batch_pass_through() {
         let data = vec![Ok(Batch::Single(SingleBatch::default()))];
@@ -383,4 +594,104 @@ mod test {
         assert_eq!(stream.span_buffer_size(), 0);
         assert!(stream.span.is_none());
     }
+
+    /// Returns a mock L2 chain of blocks #0 to #5, two seconds apart, with L1 origin #1.
+    fn l2_chain() -> Vec<L2BlockInfo> {
+        (0..=5)
+            .map(|number| L2BlockInfo {
+                block_info: BlockInfo {
+                    hash: B256::repeat_byte(number as u8 + 1),
+                    number,
+                    parent_hash: B256::repeat_byte(number as u8),
+                    timestamp: number * 2,
+                },
+                l1_origin: BlockNumHash { number: 1, hash: B256::repeat_byte(0xAA) },
+                seq_num: 0,
+            })
+            .collect()
+    }
+
+    /// Checks a span batch overlapping the safe head of the mock L2 chain, so that its parent
+    /// block (#4) is fetched from the [L2ChainProvider].
+    async fn check_span_prefix(
+        parent_check: &[u8],
+        l1_origin_check: &[u8],
+    ) -> PipelineResult<Batch> {
+        let span = SpanBatch {
+            parent_check: FixedBytes::from_slice(&parent_check[..20]),
+            l1_origin_check: FixedBytes::from_slice(&l1_origin_check[..20]),
+            batches: vec![
+                SpanBatchElement { epoch_num: 1, timestamp: 10, ..Default::default() },
+                SpanBatchElement { epoch_num: 1, timestamp: 12, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let config = Arc::new(RollupConfig {
+            delta_time: Some(0),
+            holocene_time: Some(0),
+            block_time: 2,
+            seq_window_size: 100,
+            ..RollupConfig::default()
+        });
+        let chain = l2_chain();
+        let safe_head = chain[5];
+        let provider = TestL2ChainProvider { blocks: chain, ..Default::default() };
+        let prev = TestBatchStreamProvider::new(vec![Ok(Batch::Span(span))]);
+        let mut stream = BatchStream::new(prev, config, provider);
+        let origin = BlockInfo { number: 1, hash: B256::repeat_byte(0xAA), ..Default::default() };
+        stream.next_batch(safe_head, &[origin]).await
+    }
+
+    #[tokio::test]
+    async fn test_span_batch_prefix_checks_pass() {
+        let parent = B256::repeat_byte(5);
+        let batch = check_span_prefix(&parent[..], &[0xAA; 32]).await.unwrap();
+        // The overlapping block is skipped, and derivation continues on top of the safe head.
+        assert_eq!(batch, Batch::Single(expected_span_successor()));
+    }
+
+    /// Returns the batch following the safe head of the mock L2 chain, decoded from the span
+    /// batch of [check_span_prefix]. Span batches do not carry the parent hashes of their blocks.
+    fn expected_span_successor() -> SingleBatch {
+        SingleBatch {
+            parent_hash: B256::ZERO,
+            epoch_num: 1,
+            epoch_hash: B256::repeat_byte(0xAA),
+            timestamp: 12,
+            transactions: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_span_batch_prefix_checks_drop() {
+        let cases = [
+            // The safe head is not the parent of the overlapping span batch.
+            (B256::repeat_byte(6), B256::repeat_byte(0xAA), "parent block mismatch"),
+            (B256::repeat_byte(5), B256::repeat_byte(0xBB), "epoch hash does not match"),
+        ];
+
+        for (parent_check, l1_origin_check, reason) in cases {
+            let trace_store: TraceStorage = Default::default();
+            let layer = CollectingLayer::new(trace_store.clone());
+            let subscriber = tracing_subscriber::Registry::default().with(layer);
+            let _guard = tracing::subscriber::set_default(subscriber);
+
+            let err = check_span_prefix(&parent_check[..], &l1_origin_check[..]).await;
+            assert_eq!(err, Err(PipelineError::Eof.temp()));
+            let warns = trace_store.get_by_level(Level::WARN);
+            assert_eq!(warns.len(), 1);
+            assert!(warns[0].contains(reason));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_span_batch_prefix_truncated_checks_accept() {
+        // Bytes beyond the 20 byte checks do not affect validity.
+        let mut parent = [5; 32];
+        parent[20..].fill(0);
+        let mut l1_origin = [0xAA; 32];
+        l1_origin[20..].fill(0xFF);
+        let batch = check_span_prefix(&parent, &l1_origin).await.unwrap();
+        assert_eq!(batch, Batch::Single(expected_span_successor()));
+    }
 }
@@ -4,10 +4,10 @@ use super::NextBatchProvider;
 use crate::{
     errors::ResetError,
     prelude::{OriginProvider, PipelineError, PipelineErrorKind},
-    traits::{AttributesProvider, OriginAdvancer, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    traits::{AttributesProvider, DerivationMetrics, OriginAdvancer, SignalReceiver},
+    types::{PipelineResult, PipelineStatus, ResetSignal, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
@@ -37,6 +37,10 @@ where
     /// If new L2 Block's L1 origin is not included in this list, fetch and
     /// push it to the list.
     pub(crate) l1_blocks: Vec<BlockInfo>,
+    /// Whether spec-violation drops are surfaced as critical errors.
+    pub(crate) strict: bool,
+    /// The metrics recorder, if any.
+    pub(crate) metrics: Option<Arc<dyn DerivationMetrics>>,
 }
 
 impl<P> BatchValidator<P>
@@ -45,7 +49,26 @@ where
 {
     /// Create a new [BatchValidator] stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, origin: None, l1_blocks: Vec::new() }
+        Self { cfg, prev, origin: None, l1_blocks: Vec::new(), strict: false, metrics: None }
+    }
+
+    /// Sets whether invalid batches fail with a critical error instead of being dropped.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording the accepted and discarded batches.
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Records the final validity of a batch with the [DerivationMetrics], if any.
+    fn record_batch(&self, validity: BatchValidity) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_batch(validity);
+        }
     }
 
     /// Returns `true` if the pipeline origin is behind the parent origin.
@@ -183,6 +206,15 @@ impl<P> AttributesProvider for BatchValidator<P>
 where
     P: NextBatchProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(
+            name = "batch_validator",
+            level = "debug",
+            skip_all,
+            fields(parent = parent.block_info.number)
+        )
+    )]
     async fn next_batch(&mut self, parent: L2BlockInfo) -> PipelineResult<SingleBatch> {
         // Update the L1 origin blocks within the stage.
         self.update_origins(&parent)?;
@@ -240,16 +272,26 @@ where
             &stage_origin,
         ) {
             BatchValidity::Accept => {
+                self.record_batch(BatchValidity::Accept);
                 info!(target: "batch-validator", "Found next batch (epoch #{})", next_batch.epoch_num);
                 Ok(next_batch)
             }
             BatchValidity::Past => {
+                self.record_batch(BatchValidity::Past);
                 warn!(target: "batch-validator", "Dropping old batch");
                 Err(PipelineError::NotEnoughData.temp())
             }
             BatchValidity::Drop => {
+                if self.strict {
+                    return Err(PipelineError::StrictViolation(format!(
+                        "invalid batch with timestamp {}",
+                        next_batch.timestamp
+                    ))
+                    .crit());
+                }
                 warn!(target: "batch-validator", "Invalid singular batch, flushing current channel.");
                 self.prev.flush();
+                self.record_batch(BatchValidity::Drop);
                 Err(PipelineError::NotEnoughData.temp())
             }
             BatchValidity::Undecided => Err(PipelineError::NotEnoughData.temp()),
@@ -272,6 +314,15 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("batch-validator", self.origin));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+    }
 }
 
 #[async_trait]
@@ -282,6 +333,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 #[async_trait]
@@ -289,22 +344,23 @@ impl<P> SignalReceiver for BatchValidator<P>
 where
     P: NextBatchProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        match signal {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let receipt = match signal {
             s @ Signal::Reset(ResetSignal { l1_origin, .. }) => {
-                self.prev.signal(s).await?;
+                let receipt = self.prev.signal(s).await?;
                 self.origin = Some(l1_origin);
                 // Include the new origin as an origin to build on.
                 // This is only for the initialization case.
                 // During normal resets we will later throw out this block.
                 self.l1_blocks.clear();
                 self.l1_blocks.push(l1_origin);
+                receipt
             }
-            s @ Signal::Activation(_) | s @ Signal::FlushChannel => {
-                self.prev.signal(s).await?;
+            s @ Signal::Activation(_) | s @ Signal::FlushChannel | s @ Signal::AdvanceOrigin => {
+                self.prev.signal(s).await?
             }
-        }
-        Ok(())
+        };
+        Ok(receipt)
     }
 }
 
@@ -319,7 +375,7 @@ mod test {
     };
     use alloc::{sync::Arc, vec, vec::Vec};
     use alloy_eips::{BlockNumHash, NumHash};
-    use alloy_primitives::B256;
+    use alloy_primitives::{Bytes, B256};
     use op_alloy_genesis::RollupConfig;
     use op_alloy_protocol::{Batch, BlockInfo, L2BlockInfo, SingleBatch, SpanBatch};
     use tracing::Level;
@@ -539,6 +595,123 @@ mod test {
         assert_eq!(batch, produced_batch);
     }
 
+    #[tokio::test]
+    async fn test_batch_validator_invalid_batch_flushes_channel() {
+        let cfg = Arc::new(RollupConfig {
+            holocene_time: Some(0),
+            block_time: 2,
+            max_sequencer_drift: 700,
+            ..Default::default()
+        });
+        let batch = SingleBatch {
+            parent_hash: B256::default(),
+            epoch_num: 2,
+            epoch_hash: B256::default(),
+            timestamp: 4,
+            transactions: Vec::new(),
+        };
+        let invalid = SingleBatch { epoch_hash: B256::repeat_byte(0x01), ..batch.clone() };
+        let parent = L2BlockInfo {
+            l1_origin: BlockNumHash { number: 0, ..Default::default() },
+            block_info: BlockInfo { timestamp: 2, ..Default::default() },
+            ..Default::default()
+        };
+
+        // The invalid batch is read first, followed by a valid batch for the same slot.
+        let batch_vec = vec![
+            PipelineResult::Ok(Batch::Single(batch.clone())),
+            PipelineResult::Ok(Batch::Single(invalid)),
+        ];
+        let mut mock = TestNextBatchProvider::new(batch_vec);
+        mock.origin = Some(BlockInfo { number: 1, ..Default::default() });
+        let mut bv = BatchValidator::new(cfg, mock);
+        bv.signal(Signal::Reset(ResetSignal {
+            l1_origin: BlockInfo { number: 1, ..Default::default() },
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        bv.l1_blocks.push(BlockInfo { number: 1, ..Default::default() });
+
+        // The invalid batch is dropped and the rest of its channel is flushed.
+        assert_eq!(bv.next_batch(parent).await.unwrap_err(), PipelineError::NotEnoughData.temp());
+        assert!(bv.prev.flushed);
+
+        // The slot is still open, so the next valid batch is accepted.
+        assert_eq!(bv.next_batch(parent).await.unwrap(), batch);
+    }
+
+    #[tokio::test]
+    async fn test_batch_validator_invalid_batch_strict() {
+        let cfg = Arc::new(RollupConfig {
+            holocene_time: Some(0),
+            block_time: 2,
+            max_sequencer_drift: 700,
+            ..Default::default()
+        });
+        let invalid = SingleBatch {
+            epoch_num: 2,
+            epoch_hash: B256::repeat_byte(0x01),
+            timestamp: 4,
+            ..Default::default()
+        };
+        let parent = L2BlockInfo {
+            block_info: BlockInfo { timestamp: 2, ..Default::default() },
+            ..Default::default()
+        };
+        let mut mock = TestNextBatchProvider::new(vec![Ok(Batch::Single(invalid))]);
+        mock.origin = Some(BlockInfo { number: 1, ..Default::default() });
+        let mut bv = BatchValidator::new(cfg, mock).with_strict(true);
+        bv.signal(Signal::Reset(ResetSignal {
+            l1_origin: BlockInfo { number: 1, ..Default::default() },
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+        bv.l1_blocks.push(BlockInfo { number: 1, ..Default::default() });
+
+        let err = bv.next_batch(parent).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Critical(PipelineError::StrictViolation(_))));
+        assert!(!bv.prev.flushed);
+    }
+
+    #[tokio::test]
+    async fn test_batch_validator_invalid_batch_empty_slot() {
+        let cfg = Arc::new(RollupConfig { seq_window_size: 5, ..Default::default() });
+        let invalid = SingleBatch {
+            parent_hash: B256::default(),
+            epoch_num: 0,
+            epoch_hash: B256::default(),
+            timestamp: cfg.block_time,
+            transactions: vec![Bytes::from_static(&[0x01])],
+        };
+        let mut mock = TestNextBatchProvider::new(vec![Ok(Batch::Single(invalid))]);
+        mock.origin = Some(BlockInfo { number: 1, ..Default::default() });
+        let mut bv = BatchValidator::new(cfg.clone(), mock);
+        bv.signal(Signal::Reset(ResetSignal {
+            l1_origin: BlockInfo { number: 1, ..Default::default() },
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+        // Advance the origin of the previous stage past the sequencing window.
+        for _ in 0..6 {
+            bv.advance_origin().await.unwrap();
+        }
+
+        // The batch was included too late, so it is dropped and its channel is flushed.
+        let parent = L2BlockInfo::default();
+        assert_eq!(bv.next_batch(parent).await.unwrap_err(), PipelineError::NotEnoughData.temp());
+        assert!(bv.prev.flushed);
+
+        // With no valid batch available, the skipped slot is filled with an empty batch.
+        let batch = bv.next_batch(parent).await.unwrap();
+        assert!(batch.transactions.is_empty());
+        assert_eq!(batch.epoch_num, 1);
+        assert_eq!(batch.timestamp, cfg.block_time);
+    }
+
     #[tokio::test]
     async fn test_batch_validator_next_batch_sequence_window_expired() {
         let trace_store: TraceStorage = Default::default();
@@ -17,7 +17,7 @@ use async_trait::async_trait;
 use op_alloy_protocol::{Batch, BlockInfo, L2BlockInfo};
 
 mod batch_stream;
-pub use batch_stream::{BatchStream, BatchStreamProvider};
+pub use batch_stream::{BatchStream, BatchStreamProvider, HoloceneSpanPolicy};
 
 mod batch_queue;
 pub use batch_queue::BatchQueue;
@@ -3,16 +3,17 @@
 use super::{ChannelReaderProvider, NextFrameProvider};
 use crate::{
     prelude::{OriginProvider, PipelineError},
-    traits::{OriginAdvancer, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{DerivationMetrics, OriginAdvancer, SignalReceiver},
+    types::{
+        ChannelCloseReason, ChannelId, DerivationLimits, PipelineResult, PipelineStatus, Signal,
+        SignalReceipt,
+    },
 };
-use alloc::{boxed::Box, sync::Arc};
-use alloy_primitives::{hex, Bytes};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
+use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use core::fmt::Debug;
-use op_alloy_genesis::{
-    RollupConfig, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD,
-};
+use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::{BlockInfo, Channel};
 use tracing::{debug, error, info, warn};
 
@@ -33,6 +34,12 @@ where
     pub(crate) prev: P,
     /// The current [Channel] being assembled.
     pub(crate) channel: Option<Channel>,
+    /// The derivation resource limits.
+    pub(crate) limits: DerivationLimits,
+    /// Whether spec-violation drops are surfaced as critical errors.
+    pub(crate) strict: bool,
+    /// The metrics recorder, if any.
+    pub(crate) metrics: Option<Arc<dyn DerivationMetrics>>,
 }
 
 impl<P> ChannelAssembler<P>
@@ -41,7 +48,40 @@ where
 {
     /// Creates a new [ChannelAssembler] stage with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, channel: None }
+        Self {
+            cfg,
+            prev,
+            channel: None,
+            limits: DerivationLimits::SPEC,
+            strict: false,
+            metrics: None,
+        }
+    }
+
+    /// Sets the [DerivationLimits] enforced by the [ChannelAssembler].
+    pub const fn with_limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether invalid frames, frames for a closed channel, and timed out or oversized
+    /// channels fail with a critical error instead of being dropped.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording opened and closed channels.
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Records a closed channel with the [DerivationMetrics], if any.
+    fn record_channel_closed(&self, reason: ChannelCloseReason) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_channel_closed(reason);
+        }
     }
 
     /// Returns whether or not the channel currently being assembled has timed out.
@@ -64,20 +104,33 @@ impl<P> ChannelReaderProvider for ChannelAssembler<P>
 where
     P: NextFrameProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(name = "channel_assembler", level = "debug", skip_all)
+    )]
     async fn next_data(&mut self) -> PipelineResult<Option<Bytes>> {
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
 
         // Time out the channel if it has timed out.
         if let Some(channel) = self.channel.as_ref() {
             if self.is_timed_out()? {
+                if self.strict {
+                    return Err(PipelineError::StrictViolation(format!(
+                        "channel {:#} timed out at L1 origin #{}",
+                        ChannelId::from(channel.id()),
+                        origin.number
+                    ))
+                    .crit());
+                }
                 warn!(
                     target: "channel-assembler",
-                    "Channel (ID: {}) timed out at L1 origin #{}, open block #{}. Discarding channel.",
-                    hex::encode(channel.id()),
+                    "Channel (ID: {:#}) timed out at L1 origin #{}, open block #{}. Discarding channel.",
+                    ChannelId::from(channel.id()),
                     origin.number,
                     channel.open_block_number()
                 );
                 self.channel = None;
+                self.record_channel_closed(ChannelCloseReason::TimedOut);
             }
         }
 
@@ -88,10 +141,16 @@ where
         if next_frame.number == 0 {
             info!(
                 target: "channel-assembler",
-                "Starting new channel (ID: {}) at L1 origin #{}",
-                hex::encode(next_frame.id),
+                "Starting new channel (ID: {:#}) at L1 origin #{}",
+                ChannelId::from(next_frame.id),
                 origin.number
             );
+            if self.channel.is_some() {
+                self.record_channel_closed(ChannelCloseReason::Superseded);
+            }
+            if let Some(metrics) = &self.metrics {
+                metrics.record_channel_opened();
+            }
             self.channel = Some(Channel::new(next_frame.id, origin));
         }
 
@@ -100,34 +159,49 @@ where
             // frame.
             debug!(
                 target: "channel-assembler",
-                "Adding frame #{} to channel (ID: {}) at L1 origin #{}",
+                "Adding frame #{} to channel (ID: {:#}) at L1 origin #{}",
                 next_frame.number,
-                hex::encode(channel.id()),
+                ChannelId::from(channel.id()),
                 origin.number
             );
+            let frame_number = next_frame.number;
             if channel.add_frame(next_frame, origin).is_err() {
+                if self.strict {
+                    return Err(PipelineError::StrictViolation(format!(
+                        "invalid frame #{} for channel {:#}",
+                        frame_number,
+                        ChannelId::from(channel.id())
+                    ))
+                    .crit());
+                }
                 error!(
                     target: "channel-assembler",
-                    "Failed to add frame to channel (ID: {}) at L1 origin #{}",
-                    hex::encode(channel.id()),
+                    "Failed to add frame to channel (ID: {:#}) at L1 origin #{}",
+                    ChannelId::from(channel.id()),
                     origin.number
                 );
                 return Err(PipelineError::NotEnoughData.temp());
             }
 
-            let max_rlp_bytes_per_channel = if self.cfg.is_fjord_active(origin.timestamp) {
-                MAX_RLP_BYTES_PER_CHANNEL_FJORD
-            } else {
-                MAX_RLP_BYTES_PER_CHANNEL_BEDROCK
-            };
-            if channel.size() > max_rlp_bytes_per_channel as usize {
+            let max_rlp_bytes_per_channel =
+                self.limits.max_rlp_bytes_per_channel(&self.cfg, origin.timestamp);
+            if channel.size() > max_rlp_bytes_per_channel {
+                if self.strict {
+                    return Err(PipelineError::StrictViolation(format!(
+                        "channel {:#} of {} bytes exceeds max RLP bytes per channel",
+                        ChannelId::from(channel.id()),
+                        channel.size()
+                    ))
+                    .crit());
+                }
                 warn!(
                     target: "channel-assembler",
-                    "Compressed channel size exceeded max RLP bytes per channel, dropping channel (ID: {}) with {} bytes",
-                    hex::encode(channel.id()),
+                    "Compressed channel size exceeded max RLP bytes per channel, dropping channel (ID: {:#}) with {} bytes",
+                    ChannelId::from(channel.id()),
                     channel.size()
                 );
                 self.channel = None;
+                self.record_channel_closed(ChannelCloseReason::Oversized);
                 return Err(PipelineError::NotEnoughData.temp());
             }
 
@@ -138,14 +212,22 @@ where
 
                 info!(
                     target: "channel-assembler",
-                    "Channel (ID: {}) ready for decompression.",
-                    hex::encode(channel.id()),
+                    "Channel (ID: {:#}) ready for decompression.",
+                    ChannelId::from(channel.id()),
                 );
 
                 // Reset the channel and return the compressed bytes.
                 self.channel = None;
+                self.record_channel_closed(ChannelCloseReason::Ready);
                 return Ok(Some(channel_bytes));
             }
+        } else if self.strict {
+            return Err(PipelineError::StrictViolation(format!(
+                "frame #{} for closed channel {:#}",
+                next_frame.number,
+                ChannelId::from(next_frame.id)
+            ))
+            .crit());
         }
 
         Err(PipelineError::NotEnoughData.temp())
@@ -160,6 +242,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 impl<P> OriginProvider for ChannelAssembler<P>
@@ -169,6 +255,19 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("channel-assembler", self.origin()));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+        if let Some(channel) = self.channel.as_ref() {
+            status.channels += 1;
+            status.channel_bytes += channel.size();
+        }
+    }
 }
 
 #[async_trait]
@@ -176,10 +275,12 @@ impl<P> SignalReceiver for ChannelAssembler<P>
 where
     P: NextFrameProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        self.prev.signal(signal).await?;
-        self.channel = None;
-        Ok(())
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let mut receipt = self.prev.signal(signal).await?;
+        if self.channel.take().is_some() {
+            receipt.flushed_channels += 1;
+        }
+        Ok(receipt)
     }
 }
 
@@ -187,9 +288,11 @@ where
 mod test {
     use super::ChannelAssembler;
     use crate::{
+        errors::PipelineErrorKind,
         prelude::PipelineError,
         stages::ChannelReaderProvider,
         test_utils::{CollectingLayer, TestNextFrameProvider, TraceStorage},
+        types::DerivationLimits,
     };
     use alloc::{sync::Arc, vec};
     use op_alloy_genesis::{
@@ -245,6 +348,27 @@ mod test {
         assert!(warning_logs[0].contains(warn_str));
     }
 
+    #[tokio::test]
+    async fn test_assembler_logs_full_channel_id() {
+        let trace_store: TraceStorage = Default::default();
+        let layer = CollectingLayer::new(trace_store.clone());
+        let subscriber = tracing_subscriber::Registry::default().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let frames = [crate::frame!(0xAB, 0, vec![0xDD; 50], true)];
+        let mock = TestNextFrameProvider::new(frames.into_iter().map(Ok).collect());
+        let cfg = Arc::new(RollupConfig::default());
+        let mut assembler = ChannelAssembler::new(cfg, mock);
+        assert!(assembler.next_data().await.unwrap().is_some());
+
+        // Log lines carry the full 32 character hex channel id, not the short display form.
+        let info_logs = trace_store.get_by_level(Level::INFO);
+        assert_eq!(info_logs.len(), 2);
+        let id = "0xabababababababababababababababab";
+        assert!(info_logs[0].contains(&alloc::format!("Starting new channel (ID: {id})")));
+        assert!(info_logs[1].contains(&alloc::format!("Channel (ID: {id}) ready")));
+    }
+
     #[tokio::test]
     async fn test_assembler_non_starting_frame() {
         let frames = [
@@ -262,6 +386,17 @@ mod test {
         assert!(assembler.channel.is_none());
     }
 
+    #[tokio::test]
+    async fn test_assembler_non_starting_frame_strict() {
+        let frames = [crate::frame!(0xFF, 1, vec![0xDD; 50], true)];
+        let mock = TestNextFrameProvider::new(frames.into_iter().map(Ok).collect());
+        let cfg = Arc::new(RollupConfig::default());
+        let mut assembler = ChannelAssembler::new(cfg, mock).with_strict(true);
+
+        let err = assembler.next_data().await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Critical(PipelineError::StrictViolation(_))));
+    }
+
     #[tokio::test]
     async fn test_assembler_already_built() {
         let trace_store: TraceStorage = Default::default();
@@ -336,6 +471,28 @@ mod test {
         assert!(message.contains("Compressed channel size exceeded max RLP bytes per channel"));
     }
 
+    #[tokio::test]
+    async fn test_assembler_size_limit_override() {
+        let frames = [
+            crate::frame!(0xFF, 0, vec![0xDD; 50], false),
+            crate::frame!(0xFF, 1, vec![0xDD; 50], true),
+        ];
+        let mock = TestNextFrameProvider::new(frames.into_iter().rev().map(Ok).collect());
+        let cfg = Arc::new(RollupConfig::default());
+        let limits = DerivationLimits { rlp_bytes_per_channel: 300, ..Default::default() };
+
+        let mut assembler = ChannelAssembler::new(cfg, mock).with_limits(limits);
+
+        // The first frame fits within the overridden limit.
+        assert_eq!(assembler.next_data().await.unwrap_err(), PipelineError::NotEnoughData.temp());
+        assert!(assembler.channel.is_some());
+
+        // The second frame pushes the channel over the overridden limit, even though it is well
+        // within the spec limit.
+        assert_eq!(assembler.next_data().await.unwrap_err(), PipelineError::NotEnoughData.temp());
+        assert!(assembler.channel.is_none());
+    }
+
     #[tokio::test]
     async fn test_assembler_size_limit_exceeded_fjord() {
         let trace_store: TraceStorage = Default::default();
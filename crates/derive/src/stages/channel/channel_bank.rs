@@ -4,22 +4,19 @@ use super::NextFrameProvider;
 use crate::{
     errors::{PipelineError, PipelineErrorKind},
     stages::ChannelReaderProvider,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{DerivationMetrics, OriginAdvancer, OriginProvider, SignalReceiver},
+    types::{
+        ChannelCloseReason, ChannelId, DerivationLimits, PipelineResult, PipelineStatus, Signal,
+        SignalReceipt,
+    },
 };
-use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
-use alloy_primitives::{hex, map::HashMap, Bytes};
+use alloc::{boxed::Box, collections::VecDeque, format, sync::Arc, vec::Vec};
+use alloy_primitives::{map::HashMap, Bytes};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
-use op_alloy_protocol::{BlockInfo, Channel, ChannelId, Frame};
-use tracing::{trace, warn};
-
-/// The maximum size of a channel bank.
-pub(crate) const MAX_CHANNEL_BANK_SIZE: usize = 100_000_000;
-
-/// The maximum size of a channel bank after the Fjord Hardfork.
-pub(crate) const FJORD_MAX_CHANNEL_BANK_SIZE: usize = 1_000_000_000;
+use op_alloy_protocol::{BlockInfo, Channel, Frame};
+use tracing::{debug, trace, warn};
 
 /// [ChannelBank] is a stateful stage that does the following:
 /// 1. Unmarshalls frames from L1 transaction data
@@ -43,6 +40,16 @@ where
     pub(crate) channels: HashMap<ChannelId, Channel>,
     /// Channels in FIFO order.
     pub(crate) channel_queue: VecDeque<ChannelId>,
+    /// The closing frame number of each open channel that has received its closing frame.
+    pub(crate) closing_frames: HashMap<ChannelId, u16>,
+    /// The total size of the buffered frames of all channels.
+    pub(crate) total_size: usize,
+    /// The derivation resource limits.
+    pub(crate) limits: DerivationLimits,
+    /// Whether spec-violation drops are surfaced as critical errors.
+    pub(crate) strict: bool,
+    /// The metrics recorder, if any.
+    pub(crate) metrics: Option<Arc<dyn DerivationMetrics>>,
     /// The previous stage of the derivation pipeline.
     pub(crate) prev: P,
 }
@@ -53,29 +60,80 @@ where
 {
     /// Create a new [ChannelBank] stage.
     pub fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, channels: HashMap::default(), channel_queue: VecDeque::new(), prev }
+        Self {
+            cfg,
+            channels: HashMap::default(),
+            channel_queue: VecDeque::new(),
+            closing_frames: HashMap::default(),
+            total_size: 0,
+            limits: DerivationLimits::SPEC,
+            strict: false,
+            metrics: None,
+            prev,
+        }
+    }
+
+    /// Sets the [DerivationLimits] enforced by the [ChannelBank].
+    pub const fn with_limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether invalid, late and timed out frames, and timed out channels, fail with a
+    /// critical error instead of being dropped.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording opened and closed channels.
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the channel timeout, in L1 blocks, at the given L1 origin timestamp.
+    ///
+    /// The timeout is reduced at Granite. Per the spec, the timeout that applies to a channel is
+    /// the one active at the current L1 origin, regardless of when the channel was opened.
+    pub fn channel_timeout(&self, origin_timestamp: u64) -> u64 {
+        self.cfg.channel_timeout(origin_timestamp)
+    }
+
+    /// Returns the total size of the buffered frames of all channels.
+    pub const fn size(&self) -> usize {
+        self.total_size
     }
 
-    /// Returns the size of the channel bank by accumulating over all channels.
-    pub fn size(&self) -> usize {
-        self.channels.iter().fold(0, |acc, (_, c)| acc + c.size())
+    /// Removes the channel with the given id, releasing its buffered frames.
+    fn remove_channel(&mut self, id: &ChannelId, reason: ChannelCloseReason) -> Option<Channel> {
+        let channel = self.channels.remove(id)?;
+        self.closing_frames.remove(id);
+        self.total_size = self.total_size.saturating_sub(channel.size());
+        if let Some(metrics) = &self.metrics {
+            metrics.record_channel_closed(reason);
+        }
+        Some(channel)
     }
 
     /// Prunes the Channel bank, until it is below the max channel bank size.
     /// Prunes from the high-priority channel since it failed to be read.
     pub fn prune(&mut self) -> PipelineResult<()> {
-        let mut total_size = self.size();
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
-        let max_channel_bank_size = if self.cfg.is_fjord_active(origin.timestamp) {
-            FJORD_MAX_CHANNEL_BANK_SIZE
-        } else {
-            MAX_CHANNEL_BANK_SIZE
-        };
-        while total_size > max_channel_bank_size {
+        let max_channel_bank_size = self.limits.max_channel_bank_size(&self.cfg, origin.timestamp);
+        while self.total_size > max_channel_bank_size {
             let id =
                 self.channel_queue.pop_front().ok_or(PipelineError::ChannelProviderEmpty.crit())?;
-            let channel = self.channels.remove(&id).ok_or(PipelineError::ChannelNotFound.crit())?;
-            total_size -= channel.size();
+            let channel = self
+                .remove_channel(&id, ChannelCloseReason::Evicted)
+                .ok_or(PipelineError::ChannelNotFound.crit())?;
+            warn!(
+                target: "channel-bank",
+                "Evicting channel (ID: {:#}) of {} bytes, channel bank exceeds {} bytes",
+                id,
+                channel.size(),
+                max_channel_bank_size
+            );
         }
         Ok(())
     }
@@ -83,35 +141,76 @@ where
     /// Adds new L1 data to the channel bank. Should only be called after all data has been read.
     pub fn ingest_frame(&mut self, frame: Frame) -> PipelineResult<()> {
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+        let id = ChannelId::from(frame.id);
 
         // Get the channel for the frame, or create a new one if it doesn't exist.
-        let current_channel = match self.channels.get_mut(&frame.id) {
+        let current_channel = match self.channels.get_mut(&id) {
             Some(c) => c,
             None => {
                 let channel = Channel::new(frame.id, origin);
-                self.channel_queue.push_back(frame.id);
-                self.channels.insert(frame.id, channel);
-                self.channels.get_mut(&frame.id).expect("Channel must be in queue")
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_channel_opened();
+                }
+                self.channel_queue.push_back(id);
+                self.channels.insert(id, channel);
+                self.channels.get_mut(&id).expect("Channel must be in queue")
             }
         };
 
         // Check if the channel is not timed out. If it has, ignore the frame.
-        if current_channel.open_block_number() + self.cfg.channel_timeout(origin.timestamp) <
-            origin.number
-        {
-            warn!(
-                target: "channel-bank",
-                "Channel (ID: {}) timed out", hex::encode(frame.id)
-            );
+        let channel_timeout = self.channel_timeout(origin.timestamp);
+        if current_channel.open_block_number() + channel_timeout < origin.number {
+            if self.strict {
+                return Err(PipelineError::StrictViolation(format!(
+                    "frame #{} for timed out channel {:#}",
+                    frame.number, id
+                ))
+                .crit());
+            }
+            warn!(target: "channel-bank", "Channel (ID: {:#}) timed out", id);
             return Ok(());
         }
 
+        // Ignore data frames that arrive after the channel's closing frame.
+        if let Some(&closing) = self.closing_frames.get(&id) {
+            if frame.number > closing {
+                if self.strict {
+                    return Err(PipelineError::StrictViolation(format!(
+                        "frame #{} after closing frame #{} of channel {:#}",
+                        frame.number, closing, id
+                    ))
+                    .crit());
+                }
+                debug!(
+                    target: "channel-bank",
+                    "Dropping frame #{} after closing frame #{} of channel (ID: {:#})",
+                    frame.number,
+                    closing,
+                    id
+                );
+                return Ok(());
+            }
+        }
+
         // Ingest the frame. If it fails, ignore the frame.
-        let frame_id = frame.id;
+        let (frame_number, is_last) = (frame.number, frame.is_last);
+        let prev_size = current_channel.size();
         if current_channel.add_frame(frame, origin).is_err() {
-            warn!(target: "channel-bank", "Failed to add frame to channel: {:?}", frame_id);
+            if self.strict {
+                return Err(PipelineError::StrictViolation(format!(
+                    "invalid frame #{} for channel {:#}",
+                    frame_number, id
+                ))
+                .crit());
+            }
+            warn!(target: "channel-bank", "Failed to add frame to channel (ID: {:#})", id);
             return Ok(());
         }
+        // A closing frame prunes the channel's frames past it, so the channel may shrink.
+        self.total_size = self.total_size - prev_size + current_channel.size();
+        if is_last {
+            self.closing_frames.insert(id, frame_number);
+        }
 
         self.prune()
     }
@@ -132,13 +231,16 @@ where
         let channel =
             self.channels.get(&first).ok_or(PipelineError::ChannelProviderEmpty.crit())?;
         let origin = self.origin().ok_or(PipelineError::ChannelProviderEmpty.crit())?;
-        if channel.open_block_number() + self.cfg.channel_timeout(origin.timestamp) < origin.number
-        {
-            warn!(
-                target: "channel-bank",
-                "Channel (ID: {}) timed out", hex::encode(first)
-            );
-            self.channels.remove(&first);
+        if channel.open_block_number() + self.channel_timeout(origin.timestamp) < origin.number {
+            if self.strict {
+                return Err(PipelineError::StrictViolation(format!(
+                    "channel {:#} timed out at L1 origin #{}",
+                    first, origin.number
+                ))
+                .crit());
+            }
+            warn!(target: "channel-bank", "Channel (ID: {:#}) timed out", first);
+            self.remove_channel(&first, ChannelCloseReason::TimedOut);
             self.channel_queue.pop_front();
             return Ok(None);
         }
@@ -167,14 +269,14 @@ where
             self.channels.get(&channel_id).ok_or(PipelineError::ChannelProviderEmpty.crit())?;
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
 
-        let timed_out = channel.open_block_number() + self.cfg.channel_timeout(origin.timestamp) <
-            origin.number;
+        let timed_out =
+            channel.open_block_number() + self.channel_timeout(origin.timestamp) < origin.number;
         if timed_out || !channel.is_ready() {
             return Err(PipelineError::Eof.temp());
         }
 
         let frame_data = channel.frame_data();
-        self.channels.remove(&channel_id);
+        self.remove_channel(&channel_id, ChannelCloseReason::Ready);
         self.channel_queue.remove(index);
 
         frame_data.ok_or(PipelineError::ChannelProviderEmpty.crit())
@@ -189,6 +291,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 #[async_trait]
@@ -196,8 +302,15 @@ impl<P> ChannelReaderProvider for ChannelBank<P>
 where
     P: NextFrameProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(name = "channel_bank", level = "debug", skip_all)
+    )]
     async fn next_data(&mut self) -> PipelineResult<Option<Bytes>> {
         match self.read() {
+            Err(e @ PipelineErrorKind::Critical(PipelineError::StrictViolation(_))) => {
+                return Err(e)
+            }
             Err(e) => {
                 if !matches!(e, PipelineErrorKind::Temporary(PipelineError::Eof)) {
                     return Err(PipelineError::ChannelProviderEmpty.crit());
@@ -226,6 +339,17 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("channel-bank", self.origin()));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+        status.channels += self.channels.len();
+        status.channel_bytes += self.size();
+    }
 }
 
 #[async_trait]
@@ -233,11 +357,14 @@ impl<P> SignalReceiver for ChannelBank<P>
 where
     P: NextFrameProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        self.prev.signal(signal).await?;
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let mut receipt = self.prev.signal(signal).await?;
+        receipt.flushed_channels += self.channels.len();
         self.channels.clear();
+        self.closing_frames.clear();
+        self.total_size = 0;
         self.channel_queue = VecDeque::with_capacity(10);
-        Ok(())
+        Ok(receipt)
     }
 }
 
@@ -246,9 +373,9 @@ mod tests {
     use super::*;
     use crate::{
         test_utils::{CollectingLayer, TestNextFrameProvider, TraceStorage},
-        types::ResetSignal,
+        types::{ResetSignal, FJORD_MAX_CHANNEL_BANK_SIZE, MAX_CHANNEL_BANK_SIZE},
     };
-    use alloc::{vec, vec::Vec};
+    use alloc::{string::ToString, vec, vec::Vec};
     use op_alloy_genesis::{BASE_MAINNET_CONFIG, OP_MAINNET_CONFIG};
     use tracing::Level;
     use tracing_subscriber::layer::SubscriberExt;
@@ -258,7 +385,7 @@ mod tests {
         let mock = TestNextFrameProvider::new(vec![]);
         let cfg = Arc::new(RollupConfig::default());
         let mut channel_bank = ChannelBank::new(cfg, mock);
-        channel_bank.channel_queue.push_back([0xFF; 16]);
+        channel_bank.channel_queue.push_back(ChannelId::new([0xFF; 16]));
         let err = channel_bank.try_read_channel_at_index(0).unwrap_err();
         assert_eq!(err, PipelineError::ChannelProviderEmpty.crit());
     }
@@ -269,8 +396,9 @@ mod tests {
         mock.block_info = None;
         let cfg = Arc::new(RollupConfig::default());
         let mut channel_bank = ChannelBank::new(cfg, mock);
-        channel_bank.channel_queue.push_back([0xFF; 16]);
-        channel_bank.channels.insert([0xFF; 16], Channel::new([0xFF; 16], BlockInfo::default()));
+        channel_bank.channel_queue.push_back(ChannelId::new([0xFF; 16]));
+        let channel = Channel::new([0xFF; 16], BlockInfo::default());
+        channel_bank.channels.insert(ChannelId::new([0xFF; 16]), channel);
         let err = channel_bank.try_read_channel_at_index(0).unwrap_err();
         assert_eq!(err, PipelineError::MissingOrigin.crit());
     }
@@ -281,19 +409,61 @@ mod tests {
         mock.block_info = Some(BlockInfo { number: 10, ..Default::default() });
         let cfg = Arc::new(RollupConfig::default());
         let mut channel_bank = ChannelBank::new(cfg, mock);
-        channel_bank.channel_queue.push_back([0xFF; 16]);
-        channel_bank.channels.insert([0xFF; 16], Channel::new([0xFF; 16], BlockInfo::default()));
+        channel_bank.channel_queue.push_back(ChannelId::new([0xFF; 16]));
+        let channel = Channel::new([0xFF; 16], BlockInfo::default());
+        channel_bank.channels.insert(ChannelId::new([0xFF; 16]), channel);
         let err = channel_bank.try_read_channel_at_index(0).unwrap_err();
         assert_eq!(err, PipelineError::Eof.temp());
     }
 
+    #[test]
+    fn test_channel_timeout_granite() {
+        let cfg = RollupConfig {
+            channel_timeout: 300,
+            granite_channel_timeout: 50,
+            granite_time: Some(100),
+            ..Default::default()
+        };
+        let channel_bank = ChannelBank::new(Arc::new(cfg), TestNextFrameProvider::new(vec![]));
+        assert_eq!(channel_bank.channel_timeout(99), 300);
+        assert_eq!(channel_bank.channel_timeout(100), 50);
+    }
+
+    #[test]
+    fn test_try_read_channel_at_index_timeout_across_granite() {
+        let cfg = Arc::new(RollupConfig {
+            channel_timeout: 300,
+            granite_channel_timeout: 50,
+            granite_time: Some(100),
+            ..Default::default()
+        });
+        let id = [0xFF; 16];
+        let opened = BlockInfo { number: 10, timestamp: 90, ..Default::default() };
+
+        // The channel is opened just before Granite. The timeout is selected by the origin it is
+        // read at: 300 blocks before the activation, and 50 blocks from the activation on.
+        for (read_timestamp, number, timed_out) in
+            [(99, 70, false), (100, 70, true), (100, 60, false), (99, 311, true)]
+        {
+            let mut mock = TestNextFrameProvider::new(vec![]);
+            mock.block_info = Some(BlockInfo { number, timestamp: read_timestamp, ..opened });
+            let mut channel_bank = ChannelBank::new(cfg.clone(), mock);
+            let mut channel = Channel::new(id, opened);
+            let frame = Frame { id, number: 0, data: vec![0xDD], is_last: true };
+            channel.add_frame(frame, opened).unwrap();
+            channel_bank.channel_queue.push_back(ChannelId::new(id));
+            channel_bank.channels.insert(ChannelId::new(id), channel);
+            assert_eq!(channel_bank.try_read_channel_at_index(0).is_err(), timed_out);
+        }
+    }
+
     #[test]
     fn test_try_read_channel_at_index() {
         let mock = TestNextFrameProvider::new(vec![]);
         let cfg = Arc::new(RollupConfig::default());
         let mut channel_bank = ChannelBank::new(cfg, mock);
-        let id: ChannelId = [0xFF; 16];
-        channel_bank.channel_queue.push_back(id);
+        let id = [0xFF; 16];
+        channel_bank.channel_queue.push_back(ChannelId::new(id));
         let mut channel = Channel::new(id, BlockInfo::default());
         channel
             .add_frame(
@@ -314,7 +484,7 @@ mod tests {
             )
             .unwrap();
         assert!(channel.is_ready());
-        channel_bank.channels.insert([0xFF; 16], channel);
+        channel_bank.channels.insert(ChannelId::new([0xFF; 16]), channel);
         let frame_data = channel_bank.try_read_channel_at_index(0).unwrap();
         assert_eq!(
             frame_data,
@@ -327,8 +497,8 @@ mod tests {
         let mock = TestNextFrameProvider::new(vec![]);
         let cfg = Arc::new(RollupConfig::default());
         let mut channel_bank = ChannelBank::new(cfg, mock);
-        let id: ChannelId = [0xFF; 16];
-        channel_bank.channel_queue.push_back(id);
+        let id = [0xFF; 16];
+        channel_bank.channel_queue.push_back(ChannelId::new(id));
         let mut channel = Channel::new(id, BlockInfo::default());
         channel
             .add_frame(
@@ -349,7 +519,7 @@ mod tests {
             )
             .unwrap();
         assert!(channel.is_ready());
-        channel_bank.channels.insert([0xFF; 16], channel);
+        channel_bank.channels.insert(ChannelId::new([0xFF; 16]), channel);
         let frame_data = channel_bank.read().unwrap();
         assert_eq!(
             frame_data,
@@ -362,8 +532,8 @@ mod tests {
         let mock = TestNextFrameProvider::new(vec![]);
         let cfg = Arc::new(RollupConfig { canyon_time: Some(0), ..Default::default() });
         let mut channel_bank = ChannelBank::new(cfg, mock);
-        let id: ChannelId = [0xFF; 16];
-        channel_bank.channel_queue.push_back(id);
+        let id = [0xFF; 16];
+        channel_bank.channel_queue.push_back(ChannelId::new(id));
         let mut channel = Channel::new(id, BlockInfo::default());
         channel
             .add_frame(
@@ -384,7 +554,7 @@ mod tests {
             )
             .unwrap();
         assert!(channel.is_ready());
-        channel_bank.channels.insert([0xFF; 16], channel);
+        channel_bank.channels.insert(ChannelId::new([0xFF; 16]), channel);
         let frame_data = channel_bank.read().unwrap();
         assert_eq!(
             frame_data,
@@ -408,8 +578,8 @@ mod tests {
         let mock = TestNextFrameProvider::new(vec![]);
         let cfg = Arc::new(RollupConfig::default());
         let mut channel_bank = ChannelBank::new(cfg, mock);
-        channel_bank.channels.insert([0xFF; 16], Channel::default());
-        channel_bank.channel_queue.push_back([0xFF; 16]);
+        channel_bank.channels.insert(ChannelId::new([0xFF; 16]), Channel::default());
+        channel_bank.channel_queue.push_back(ChannelId::new([0xFF; 16]));
         assert!(!channel_bank.prev.reset);
         channel_bank.signal(ResetSignal::default().signal()).await.unwrap();
         assert_eq!(channel_bank.channels.len(), 0);
@@ -417,6 +587,73 @@ mod tests {
         assert!(channel_bank.prev.reset);
     }
 
+    #[test]
+    fn test_ingest_frame_after_closing_frame_dropped() {
+        let trace_store: TraceStorage = Default::default();
+        let layer = CollectingLayer::new(trace_store.clone());
+        let subscriber = tracing_subscriber::Registry::default().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mock = TestNextFrameProvider::new(vec![]);
+        let cfg = Arc::new(RollupConfig { channel_timeout: 10, ..Default::default() });
+        let mut channel_bank = ChannelBank::new(cfg, mock);
+        channel_bank.ingest_frame(crate::frame!(0xFF, 1, vec![0xBB; 10], true)).unwrap();
+        assert_eq!(channel_bank.closing_frames.get(&ChannelId::new([0xFF; 16])), Some(&1));
+
+        // A data frame after the closing frame is ignored.
+        channel_bank.ingest_frame(crate::frame!(0xFF, 2, vec![0xCC; 10], false)).unwrap();
+        let logs = trace_store.get_by_level(Level::DEBUG);
+        assert!(logs.iter().any(|l| l.contains("Dropping frame #2 after closing frame #1")));
+        assert!(trace_store.get_by_level(Level::WARN).is_empty());
+
+        // The channel completes without the late frame.
+        channel_bank.ingest_frame(crate::frame!(0xFF, 0, vec![0xAA; 10], false)).unwrap();
+        let data = channel_bank.read().unwrap().unwrap();
+        assert_eq!(data.to_vec(), [[0xAA; 10], [0xBB; 10]].concat());
+        assert!(channel_bank.closing_frames.is_empty());
+    }
+
+    #[test]
+    fn test_ingest_late_closing_frame_tracks_size() {
+        let mock = TestNextFrameProvider::new(vec![]);
+        let cfg = Arc::new(RollupConfig { channel_timeout: 10, ..Default::default() });
+        let mut channel_bank = ChannelBank::new(cfg, mock);
+        channel_bank.ingest_frame(crate::frame!(0xFF, 0, vec![0xAA; 10], false)).unwrap();
+        channel_bank.ingest_frame(crate::frame!(0xFF, 5, vec![0xCC; 10], false)).unwrap();
+
+        // The closing frame #2 arrives after frame #5, which the channel prunes.
+        channel_bank.ingest_frame(crate::frame!(0xFF, 2, vec![0xBB; 10], true)).unwrap();
+        let channel = channel_bank.channels.get(&ChannelId::new([0xFF; 16])).unwrap();
+        assert_eq!(channel_bank.size(), channel.size());
+    }
+
+    #[test]
+    fn test_ingest_frame_after_closing_frame_strict() {
+        let mock = TestNextFrameProvider::new(vec![]);
+        let cfg = Arc::new(RollupConfig { channel_timeout: 10, ..Default::default() });
+        let mut channel_bank = ChannelBank::new(cfg, mock).with_strict(true);
+        channel_bank.ingest_frame(crate::frame!(0xFF, 1, vec![0xBB; 10], true)).unwrap();
+        let err = channel_bank.ingest_frame(crate::frame!(0xFF, 2, vec![0xCC; 10], false));
+        assert!(matches!(
+            err.unwrap_err(),
+            PipelineErrorKind::Critical(PipelineError::StrictViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_timed_out_channel_strict() {
+        let mock = TestNextFrameProvider::new(vec![]);
+        let cfg = Arc::new(RollupConfig { channel_timeout: 10, ..Default::default() });
+        let mut channel_bank = ChannelBank::new(cfg, mock).with_strict(true);
+        channel_bank.ingest_frame(crate::frame!(0xFF, 0, vec![0xDD; 50], false)).unwrap();
+
+        channel_bank.prev.block_info = Some(BlockInfo { number: 11, ..Default::default() });
+        assert!(matches!(
+            channel_bank.read().unwrap_err(),
+            PipelineErrorKind::Critical(PipelineError::StrictViolation(_))
+        ));
+    }
+
     #[test]
     fn test_ingest_invalid_frame() {
         let trace_store: TraceStorage = Default::default();
@@ -465,6 +702,82 @@ mod tests {
         assert_eq!(channel_bank.size(), current_size);
     }
 
+    #[test]
+    fn test_ingest_and_prune_channel_bank_with_limits_override() {
+        let mock = TestNextFrameProvider::new(vec![]);
+        let cfg = Arc::new(RollupConfig::default());
+        let limits = DerivationLimits { channel_bank_size: 300, ..Default::default() };
+        let mut channel_bank = ChannelBank::new(cfg, mock).with_limits(limits);
+        for id in 0..3 {
+            channel_bank.ingest_frame(crate::frame!(id, 0, vec![0xDD; 50], false)).unwrap();
+            assert!(channel_bank.size() <= 300);
+        }
+        // Each channel is larger than half of the limit, so only the latest channel is retained.
+        assert_eq!(channel_bank.channels.len(), 1);
+        assert_eq!(channel_bank.channel_queue, [ChannelId::new([2; 16])]);
+    }
+
+    #[test]
+    fn test_prune_evicts_oldest_channel() {
+        let trace_store: TraceStorage = Default::default();
+        let layer = CollectingLayer::new(trace_store.clone());
+        let subscriber = tracing_subscriber::Registry::default().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mock = TestNextFrameProvider::new(vec![]);
+        let limits = DerivationLimits { channel_bank_size: 500, ..Default::default() };
+        let mut channel_bank =
+            ChannelBank::new(Arc::new(RollupConfig::default()), mock).with_limits(limits);
+        let channel_size = 150 + op_alloy_protocol::FRAME_OVERHEAD;
+        for id in 1..=2 {
+            channel_bank.ingest_frame(crate::frame!(id, 0, vec![0xDD; 150], false)).unwrap();
+        }
+        assert_eq!(channel_bank.size(), 2 * channel_size);
+
+        // An oversized frame pushes the bank over the limit, evicting the oldest channel.
+        channel_bank.ingest_frame(crate::frame!(3, 0, vec![0xDD; 150], false)).unwrap();
+        assert_eq!(channel_bank.size(), 2 * channel_size);
+        assert_eq!(channel_bank.channel_queue, [2, 3].map(|b| ChannelId::new([b; 16])));
+        assert!(!channel_bank.channels.contains_key(&ChannelId::new([1; 16])));
+
+        let warnings = trace_store.get_by_level(Level::WARN);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(&ChannelId::new([1; 16]).to_string()));
+
+        // Reading a channel releases its frames.
+        let frame = crate::frame!(2, 1, vec![0xDD; 10], true);
+        channel_bank.ingest_frame(frame).unwrap();
+        assert_eq!(channel_bank.read().unwrap(), Some(Bytes::from(vec![0xDD; 160])));
+        assert_eq!(channel_bank.size(), channel_size);
+    }
+
+    #[test]
+    fn test_prune_channel_bank_across_fjord() {
+        let mock = TestNextFrameProvider::new(vec![]);
+        let cfg = RollupConfig { fjord_time: Some(10), ..Default::default() };
+        let limits = DerivationLimits {
+            channel_bank_size: 300,
+            fjord_channel_bank_size: 600,
+            ..Default::default()
+        };
+        let mut channel_bank = ChannelBank::new(Arc::new(cfg), mock).with_limits(limits);
+
+        // Before Fjord, only a single channel fits in the bank.
+        for id in 1..=2 {
+            channel_bank.ingest_frame(crate::frame!(id, 0, vec![0xDD; 150], false)).unwrap();
+        }
+        assert_eq!(channel_bank.channel_queue, [ChannelId::new([2; 16])]);
+
+        // After Fjord, the larger limit applies.
+        channel_bank.prev.block_info =
+            Some(BlockInfo { number: 1, timestamp: 10, ..Default::default() });
+        for id in 3..=5 {
+            channel_bank.ingest_frame(crate::frame!(id, 0, vec![0xDD; 150], false)).unwrap();
+        }
+        assert_eq!(channel_bank.channel_queue, [3, 4, 5].map(|b| ChannelId::new([b; 16])));
+        assert!(channel_bank.size() <= 600);
+    }
+
     #[test]
     fn test_ingest_and_prune_channel_bank_fjord() {
         let mut frames = crate::frames!(0xFF, 0, vec![0xDD; 50], 100000);
@@ -3,10 +3,10 @@
 use super::{ChannelAssembler, ChannelBank, ChannelReaderProvider, NextFrameProvider};
 use crate::{
     errors::PipelineError,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{DerivationMetrics, OriginAdvancer, OriginProvider, SignalReceiver},
+    types::{DerivationLimits, PipelineResult, PipelineStatus, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use core::fmt::Debug;
@@ -40,6 +40,12 @@ where
     ///
     /// Must be [None] if `prev` or `channel_bank` is [Some].
     channel_assembler: Option<ChannelAssembler<P>>,
+    /// The derivation resource limits, passed on to the active stage.
+    limits: DerivationLimits,
+    /// Whether the active stage runs in strict mode.
+    strict: bool,
+    /// The metrics recorder passed on to the active stage, if any.
+    metrics: Option<Arc<dyn DerivationMetrics>>,
 }
 
 impl<P> ChannelProvider<P>
@@ -48,7 +54,53 @@ where
 {
     /// Creates a new [ChannelProvider] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev: Some(prev), channel_bank: None, channel_assembler: None }
+        Self {
+            cfg,
+            prev: Some(prev),
+            channel_bank: None,
+            channel_assembler: None,
+            limits: DerivationLimits::SPEC,
+            strict: false,
+            metrics: None,
+        }
+    }
+
+    /// Sets the [DerivationLimits] enforced by the [ChannelBank] and [ChannelAssembler].
+    pub const fn with_limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether the [ChannelBank] and [ChannelAssembler] fail with a critical error on
+    /// invalid frames instead of dropping them.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording the channels of the [ChannelBank] and
+    /// [ChannelAssembler].
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Creates a [ChannelBank] on top of `prev`, configured like the provider.
+    fn new_channel_bank(&self, prev: P) -> ChannelBank<P> {
+        let mut bank = ChannelBank::new(self.cfg.clone(), prev)
+            .with_limits(self.limits)
+            .with_strict(self.strict);
+        bank.metrics = self.metrics.clone();
+        bank
+    }
+
+    /// Creates a [ChannelAssembler] on top of `prev`, configured like the provider.
+    fn new_channel_assembler(&self, prev: P) -> ChannelAssembler<P> {
+        let mut assembler = ChannelAssembler::new(self.cfg.clone(), prev)
+            .with_limits(self.limits)
+            .with_strict(self.strict);
+        assembler.metrics = self.metrics.clone();
+        assembler
     }
 
     /// Attempts to update the active stage of the mux.
@@ -58,16 +110,15 @@ where
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
             if self.cfg.is_holocene_active(origin.timestamp) {
-                self.channel_assembler = Some(ChannelAssembler::new(self.cfg.clone(), prev));
+                self.channel_assembler = Some(self.new_channel_assembler(prev));
             } else {
-                self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), prev));
+                self.channel_bank = Some(self.new_channel_bank(prev));
             }
         } else if self.channel_bank.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
             // If the channel bank is active and Holocene is also active, transition to the channel
             // assembler.
             let channel_bank = self.channel_bank.take().expect("Must have channel bank");
-            self.channel_assembler =
-                Some(ChannelAssembler::new(self.cfg.clone(), channel_bank.prev));
+            self.channel_assembler = Some(self.new_channel_assembler(channel_bank.prev));
         } else if self.channel_assembler.is_some() && !self.cfg.is_holocene_active(origin.timestamp)
         {
             // If the channel assembler is active, and Holocene is not active, it indicates an L1
@@ -75,7 +126,7 @@ where
             // until Holocene re-activates.
             let channel_assembler =
                 self.channel_assembler.take().expect("Must have channel assembler");
-            self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), channel_assembler.prev));
+            self.channel_bank = Some(self.new_channel_bank(channel_assembler.prev));
         }
         Ok(())
     }
@@ -97,6 +148,18 @@ where
             Err(PipelineError::NotEnoughData.temp())
         }
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.attempt_update()?;
+
+        if let Some(channel_assembler) = self.channel_assembler.as_mut() {
+            channel_assembler.advance_origin_to(target).await
+        } else if let Some(channel_bank) = self.channel_bank.as_mut() {
+            channel_bank.advance_origin_to(target).await
+        } else {
+            Err(PipelineError::NotEnoughData.temp())
+        }
+    }
 }
 
 impl<P> OriginProvider for ChannelProvider<P>
@@ -114,6 +177,26 @@ where
             |channel_assembler| channel_assembler.origin(),
         )
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        if let Some(channel_assembler) = self.channel_assembler.as_ref() {
+            channel_assembler.collect_stage_origins(origins);
+        } else if let Some(channel_bank) = self.channel_bank.as_ref() {
+            channel_bank.collect_stage_origins(origins);
+        } else if let Some(prev) = self.prev.as_ref() {
+            prev.collect_stage_origins(origins);
+        }
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        if let Some(channel_assembler) = self.channel_assembler.as_ref() {
+            channel_assembler.collect_status(status);
+        } else if let Some(channel_bank) = self.channel_bank.as_ref() {
+            channel_bank.collect_status(status);
+        } else if let Some(prev) = self.prev.as_ref() {
+            prev.collect_status(status);
+        }
+    }
 }
 
 #[async_trait]
@@ -121,7 +204,7 @@ impl<P> SignalReceiver for ChannelProvider<P>
 where
     P: NextFrameProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         self.attempt_update()?;
 
         if let Some(channel_assembler) = self.channel_assembler.as_mut() {
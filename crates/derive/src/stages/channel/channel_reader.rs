@@ -1,32 +1,21 @@
 //! This module contains the `ChannelReader` struct.
 
+use super::compression::decompressor;
 use crate::{
-    errors::PipelineError,
-    stages::{decompress_brotli, BatchStreamProvider},
+    errors::{BatchDecompressionError, PipelineError},
+    stages::BatchStreamProvider,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{DerivationLimits, PipelineResult, PipelineStatus, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
 use alloy_primitives::Bytes;
 use alloy_rlp::Decodable;
 use async_trait::async_trait;
 use core::fmt::Debug;
-use miniz_oxide::inflate::decompress_to_vec_zlib;
-use op_alloy_genesis::{
-    RollupConfig, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD,
-};
+use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::{Batch, BlockInfo};
 use tracing::{debug, error, warn};
 
-/// ZLIB Deflate Compression Method.
-pub(crate) const ZLIB_DEFLATE_COMPRESSION_METHOD: u8 = 8;
-
-/// ZLIB Reserved Compression Info.
-pub(crate) const ZLIB_RESERVED_COMPRESSION_METHOD: u8 = 15;
-
-/// Brotili Compression Channel Version.
-pub(crate) const CHANNEL_VERSION_BROTLI: u8 = 1;
-
 /// The [ChannelReader] provider trait.
 #[async_trait]
 pub trait ChannelReaderProvider {
@@ -57,6 +46,14 @@ where
     next_batch: Option<BatchReader>,
     /// The rollup coonfiguration.
     cfg: Arc<RollupConfig>,
+    /// The derivation resource limits.
+    limits: DerivationLimits,
+    /// Whether spec-violation drops are surfaced as critical errors.
+    strict: bool,
+    /// The number of channels discarded because they held no batches.
+    empty_channels: u64,
+    /// The number of channels discarded because they failed to decompress within the limits.
+    undecompressable_channels: u64,
 }
 
 impl<P> ChannelReader<P>
@@ -65,7 +62,38 @@ where
 {
     /// Create a new [ChannelReader] stage.
     pub const fn new(prev: P, cfg: Arc<RollupConfig>) -> Self {
-        Self { prev, next_batch: None, cfg }
+        Self {
+            prev,
+            next_batch: None,
+            cfg,
+            limits: DerivationLimits::SPEC,
+            strict: false,
+            empty_channels: 0,
+            undecompressable_channels: 0,
+        }
+    }
+
+    /// Sets the [DerivationLimits] enforced by the [ChannelReader].
+    pub const fn with_limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether malformed channels fail with a critical error instead of being dropped.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns the number of channels discarded because they held no batches.
+    pub const fn empty_channels(&self) -> u64 {
+        self.empty_channels
+    }
+
+    /// Returns the number of channels discarded because their data failed to decompress, or
+    /// decompressed to more than the maximum number of RLP bytes per channel.
+    pub const fn undecompressable_channels(&self) -> u64 {
+        self.undecompressable_channels
     }
 
     /// Creates the batch reader from available channel data.
@@ -75,14 +103,11 @@ where
                 self.prev.next_data().await?.ok_or(PipelineError::ChannelReaderEmpty.temp())?;
 
             let origin = self.prev.origin().ok_or(PipelineError::MissingOrigin.crit())?;
-            let max_rlp_bytes_per_channel = if self.cfg.is_fjord_active(origin.timestamp) {
-                MAX_RLP_BYTES_PER_CHANNEL_FJORD
-            } else {
-                MAX_RLP_BYTES_PER_CHANNEL_BEDROCK
-            };
+            let max_rlp_bytes_per_channel =
+                self.limits.max_rlp_bytes_per_channel(&self.cfg, origin.timestamp);
 
             self.next_batch =
-                Some(BatchReader::new(&channel[..], max_rlp_bytes_per_channel as usize));
+                Some(BatchReader::new(&channel[..], max_rlp_bytes_per_channel, origin.timestamp));
         }
         Ok(())
     }
@@ -102,6 +127,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 #[async_trait]
@@ -120,6 +149,10 @@ where
         self.next_channel();
     }
 
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(name = "channel_reader", level = "debug", skip_all)
+    )]
     async fn next_batch(&mut self) -> PipelineResult<Batch> {
         if let Err(e) = self.set_batch_reader().await {
             debug!(target: "channel-reader", "Failed to set batch reader: {:?}", e);
@@ -133,9 +166,59 @@ where
             .next_batch(self.cfg.as_ref())
             .ok_or(PipelineError::NotEnoughData.temp())
         {
+            Ok(Batch::Span(span))
+                if span.batches.len() as u64 > self.limits.span_batch_elements =>
+            {
+                if self.strict {
+                    return Err(PipelineError::StrictViolation(format!(
+                        "span batch with {} elements exceeds the limit of {}",
+                        span.batches.len(),
+                        self.limits.span_batch_elements
+                    ))
+                    .crit());
+                }
+                warn!(
+                    target: "channel-reader",
+                    "Span batch with {} elements exceeds the limit of {}, dropping channel",
+                    span.batches.len(),
+                    self.limits.span_batch_elements
+                );
+                self.next_channel();
+                Err(PipelineError::NotEnoughData.temp())
+            }
             Ok(batch) => Ok(batch),
             Err(e) => {
+                // The batch reader stops at both the end of the channel and the first malformed
+                // batch, so only a reader with unread data indicates a violation. Channels without
+                // any batches are discarded without being treated as malformed.
+                let mut malformed = false;
+                if let Some(reader) = self.next_batch.as_ref() {
+                    if let Some(err) = reader.decompression_error() {
+                        warn!(target: "channel-reader", "Dropping channel: {}", err);
+                        self.undecompressable_channels += 1;
+                        malformed = true;
+                    } else if reader.is_batchless() {
+                        debug!(target: "channel-reader", "Discarding channel without batches");
+                        self.empty_channels += 1;
+                    } else if !reader.is_exhausted() {
+                        if reader.batches_read() > 0 {
+                            debug!(
+                                target: "channel-reader",
+                                "Dropping trailing channel data after {} batches",
+                                reader.batches_read()
+                            );
+                        }
+                        malformed = true;
+                    }
+                }
                 self.next_channel();
+                if self.strict && malformed {
+                    let origin = self.prev.origin().map(|o| o.number).unwrap_or_default();
+                    return Err(PipelineError::StrictViolation(format!(
+                        "malformed channel data at L1 origin #{origin}"
+                    ))
+                    .crit());
+                }
                 Err(e)
             }
         }
@@ -149,6 +232,15 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("channel-reader", self.origin()));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+    }
 }
 
 #[async_trait]
@@ -156,24 +248,26 @@ impl<P> SignalReceiver for ChannelReader<P>
 where
     P: ChannelReaderProvider + OriginAdvancer + OriginProvider + SignalReceiver + Debug + Send,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        match signal {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let mut receipt = match signal {
             Signal::FlushChannel => {
                 // Drop the current in-progress channel.
                 warn!(target: "channel-reader", "Flushed channel");
-                self.next_batch = None;
-            }
-            s => {
-                self.prev.signal(s).await?;
-                self.next_channel();
+                SignalReceipt::default()
             }
+            s => self.prev.signal(s).await?,
+        };
+        if self.next_batch.is_some() {
+            receipt.flushed_channels += 1;
         }
-        Ok(())
+        self.next_channel();
+        Ok(receipt)
     }
 }
 
 /// Batch Reader provides a function that iteratively consumes batches from the reader.
-/// The L1Inclusion block is also provided at creation time.
+/// The timestamp of the L1 origin the channel is read at is provided at creation time, and gates
+/// the compression algorithm of the channel.
 /// Warning: the batch reader can read every batch-type.
 /// The caller of the batch-reader should filter the results.
 #[derive(Debug)]
@@ -186,11 +280,20 @@ pub(crate) struct BatchReader {
     cursor: usize,
     /// The maximum RLP bytes per channel.
     max_rlp_bytes_per_channel: usize,
+    /// Whether the channel data was decompressed successfully.
+    decompressed_ok: bool,
+    /// The number of batches read from the channel.
+    batches_read: usize,
+    /// The timestamp of the L1 origin the channel is read at.
+    l1_timestamp: u64,
+    /// The error the channel data failed to decompress with, if any.
+    decompression_error: Option<BatchDecompressionError>,
 }
 
 impl BatchReader {
-    /// Creates a new [BatchReader] from the given data and max decompressed RLP bytes per channel.
-    pub(crate) fn new<T>(data: T, max_rlp_bytes_per_channel: usize) -> Self
+    /// Creates a new [BatchReader] from the given data, max decompressed RLP bytes per channel, and
+    /// the timestamp of the L1 origin the channel is read at.
+    pub(crate) fn new<T>(data: T, max_rlp_bytes_per_channel: usize, l1_timestamp: u64) -> Self
     where
         T: Into<Vec<u8>>,
     {
@@ -199,39 +302,80 @@ impl BatchReader {
             decompressed: Vec::new(),
             cursor: 0,
             max_rlp_bytes_per_channel,
+            decompressed_ok: false,
+            batches_read: 0,
+            l1_timestamp,
+            decompression_error: None,
         }
     }
 
+    /// Returns the number of batches read from the channel.
+    pub(crate) const fn batches_read(&self) -> usize {
+        self.batches_read
+    }
+
+    /// Returns the error the channel data failed to decompress with, if any.
+    pub(crate) const fn decompression_error(&self) -> Option<&BatchDecompressionError> {
+        self.decompression_error.as_ref()
+    }
+
+    /// Returns whether all batches were read from the decompressed channel data.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.decompressed_ok && self.cursor >= self.decompressed.len()
+    }
+
+    /// Returns whether the channel holds no batches at all: it is empty, or its RLP stream only
+    /// contains empty byte strings.
+    pub(crate) fn is_batchless(&self) -> bool {
+        if !self.decompressed_ok || self.batches_read > 0 {
+            return false;
+        }
+        let mut buf = &self.decompressed[self.cursor..];
+        while !buf.is_empty() {
+            match Bytes::decode(&mut buf) {
+                Ok(bytes) if bytes.is_empty() => continue,
+                _ => return false,
+            }
+        }
+        true
+    }
+
     /// Pulls out the next batch from the reader.
     pub(crate) fn next_batch(&mut self, cfg: &RollupConfig) -> Option<Batch> {
         // If the data is not already decompressed, decompress it.
-        let mut brotli_used = false;
-
         if let Some(data) = self.data.take() {
             // Peek at the data to determine the compression type.
             if data.is_empty() {
-                warn!(target: "batch-reader", "Data is too short to determine compression type, skipping batch");
+                debug!(target: "batch-reader", "Channel data is empty");
+                self.decompressed_ok = true;
                 return None;
             }
 
-            let compression_type = data[0];
-            if (compression_type & 0x0F) == ZLIB_DEFLATE_COMPRESSION_METHOD ||
-                (compression_type & 0x0F) == ZLIB_RESERVED_COMPRESSION_METHOD
-            {
-                self.decompressed = decompress_to_vec_zlib(&data).ok()?;
+            let Some(decompressor) = decompressor(data[0]) else {
+                error!(target: "batch-reader", "Unknown channel version: {:x}, dropping channel", data[0]);
+                return None;
+            };
 
-                // Check the size of the decompressed channel RLP.
-                if self.decompressed.len() > self.max_rlp_bytes_per_channel {
+            // Confirm that the compression algorithm is active at the L1 origin of the channel.
+            if !(decompressor.is_active)(cfg, self.l1_timestamp) {
+                warn!(
+                    target: "batch-reader",
+                    "{} compression used before activation, dropping channel", decompressor.name
+                );
+                self.decompression_error =
+                    Some(BatchDecompressionError::InactiveCompression(decompressor.name));
+                return None;
+            }
+
+            // Decompression fails if the channel RLP exceeds the maximum size.
+            match (decompressor.decompress)(&data, self.max_rlp_bytes_per_channel) {
+                Ok(decompressed) => self.decompressed = decompressed,
+                Err(e) => {
+                    self.decompression_error = Some(e);
                     return None;
                 }
-            } else if compression_type == CHANNEL_VERSION_BROTLI {
-                brotli_used = true;
-                self.decompressed =
-                    decompress_brotli(&data[1..], self.max_rlp_bytes_per_channel).ok()?;
-            } else {
-                error!(target: "batch-reader", "Unsupported compression type: {:x}, skipping batch", compression_type);
-                return None;
             }
+            self.decompressed_ok = true;
         }
 
         // Decompress and RLP decode the batch data, before finally decoding the batch itself.
@@ -242,14 +386,9 @@ impl BatchReader {
             return None;
         };
 
-        // Confirm that brotli decompression was performed *after* the Fjord hardfork.
-        if brotli_used && !cfg.is_fjord_active(batch.timestamp()) {
-            warn!(target: "batch-reader", "Brotli compression used before Fjord hardfork, skipping batch");
-            return None;
-        }
-
         // Advance the cursor on the reader.
         self.cursor = self.decompressed.len() - decompressed_reader.len();
+        self.batches_read += 1;
         Some(batch)
     }
 }
@@ -258,9 +397,14 @@ impl BatchReader {
 mod test {
     use super::*;
     use crate::{
-        errors::PipelineErrorKind, test_utils::TestChannelReaderProvider, types::ResetSignal,
+        errors::PipelineErrorKind, stages::channel::compression::CHANNEL_VERSION_BROTLI,
+        test_utils::TestChannelReaderProvider, types::ResetSignal,
     };
     use alloc::vec;
+    use alloy_rlp::Encodable;
+    use miniz_oxide::{deflate::compress_to_vec_zlib, inflate::decompress_to_vec_zlib};
+    use op_alloy_genesis::{MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD};
+    use op_alloy_protocol::SingleBatch;
 
     fn new_compressed_batch_data() -> Bytes {
         let file_contents =
@@ -277,9 +421,15 @@ mod test {
         reader.next_batch = Some(BatchReader::new(
             new_compressed_batch_data(),
             MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize,
+            0,
         ));
-        reader.signal(Signal::FlushChannel).await.unwrap();
+        let receipt = reader.signal(Signal::FlushChannel).await.unwrap();
+        assert_eq!(receipt.flushed_channels, 1);
         assert!(reader.next_batch.is_none());
+
+        // Without a channel in progress, nothing is flushed.
+        let receipt = reader.signal(Signal::FlushChannel).await.unwrap();
+        assert_eq!(receipt.flushed_channels, 0);
     }
 
     #[tokio::test]
@@ -289,6 +439,7 @@ mod test {
         reader.next_batch = Some(BatchReader::new(
             vec![0x00, 0x01, 0x02],
             MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize,
+            0,
         ));
         assert!(!reader.prev.reset);
         reader.signal(ResetSignal::default().signal()).await.unwrap();
@@ -325,6 +476,202 @@ mod test {
         assert!(reader.next_batch.is_none());
     }
 
+    #[tokio::test]
+    async fn test_next_batch_strict_malformed_channel() {
+        for strict in [false, true] {
+            let mut data = new_compressed_batch_data();
+            let half = data.len() / 2;
+            data.truncate(half);
+            let mock = TestChannelReaderProvider::new(vec![Ok(Some(data))]);
+            let mut reader =
+                ChannelReader::new(mock, Arc::new(RollupConfig::default())).with_strict(strict);
+            let err = reader.next_batch().await.unwrap_err();
+            if strict {
+                assert!(matches!(
+                    err,
+                    PipelineErrorKind::Critical(PipelineError::StrictViolation(_))
+                ));
+            } else {
+                assert_eq!(err, PipelineError::NotEnoughData.temp());
+            }
+            assert!(reader.next_batch.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_strict_end_of_channel() {
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(new_compressed_batch_data()))]);
+        let mut reader =
+            ChannelReader::new(mock, Arc::new(RollupConfig::default())).with_strict(true);
+        assert!(reader.next_batch().await.is_ok());
+        // Reaching the end of a well-formed channel is not a violation.
+        assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+    }
+
+    /// Compresses the RLP stream of a channel, as the batcher's channel-out would.
+    fn channel_out(rlp: &[u8]) -> Bytes {
+        compress_to_vec_zlib(rlp, 9).into()
+    }
+
+    /// Encodes the RLP stream of a channel as a brotli channel, storing the stream in a single
+    /// uncompressed meta-block.
+    fn brotli_channel_out(rlp: &[u8]) -> Bytes {
+        assert!(!rlp.is_empty() && rlp.len() <= u16::MAX as usize);
+        // WBITS = 16, ISLAST = 0, MNIBBLES = 4, MLEN - 1 and ISUNCOMPRESSED = 1, padded to a byte.
+        let header = ((rlp.len() as u32 - 1) << 4) | (1 << 20);
+        let mut data = vec![CHANNEL_VERSION_BROTLI];
+        data.extend_from_slice(&header.to_le_bytes()[..3]);
+        data.extend_from_slice(rlp);
+        // A last, empty meta-block: ISLAST = 1 and ISLASTEMPTY = 1.
+        data.push(0x03);
+        data.into()
+    }
+
+    fn single_batch_rlp(timestamp: u64) -> Vec<u8> {
+        let mut data = vec![0u8];
+        SingleBatch { timestamp, ..Default::default() }.encode(&mut data);
+        let mut rlp = Vec::new();
+        Bytes::from(data).encode(&mut rlp);
+        rlp
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_discards_empty_channels() {
+        let mut no_batches = Vec::new();
+        Bytes::new().encode(&mut no_batches);
+        let channels = [Bytes::new(), channel_out(&[]), channel_out(&no_batches)];
+        for channel in channels {
+            let next = channel_out(&single_batch_rlp(2));
+            let mock = TestChannelReaderProvider::new(vec![Ok(Some(next)), Ok(Some(channel))]);
+            let mut reader =
+                ChannelReader::new(mock, Arc::new(RollupConfig::default())).with_strict(true);
+            assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+            assert_eq!(reader.empty_channels(), 1);
+            assert!(reader.next_batch.is_none());
+
+            // Derivation proceeds with the next channel.
+            assert_eq!(reader.next_batch().await.unwrap().timestamp(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_brotli_channel() {
+        let mut rlp = single_batch_rlp(2);
+        rlp.extend_from_slice(&single_batch_rlp(4));
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(brotli_channel_out(&rlp)))]);
+        let cfg = RollupConfig { fjord_time: Some(0), ..Default::default() };
+        let mut reader = ChannelReader::new(mock, Arc::new(cfg)).with_strict(true);
+        assert_eq!(reader.next_batch().await.unwrap().timestamp(), 2);
+        assert_eq!(reader.next_batch().await.unwrap().timestamp(), 4);
+        assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_compression_across_fjord() {
+        let cfg = RollupConfig { fjord_time: Some(10), ..Default::default() };
+        let not_enough_data = Err(PipelineError::NotEnoughData.temp());
+        let read = |channel: Bytes, origin_timestamp: u64| {
+            let mock = TestChannelReaderProvider::new(vec![Ok(Some(channel))]);
+            let mut reader = ChannelReader::new(mock, Arc::new(cfg.clone()));
+            reader.prev.block_info =
+                Some(BlockInfo { timestamp: origin_timestamp, ..Default::default() });
+            reader
+        };
+
+        // Brotli channels read before Fjord are dropped, whatever their batch timestamps.
+        let mut reader = read(brotli_channel_out(&single_batch_rlp(12)), 9);
+        assert_eq!(reader.next_batch().await, not_enough_data);
+        assert_eq!(reader.undecompressable_channels(), 1);
+        assert!(reader.next_batch.is_none());
+
+        // Brotli channels read after Fjord are accepted, whatever their batch timestamps.
+        let mut reader = read(brotli_channel_out(&single_batch_rlp(8)), 10);
+        assert_eq!(reader.next_batch().await.unwrap().timestamp(), 8);
+
+        // Zlib channels are accepted on both sides of the activation.
+        for origin_timestamp in [9, 10] {
+            let mut reader = read(channel_out(&single_batch_rlp(12)), origin_timestamp);
+            assert_eq!(reader.next_batch().await.unwrap().timestamp(), 12);
+        }
+    }
+
+    #[test]
+    fn test_batch_reader_inactive_compression() {
+        let cfg = RollupConfig { fjord_time: Some(10), ..Default::default() };
+        let channel = brotli_channel_out(&single_batch_rlp(12));
+        let mut batch_reader =
+            BatchReader::new(&channel[..], MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize, 9);
+        assert!(batch_reader.next_batch(&cfg).is_none());
+        assert_eq!(
+            batch_reader.decompression_error(),
+            Some(&BatchDecompressionError::InactiveCompression("brotli"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_decompression_bomb() {
+        // A megabyte of zeros compresses to about a kilobyte.
+        let bomb = channel_out(&vec![0u8; 1 << 20]);
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(bomb))]);
+        let limits = DerivationLimits { rlp_bytes_per_channel: 1 << 16, ..Default::default() };
+        let mut reader =
+            ChannelReader::new(mock, Arc::new(RollupConfig::default())).with_limits(limits);
+        reader.set_batch_reader().await.unwrap();
+        let batch_reader = reader.next_batch.as_mut().unwrap();
+        assert!(batch_reader.next_batch(&RollupConfig::default()).is_none());
+        assert_eq!(
+            batch_reader.decompression_error(),
+            Some(&BatchDecompressionError::BatchTooLarge)
+        );
+        assert!(batch_reader.decompressed.capacity() <= 1 << 16);
+
+        // The channel is dropped.
+        assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+        assert_eq!(reader.undecompressable_channels(), 1);
+        assert!(reader.next_batch.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_decompression_limit_by_fork() {
+        let rlp = single_batch_rlp(2);
+        let limits = DerivationLimits { rlp_bytes_per_channel: 1, ..Default::default() };
+        for (fjord_time, accepted) in [(None, false), (Some(0), true)] {
+            let mock = TestChannelReaderProvider::new(vec![Ok(Some(channel_out(&rlp)))]);
+            let cfg = RollupConfig { fjord_time, ..Default::default() };
+            let mut reader = ChannelReader::new(mock, Arc::new(cfg)).with_limits(limits);
+            assert_eq!(reader.next_batch().await.is_ok(), accepted);
+            assert_eq!(reader.undecompressable_channels(), !accepted as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_strict_decompression_error() {
+        let bomb = channel_out(&vec![0u8; 1 << 20]);
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(bomb))]);
+        let limits = DerivationLimits { rlp_bytes_per_channel: 1 << 16, ..Default::default() };
+        let mut reader = ChannelReader::new(mock, Arc::new(RollupConfig::default()))
+            .with_limits(limits)
+            .with_strict(true);
+        assert!(matches!(
+            reader.next_batch().await,
+            Err(PipelineErrorKind::Critical(PipelineError::StrictViolation(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_trailing_garbage() {
+        let mut rlp = single_batch_rlp(2);
+        rlp.extend_from_slice(&single_batch_rlp(4));
+        rlp.extend_from_slice(&[0xFF, 0x00, 0x01]);
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(channel_out(&rlp)))]);
+        let mut reader = ChannelReader::new(mock, Arc::new(RollupConfig::default()));
+        assert_eq!(reader.next_batch().await.unwrap().timestamp(), 2);
+        assert_eq!(reader.next_batch().await.unwrap().timestamp(), 4);
+        assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+        assert_eq!(reader.empty_channels(), 0);
+        assert!(reader.next_batch.is_none());
+    }
+
     #[tokio::test]
     async fn test_next_batch_succeeds() {
         let raw = new_compressed_batch_data();
@@ -335,20 +682,50 @@ mod test {
         assert!(reader.next_batch.is_some());
     }
 
+    #[tokio::test]
+    async fn test_next_batch_rlp_bytes_limit_override() {
+        let raw = new_compressed_batch_data();
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(raw))]);
+        let limits = DerivationLimits { rlp_bytes_per_channel: 1, ..Default::default() };
+        let mut reader =
+            ChannelReader::new(mock, Arc::new(RollupConfig::default())).with_limits(limits);
+        assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+        assert!(reader.next_batch.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_span_batch_elements_limit_override() {
+        let raw = new_compressed_batch_data();
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(raw))]);
+        let limits = DerivationLimits { span_batch_elements: 0, ..Default::default() };
+        let mut reader =
+            ChannelReader::new(mock, Arc::new(RollupConfig::default())).with_limits(limits);
+        assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+        assert!(reader.next_batch.is_none());
+    }
+
     #[test]
     fn test_batch_reader() {
         let raw = new_compressed_batch_data();
         let decompressed_len = decompress_to_vec_zlib(&raw).unwrap().len();
-        let mut reader = BatchReader::new(raw, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize);
+        let mut reader = BatchReader::new(raw, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize, 0);
         reader.next_batch(&RollupConfig::default()).unwrap();
         assert_eq!(reader.cursor, decompressed_len);
     }
 
+    #[test]
+    fn test_batch_reader_unknown_version() {
+        let mut reader =
+            BatchReader::new(vec![0x02, 0x00], MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize, 0);
+        assert!(reader.next_batch(&RollupConfig::default()).is_none());
+        assert!(!reader.is_exhausted());
+    }
+
     #[test]
     fn test_batch_reader_fjord() {
         let raw = new_compressed_batch_data();
         let decompressed_len = decompress_to_vec_zlib(&raw).unwrap().len();
-        let mut reader = BatchReader::new(raw, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize);
+        let mut reader = BatchReader::new(raw, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize, 0);
         reader.next_batch(&RollupConfig { fjord_time: Some(0), ..Default::default() }).unwrap();
         assert_eq!(reader.cursor, decompressed_len);
     }
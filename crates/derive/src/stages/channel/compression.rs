@@ -0,0 +1,101 @@
+//! Contains the channel decompression dispatch table used by the [BatchReader].
+//!
+//! [BatchReader]: super::channel_reader::BatchReader
+
+use crate::{errors::BatchDecompressionError, stages::decompress_brotli};
+use alloc::vec::Vec;
+use miniz_oxide::inflate::{decompress_to_vec_zlib_with_limit, TINFLStatus};
+use op_alloy_genesis::RollupConfig;
+
+/// ZLIB Deflate Compression Method.
+pub(crate) const ZLIB_DEFLATE_COMPRESSION_METHOD: u8 = 8;
+
+/// ZLIB Reserved Compression Info.
+pub(crate) const ZLIB_RESERVED_COMPRESSION_METHOD: u8 = 15;
+
+/// Brotili Compression Channel Version.
+pub(crate) const CHANNEL_VERSION_BROTLI: u8 = 1;
+
+/// A decompressor for a channel version, selected by the first byte of the channel data.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Decompressor {
+    /// The name of the compression algorithm, used for logging.
+    pub(crate) name: &'static str,
+    /// Returns whether the first byte of the channel data selects this decompressor.
+    pub(crate) matches: fn(u8) -> bool,
+    /// Decompresses the full channel data, bounded by the maximum number of RLP bytes. The output
+    /// never exceeds the bound, so a highly compressible payload cannot exhaust memory.
+    pub(crate) decompress: fn(&[u8], usize) -> Result<Vec<u8>, BatchDecompressionError>,
+    /// Returns whether the compression algorithm may be used for a channel read at an L1 origin
+    /// with the given timestamp. Checked once per channel, before decompressing it.
+    pub(crate) is_active: fn(&RollupConfig, u64) -> bool,
+}
+
+/// The decompressors of all spec channel versions.
+const DECOMPRESSORS: &[Decompressor] = &[
+    Decompressor {
+        name: "zlib",
+        matches: |byte| {
+            byte & 0x0F == ZLIB_DEFLATE_COMPRESSION_METHOD ||
+                byte & 0x0F == ZLIB_RESERVED_COMPRESSION_METHOD
+        },
+        decompress: |data, max| {
+            decompress_to_vec_zlib_with_limit(data, max).map_err(|e| match e.status {
+                TINFLStatus::HasMoreOutput => BatchDecompressionError::BatchTooLarge,
+                _ => BatchDecompressionError::InvalidData,
+            })
+        },
+        is_active: |_, _| true,
+    },
+    Decompressor {
+        name: "brotli",
+        matches: |byte| byte == CHANNEL_VERSION_BROTLI,
+        decompress: |data, max| decompress_brotli(&data[1..], max),
+        is_active: |cfg, timestamp| cfg.is_fjord_active(timestamp),
+    },
+];
+
+/// The registration point for experimental decompressors, such as zstd. Experimental channel
+/// versions are not part of the spec and are only recognized with the `experimental-compression`
+/// feature enabled.
+#[cfg(feature = "experimental-compression")]
+const EXPERIMENTAL_DECOMPRESSORS: &[Decompressor] = &[];
+
+/// Returns the [Decompressor] for the channel version byte, or [None] if the version is unknown.
+pub(crate) fn decompressor(version: u8) -> Option<&'static Decompressor> {
+    #[cfg(not(feature = "experimental-compression"))]
+    let mut registered = DECOMPRESSORS.iter();
+    #[cfg(feature = "experimental-compression")]
+    let mut registered = DECOMPRESSORS.iter().chain(EXPERIMENTAL_DECOMPRESSORS);
+    registered.find(|d| (d.matches)(version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompressor_zlib() {
+        for version in [0x78, 0x08, 0x0F, 0xFF] {
+            let decompressor = decompressor(version).unwrap();
+            assert_eq!(decompressor.name, "zlib");
+            assert!((decompressor.is_active)(&RollupConfig::default(), 0));
+        }
+    }
+
+    #[test]
+    fn test_decompressor_brotli_gated_by_fjord() {
+        let decompressor = decompressor(CHANNEL_VERSION_BROTLI).unwrap();
+        assert_eq!(decompressor.name, "brotli");
+        let cfg = RollupConfig { fjord_time: Some(10), ..Default::default() };
+        assert!(!(decompressor.is_active)(&cfg, 9));
+        assert!((decompressor.is_active)(&cfg, 10));
+    }
+
+    #[test]
+    fn test_decompressor_unknown_version() {
+        for version in [0x00, 0x02, 0x7E] {
+            assert!(decompressor(version).is_none());
+        }
+    }
+}
@@ -25,7 +25,11 @@ pub use channel_bank::ChannelBank;
 pub(crate) mod channel_assembler;
 pub use channel_assembler::ChannelAssembler;
 
+pub(crate) mod compression;
+pub(crate) use compression::CHANNEL_VERSION_BROTLI;
+
 pub(crate) mod channel_reader;
+pub(crate) use channel_reader::BatchReader;
 pub use channel_reader::{ChannelReader, ChannelReaderProvider};
 
 /// Provides frames for the [ChannelBank] and [ChannelAssembler] stages.
@@ -1,18 +1,39 @@
 //! This module contains the [FrameQueue] stage of the derivation pipeline.
 
 use crate::{
-    errors::PipelineError,
+    errors::{FrameValidationError, PipelineError},
     stages::NextFrameProvider,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{DerivationMetrics, OriginAdvancer, OriginProvider, SignalReceiver},
+    types::{ChannelId, DerivationLimits, PipelineResult, PipelineStatus, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use alloc::{boxed::Box, collections::VecDeque, format, sync::Arc, vec::Vec};
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use core::fmt::Debug;
 use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::{BlockInfo, Frame};
-use tracing::{debug, error, trace};
+use tracing::{debug, trace, warn};
+
+/// The length of a frame without its data: the channel id, frame number, data length and
+/// `is_last` flag.
+pub(crate) const FRAME_OVERHEAD_LEN: usize = 16 + 2 + 4 + 1;
+
+/// Parses the frames of a batcher transaction with [Frame::parse_frames], and validates the data
+/// length of every frame against `max_frame_len`.
+///
+/// Per the spec, a single malformed frame invalidates all frames in the transaction.
+pub(crate) fn parse_frames(
+    data: &[u8],
+    max_frame_len: usize,
+) -> Result<Vec<Frame>, FrameValidationError> {
+    let frames = Frame::parse_frames(data).map_err(FrameValidationError::Parse)?;
+    if let Some((i, frame)) =
+        frames.iter().enumerate().find(|(_, frame)| frame.data.len() > max_frame_len)
+    {
+        return Err(FrameValidationError::DataTooLong(i, frame.data.len(), max_frame_len));
+    }
+    Ok(frames)
+}
 
 /// Provides data frames for the [FrameQueue] stage.
 #[async_trait]
@@ -41,6 +62,12 @@ where
     queue: VecDeque<Frame>,
     /// The rollup config.
     rollup_config: Arc<RollupConfig>,
+    /// The derivation resource limits.
+    limits: DerivationLimits,
+    /// Whether spec-violation drops are surfaced as critical errors.
+    strict: bool,
+    /// The metrics recorder, if any.
+    metrics: Option<Arc<dyn DerivationMetrics>>,
 }
 
 impl<P> FrameQueue<P>
@@ -51,7 +78,33 @@ where
     ///
     /// [L1Retrieval]: crate::stages::L1Retrieval
     pub const fn new(prev: P, cfg: Arc<RollupConfig>) -> Self {
-        Self { prev, queue: VecDeque::new(), rollup_config: cfg }
+        Self {
+            prev,
+            queue: VecDeque::new(),
+            rollup_config: cfg,
+            limits: DerivationLimits::SPEC,
+            strict: false,
+            metrics: None,
+        }
+    }
+
+    /// Sets the [DerivationLimits] enforced by the [FrameQueue].
+    pub const fn with_limits(mut self, limits: DerivationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether malformed batcher transactions, and frames dropped by the Holocene ordering
+    /// rules, fail with a critical error instead of being dropped.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the [DerivationMetrics] recording the parsed frames.
+    pub fn with_metrics(mut self, metrics: Arc<dyn DerivationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
     }
 
     /// Returns if holocene is active.
@@ -59,14 +112,32 @@ where
         self.rollup_config.is_holocene_active(origin.timestamp)
     }
 
+    /// Returns a critical [PipelineError::StrictViolation] for a frame dropped by the Holocene
+    /// ordering rules, if the [FrameQueue] runs in strict mode.
+    fn check_strict(&self, frame: &Frame, reason: &str) -> PipelineResult<()> {
+        if self.strict {
+            return Err(PipelineError::StrictViolation(format!(
+                "{} frame #{} of channel {:#}",
+                reason,
+                frame.number,
+                ChannelId::from(frame.id)
+            ))
+            .crit());
+        }
+        Ok(())
+    }
+
     /// Prunes frames if Holocene is active.
-    pub fn prune(&mut self, origin: BlockInfo) {
+    ///
+    /// In strict mode, the first frame that would be dropped fails with a critical
+    /// [PipelineError::StrictViolation] instead.
+    pub fn prune(&mut self, origin: BlockInfo) -> PipelineResult<()> {
         if !self.is_holocene_active(origin) {
-            return;
+            return Ok(());
         }
 
         let mut i = 0;
-        while i < self.queue.len() - 1 {
+        while i + 1 < self.queue.len() {
             let prev_frame = &self.queue[i];
             let next_frame = &self.queue[i + 1];
             let extends_channel = prev_frame.id == next_frame.id;
@@ -74,18 +145,21 @@ where
             // If the frames are in the same channel, and the frame numbers are not sequential,
             // drop the next frame.
             if extends_channel && prev_frame.number + 1 != next_frame.number {
+                self.check_strict(next_frame, "non-sequential")?;
                 self.queue.remove(i + 1);
                 continue;
             }
 
             // If the frames are in the same channel, and the previous is last, drop the next frame.
             if extends_channel && prev_frame.is_last {
+                self.check_strict(next_frame, "late")?;
                 self.queue.remove(i + 1);
                 continue;
             }
 
             // If the frames are in different channels, the next frame must be first.
             if !extends_channel && next_frame.number != 0 {
+                self.check_strict(next_frame, "non-first")?;
                 self.queue.remove(i + 1);
                 continue;
             }
@@ -93,6 +167,8 @@ where
             // If the frames are in different channels, and the current channel is not last, walk
             // back the channel and drop all prev frames.
             if !extends_channel && !prev_frame.is_last && next_frame.number == 0 {
+                self.check_strict(prev_frame, "unclosed")?;
+
                 // Find the index of the first frame in the queue with the same channel ID
                 // as the previous frame.
                 let first_frame =
@@ -106,6 +182,7 @@ where
 
             i += 1;
         }
+        Ok(())
     }
 
     /// Loads more frames into the [FrameQueue].
@@ -124,21 +201,33 @@ where
             }
         };
 
-        let Ok(frames) = Frame::parse_frames(&data.into()) else {
-            // There may be more frames in the queue for the
-            // pipeline to advance, so don't return an error here.
-            error!(target: "frame-queue", "Failed to parse frames from data.");
-            return Ok(());
+        let data: Bytes = data.into();
+        let frames = match parse_frames(&data, self.limits.frame_len) {
+            Ok(frames) => frames,
+            Err(e) if self.strict => {
+                return Err(PipelineError::StrictViolation(format!(
+                    "malformed batcher transaction: {e}"
+                ))
+                .crit());
+            }
+            Err(e) => {
+                // There may be more frames in the queue for the
+                // pipeline to advance, so don't return an error here.
+                warn!(target: "frame-queue", "Dropping all frames of batcher transaction: {}", e);
+                return Ok(());
+            }
         };
 
+        if let Some(metrics) = &self.metrics {
+            frames.iter().for_each(|frame| metrics.record_frame(frame.data.len()));
+        }
+
         // Optimistically extend the queue with the new frames.
         self.queue.extend(frames);
 
         // Prune frames if Holocene is active.
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
-        self.prune(origin);
-
-        Ok(())
+        self.prune(origin)
     }
 }
 
@@ -150,6 +239,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 #[async_trait]
@@ -157,6 +250,10 @@ impl<P> NextFrameProvider for FrameQueue<P>
 where
     P: FrameQueueProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(name = "frame_queue", level = "debug", skip_all)
+    )]
     async fn next_frame(&mut self) -> PipelineResult<Frame> {
         self.load_frames().await?;
 
@@ -177,6 +274,15 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("frame-queue", self.origin()));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+    }
 }
 
 #[async_trait]
@@ -184,18 +290,24 @@ impl<P> SignalReceiver for FrameQueue<P>
 where
     P: FrameQueueProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send + Debug,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        self.prev.signal(signal).await?;
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let receipt = self.prev.signal(signal).await?;
         self.queue = VecDeque::default();
-        Ok(())
+        Ok(receipt)
     }
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
-    use crate::{test_utils::TestFrameQueueProvider, types::ResetSignal};
+    use crate::{
+        errors::PipelineErrorKind,
+        stages::{ChannelAssembler, ChannelReaderProvider},
+        test_utils::TestFrameQueueProvider,
+        types::{ResetSignal, MAX_FRAME_LEN},
+    };
     use alloc::vec;
+    use op_alloy_protocol::{FrameDecodingError, FrameParseError, DERIVATION_VERSION_0};
 
     #[tokio::test]
     async fn test_frame_queue_reset() {
@@ -229,6 +341,143 @@ pub(crate) mod tests {
         assert_eq!(err, PipelineError::NotEnoughData.temp());
     }
 
+    #[tokio::test]
+    async fn test_frame_queue_frame_len_limit_override() {
+        let frames = [
+            crate::frame!(0xEE, 0, vec![0xDD; 50], true),
+            crate::frame!(0xFF, 0, vec![0xDD; 10], true),
+        ];
+        let mut data = vec![DERIVATION_VERSION_0];
+        frames.iter().for_each(|f| data.extend_from_slice(&f.encode()));
+        let mut mock = TestFrameQueueProvider::new(vec![Ok(Bytes::from(data))]);
+        mock.set_origin(BlockInfo::default());
+        let limits = DerivationLimits { frame_len: 20, ..Default::default() };
+        let mut frame_queue = FrameQueue::new(mock, Default::default()).with_limits(limits);
+        // The oversized frame invalidates every frame in the transaction.
+        let err = frame_queue.next_frame().await.unwrap_err();
+        assert_eq!(err, PipelineError::NotEnoughData.temp());
+        assert!(frame_queue.queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_frame_queue_strict_malformed_transaction() {
+        for strict in [false, true] {
+            let data = vec![Ok(Bytes::from(vec![DERIVATION_VERSION_0, 0x01]))];
+            let mut mock = TestFrameQueueProvider::new(data);
+            mock.set_origin(BlockInfo::default());
+            let mut frame_queue = FrameQueue::new(mock, Default::default()).with_strict(strict);
+            let err = frame_queue.next_frame().await.unwrap_err();
+            if strict {
+                assert!(matches!(
+                    err,
+                    PipelineErrorKind::Critical(PipelineError::StrictViolation(_))
+                ));
+            } else {
+                assert_eq!(err, PipelineError::NotEnoughData.temp());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_frame_queue_strict_holocene_prune() {
+        let frames = [
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false),
+            crate::frame!(0xEE, 2, vec![0xDD; 50], true),
+        ];
+        let cfg = Arc::new(RollupConfig { holocene_time: Some(0), ..Default::default() });
+        for strict in [false, true] {
+            let data = vec![Ok(Bytes::from(encode(&frames)))];
+            let mut mock = TestFrameQueueProvider::new(data);
+            mock.set_origin(BlockInfo::default());
+            let mut frame_queue = FrameQueue::new(mock, cfg.clone()).with_strict(strict);
+            let result = frame_queue.next_frame().await;
+            if strict {
+                assert!(matches!(
+                    result.unwrap_err(),
+                    PipelineErrorKind::Critical(PipelineError::StrictViolation(_))
+                ));
+            } else {
+                // The non-sequential frame is pruned.
+                assert_eq!(result.unwrap(), frames[0]);
+                assert!(frame_queue.queue.is_empty());
+            }
+        }
+    }
+
+    fn encode(frames: &[Frame]) -> Vec<u8> {
+        let mut data = vec![DERIVATION_VERSION_0];
+        frames.iter().for_each(|f| data.extend_from_slice(&f.encode()));
+        data
+    }
+
+    #[test]
+    fn test_parse_frames_valid() {
+        let frames =
+            [crate::frame!(0xEE, 0, vec![0xDD; 50], false), crate::frame!(0xEE, 1, vec![], true)];
+        assert_eq!(parse_frames(&encode(&frames), MAX_FRAME_LEN), Ok(frames.to_vec()));
+    }
+
+    #[test]
+    fn test_parse_frames_malformed() {
+        let frame = crate::frame!(0xEE, 0, vec![0xDD; 10], true);
+        let valid = encode(&[frame.clone()]);
+        // The offset of the second frame in a transaction with two frames.
+        let second = valid.len();
+
+        let mut long_declared = valid.clone();
+        long_declared[1 + 21] = 11;
+
+        // With one byte less of data, the last data byte is read as the is_last flag and the
+        // real is_last flag is left over.
+        let mut short_declared = valid.clone();
+        short_declared[1 + 21] = 9;
+
+        let mut trailing = valid.clone();
+        trailing.extend_from_slice(&[0xAA; 5]);
+
+        let mut malformed_second = encode(&[frame.clone(), frame.clone()]);
+        malformed_second[second + 21] = 0xFF;
+
+        let oversized_second = encode(&[crate::frame!(0xEE, 0, vec![0xDD; 5], false), frame]);
+
+        let parse = FrameValidationError::Parse;
+        let decoding = |e| FrameValidationError::Parse(FrameParseError::FrameDecodingError(e));
+        let cases: [(&str, Vec<u8>, usize, FrameValidationError); 8] = [
+            ("empty", vec![], MAX_FRAME_LEN, parse(FrameParseError::NoFrames)),
+            ("bad version", vec![1, 0], MAX_FRAME_LEN, parse(FrameParseError::UnsupportedVersion)),
+            ("no frames", vec![0], MAX_FRAME_LEN, parse(FrameParseError::NoFramesDecoded)),
+            (
+                "declared length past end",
+                long_declared,
+                MAX_FRAME_LEN,
+                decoding(FrameDecodingError::DataTooLarge(11)),
+            ),
+            (
+                "declared length short",
+                short_declared,
+                MAX_FRAME_LEN,
+                decoding(FrameDecodingError::DataTooShort(1)),
+            ),
+            (
+                "trailing bytes",
+                trailing,
+                MAX_FRAME_LEN,
+                decoding(FrameDecodingError::DataTooShort(5)),
+            ),
+            (
+                "malformed second frame",
+                malformed_second,
+                MAX_FRAME_LEN,
+                decoding(FrameDecodingError::DataTooLarge(0xFF)),
+            ),
+            ("too long", oversized_second, 9, FrameValidationError::DataTooLong(1, 10, 9)),
+        ];
+
+        for (name, data, max_frame_len, err) in cases {
+            assert_eq!(parse_frames(&data, max_frame_len), Err(err), "case: {name}");
+        }
+    }
+
     #[tokio::test]
     async fn test_frame_queue_wrong_derivation_version() {
         let assert = crate::test_utils::FrameQueueBuilder::new()
@@ -498,4 +747,88 @@ pub(crate) mod tests {
         assert.holocene_active(true);
         assert.next_frames().await;
     }
+
+    #[tokio::test]
+    async fn test_holocene_duplicate_frames() {
+        let frames = [
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false),
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false), // Dropped
+            crate::frame!(0xEE, 1, vec![0xDD; 50], false),
+            crate::frame!(0xEE, 1, vec![0xDD; 50], false), // Dropped
+            crate::frame!(0xEE, 2, vec![0xDD; 50], true),
+        ];
+        let assert = crate::test_utils::FrameQueueBuilder::new()
+            .with_rollup_config(&RollupConfig { holocene_time: Some(0), ..Default::default() })
+            .with_origin(BlockInfo::default())
+            .with_expected_frames(&[&frames[0..1], &frames[2..3], &frames[4..]].concat())
+            .with_frames(&frames)
+            .build();
+        assert.holocene_active(true);
+        assert.next_frames().await;
+    }
+
+    #[tokio::test]
+    async fn test_holocene_prunes_within_transaction() {
+        let first = [
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false),
+            crate::frame!(0xEE, 1, vec![0xDD; 50], false),
+        ];
+        // Frames are only ordered against the frames of their own batcher transaction, so the
+        // restarted channel is passed on for the channel assembler to reset.
+        let second = [
+            crate::frame!(0xEE, 0, vec![0xCC; 50], false),
+            crate::frame!(0xEE, 1, vec![0xCC; 50], true),
+        ];
+        let assert = crate::test_utils::FrameQueueBuilder::new()
+            .with_rollup_config(&RollupConfig { holocene_time: Some(0), ..Default::default() })
+            .with_origin(BlockInfo::default())
+            .with_expected_frames(&[&first[..], &second[..]].concat())
+            .with_frames(&first)
+            .with_frames(&second)
+            .build();
+        assert.holocene_active(true);
+        assert.next_frames().await;
+    }
+
+    #[tokio::test]
+    async fn test_holocene_first_frame_after_unclosed_channel_reaches_assembler() {
+        let first = [
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false),
+            crate::frame!(0xEE, 1, vec![0xDD; 50], false),
+        ];
+        let second = [
+            crate::frame!(0xEE, 0, vec![0xCC; 50], false),
+            crate::frame!(0xEE, 1, vec![0xCC; 50], true),
+        ];
+        // The mock provider pops data from the back.
+        let data = vec![Ok(Bytes::from(encode(&second))), Ok(Bytes::from(encode(&first)))];
+        let mut mock = TestFrameQueueProvider::new(data);
+        mock.set_origin(BlockInfo::default());
+        let cfg = Arc::new(RollupConfig { holocene_time: Some(0), ..Default::default() });
+        let frame_queue = FrameQueue::new(mock, cfg.clone());
+        let mut assembler = ChannelAssembler::new(cfg, frame_queue);
+
+        for _ in 0..3 {
+            let err = assembler.next_data().await.unwrap_err();
+            assert_eq!(err, PipelineError::NotEnoughData.temp());
+        }
+
+        // The channel restarted by the second transaction's first frame is assembled.
+        let channel = assembler.next_data().await.unwrap().unwrap();
+        assert_eq!(channel, Bytes::from(vec![0xCC; 100]));
+    }
+
+    #[tokio::test]
+    async fn test_pre_holocene_no_ordering_across_transactions() {
+        let first = [crate::frame!(0xEE, 0, vec![0xDD; 50], true)];
+        let second = [crate::frame!(0xEE, 0, vec![0xDD; 50], true)];
+        let assert = crate::test_utils::FrameQueueBuilder::new()
+            .with_origin(BlockInfo::default())
+            .with_expected_frames(&[&first[..], &second[..]].concat())
+            .with_frames(&first)
+            .with_frames(&second)
+            .build();
+        assert.holocene_active(false);
+        assert.next_frames().await;
+    }
 }
@@ -0,0 +1,286 @@
+//! Contains the [IndexedTraversal] stage of the derivation pipeline.
+
+use crate::{
+    errors::{PipelineError, ResetError},
+    stages::{l1_traversal::apply_next_origin, L1RetrievalProvider},
+    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
+    types::{ActivationSignal, PipelineResult, ResetSignal, Signal, SignalReceipt},
+};
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+use alloy_consensus::Receipt;
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use op_alloy_genesis::{RollupConfig, SystemConfig};
+use op_alloy_protocol::BlockInfo;
+use tracing::{debug, trace};
+
+/// The [IndexedTraversal] stage of the derivation pipeline.
+///
+/// An alternative to the [L1Traversal] stage for drivers that already follow the L1 chain.
+/// Instead of polling a [ChainProvider], the stage advances through L1 blocks that the driver
+/// pushes in with [IndexedTraversal::provide_block], along with their receipts.
+/// [OriginAdvancer::advance_origin] returns [PipelineError::Eof] until the next block has been
+/// provided.
+///
+/// [L1Traversal]: crate::stages::L1Traversal
+/// [ChainProvider]: crate::traits::ChainProvider
+#[derive(Debug, Clone)]
+pub struct IndexedTraversal {
+    /// The current block in the traversal stage.
+    pub block: Option<BlockInfo>,
+    /// The provided blocks that the traversal stage has not advanced to yet, with their receipts.
+    pub queue: VecDeque<(BlockInfo, Vec<Receipt>)>,
+    /// Signals whether or not the traversal stage is complete.
+    pub done: bool,
+    /// The system config.
+    pub system_config: SystemConfig,
+    /// A reference to the rollup config.
+    pub rollup_config: Arc<RollupConfig>,
+}
+
+impl IndexedTraversal {
+    /// Creates a new [IndexedTraversal] instance.
+    pub fn new(cfg: Arc<RollupConfig>) -> Self {
+        Self {
+            block: Some(BlockInfo::default()),
+            queue: VecDeque::new(),
+            done: false,
+            system_config: SystemConfig::default(),
+            rollup_config: cfg,
+        }
+    }
+
+    /// Queues the next L1 block and its receipts for the traversal stage to advance to.
+    ///
+    /// The block must be the child of the last queued block, or of the current origin if no
+    /// block is queued. A block with an unexpected number is rejected with
+    /// [PipelineError::NonContiguousBlock], and a block that does not link to its parent by hash
+    /// with [ResetError::ReorgDetected].
+    pub fn provide_block(
+        &mut self,
+        block: BlockInfo,
+        receipts: Vec<Receipt>,
+    ) -> PipelineResult<()> {
+        let parent = self
+            .queue
+            .back()
+            .map(|(block, _)| *block)
+            .or(self.block)
+            .ok_or(PipelineError::MissingOrigin.crit())?;
+        if block.number != parent.number + 1 {
+            return Err(PipelineError::NonContiguousBlock(parent.number + 1, block.number).crit());
+        }
+        if block.parent_hash != parent.hash {
+            return Err(ResetError::ReorgDetected(parent.hash, block.parent_hash).reset());
+        }
+        debug!(target: "indexed-traversal", "Queued L1 block #{}", block.number);
+        self.queue.push_back((block, receipts));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl L1RetrievalProvider for IndexedTraversal {
+    fn batcher_addr(&self) -> Address {
+        self.system_config.batcher_address
+    }
+
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(name = "indexed_traversal", level = "debug", skip_all)
+    )]
+    async fn next_l1_block(&mut self) -> PipelineResult<Option<BlockInfo>> {
+        if !self.done {
+            self.done = true;
+            Ok(self.block)
+        } else {
+            Err(PipelineError::Eof.temp())
+        }
+    }
+}
+
+#[async_trait]
+impl OriginAdvancer for IndexedTraversal {
+    /// Advances the [IndexedTraversal] stage to the next provided L1 block, updating the
+    /// [SystemConfig] with the receipts from the block.
+    async fn advance_origin(&mut self) -> PipelineResult<()> {
+        let block = self.block.ok_or(PipelineError::Eof.temp())?;
+        let Some((next_l1_origin, receipts)) = self.queue.pop_front() else {
+            trace!(target: "indexed-traversal", "Waiting for L1 block #{}", block.number + 1);
+            return Err(PipelineError::Eof.temp());
+        };
+
+        let holocene_activation = apply_next_origin(
+            &self.rollup_config,
+            &mut self.system_config,
+            &block,
+            &next_l1_origin,
+            &receipts,
+        )?;
+
+        // Update the block origin regardless of if a holocene activation is required.
+        self.block = Some(next_l1_origin);
+        self.done = false;
+
+        // If the prev block is not holocene, but the next is, we need to flag this
+        // so the pipeline driver will reset the pipeline for holocene activation.
+        if holocene_activation {
+            return Err(ResetError::HoloceneActivation.reset());
+        }
+
+        Ok(())
+    }
+}
+
+impl OriginProvider for IndexedTraversal {
+    fn origin(&self) -> Option<BlockInfo> {
+        self.block
+    }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("indexed-traversal", self.block));
+    }
+}
+
+#[async_trait]
+impl SignalReceiver for IndexedTraversal {
+    /// Resets the traversal stage to the signalled L1 origin. Queued blocks that extend the new
+    /// origin are kept, while blocks at or below it are dropped. If the queued blocks do not
+    /// extend the new origin, they are all dropped and must be provided again.
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        match signal {
+            Signal::Reset(ResetSignal { l1_origin, system_config, .. }) |
+            Signal::Activation(ActivationSignal { l1_origin, system_config, .. }) => {
+                self.queue.retain(|(block, _)| block.number > l1_origin.number);
+                if self.queue.front().is_some_and(|(block, _)| {
+                    block.number != l1_origin.number + 1 || block.parent_hash != l1_origin.hash
+                }) {
+                    self.queue.clear();
+                }
+                self.block = Some(l1_origin);
+                self.done = false;
+                self.system_config = system_config.expect("System config must be provided.");
+                Ok(SignalReceipt { stages_reset: true, ..Default::default() })
+            }
+            _ => Ok(SignalReceipt::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::PipelineErrorKind;
+    use alloc::vec;
+    use alloy_primitives::B256;
+
+    fn chain(len: u64) -> Vec<BlockInfo> {
+        (0..len)
+            .map(|n| BlockInfo {
+                number: n,
+                hash: B256::with_last_byte(n as u8 + 1),
+                parent_hash: B256::with_last_byte(n as u8),
+                timestamp: n * 12,
+            })
+            .collect()
+    }
+
+    fn new_traversal(origin: BlockInfo) -> IndexedTraversal {
+        let mut traversal = IndexedTraversal::new(Arc::new(RollupConfig::default()));
+        traversal.block = Some(origin);
+        traversal
+    }
+
+    #[tokio::test]
+    async fn test_indexed_traversal_advances_through_provided_blocks() {
+        let blocks = chain(3);
+        let mut traversal = new_traversal(blocks[0]);
+        assert_eq!(traversal.advance_origin().await.unwrap_err(), PipelineError::Eof.temp());
+
+        traversal.provide_block(blocks[1], vec![]).unwrap();
+        traversal.provide_block(blocks[2], vec![]).unwrap();
+        assert_eq!(traversal.next_l1_block().await.unwrap(), Some(blocks[0]));
+        for block in &blocks[1..] {
+            traversal.advance_origin().await.unwrap();
+            assert_eq!(traversal.origin(), Some(*block));
+            assert_eq!(traversal.next_l1_block().await.unwrap(), Some(*block));
+        }
+        assert_eq!(traversal.advance_origin().await.unwrap_err(), PipelineError::Eof.temp());
+        assert_eq!(traversal.origin(), Some(blocks[2]));
+    }
+
+    #[test]
+    fn test_indexed_traversal_rejects_out_of_order_blocks() {
+        let blocks = chain(4);
+        let mut traversal = new_traversal(blocks[0]);
+
+        let err = traversal.provide_block(blocks[2], vec![]).unwrap_err();
+        assert_eq!(err, PipelineError::NonContiguousBlock(1, 2).crit());
+        traversal.provide_block(blocks[1], vec![]).unwrap();
+        let err = traversal.provide_block(blocks[1], vec![]).unwrap_err();
+        assert_eq!(err, PipelineError::NonContiguousBlock(2, 1).crit());
+        assert_eq!(traversal.queue.len(), 1);
+    }
+
+    #[test]
+    fn test_indexed_traversal_rejects_unlinked_block() {
+        let blocks = chain(3);
+        let mut traversal = new_traversal(blocks[0]);
+        traversal.provide_block(blocks[1], vec![]).unwrap();
+
+        let forked = BlockInfo { parent_hash: B256::repeat_byte(0xFF), ..blocks[2] };
+        let err = traversal.provide_block(forked, vec![]).unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Reset(ResetError::ReorgDetected(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_indexed_traversal_reset_drops_queued_blocks() {
+        let blocks = chain(4);
+        let mut traversal = new_traversal(blocks[0]);
+        traversal.provide_block(blocks[1], vec![]).unwrap();
+        traversal.provide_block(blocks[2], vec![]).unwrap();
+        traversal.advance_origin().await.unwrap();
+
+        let cfg = SystemConfig { gas_limit: 1, ..Default::default() };
+        let reset =
+            ResetSignal { l1_origin: blocks[2], system_config: Some(cfg), ..Default::default() };
+        traversal.signal(reset.signal()).await.unwrap();
+        assert!(traversal.queue.is_empty());
+        assert_eq!(traversal.origin(), Some(blocks[2]));
+        assert_eq!(traversal.system_config, cfg);
+
+        // Blocks are provided again on top of the new origin.
+        traversal.provide_block(blocks[3], vec![]).unwrap();
+        traversal.advance_origin().await.unwrap();
+        assert_eq!(traversal.origin(), Some(blocks[3]));
+    }
+
+    #[tokio::test]
+    async fn test_indexed_traversal_signal_keeps_extending_blocks() {
+        let blocks = chain(4);
+        let mut traversal = new_traversal(blocks[0]);
+        (1..4).for_each(|n| traversal.provide_block(blocks[n], vec![]).unwrap());
+        traversal.advance_origin().await.unwrap();
+
+        // An activation at the current origin keeps the blocks queued on top of it.
+        let activation = ActivationSignal {
+            l1_origin: blocks[1],
+            system_config: Some(SystemConfig::default()),
+            ..Default::default()
+        };
+        traversal.signal(activation.signal()).await.unwrap();
+        assert_eq!(traversal.queue.len(), 2);
+        traversal.advance_origin().await.unwrap();
+        assert_eq!(traversal.origin(), Some(blocks[2]));
+
+        // A reset to an origin on another fork drops them.
+        let forked = BlockInfo { hash: B256::repeat_byte(0xFF), ..blocks[2] };
+        let reset = ResetSignal {
+            l1_origin: forked,
+            system_config: Some(SystemConfig::default()),
+            ..Default::default()
+        };
+        traversal.signal(reset.signal()).await.unwrap();
+        assert!(traversal.queue.is_empty());
+    }
+}
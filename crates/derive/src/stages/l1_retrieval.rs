@@ -4,9 +4,9 @@ use crate::{
     errors::{PipelineError, PipelineErrorKind},
     stages::FrameQueueProvider,
     traits::{DataAvailabilityProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{ActivationSignal, PipelineResult, ResetSignal, Signal},
+    types::{ActivationSignal, PipelineResult, PipelineStatus, ResetSignal, Signal, SignalReceipt},
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_primitives::Address;
 use async_trait::async_trait;
 use op_alloy_protocol::BlockInfo;
@@ -70,6 +70,10 @@ where
     async fn advance_origin(&mut self) -> PipelineResult<()> {
         self.prev.advance_origin().await
     }
+
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.prev.advance_origin_to(target).await
+    }
 }
 
 #[async_trait]
@@ -80,6 +84,10 @@ where
 {
     type Item = DAP::Item;
 
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(name = "l1_retrieval", level = "debug", skip_all)
+    )]
     async fn next_data(&mut self) -> PipelineResult<Self::Item> {
         if self.next.is_none() {
             self.next = Some(
@@ -113,6 +121,15 @@ where
     fn origin(&self) -> Option<BlockInfo> {
         self.prev.origin()
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        self.prev.collect_stage_origins(origins);
+        origins.push(("l1-retrieval", self.origin()));
+    }
+
+    fn collect_status(&self, status: &mut PipelineStatus) {
+        self.prev.collect_status(status);
+    }
 }
 
 #[async_trait]
@@ -121,8 +138,8 @@ where
     DAP: DataAvailabilityProvider + Send,
     P: L1RetrievalProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send,
 {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
-        self.prev.signal(signal).await?;
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        let receipt = self.prev.signal(signal).await?;
         match signal {
             Signal::Reset(ResetSignal { l1_origin, .. }) |
             Signal::Activation(ActivationSignal { l1_origin, .. }) => {
@@ -130,7 +147,7 @@ where
             }
             _ => {}
         }
-        Ok(())
+        Ok(receipt)
     }
 }
 
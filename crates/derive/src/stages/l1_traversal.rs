@@ -4,14 +4,15 @@ use crate::{
     errors::{PipelineError, ResetError},
     stages::L1RetrievalProvider,
     traits::{ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{ActivationSignal, PipelineResult, ResetSignal, Signal},
+    types::{ActivationSignal, PipelineResult, ResetSignal, Signal, SignalReceipt},
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloy_consensus::Receipt;
 use alloy_primitives::Address;
 use async_trait::async_trait;
 use op_alloy_genesis::{RollupConfig, SystemConfig};
 use op_alloy_protocol::BlockInfo;
-use tracing::warn;
+use tracing::{trace, warn};
 
 /// The [L1Traversal] stage of the derivation pipeline.
 ///
@@ -32,6 +33,9 @@ pub struct L1Traversal<Provider: ChainProvider> {
     pub system_config: SystemConfig,
     /// A reference to the rollup config.
     pub rollup_config: Arc<RollupConfig>,
+    /// The last L1 block the traversal stage may advance to. L1 blocks beyond it are treated
+    /// as nonexistent. This is configuration rather than state, so it survives resets.
+    pub l1_end: Option<BlockInfo>,
 }
 
 #[async_trait]
@@ -40,6 +44,10 @@ impl<F: ChainProvider + Send> L1RetrievalProvider for L1Traversal<F> {
         self.system_config.batcher_address
     }
 
+    #[cfg_attr(
+        feature = "tracing-spans",
+        tracing::instrument(name = "l1_traversal", level = "debug", skip_all)
+    )]
     async fn next_l1_block(&mut self) -> PipelineResult<Option<BlockInfo>> {
         if !self.done {
             self.done = true;
@@ -59,8 +67,16 @@ impl<F: ChainProvider> L1Traversal<F> {
             done: false,
             system_config: SystemConfig::default(),
             rollup_config: cfg,
+            l1_end: None,
         }
     }
+
+    /// Pins the traversal stage to the given L1 block. Once the origin reaches the pin,
+    /// [OriginAdvancer::advance_origin] returns EOF instead of fetching newer blocks.
+    pub const fn with_l1_end(mut self, l1_end: BlockInfo) -> Self {
+        self.l1_end = Some(l1_end);
+        self
+    }
 }
 
 #[async_trait]
@@ -78,6 +94,10 @@ impl<F: ChainProvider + Send> OriginAdvancer for L1Traversal<F> {
                 return Err(PipelineError::Eof.temp());
             }
         };
+        if self.l1_end.is_some_and(|end| block.number >= end.number) {
+            trace!(target: "l1-traversal", "Reached pinned L1 end block #{}", block.number);
+            return Err(PipelineError::Eof.temp());
+        }
         let next_l1_origin =
             self.data_source.block_info_by_number(block.number + 1).await.map_err(Into::into)?;
 
@@ -89,17 +109,13 @@ impl<F: ChainProvider + Send> OriginAdvancer for L1Traversal<F> {
         // Fetch receipts for the next l1 block and update the system config.
         let receipts =
             self.data_source.receipts_by_hash(next_l1_origin.hash).await.map_err(Into::into)?;
-
-        if let Err(e) = self.system_config.update_with_receipts(
-            receipts.as_slice(),
-            self.rollup_config.l1_system_config_address,
-            self.rollup_config.is_ecotone_active(next_l1_origin.timestamp),
-        ) {
-            return Err(PipelineError::SystemConfigUpdate(e).crit());
-        }
-
-        let prev_block_holocene = self.rollup_config.is_holocene_active(block.timestamp);
-        let next_block_holocene = self.rollup_config.is_holocene_active(next_l1_origin.timestamp);
+        let holocene_activation = apply_next_origin(
+            &self.rollup_config,
+            &mut self.system_config,
+            &block,
+            &next_l1_origin,
+            &receipts,
+        )?;
 
         // Update the block origin regardless of if a holocene activation is required.
         self.block = Some(next_l1_origin);
@@ -107,41 +123,119 @@ impl<F: ChainProvider + Send> OriginAdvancer for L1Traversal<F> {
 
         // If the prev block is not holocene, but the next is, we need to flag this
         // so the pipeline driver will reset the pipeline for holocene activation.
-        if !prev_block_holocene && next_block_holocene {
+        if holocene_activation {
             return Err(ResetError::HoloceneActivation.reset());
         }
 
         Ok(())
     }
+
+    /// Skips the L1 blocks up to `target` that carry neither batcher transactions nor system
+    /// config updates, as reported by [ChainProvider::block_has_batcher_tx], without fetching
+    /// their receipts. The first L1 block that may carry either, or the target itself, is then
+    /// advanced to like in [OriginAdvancer::advance_origin], so its system config updates are
+    /// applied.
+    ///
+    /// Once the origin reaches the `target` block number, it is checked to be the `target` block,
+    /// so a reorg of the L1 origin of the L2 safe head is detected.
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        let target_number = self.l1_end.map_or(target.number, |end| target.number.min(end.number));
+        while let Some(block) = self.block {
+            if block.number + 1 >= target_number {
+                break;
+            }
+            let next_l1_origin = self
+                .data_source
+                .block_info_by_number(block.number + 1)
+                .await
+                .map_err(Into::into)?;
+            let parent_hash = next_l1_origin.parent_hash;
+            if block.hash != parent_hash {
+                return Err(ResetError::ReorgDetected(block.hash, parent_hash).into());
+            }
+            let holocene_activation = !self.rollup_config.is_holocene_active(block.timestamp) &&
+                self.rollup_config.is_holocene_active(next_l1_origin.timestamp);
+            let has_data = self
+                .data_source
+                .block_has_batcher_tx(
+                    next_l1_origin.hash,
+                    self.rollup_config.batch_inbox_address,
+                    self.rollup_config.l1_system_config_address,
+                )
+                .await
+                .map_err(Into::into)?;
+            if has_data || holocene_activation {
+                break;
+            }
+            trace!(target: "l1-traversal", "Skipping L1 block #{}", next_l1_origin.number);
+            self.block = Some(next_l1_origin);
+            self.done = false;
+        }
+        self.advance_origin().await?;
+
+        if let Some(block) = self.block.filter(|block| block.number == target.number) {
+            if block.hash != target.hash {
+                return Err(ResetError::ReorgDetected(target.hash, block.hash).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Updates the [SystemConfig] with the receipts of the `next` L1 origin, and returns whether
+/// advancing to it from `prev` activates Holocene. Shared by the traversal stages.
+pub(crate) fn apply_next_origin(
+    rollup_config: &RollupConfig,
+    system_config: &mut SystemConfig,
+    prev: &BlockInfo,
+    next: &BlockInfo,
+    receipts: &[Receipt],
+) -> PipelineResult<bool> {
+    system_config
+        .update_with_receipts(
+            receipts,
+            rollup_config.l1_system_config_address,
+            rollup_config.is_ecotone_active(next.timestamp),
+        )
+        .map_err(|e| PipelineError::SystemConfigUpdate(e).crit())?;
+    Ok(!rollup_config.is_holocene_active(prev.timestamp) &&
+        rollup_config.is_holocene_active(next.timestamp))
 }
 
 impl<F: ChainProvider> OriginProvider for L1Traversal<F> {
     fn origin(&self) -> Option<BlockInfo> {
         self.block
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("l1-traversal", self.block));
+    }
 }
 
 #[async_trait]
 impl<F: ChainProvider + Send> SignalReceiver for L1Traversal<F> {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         match signal {
             Signal::Reset(ResetSignal { l1_origin, system_config, .. }) |
             Signal::Activation(ActivationSignal { l1_origin, system_config, .. }) => {
+                self.data_source.invalidate_l1_above(l1_origin.number);
                 self.block = Some(l1_origin);
                 self.done = false;
                 self.system_config = system_config.expect("System config must be provided.");
+                Ok(SignalReceipt { stages_reset: true, ..Default::default() })
             }
-            _ => {}
+            _ => Ok(SignalReceipt::default()),
         }
-
-        Ok(())
     }
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
-    use crate::{errors::PipelineErrorKind, test_utils::TestChainProvider};
+    use crate::{
+        errors::PipelineErrorKind,
+        test_utils::{TestChainProvider, TestChainProviderBuilder},
+    };
     use alloc::vec;
     use alloy_consensus::Receipt;
     use alloy_primitives::{address, b256, hex, Bytes, Log, LogData, B256};
@@ -276,6 +370,97 @@ pub(crate) mod tests {
         );
     }
 
+    fn new_chain_traversal(
+        builder: TestChainProviderBuilder,
+    ) -> (alloc::vec::Vec<BlockInfo>, L1Traversal<TestChainProvider>) {
+        let blocks = builder.blocks();
+        let cfg =
+            RollupConfig { l1_system_config_address: L1_SYS_CONFIG_ADDR, ..Default::default() };
+        (blocks, L1Traversal::new(builder.build(), Arc::new(cfg)))
+    }
+
+    #[tokio::test]
+    async fn test_l1_traversal_pinned_eof() {
+        let (blocks, traversal) = new_chain_traversal(TestChainProviderBuilder::new(6));
+        let mut traversal = traversal.with_l1_end(blocks[3]);
+        traversal.block = Some(blocks[0]);
+
+        // With a sequencing window of 3 blocks, the origin must still reach block #3 for the
+        // batch queue to force empty batches for epoch #0 within the pinned range.
+        for number in 1..=3 {
+            assert!(traversal.advance_origin().await.is_ok());
+            assert_eq!(traversal.origin(), Some(blocks[number]));
+        }
+        for _ in 0..3 {
+            assert_eq!(traversal.advance_origin().await.unwrap_err(), PipelineError::Eof.temp());
+        }
+        assert_eq!(traversal.origin(), Some(blocks[3]));
+    }
+
+    #[tokio::test]
+    async fn test_l1_traversal_pin_survives_reset() {
+        let (blocks, traversal) = new_chain_traversal(TestChainProviderBuilder::new(4));
+        let mut traversal = traversal.with_l1_end(blocks[1]);
+        traversal.block = Some(blocks[1]);
+        assert_eq!(traversal.advance_origin().await.unwrap_err(), PipelineError::Eof.temp());
+
+        let reset = ResetSignal {
+            l1_origin: blocks[0],
+            system_config: Some(SystemConfig::default()),
+            ..Default::default()
+        };
+        assert!(traversal.signal(reset.signal()).await.is_ok());
+        assert_eq!(traversal.l1_end, Some(blocks[1]));
+        assert!(traversal.advance_origin().await.is_ok());
+        assert_eq!(traversal.origin(), Some(blocks[1]));
+        assert_eq!(traversal.advance_origin().await.unwrap_err(), PipelineError::Eof.temp());
+    }
+
+    #[tokio::test]
+    async fn test_l1_traversal_advance_origin_to() {
+        const BATCH_INBOX: Address = address!("ff00000000000000000000000000000000000010");
+        let builder = TestChainProviderBuilder::new(8)
+            .with_batch_inbox_address(BATCH_INBOX)
+            .with_log(3, new_update_batcher_log())
+            .with_batcher_tx(5, Bytes::from_static(&[0x00]));
+        let blocks = builder.blocks();
+        let cfg = RollupConfig {
+            l1_system_config_address: L1_SYS_CONFIG_ADDR,
+            batch_inbox_address: BATCH_INBOX,
+            ..Default::default()
+        };
+        let mut traversal = L1Traversal::new(builder.build(), Arc::new(cfg));
+        traversal.block = Some(blocks[0]);
+
+        // The receipts of the skipped blocks are never fetched.
+        let skipped = [1, 2, 4, 6].map(|number| blocks[number].hash);
+        traversal.data_source.receipts.retain(|(hash, _)| !skipped.contains(hash));
+
+        // The origin stops at the system config update in the skipped range, and applies it.
+        traversal.advance_origin_to(blocks[7]).await.unwrap();
+        assert_eq!(traversal.origin(), Some(blocks[3]));
+        let expected = address!("000000000000000000000000000000000000bEEF");
+        assert_eq!(traversal.system_config.batcher_address, expected);
+
+        // It then stops at the batcher transaction, and at the target.
+        traversal.advance_origin_to(blocks[7]).await.unwrap();
+        assert_eq!(traversal.origin(), Some(blocks[5]));
+        traversal.advance_origin_to(blocks[7]).await.unwrap();
+        assert_eq!(traversal.origin(), Some(blocks[7]));
+        assert!(!traversal.done);
+    }
+
+    #[tokio::test]
+    async fn test_l1_traversal_advance_origin_to_reorged_target() {
+        let (blocks, mut traversal) = new_chain_traversal(TestChainProviderBuilder::new(4));
+        traversal.block = Some(blocks[0]);
+
+        // The target was reorged out since the L2 safe head was derived from it.
+        let target = BlockInfo { hash: B256::repeat_byte(0xFF), ..blocks[3] };
+        let err = traversal.advance_origin_to(target).await.unwrap_err();
+        assert_eq!(err, ResetError::ReorgDetected(target.hash, blocks[3].hash).into());
+    }
+
     #[tokio::test]
     async fn test_l1_traversal_reorgs() {
         let hash = b256!("3333333333333333333333333333333333333333333333333333333333333333");
@@ -288,6 +473,22 @@ pub(crate) mod tests {
         assert_eq!(err, ResetError::ReorgDetected(block.hash, block.parent_hash).into());
     }
 
+    #[tokio::test]
+    async fn test_l1_traversal_shallow_reorg() {
+        let (mut blocks, mut traversal) = new_chain_traversal(TestChainProviderBuilder::new(3));
+        // Block #2 was reorged out and replaced by a block that doesn't build on block #1.
+        blocks[2].parent_hash = B256::repeat_byte(0xFF);
+        traversal.data_source.blocks[2].1 = blocks[2];
+        traversal.block = Some(blocks[0]);
+        assert!(traversal.advance_origin().await.is_ok());
+
+        let err = traversal.advance_origin().await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Reset(_)));
+        assert_eq!(err, ResetError::ReorgDetected(blocks[1].hash, blocks[2].parent_hash).into());
+        // The origin is not advanced onto the incompatible chain.
+        assert_eq!(traversal.origin(), Some(blocks[1]));
+    }
+
     #[tokio::test]
     async fn test_l1_traversal_missing_blocks() {
         let mut traversal = new_test_traversal(vec![], vec![]);
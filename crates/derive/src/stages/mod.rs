@@ -18,13 +18,18 @@
 mod l1_traversal;
 pub use l1_traversal::L1Traversal;
 
+mod indexed_traversal;
+pub use indexed_traversal::IndexedTraversal;
+
 mod l1_retrieval;
 pub use l1_retrieval::{L1Retrieval, L1RetrievalProvider};
 
 mod frame_queue;
+pub(crate) use frame_queue::{parse_frames, FRAME_OVERHEAD_LEN};
 pub use frame_queue::{FrameQueue, FrameQueueProvider};
 
 mod channel;
+pub(crate) use channel::{BatchReader, CHANNEL_VERSION_BROTLI};
 pub use channel::{
     ChannelAssembler, ChannelBank, ChannelProvider, ChannelReader, ChannelReaderProvider,
     NextFrameProvider,
@@ -32,7 +37,8 @@ pub use channel::{
 
 mod batch;
 pub use batch::{
-    BatchProvider, BatchQueue, BatchStream, BatchStreamProvider, BatchValidator, NextBatchProvider,
+    BatchProvider, BatchQueue, BatchStream, BatchStreamProvider, BatchValidator,
+    HoloceneSpanPolicy, NextBatchProvider,
 };
 
 mod attributes_queue;
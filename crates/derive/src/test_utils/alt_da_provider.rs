@@ -0,0 +1,42 @@
+//! An implementation of the [AltDaProvider] trait for tests.
+
+use crate::{errors::AltDaError, sources::AltDaCommitment, traits::AltDaProvider};
+use alloc::{boxed::Box, string::ToString};
+use alloy_primitives::{map::HashMap, Bytes};
+use async_trait::async_trait;
+use op_alloy_protocol::BlockInfo;
+
+/// A mock alt-DA provider for testing.
+///
+/// Commitments without a registered input or error fail with [AltDaError::Backend].
+#[derive(Debug, Clone, Default)]
+pub struct TestAltDaProvider {
+    /// Maps commitments to their input data, or the error returned for them.
+    pub inputs: HashMap<AltDaCommitment, Result<Bytes, AltDaError>>,
+}
+
+impl TestAltDaProvider {
+    /// Insert the input data for a commitment.
+    pub fn insert_input(&mut self, commitment: AltDaCommitment, input: Bytes) {
+        self.inputs.insert(commitment, Ok(input));
+    }
+
+    /// Insert the error returned for a commitment.
+    pub fn insert_error(&mut self, commitment: AltDaCommitment, err: AltDaError) {
+        self.inputs.insert(commitment, Err(err));
+    }
+}
+
+#[async_trait]
+impl AltDaProvider for TestAltDaProvider {
+    async fn get_input(
+        &mut self,
+        _: &BlockInfo,
+        commitment: &AltDaCommitment,
+    ) -> Result<Bytes, AltDaError> {
+        self.inputs
+            .get(commitment)
+            .cloned()
+            .unwrap_or_else(|| Err(AltDaError::Backend("Input not found".to_string())))
+    }
+}
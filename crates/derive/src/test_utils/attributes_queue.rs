@@ -5,7 +5,7 @@ use crate::{
     traits::{
         AttributesBuilder, AttributesProvider, OriginAdvancer, OriginProvider, SignalReceiver,
     },
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, Signal, SignalReceipt},
 };
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use alloy_eips::BlockNumHash;
@@ -48,7 +48,7 @@ impl AttributesBuilder for TestAttributesBuilder {
 #[derive(Debug, Default)]
 pub struct TestAttributesProvider {
     /// The origin of the L1 block.
-    origin: Option<BlockInfo>,
+    pub origin: Option<BlockInfo>,
     /// A list of batches to return.
     batches: Vec<PipelineResult<SingleBatch>>,
     /// Tracks if the provider has been reset.
@@ -61,6 +61,10 @@ impl OriginProvider for TestAttributesProvider {
     fn origin(&self) -> Option<BlockInfo> {
         self.origin
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("test-attributes-provider", self.origin()));
+    }
 }
 
 #[async_trait]
@@ -72,13 +76,13 @@ impl OriginAdvancer for TestAttributesProvider {
 
 #[async_trait]
 impl SignalReceiver for TestAttributesProvider {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         match signal {
             Signal::FlushChannel => self.flushed = true,
             Signal::Reset { .. } => self.reset = true,
             _ => {}
         }
-        Ok(())
+        Ok(SignalReceipt::default())
     }
 }
 
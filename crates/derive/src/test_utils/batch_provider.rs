@@ -4,7 +4,7 @@ use crate::{
     errors::PipelineError,
     stages::NextBatchProvider,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, Signal, SignalReceipt},
 };
 use alloc::{boxed::Box, vec::Vec};
 use async_trait::async_trait;
@@ -34,6 +34,10 @@ impl OriginProvider for TestNextBatchProvider {
     fn origin(&self) -> Option<BlockInfo> {
         self.origin
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("test-next-batch-provider", self.origin()));
+    }
 }
 
 #[async_trait]
@@ -64,12 +68,12 @@ impl OriginAdvancer for TestNextBatchProvider {
 
 #[async_trait]
 impl SignalReceiver for TestNextBatchProvider {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         match signal {
             Signal::Reset { .. } => self.reset = true,
             Signal::FlushChannel => self.flushed = true,
             _ => {}
         }
-        Ok(())
+        Ok(SignalReceipt::default())
     }
 }
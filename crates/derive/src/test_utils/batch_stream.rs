@@ -6,7 +6,7 @@ use crate::{
     errors::PipelineError,
     stages::BatchStreamProvider,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, Signal, SignalReceipt},
 };
 use alloc::{boxed::Box, vec::Vec};
 use async_trait::async_trait;
@@ -38,6 +38,10 @@ impl OriginProvider for TestBatchStreamProvider {
     fn origin(&self) -> Option<BlockInfo> {
         self.origin
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("test-batch-stream-provider", self.origin()));
+    }
 }
 
 #[async_trait]
@@ -58,12 +62,12 @@ impl OriginAdvancer for TestBatchStreamProvider {
 
 #[async_trait]
 impl SignalReceiver for TestBatchStreamProvider {
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
         match signal {
             Signal::Reset { .. } => self.reset = true,
             Signal::FlushChannel => self.flushed = true,
             _ => {}
         }
-        Ok(())
+        Ok(SignalReceipt::default())
     }
 }
@@ -0,0 +1,265 @@
+//! Builders for mock chain providers that serve linked chains.
+
+use crate::{
+    sources::{chain_headers, header_block_info},
+    test_utils::{TestChainProvider, TestL2ChainProvider},
+};
+use alloc::{vec, vec::Vec};
+use alloy_consensus::{Eip658Value, Header, Receipt, Signed, TxEnvelope, TxLegacy};
+use alloy_primitives::{
+    keccak256, logs_bloom, Address, Bytes, Log, LogData, Signature, TxKind, B256, U256,
+};
+use op_alloy_consensus::TxDeposit;
+use op_alloy_genesis::SystemConfig;
+use op_alloy_protocol::{BlockInfo, L2BlockInfo, DEPOSIT_EVENT_ABI_HASH};
+
+/// The default block time of a [TestChainProviderBuilder] chain.
+const DEFAULT_L1_BLOCK_TIME: u64 = 12;
+
+/// The default block time of a [TestL2ChainProviderBuilder] chain.
+const DEFAULT_L2_BLOCK_TIME: u64 = 2;
+
+/// A [TestChainProvider] builder that generates a linked L1 chain.
+///
+/// Block `n` of the chain has the timestamp `genesis_timestamp + n * block_time` and links to
+/// block `n - 1` by the hash of its header. Every block has a header, transactions and receipts,
+/// so the chain can be traversed from any block.
+///
+/// Batcher transactions are unprotected legacy transactions to the batch inbox address, signed
+/// with [Signature::test_signature]. Deposits are attached as receipts with a single
+/// `TransactionDeposited` event log, emitted by the deposit contract.
+#[derive(Debug, Clone)]
+pub struct TestChainProviderBuilder {
+    len: u64,
+    block_time: u64,
+    genesis_timestamp: u64,
+    batch_inbox_address: Address,
+    deposit_contract: Address,
+    batcher_txs: Vec<(u64, Bytes)>,
+    deposits: Vec<(u64, TxDeposit)>,
+    logs: Vec<(u64, Log)>,
+}
+
+impl TestChainProviderBuilder {
+    /// Creates a new [TestChainProviderBuilder] for a chain of `len` blocks, starting at block 0.
+    pub const fn new(len: u64) -> Self {
+        Self {
+            len,
+            block_time: DEFAULT_L1_BLOCK_TIME,
+            genesis_timestamp: 0,
+            batch_inbox_address: Address::ZERO,
+            deposit_contract: Address::ZERO,
+            batcher_txs: Vec::new(),
+            deposits: Vec::new(),
+            logs: Vec::new(),
+        }
+    }
+
+    /// Sets the block time of the chain.
+    pub const fn with_block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    /// Sets the timestamp of block 0.
+    pub const fn with_genesis_timestamp(mut self, timestamp: u64) -> Self {
+        self.genesis_timestamp = timestamp;
+        self
+    }
+
+    /// Sets the batch inbox address that batcher transactions are sent to.
+    pub const fn with_batch_inbox_address(mut self, address: Address) -> Self {
+        self.batch_inbox_address = address;
+        self
+    }
+
+    /// Sets the deposit contract address that emits the deposit event logs.
+    pub const fn with_deposit_contract(mut self, address: Address) -> Self {
+        self.deposit_contract = address;
+        self
+    }
+
+    /// Adds a batcher transaction carrying the given data, e.g. encoded frames, to the block with
+    /// the given number. Transactions are included in the order they are added.
+    pub fn with_batcher_tx(mut self, number: u64, data: Bytes) -> Self {
+        self.batcher_txs.push((number, data));
+        self
+    }
+
+    /// Adds a deposit to the block with the given number. Its source hash and system transaction
+    /// flag are ignored, as they are not part of the deposit event.
+    pub fn with_deposit(mut self, number: u64, deposit: TxDeposit) -> Self {
+        self.deposits.push((number, deposit));
+        self
+    }
+
+    /// Adds a receipt emitting the given log, e.g. a system config update, to the block with the
+    /// given number. Receipts of logs follow the receipts of deposits.
+    pub fn with_log(mut self, number: u64, log: Log) -> Self {
+        self.logs.push((number, log));
+        self
+    }
+
+    /// Returns the headers of the chain. The logs bloom of each header covers its receipts.
+    pub fn headers(&self) -> Vec<Header> {
+        chain_headers(0, self.genesis_timestamp, self.block_time, self.len, |header| {
+            let receipts = self.receipts(header.number);
+            header.logs_bloom = logs_bloom(receipts.iter().flat_map(|receipt| receipt.logs.iter()));
+        })
+    }
+
+    /// Returns the blocks of the chain.
+    pub fn blocks(&self) -> Vec<BlockInfo> {
+        self.headers().iter().map(header_block_info).collect()
+    }
+
+    /// Builds the [TestChainProvider].
+    pub fn build(self) -> TestChainProvider {
+        let mut provider = TestChainProvider::default();
+        for header in self.headers() {
+            let block = header_block_info(&header);
+            let txs = self
+                .batcher_txs
+                .iter()
+                .filter(|(number, _)| *number == block.number)
+                .map(|(_, data)| self.batcher_tx(data.clone()))
+                .collect();
+            provider.insert_block_with_transactions(block.number, block, txs);
+            provider.insert_header(block.hash, header);
+            provider.insert_receipts(block.hash, self.receipts(block.number));
+        }
+        provider
+    }
+
+    /// Returns the receipts of the block with the given number.
+    fn receipts(&self, number: u64) -> Vec<Receipt> {
+        let deposit_logs = self
+            .deposits
+            .iter()
+            .filter(|(n, _)| *n == number)
+            .map(|(_, deposit)| deposit_log(self.deposit_contract, deposit));
+        let logs = self.logs.iter().filter(|(n, _)| *n == number).map(|(_, log)| log.clone());
+        deposit_logs
+            .chain(logs)
+            .map(|log| Receipt {
+                status: Eip658Value::Eip658(true),
+                logs: vec![log],
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Returns a batcher transaction carrying the given data.
+    fn batcher_tx(&self, input: Bytes) -> TxEnvelope {
+        let tx =
+            TxLegacy { to: TxKind::Call(self.batch_inbox_address), input, ..Default::default() };
+        TxEnvelope::Legacy(Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO))
+    }
+}
+
+/// Encodes the version 0 `TransactionDeposited` event log of a deposit.
+fn deposit_log(deposit_contract: Address, deposit: &TxDeposit) -> Log {
+    let (to, is_creation) = match deposit.to {
+        TxKind::Call(to) => (to, false),
+        TxKind::Create => (Address::ZERO, true),
+    };
+    let mut opaque = Vec::new();
+    opaque.extend_from_slice(&U256::from(deposit.mint.unwrap_or_default()).to_be_bytes::<32>());
+    opaque.extend_from_slice(&deposit.value.to_be_bytes::<32>());
+    opaque.extend_from_slice(&deposit.gas_limit.to_be_bytes());
+    opaque.push(is_creation as u8);
+    opaque.extend_from_slice(&deposit.input);
+
+    // The opaque data is ABI encoded as dynamic bytes: offset, length, and the padded data.
+    let mut data = Vec::new();
+    data.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+    data.extend_from_slice(&U256::from(opaque.len()).to_be_bytes::<32>());
+    data.extend_from_slice(&opaque);
+    data.resize(data.len().next_multiple_of(32), 0);
+
+    let topics = vec![DEPOSIT_EVENT_ABI_HASH, deposit.from.into_word(), to.into_word(), B256::ZERO];
+    Log { address: deposit_contract, data: LogData::new_unchecked(topics, data.into()) }
+}
+
+/// A [TestL2ChainProvider] builder that derives a consistent L2 chain from an L1 chain.
+///
+/// L2 block 0 starts at the timestamp of the first L1 block. Each L2 block adopts the latest L1
+/// block whose timestamp does not exceed its own as its L1 origin, and its sequence number counts
+/// the blocks since the epoch changed.
+#[derive(Debug, Clone)]
+pub struct TestL2ChainProviderBuilder {
+    l1_chain: Vec<BlockInfo>,
+    len: u64,
+    block_time: u64,
+    system_config: Option<SystemConfig>,
+}
+
+impl TestL2ChainProviderBuilder {
+    /// Creates a new [TestL2ChainProviderBuilder] for a chain of `len` L2 blocks on top of the
+    /// given L1 chain, which must not be empty.
+    pub fn new(l1_chain: &[BlockInfo], len: u64) -> Self {
+        Self {
+            l1_chain: l1_chain.to_vec(),
+            len,
+            block_time: DEFAULT_L2_BLOCK_TIME,
+            system_config: None,
+        }
+    }
+
+    /// Sets the block time of the L2 chain.
+    pub const fn with_block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    /// Sets the system config served for every L2 block.
+    pub const fn with_system_config(mut self, system_config: SystemConfig) -> Self {
+        self.system_config = Some(system_config);
+        self
+    }
+
+    /// Returns the L2 blocks of the chain.
+    pub fn blocks(&self) -> Vec<L2BlockInfo> {
+        let genesis_timestamp = self.l1_chain[0].timestamp;
+        let mut blocks = Vec::<L2BlockInfo>::with_capacity(self.len as usize);
+        let mut epoch = 0;
+        for number in 0..self.len {
+            let timestamp = genesis_timestamp + number * self.block_time;
+            while self.l1_chain.get(epoch + 1).is_some_and(|next| next.timestamp <= timestamp) {
+                epoch += 1;
+            }
+            let l1_origin = self.l1_chain[epoch].id();
+            let (parent_hash, seq_num) = match blocks.last() {
+                Some(parent) if parent.l1_origin == l1_origin => {
+                    (parent.block_info.hash, parent.seq_num + 1)
+                }
+                Some(parent) => (parent.block_info.hash, 0),
+                None => (B256::ZERO, 0),
+            };
+            blocks.push(L2BlockInfo {
+                block_info: BlockInfo {
+                    hash: keccak256(number.to_be_bytes()),
+                    number,
+                    parent_hash,
+                    timestamp,
+                },
+                l1_origin,
+                seq_num,
+            });
+        }
+        blocks
+    }
+
+    /// Builds the [TestL2ChainProvider].
+    pub fn build(self) -> TestL2ChainProvider {
+        let blocks = self.blocks();
+        let mut provider = TestL2ChainProvider::default();
+        if let Some(system_config) = self.system_config {
+            for block in &blocks {
+                provider.system_configs.insert(block.block_info.number, system_config);
+            }
+        }
+        provider.blocks = blocks;
+        provider
+    }
+}
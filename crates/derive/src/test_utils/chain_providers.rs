@@ -1,10 +1,10 @@
 //! Test Utilities for chain provider traits
 
 use crate::{
-    errors::{PipelineError, PipelineErrorKind},
-    traits::{ChainProvider, L2ChainProvider},
+    errors::{PipelineError, PipelineErrorKind, ProviderErrorKind},
+    traits::{ChainProvider, L2ChainProvider, TransientError},
 };
-use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_consensus::{Header, Receipt, TxEnvelope};
 use alloy_primitives::{map::HashMap, B256};
 use async_trait::async_trait;
@@ -78,31 +78,50 @@ impl TestChainProvider {
 /// An error for the [TestChainProvider] and [TestL2ChainProvider].
 #[derive(Debug, derive_more::Display)]
 pub enum TestProviderError {
-    /// The block was not found.
-    #[display("Block not found")]
-    BlockNotFound,
-    /// The header was not found.
-    #[display("Header not found")]
-    HeaderNotFound,
-    /// The receipts were not found.
-    #[display("Receipts not found")]
-    ReceiptsNotFound,
-    /// The L2 block was not found.
-    #[display("L2 Block not found")]
-    L2BlockNotFound,
-    /// The system config was not found.
-    #[display("System config not found")]
+    /// The block with the given number was not found.
+    #[display("Block #{_0} not found")]
+    BlockNotFound(u64),
+    /// The block with the given hash was not found.
+    #[display("Block {_0} not found")]
+    BlockHashNotFound(B256),
+    /// The header with the given hash was not found.
+    #[display("Header {_0} not found")]
+    HeaderNotFound(B256),
+    /// The receipts of the block with the given hash were not found.
+    #[display("Receipts of block {_0} not found")]
+    ReceiptsNotFound(B256),
+    /// The L2 block with the given number was not found.
+    #[display("L2 block #{_0} not found")]
+    L2BlockNotFound(u64),
+    /// The system config of the L2 block with the given number was not found.
+    #[display("System config of L2 block #{_0} not found")]
     SystemConfigNotFound(u64),
 }
 
 impl From<TestProviderError> for PipelineErrorKind {
     fn from(val: TestProviderError) -> Self {
-        PipelineError::Provider(val.to_string()).temp()
+        let kind = match val {
+            TestProviderError::BlockNotFound(number) |
+            TestProviderError::L2BlockNotFound(number) => ProviderErrorKind::BlockNotFound(number),
+            TestProviderError::BlockHashNotFound(hash) |
+            TestProviderError::HeaderNotFound(hash) => ProviderErrorKind::BlockHashNotFound(hash),
+            TestProviderError::ReceiptsNotFound(hash) => ProviderErrorKind::ReceiptsNotFound(hash),
+            TestProviderError::SystemConfigNotFound(number) => {
+                ProviderErrorKind::SystemConfigNotFound(number)
+            }
+        };
+        PipelineError::Provider(kind).temp()
     }
 }
 
 impl core::error::Error for TestProviderError {}
 
+impl TransientError for TestProviderError {
+    fn is_transient(&self) -> bool {
+        false
+    }
+}
+
 #[async_trait]
 impl ChainProvider for TestChainProvider {
     type Error = TestProviderError;
@@ -111,23 +130,23 @@ impl ChainProvider for TestChainProvider {
         if let Some((_, header)) = self.headers.iter().find(|(_, b)| b.hash_slow() == hash) {
             Ok(header.clone())
         } else {
-            Err(TestProviderError::HeaderNotFound)
+            Err(TestProviderError::HeaderNotFound(hash))
         }
     }
 
-    async fn block_info_by_number(&mut self, _number: u64) -> Result<BlockInfo, Self::Error> {
-        if let Some((_, block)) = self.blocks.iter().find(|(n, _)| *n == _number) {
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        if let Some((_, block)) = self.blocks.iter().find(|(n, _)| *n == number) {
             Ok(*block)
         } else {
-            Err(TestProviderError::BlockNotFound)
+            Err(TestProviderError::BlockNotFound(number))
         }
     }
 
-    async fn receipts_by_hash(&mut self, _hash: B256) -> Result<Vec<Receipt>, Self::Error> {
-        if let Some((_, receipts)) = self.receipts.iter().find(|(h, _)| *h == _hash) {
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        if let Some((_, receipts)) = self.receipts.iter().find(|(h, _)| *h == hash) {
             Ok(receipts.clone())
         } else {
-            Err(TestProviderError::ReceiptsNotFound)
+            Err(TestProviderError::ReceiptsNotFound(hash))
         }
     }
 
@@ -140,7 +159,7 @@ impl ChainProvider for TestChainProvider {
             .iter()
             .find(|(_, b)| b.hash == hash)
             .map(|(_, b)| *b)
-            .ok_or_else(|| TestProviderError::BlockNotFound)?;
+            .ok_or(TestProviderError::BlockHashNotFound(hash))?;
         let txs = self
             .transactions
             .iter()
@@ -181,13 +200,13 @@ impl BatchValidationProvider for TestL2ChainProvider {
 
     async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
         if self.short_circuit {
-            return self.blocks.first().copied().ok_or_else(|| TestProviderError::BlockNotFound);
+            return self.blocks.first().copied().ok_or(TestProviderError::BlockNotFound(number));
         }
         self.blocks
             .iter()
             .find(|b| b.block_info.number == number)
             .cloned()
-            .ok_or_else(|| TestProviderError::BlockNotFound)
+            .ok_or(TestProviderError::BlockNotFound(number))
     }
 
     async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
@@ -195,7 +214,7 @@ impl BatchValidationProvider for TestL2ChainProvider {
             .iter()
             .find(|p| p.header.number == number)
             .cloned()
-            .ok_or_else(|| TestProviderError::L2BlockNotFound)
+            .ok_or(TestProviderError::L2BlockNotFound(number))
     }
 }
 
@@ -210,7 +229,7 @@ impl L2ChainProvider for TestL2ChainProvider {
     ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
         self.system_configs
             .get(&number)
-            .ok_or_else(|| TestProviderError::SystemConfigNotFound(number))
+            .ok_or(TestProviderError::SystemConfigNotFound(number))
             .cloned()
     }
 }
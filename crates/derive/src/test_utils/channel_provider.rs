@@ -6,7 +6,7 @@ use crate::{
     errors::PipelineError,
     stages::NextFrameProvider,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, Signal, SignalReceipt},
 };
 use alloc::{boxed::Box, vec::Vec};
 use async_trait::async_trait;
@@ -36,6 +36,10 @@ impl OriginProvider for TestNextFrameProvider {
     fn origin(&self) -> Option<BlockInfo> {
         self.block_info
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("test-next-frame-provider", self.origin()));
+    }
 }
 
 #[async_trait]
@@ -58,8 +62,8 @@ impl NextFrameProvider for TestNextFrameProvider {
 
 #[async_trait]
 impl SignalReceiver for TestNextFrameProvider {
-    async fn signal(&mut self, _: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, _: Signal) -> PipelineResult<SignalReceipt> {
         self.reset = true;
-        Ok(())
+        Ok(SignalReceipt::default())
     }
 }
@@ -6,7 +6,7 @@ use crate::{
     errors::PipelineError,
     stages::ChannelReaderProvider,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, Signal, SignalReceipt},
 };
 use alloc::{boxed::Box, vec::Vec};
 use alloy_primitives::Bytes;
@@ -37,6 +37,10 @@ impl OriginProvider for TestChannelReaderProvider {
     fn origin(&self) -> Option<BlockInfo> {
         self.block_info
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("test-channel-reader-provider", self.origin()));
+    }
 }
 
 #[async_trait]
@@ -55,8 +59,8 @@ impl ChannelReaderProvider for TestChannelReaderProvider {
 
 #[async_trait]
 impl SignalReceiver for TestChannelReaderProvider {
-    async fn signal(&mut self, _: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, _: Signal) -> PipelineResult<SignalReceipt> {
         self.reset = true;
-        Ok(())
+        Ok(SignalReceipt::default())
     }
 }
@@ -0,0 +1,49 @@
+//! Contains a bounded, in-memory [EventSink].
+
+use crate::{traits::EventSink, types::PipelineEvent};
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use spin::Mutex;
+
+/// A bounded, in-memory [EventSink] that retains the most recent events.
+///
+/// The sink is a cheap handle around shared storage, so a clone can be registered on the
+/// pipeline while another is kept to inspect the events.
+#[derive(Debug, Default, Clone)]
+pub struct BoundedEventSink {
+    /// The maximum number of events retained. Zero means unbounded.
+    capacity: usize,
+    /// The retained events, oldest first.
+    events: Arc<Mutex<VecDeque<PipelineEvent>>>,
+}
+
+impl BoundedEventSink {
+    /// Creates a new [BoundedEventSink] that retains at most `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, events: Default::default() }
+    }
+
+    /// Returns a copy of the retained events, oldest first.
+    pub fn events(&self) -> Vec<PipelineEvent> {
+        self.events.lock().iter().copied().collect()
+    }
+
+    /// Returns the number of retained events.
+    pub fn len(&self) -> usize {
+        self.events.lock().len()
+    }
+
+    /// Returns whether no events are retained.
+    pub fn is_empty(&self) -> bool {
+        self.events.lock().is_empty()
+    }
+}
+
+impl EventSink for BoundedEventSink {
+    fn emit(&mut self, event: PipelineEvent) {
+        let mut events = self.events.lock();
+        if self.capacity != 0 && events.len() >= self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+}
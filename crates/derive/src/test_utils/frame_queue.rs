@@ -4,7 +4,7 @@ use crate::{
     errors::PipelineError,
     stages::FrameQueueProvider,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, Signal, SignalReceipt},
 };
 use alloc::{boxed::Box, vec::Vec};
 use alloy_primitives::Bytes;
@@ -40,6 +40,10 @@ impl OriginProvider for TestFrameQueueProvider {
     fn origin(&self) -> Option<BlockInfo> {
         self.origin
     }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("test-frame-queue-provider", self.origin()));
+    }
 }
 
 #[async_trait]
@@ -60,8 +64,8 @@ impl FrameQueueProvider for TestFrameQueueProvider {
 
 #[async_trait]
 impl SignalReceiver for TestFrameQueueProvider {
-    async fn signal(&mut self, _: Signal) -> PipelineResult<()> {
+    async fn signal(&mut self, _: Signal) -> PipelineResult<SignalReceipt> {
         self.reset = true;
-        Ok(())
+        Ok(SignalReceipt::default())
     }
 }
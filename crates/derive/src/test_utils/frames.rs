@@ -67,10 +67,13 @@ impl FrameQueueBuilder {
         self
     }
 
-    /// Adds frames to the mock provider.
+    /// Adds a batcher transaction carrying the frames to the mock provider.
+    ///
+    /// Transactions are returned in the order they are added.
     pub fn with_frames(mut self, frames: &[Frame]) -> Self {
-        let encoded = encode_frames(frames);
-        let mock = self.mock.unwrap_or_else(|| TestFrameQueueProvider::new(vec![Ok(encoded)]));
+        let mut mock = self.mock.unwrap_or_default();
+        // The mock provider pops data from the back.
+        mock.data.insert(0, Ok(encode_frames(frames)));
         self.mock = Some(mock);
         self
     }
@@ -7,12 +7,24 @@ pub use pipeline::{
     TestNextAttributes, TestPipeline,
 };
 
+mod events;
+pub use events::BoundedEventSink;
+
+mod replay;
+pub use replay::TestReplaySink;
+
 mod blob_provider;
 pub use blob_provider::TestBlobProvider;
 
+mod alt_da_provider;
+pub use alt_da_provider::TestAltDaProvider;
+
 mod chain_providers;
 pub use chain_providers::{TestChainProvider, TestL2ChainProvider, TestProviderError};
 
+mod chain_builders;
+pub use chain_builders::{TestChainProviderBuilder, TestL2ChainProviderBuilder};
+
 mod data_availability_provider;
 pub use data_availability_provider::TestDAP;
 
@@ -38,7 +50,7 @@ mod frame_queue;
 pub use frame_queue::TestFrameQueueProvider;
 
 mod tracing;
-pub use tracing::{CollectingLayer, TraceStorage};
+pub use tracing::{CollectingLayer, SpanCollectingLayer, SpanStorage, TraceStorage};
 
 mod sys_config_fetcher;
 pub use sys_config_fetcher::{TestSystemConfigL2Fetcher, TestSystemConfigL2FetcherError};
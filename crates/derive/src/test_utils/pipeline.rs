@@ -6,14 +6,14 @@ use crate::{
     test_utils::{TestChainProvider, TestL2ChainProvider},
     types::PipelineResult,
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
 use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::{BlockInfo, L2BlockInfo};
 use op_alloy_rpc_types_engine::OpAttributesWithParent;
 
 // Re-export these types used internally to the test pipeline.
 use crate::{
-    errors::PipelineError,
+    errors::{PipelineError, PipelineErrorKind},
     pipeline::{DerivationPipeline, PipelineBuilder},
     stages::{
         AttributesQueue, BatchStream, ChannelProvider, ChannelReader, FrameQueue, L1Retrieval,
@@ -21,21 +21,53 @@ use crate::{
     },
     test_utils::{TestAttributesBuilder, TestDAP},
     traits::{NextAttributes, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::Signal,
+    types::{Signal, SignalReceipt},
 };
 
 /// A fully custom [NextAttributes].
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug)]
 pub struct TestNextAttributes {
     /// The next [OpAttributesWithParent] to return.
     pub next_attributes: Option<OpAttributesWithParent>,
+    /// A queue of [OpAttributesWithParent] to return once `next_attributes` is consumed.
+    pub queue: VecDeque<OpAttributesWithParent>,
+    /// An error to return from the next call to [NextAttributes::next_attributes].
+    pub step_error: Option<PipelineErrorKind>,
+    /// The number of [PipelineError::NotEnoughData] errors to return from
+    /// [NextAttributes::next_attributes] before any attributes.
+    pub not_enough_data: usize,
+    /// The number of [Signal::FlushChannel] signals received.
+    pub flushed: usize,
+    /// The number of [Signal::Reset] signals received.
+    pub resets: usize,
+    /// The number of [Signal::Activation] signals received.
+    pub activations: usize,
+    /// The current origin, whose number is incremented on each origin advance.
+    pub origin: BlockInfo,
+    /// The highest origin number that can be advanced to. Further advances fail with
+    /// [PipelineError::Eof].
+    pub origin_limit: Option<u64>,
+}
+
+impl TestNextAttributes {
+    /// Creates a new [TestNextAttributes] that returns the given attributes in order.
+    pub fn with_queue(attributes: Vec<OpAttributesWithParent>) -> Self {
+        Self { queue: attributes.into(), ..Default::default() }
+    }
 }
 
 #[async_trait::async_trait]
 impl SignalReceiver for TestNextAttributes {
     /// Resets the derivation stage to its initial state.
-    async fn signal(&mut self, _: Signal) -> PipelineResult<()> {
-        Ok(())
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt> {
+        match signal {
+            Signal::Reset(_) => self.resets += 1,
+            Signal::Activation(_) => self.activations += 1,
+            Signal::FlushChannel => self.flushed += 1,
+            Signal::AdvanceOrigin => {}
+        }
+        let stages_reset = matches!(signal, Signal::Reset(_) | Signal::Activation(_));
+        Ok(SignalReceipt { stages_reset, ..Default::default() })
     }
 }
 
@@ -43,7 +75,11 @@ impl SignalReceiver for TestNextAttributes {
 impl OriginProvider for TestNextAttributes {
     /// Returns the current origin.
     fn origin(&self) -> Option<BlockInfo> {
-        Some(BlockInfo::default())
+        Some(self.origin)
+    }
+
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>) {
+        origins.push(("test-next-attributes", self.origin()));
     }
 }
 
@@ -51,6 +87,18 @@ impl OriginProvider for TestNextAttributes {
 impl OriginAdvancer for TestNextAttributes {
     /// Advances the origin to the given block.
     async fn advance_origin(&mut self) -> PipelineResult<()> {
+        if self.origin_limit.is_some_and(|limit| self.origin.number >= limit) {
+            return Err(PipelineError::Eof.temp());
+        }
+        self.origin.number += 1;
+        Ok(())
+    }
+
+    /// Advances the origin straight to the target block, as if the blocks before it were empty.
+    async fn advance_origin_to(&mut self, target: BlockInfo) -> PipelineResult<()> {
+        self.advance_origin().await?;
+        let target = self.origin_limit.map_or(target.number, |limit| target.number.min(limit));
+        self.origin.number = self.origin.number.max(target);
         Ok(())
     }
 }
@@ -59,7 +107,21 @@ impl OriginAdvancer for TestNextAttributes {
 impl NextAttributes for TestNextAttributes {
     /// Returns the next valid [OpAttributesWithParent].
     async fn next_attributes(&mut self, _: L2BlockInfo) -> PipelineResult<OpAttributesWithParent> {
-        self.next_attributes.take().ok_or(PipelineError::Eof.temp())
+        if let Some(err) = self.step_error.take() {
+            return Err(err);
+        }
+        if self.not_enough_data > 0 {
+            self.not_enough_data -= 1;
+            return Err(PipelineError::NotEnoughData.temp());
+        }
+        self.next_attributes
+            .take()
+            .or_else(|| self.queue.pop_front())
+            .ok_or(PipelineError::Eof.temp())
+    }
+
+    fn derived_from(&self) -> Option<BlockInfo> {
+        self.origin()
     }
 }
 
@@ -94,10 +156,12 @@ pub type TestPipeline = DerivationPipeline<TestAttributesQueue, TestL2ChainProvi
 pub fn new_test_pipeline() -> TestPipeline {
     PipelineBuilder::new()
         .rollup_config(Arc::new(RollupConfig::default()))
+        .validate_config(false)
         .origin(BlockInfo::default())
         .dap_source(TestDAP::default())
         .builder(TestAttributesBuilder::default())
         .chain_provider(TestChainProvider::default())
         .l2_chain_provider(TestL2ChainProvider::default())
         .build()
+        .expect("test pipeline")
 }
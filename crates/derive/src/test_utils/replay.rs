@@ -0,0 +1,28 @@
+//! Contains an in-memory [ReplaySink].
+
+use crate::{traits::ReplaySink, types::ReplayEntry};
+use alloc::{sync::Arc, vec::Vec};
+use spin::Mutex;
+
+/// An in-memory [ReplaySink] that retains every recorded entry.
+///
+/// The sink is a cheap handle around shared storage, so a clone can be handed to the recording
+/// provider while another is kept to inspect the entries.
+#[derive(Debug, Default, Clone)]
+pub struct TestReplaySink {
+    /// The recorded entries, in the order they were appended.
+    entries: Arc<Mutex<Vec<ReplayEntry>>>,
+}
+
+impl TestReplaySink {
+    /// Returns a copy of the recorded entries, in the order they were appended.
+    pub fn entries(&self) -> Vec<ReplayEntry> {
+        self.entries.lock().clone()
+    }
+}
+
+impl ReplaySink for TestReplaySink {
+    fn append(&self, entry: ReplayEntry) {
+        self.entries.lock().push(entry);
+    }
+}
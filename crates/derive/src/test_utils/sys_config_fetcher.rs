@@ -1,10 +1,10 @@
 //! Implements a mock [L2SystemConfigFetcher] for testing.
 
 use crate::{
-    errors::{PipelineError, PipelineErrorKind},
+    errors::{PipelineError, PipelineErrorKind, ProviderErrorKind},
     traits::L2ChainProvider,
 };
-use alloc::{boxed::Box, string::ToString, sync::Arc};
+use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::map::HashMap;
 use async_trait::async_trait;
 use op_alloy_consensus::OpBlock;
@@ -12,10 +12,14 @@ use op_alloy_genesis::{RollupConfig, SystemConfig};
 use op_alloy_protocol::{BatchValidationProvider, L2BlockInfo};
 
 /// A mock implementation of the `SystemConfigL2Fetcher` for testing.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct TestSystemConfigL2Fetcher {
     /// A map from [u64] block number to a [SystemConfig].
     pub system_configs: HashMap<u64, SystemConfig>,
+    /// A map from [u64] block number to an [L2BlockInfo].
+    pub l2_block_infos: HashMap<u64, L2BlockInfo>,
+    /// A map from [u64] block number to an [OpBlock].
+    pub blocks: HashMap<u64, OpBlock>,
 }
 
 impl TestSystemConfigL2Fetcher {
@@ -24,9 +28,21 @@ impl TestSystemConfigL2Fetcher {
         self.system_configs.insert(number, config);
     }
 
-    /// Clears all system configs from the mock fetcher.
+    /// Inserts a new [L2BlockInfo] into the mock fetcher, keyed by its block number.
+    pub fn insert_l2_block_info(&mut self, block: L2BlockInfo) {
+        self.l2_block_infos.insert(block.block_info.number, block);
+    }
+
+    /// Inserts a new [OpBlock] into the mock fetcher, keyed by its header number.
+    pub fn insert_block(&mut self, block: OpBlock) {
+        self.blocks.insert(block.header.number, block);
+    }
+
+    /// Clears all system configs, L2 block infos and blocks from the mock fetcher.
     pub fn clear(&mut self) {
         self.system_configs.clear();
+        self.l2_block_infos.clear();
+        self.blocks.clear();
     }
 }
 
@@ -36,11 +52,26 @@ pub enum TestSystemConfigL2FetcherError {
     /// The system config was not found.
     #[display("system config not found: {_0}")]
     NotFound(u64),
+    /// The L2 block info was not found.
+    #[display("L2 block info not found: {_0}")]
+    L2BlockInfoNotFound(u64),
+    /// The L2 block was not found.
+    #[display("L2 block not found: {_0}")]
+    BlockNotFound(u64),
 }
 
 impl From<TestSystemConfigL2FetcherError> for PipelineErrorKind {
     fn from(val: TestSystemConfigL2FetcherError) -> Self {
-        PipelineError::Provider(val.to_string()).temp()
+        let kind = match val {
+            TestSystemConfigL2FetcherError::NotFound(number) => {
+                ProviderErrorKind::SystemConfigNotFound(number)
+            }
+            TestSystemConfigL2FetcherError::L2BlockInfoNotFound(number) |
+            TestSystemConfigL2FetcherError::BlockNotFound(number) => {
+                ProviderErrorKind::BlockNotFound(number)
+            }
+        };
+        PipelineError::Provider(kind).temp()
     }
 }
 
@@ -50,12 +81,18 @@ impl core::error::Error for TestSystemConfigL2FetcherError {}
 impl BatchValidationProvider for TestSystemConfigL2Fetcher {
     type Error = TestSystemConfigL2FetcherError;
 
-    async fn block_by_number(&mut self, _: u64) -> Result<OpBlock, Self::Error> {
-        unimplemented!()
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        self.blocks
+            .get(&number)
+            .cloned()
+            .ok_or_else(|| TestSystemConfigL2FetcherError::BlockNotFound(number))
     }
 
-    async fn l2_block_info_by_number(&mut self, _: u64) -> Result<L2BlockInfo, Self::Error> {
-        unimplemented!()
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        self.l2_block_infos
+            .get(&number)
+            .copied()
+            .ok_or_else(|| TestSystemConfigL2FetcherError::L2BlockInfoNotFound(number))
     }
 }
 
@@ -1,10 +1,10 @@
-//! This module contains a subscriber layer for `tracing-subscriber` that collects traces and their
-//! log levels.
+//! This module contains subscriber layers for `tracing-subscriber` that collect traces and their
+//! log levels, and spans and their parents.
 
 use alloc::{format, string::String, sync::Arc, vec::Vec};
 use spin::Mutex;
-use tracing::{Event, Level, Subscriber};
-use tracing_subscriber::{layer::Context, Layer};
+use tracing::{span::Attributes, Event, Id, Level, Subscriber};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
 
 /// The storage for the collected traces.
 #[derive(Debug, Default, Clone)]
@@ -55,3 +55,39 @@ impl<S: Subscriber> Layer<S> for CollectingLayer {
         storage.push((level, message));
     }
 }
+
+/// The storage for the collected spans, as the name of each span and the name of its parent.
+#[derive(Debug, Default, Clone)]
+pub struct SpanStorage(pub Arc<Mutex<Vec<(&'static str, Option<&'static str>)>>>);
+
+impl SpanStorage {
+    /// Returns the name of the parent of the first span with the given name, or `None` if no such
+    /// span was collected.
+    pub fn parent_of(&self, name: &str) -> Option<Option<&'static str>> {
+        self.0.lock().iter().find(|(span, _)| *span == name).map(|(_, parent)| *parent)
+    }
+}
+
+/// A subscriber layer that collects the spans that are created and their parents.
+#[derive(Debug, Default)]
+pub struct SpanCollectingLayer {
+    /// The storage for the collected spans.
+    pub storage: SpanStorage,
+}
+
+impl SpanCollectingLayer {
+    /// Creates a new span collecting layer with the specified storage.
+    pub const fn new(storage: SpanStorage) -> Self {
+        Self { storage }
+    }
+}
+
+impl<S> Layer<S> for SpanCollectingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let parent = ctx.span(id).and_then(|span| span.parent()).map(|parent| parent.name());
+        self.storage.0.lock().push((attrs.metadata().name(), parent));
+    }
+}
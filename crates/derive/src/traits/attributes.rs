@@ -4,7 +4,7 @@ use crate::types::PipelineResult;
 use alloc::boxed::Box;
 use alloy_eips::BlockNumHash;
 use async_trait::async_trait;
-use op_alloy_protocol::{L2BlockInfo, SingleBatch};
+use op_alloy_protocol::{BlockInfo, L2BlockInfo, SingleBatch};
 use op_alloy_rpc_types_engine::{OpAttributesWithParent, OpPayloadAttributes};
 
 /// [AttributesProvider] is a trait abstraction that generalizes the [BatchQueue] stage.
@@ -28,6 +28,12 @@ pub trait NextAttributes {
         &mut self,
         parent: L2BlockInfo,
     ) -> PipelineResult<OpAttributesWithParent>;
+
+    /// Returns the L1 origin that the most recently returned [OpAttributesWithParent] were
+    /// derived from, if any. Defaults to [None] for implementations that do not track it.
+    fn derived_from(&self) -> Option<BlockInfo> {
+        None
+    }
 }
 
 /// The [AttributesBuilder] is responsible for preparing [OpPayloadAttributes]
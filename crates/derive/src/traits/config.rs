@@ -0,0 +1,229 @@
+//! Contains the [ValidateRollupConfig] extension trait.
+
+use crate::errors::ConfigError;
+use alloc::{string::ToString, vec::Vec};
+use alloy_primitives::Address;
+use op_alloy_genesis::RollupConfig;
+
+/// Sanity checks the internal consistency of a [RollupConfig].
+///
+/// A subtly wrong rollup config does not fail on construction of the pipeline, but leads to
+/// confusing derivation behavior much later. Validating up front surfaces these errors early.
+pub trait ValidateRollupConfig {
+    /// Validates the config, returning every inconsistency found.
+    fn validate(&self) -> Result<(), Vec<ConfigError>>;
+}
+
+impl ValidateRollupConfig for RollupConfig {
+    fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        if self.block_time == 0 {
+            errors.push(ConfigError::ZeroBlockTime);
+        }
+        if self.seq_window_size == 0 {
+            errors.push(ConfigError::ZeroSeqWindowSize);
+        }
+        if self.channel_timeout == 0 {
+            errors.push(ConfigError::ZeroChannelTimeout);
+        }
+        if self.seq_window_size < self.channel_timeout {
+            errors.push(ConfigError::SeqWindowSmallerThanChannelTimeout(
+                self.seq_window_size,
+                self.channel_timeout,
+            ));
+        }
+        if self.l1_chain_id == 0 {
+            errors.push(ConfigError::ZeroL1ChainId);
+        }
+        if self.l2_chain_id == 0 {
+            errors.push(ConfigError::ZeroL2ChainId);
+        }
+        if self.genesis.system_config.is_none() {
+            errors.push(ConfigError::MissingGenesisSystemConfig);
+        }
+        if self.batch_inbox_address == Address::ZERO {
+            errors.push(ConfigError::ZeroBatchInboxAddress);
+        }
+
+        let forks = [
+            ("regolith_time", self.regolith_time),
+            ("canyon_time", self.canyon_time),
+            ("delta_time", self.delta_time),
+            ("ecotone_time", self.ecotone_time),
+            ("fjord_time", self.fjord_time),
+            ("granite_time", self.granite_time),
+            ("holocene_time", self.holocene_time),
+        ];
+        for pair in forks.windows(2) {
+            let (prev_name, prev) = pair[0];
+            let (name, time) = pair[1];
+            let Some(time) = time else { continue };
+            match prev {
+                None => errors.push(ConfigError::MissingPredecessorFork(
+                    name.to_string(),
+                    time,
+                    prev_name.to_string(),
+                )),
+                Some(prev) if time < prev => errors.push(ConfigError::ForkOutOfOrder(
+                    name.to_string(),
+                    time,
+                    prev_name.to_string(),
+                    prev,
+                )),
+                _ => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::{address, U256};
+    use op_alloy_genesis::{SystemConfig, OP_MAINNET_CONFIG};
+
+    fn valid_config() -> RollupConfig {
+        let mut cfg = OP_MAINNET_CONFIG;
+        cfg.genesis.system_config = Some(SystemConfig::default());
+        cfg
+    }
+
+    fn errors(cfg: RollupConfig) -> Vec<ConfigError> {
+        cfg.validate().unwrap_err()
+    }
+
+    #[test]
+    fn test_validate_mainnet() {
+        assert_eq!(valid_config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_zero_block_time() {
+        let cfg = RollupConfig { block_time: 0, ..valid_config() };
+        let errs = errors(cfg);
+        assert_eq!(errs, vec![ConfigError::ZeroBlockTime]);
+        assert_eq!(errs[0].to_string(), "block_time must be non-zero, got 0");
+    }
+
+    #[test]
+    fn test_validate_zero_seq_window_size() {
+        let cfg = RollupConfig { seq_window_size: 0, ..valid_config() };
+        let errs = errors(cfg);
+        assert!(errs.contains(&ConfigError::ZeroSeqWindowSize));
+    }
+
+    #[test]
+    fn test_validate_zero_channel_timeout() {
+        let cfg = RollupConfig { channel_timeout: 0, ..valid_config() };
+        assert_eq!(errors(cfg), vec![ConfigError::ZeroChannelTimeout]);
+    }
+
+    #[test]
+    fn test_validate_seq_window_smaller_than_channel_timeout() {
+        let cfg = RollupConfig { seq_window_size: 10, channel_timeout: 300, ..valid_config() };
+        let errs = errors(cfg);
+        assert_eq!(errs, vec![ConfigError::SeqWindowSmallerThanChannelTimeout(10, 300)]);
+        assert_eq!(
+            errs[0].to_string(),
+            "seq_window_size (10) must not be smaller than channel_timeout (300)"
+        );
+    }
+
+    #[test]
+    fn test_validate_zero_chain_ids() {
+        let cfg = RollupConfig { l1_chain_id: 0, l2_chain_id: 0, ..valid_config() };
+        assert_eq!(errors(cfg), vec![ConfigError::ZeroL1ChainId, ConfigError::ZeroL2ChainId]);
+    }
+
+    #[test]
+    fn test_validate_missing_genesis_system_config() {
+        let mut cfg = valid_config();
+        cfg.genesis.system_config = None;
+        assert_eq!(errors(cfg), vec![ConfigError::MissingGenesisSystemConfig]);
+    }
+
+    #[test]
+    fn test_validate_zero_batch_inbox() {
+        let cfg = RollupConfig { batch_inbox_address: Address::ZERO, ..valid_config() };
+        assert_eq!(errors(cfg), vec![ConfigError::ZeroBatchInboxAddress]);
+    }
+
+    #[test]
+    fn test_validate_fork_out_of_order() {
+        let cfg = RollupConfig { fjord_time: Some(10), granite_time: Some(5), ..valid_config() };
+        let errs = errors(cfg);
+        assert!(errs.contains(&ConfigError::ForkOutOfOrder(
+            "granite_time".to_string(),
+            5,
+            "fjord_time".to_string(),
+            10
+        )));
+        assert!(errs
+            .iter()
+            .any(|e| e.to_string() == "granite_time (5) must not be before fjord_time (10)"));
+    }
+
+    #[test]
+    fn test_validate_missing_predecessor_fork() {
+        let cfg = RollupConfig {
+            regolith_time: Some(0),
+            canyon_time: None,
+            delta_time: None,
+            ecotone_time: None,
+            fjord_time: None,
+            granite_time: None,
+            holocene_time: Some(100),
+            ..valid_config()
+        };
+        let errs = errors(cfg);
+        assert_eq!(
+            errs,
+            vec![ConfigError::MissingPredecessorFork(
+                "holocene_time".to_string(),
+                100,
+                "granite_time".to_string()
+            )]
+        );
+        assert_eq!(
+            errs[0].to_string(),
+            "holocene_time is set (100) but its predecessor granite_time is not"
+        );
+    }
+
+    #[test]
+    fn test_registry_configs() {
+        // Registry bumps that change these fields change the derived chain, so they must not go
+        // unnoticed.
+        let op = RollupConfig::from_l2_chain_id(10).unwrap();
+        assert_eq!((op.l1_chain_id, op.l2_chain_id, op.block_time), (1, 10, 2));
+        assert_eq!(op.genesis.l2.number, 105_235_063);
+        assert_eq!(op.ecotone_time, Some(1_710_374_401));
+        assert_eq!(op.fjord_time, Some(1_720_627_201));
+        assert_eq!(op.granite_time, Some(1_726_070_401));
+        let sys = op.genesis.system_config.unwrap();
+        assert_eq!(sys.batcher_address, address!("6887246668a3b87f54deb3b94ba47a6f63f32985"));
+        assert_eq!(sys.gas_limit, 30_000_000);
+        assert_eq!((sys.overhead, sys.scalar), (U256::from(0xbc), U256::from(0xa6fe0)));
+
+        let base = RollupConfig::from_l2_chain_id(8453).unwrap();
+        assert_eq!((base.l1_chain_id, base.l2_chain_id, base.block_time), (1, 8453, 2));
+        assert_eq!(base.genesis.l2.number, 0);
+        assert_eq!(base.ecotone_time, Some(1_710_374_401));
+        assert_eq!(base.fjord_time, Some(1_720_627_201));
+        assert_eq!(base.granite_time, Some(1_726_070_401));
+        let sys = base.genesis.system_config.unwrap();
+        assert_eq!(sys.batcher_address, address!("5050f69a9786f081509234f1a7f4684b5e5b76c9"));
+        assert_eq!(sys.gas_limit, 30_000_000);
+        assert_eq!((sys.overhead, sys.scalar), (U256::from(0xbc), U256::from(0xa6fe0)));
+
+        assert_eq!(RollupConfig::from_l2_chain_id(0), None);
+    }
+}
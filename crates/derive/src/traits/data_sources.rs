@@ -1,7 +1,11 @@
 //! Contains traits that describe the functionality of various data sources used in the derivation
 //! pipeline's stages.
 
-use crate::{errors::PipelineErrorKind, sources::IndexedBlobHash, types::PipelineResult};
+use crate::{
+    errors::{AltDaError, PipelineErrorKind},
+    sources::{AltDaCommitment, IndexedBlobHash},
+    types::PipelineResult,
+};
 use alloc::{boxed::Box, fmt::Debug, string::ToString, vec::Vec};
 use alloy_eips::eip4844::Blob;
 use alloy_primitives::Bytes;
@@ -23,6 +27,18 @@ pub trait BlobProvider {
     ) -> Result<Vec<Box<Blob>>, Self::Error>;
 }
 
+/// The AltDaProvider trait specifies the functionality of an alternative data availability
+/// layer, which resolves the commitments posted by the batcher to their input data.
+#[async_trait]
+pub trait AltDaProvider {
+    /// Fetches the input data committed to by `commitment`, posted in the given L1 block.
+    async fn get_input(
+        &mut self,
+        block_ref: &BlockInfo,
+        commitment: &AltDaCommitment,
+    ) -> Result<Bytes, AltDaError>;
+}
+
 /// Describes the functionality of a data source that can provide data availability information.
 #[async_trait]
 pub trait DataAvailabilityProvider {
@@ -0,0 +1,25 @@
+//! Contains the [DepositFilter] trait for filtering user deposits during attributes derivation.
+
+use core::fmt::Debug;
+use op_alloy_consensus::TxDeposit;
+
+/// Filters the user deposits derived from `TransactionDeposited` events before they are
+/// included in the payload attributes.
+///
+/// The filter is invoked once per deposit, in log order, and may pass the deposit through,
+/// transform it, or drop it. Decisions must be deterministic: every node deriving the same L1
+/// block with the same filter has to produce the same deposits.
+pub trait DepositFilter: Debug + Send + Sync {
+    /// Returns the deposit to include in the payload attributes, or [None] to drop it.
+    ///
+    /// By default, the deposit is passed through unchanged.
+    fn filter(&self, deposit: TxDeposit) -> Option<TxDeposit> {
+        Some(deposit)
+    }
+}
+
+/// A [DepositFilter] implementation that passes every deposit through unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PassthroughDepositFilter;
+
+impl DepositFilter for PassthroughDepositFilter {}
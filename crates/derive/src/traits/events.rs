@@ -0,0 +1,13 @@
+//! Contains the [EventSink] trait for observing the derivation pipeline.
+
+use crate::types::PipelineEvent;
+use core::fmt::Debug;
+
+/// Receives [PipelineEvent]s from the derivation pipeline.
+///
+/// Events are emitted only after the state change they describe has been committed, so an
+/// observer never sees progress that is later rolled back within the same call.
+pub trait EventSink: Debug + Send + Sync {
+    /// Handles a single [PipelineEvent].
+    fn emit(&mut self, event: PipelineEvent);
+}
@@ -0,0 +1,45 @@
+//! Contains the [DerivationMetrics] trait for instrumenting the derivation pipeline.
+
+use crate::types::ChannelCloseReason;
+use core::fmt::Debug;
+use op_alloy_protocol::BatchValidity;
+
+/// Records metrics about the health of the derivation pipeline.
+///
+/// The stages hold a shared handle to the metrics and call into it as data flows through them,
+/// so implementations must use interior mutability. Every method defaults to a no-op, letting an
+/// implementation record only the metrics it is interested in.
+pub trait DerivationMetrics: Debug + Send + Sync {
+    /// Records a frame parsed from a batcher transaction, with the length of its data.
+    fn record_frame(&self, _len: usize) {}
+
+    /// Records a channel opened by its first frame.
+    fn record_channel_opened(&self) {}
+
+    /// Records a channel that was closed, along with the reason it was closed.
+    fn record_channel_closed(&self, _reason: ChannelCloseReason) {}
+
+    /// Records the final [BatchValidity] of a batch that was accepted or discarded.
+    fn record_batch(&self, _validity: BatchValidity) {}
+
+    /// Records payload attributes prepared for the pipeline consumer.
+    fn record_attributes_prepared(&self) {}
+
+    /// Records the number of L1 blocks the pipeline origin is ahead of the L1 origin of the L2
+    /// safe head.
+    fn record_safe_origin_distance(&self, _distance: u64) {}
+
+    /// Records the number of L1 blocks the pipeline origin is behind the L1 head.
+    ///
+    /// The pipeline does not know the L1 head, so this is left to the driver, e.g. with the value
+    /// of [Pipeline::origin_lag].
+    ///
+    /// [Pipeline::origin_lag]: crate::traits::Pipeline::origin_lag
+    fn record_l1_head_lag(&self, _lag: u64) {}
+}
+
+/// A [DerivationMetrics] implementation that records nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoopMetrics;
+
+impl DerivationMetrics for NoopMetrics {}
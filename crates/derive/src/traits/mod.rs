@@ -11,10 +11,31 @@ mod attributes;
 pub use attributes::{AttributesBuilder, AttributesProvider, NextAttributes};
 
 mod data_sources;
-pub use data_sources::{BlobProvider, DataAvailabilityProvider};
+pub use data_sources::{AltDaProvider, BlobProvider, DataAvailabilityProvider};
 
 mod reset;
 pub use reset::ResetProvider;
 
 mod stages;
 pub use stages::{OriginAdvancer, OriginProvider, SignalReceiver};
+
+mod config;
+pub use config::ValidateRollupConfig;
+
+mod events;
+pub use events::EventSink;
+
+mod replay;
+pub use replay::ReplaySink;
+
+mod retry;
+pub use retry::{RetrySleep, TransientError};
+
+mod metrics;
+pub use metrics::{DerivationMetrics, NoopMetrics};
+
+mod deposits;
+pub use deposits::{DepositFilter, PassthroughDepositFilter};
+
+mod sequencer;
+pub use sequencer::{SequencerPolicy, VerifierPolicy};
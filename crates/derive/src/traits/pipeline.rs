@@ -1,13 +1,18 @@
 //! Defines the interface for the core derivation pipeline.
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use async_trait::async_trait;
 use core::iter::Iterator;
 use op_alloy_genesis::{RollupConfig, SystemConfig};
-use op_alloy_protocol::L2BlockInfo;
+use op_alloy_protocol::{BlockInfo, L2BlockInfo};
 use op_alloy_rpc_types_engine::OpAttributesWithParent;
+use tracing::debug;
 
-use crate::{errors::PipelineErrorKind, traits::OriginProvider, types::StepResult};
+use crate::{
+    errors::{PipelineError, PipelineErrorKind, ResetError},
+    traits::OriginProvider,
+    types::{PipelineResult, PipelineStatus, StepResult},
+};
 
 /// This trait defines the interface for interacting with the derivation pipeline.
 #[async_trait]
@@ -15,15 +20,127 @@ pub trait Pipeline: OriginProvider + Iterator<Item = OpAttributesWithParent> {
     /// Peeks at the next [OpAttributesWithParent] from the pipeline.
     fn peek(&self) -> Option<&OpAttributesWithParent>;
 
+    /// Peeks at the [OpAttributesWithParent] `n` positions ahead in the pipeline, where `0` is
+    /// the next one.
+    fn peek_nth(&self, n: usize) -> Option<&OpAttributesWithParent>;
+
+    /// Returns the number of prepared [OpAttributesWithParent] in the pipeline.
+    fn prepared_len(&self) -> usize;
+
+    /// Returns the L1 origin that the next [OpAttributesWithParent] were derived from, if known.
+    /// Defaults to [None] for pipelines that do not track it.
+    fn derived_from(&self) -> Option<BlockInfo> {
+        None
+    }
+
+    /// Returns the number of L1 blocks the pipeline origin is behind `l1_head`. Without an
+    /// origin, the whole L1 chain up to `l1_head` is considered behind.
+    fn origin_lag(&self, l1_head: BlockInfo) -> u64 {
+        let origin = self.origin().map_or(0, |origin| origin.number);
+        l1_head.number.saturating_sub(origin)
+    }
+
     /// Attempts to progress the pipeline.
     async fn step(&mut self, cursor: L2BlockInfo) -> StepResult;
 
     /// Returns the rollup config.
     fn rollup_config(&self) -> &RollupConfig;
 
+    /// Returns the name and L1 origin of every stage, from the bottom of the pipeline up. Useful
+    /// for finding the stage at which origin advancement is stalled.
+    fn stage_origins(&self) -> Vec<(&'static str, Option<BlockInfo>)> {
+        let mut origins = Vec::new();
+        self.collect_stage_origins(&mut origins);
+        origins
+    }
+
+    /// Returns a [PipelineStatus] snapshot of the pipeline, for logging when derivation fails or
+    /// stalls.
+    fn status(&self) -> PipelineStatus {
+        let prepared_len = self.prepared_len();
+        let mut status = PipelineStatus {
+            origin: self.origin(),
+            last_prepared_cursor: prepared_len
+                .checked_sub(1)
+                .and_then(|n| self.peek_nth(n))
+                .map(|attributes| attributes.parent),
+            prepared_len,
+            stage_origins: self.stage_origins(),
+            ..Default::default()
+        };
+        self.collect_status(&mut status);
+        status
+    }
+
     /// Returns the [SystemConfig] by L2 number.
     async fn system_config_by_number(
         &mut self,
         number: u64,
     ) -> Result<SystemConfig, PipelineErrorKind>;
+
+    /// Steps the pipeline until its L1 origin reaches the `target` block number, and returns the
+    /// reached origin.
+    ///
+    /// Before every step, `cursor` is called for the L2 block to step on. Prepared attributes are
+    /// drained into `sink` after every step, so that the caller can apply them and advance the
+    /// cursor. Returns immediately if the origin is already at or beyond the target.
+    ///
+    /// If the data source has no further L1 blocks, for example because the pipeline is pinned to
+    /// an L1 block below the target, [PipelineError::Eof] is returned immediately so that the
+    /// caller can wait for new L1 data before calling this again. [PipelineError::NotEnoughData]
+    /// is stepped through, as it signals progress within the pipeline. Other temporary errors are
+    /// returned right away, so that the caller can back off before calling this again.
+    ///
+    /// Retry-after hints are not supported, as [PipelineError] carries none. Transient provider
+    /// failures can be retried with backoff by wrapping the providers in a [RetryProvider].
+    ///
+    /// [RetryProvider]: crate::sources::RetryProvider
+    async fn advance_to_origin<C, S>(
+        &mut self,
+        mut cursor: C,
+        target: u64,
+        mut sink: S,
+    ) -> PipelineResult<BlockInfo>
+    where
+        Self: Send,
+        C: FnMut() -> L2BlockInfo + Send,
+        S: FnMut(OpAttributesWithParent) + Send,
+    {
+        loop {
+            let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+            if origin.number >= target {
+                return Ok(origin);
+            }
+
+            let result = self.step(cursor()).await;
+            while let Some(attributes) = self.next() {
+                sink(attributes);
+            }
+            match result {
+                StepResult::PreparedAttributes |
+                StepResult::AdvancedOrigin |
+                StepResult::Stalled(_) |
+                StepResult::SkippedRestoredAttributes => {}
+                StepResult::StaleCursor(stale, highest) => {
+                    return Err(ResetError::StaleCursor(stale, highest).reset());
+                }
+                // The prepared attributes were drained into the sink above, and a stage that
+                // needs more data made progress that only the next step can use.
+                StepResult::PreparedQueueFull |
+                StepResult::StepFailed(PipelineErrorKind::Temporary(
+                    PipelineError::NotEnoughData,
+                )) => {}
+                // The data source has no further L1 blocks yet. Stepping again cannot make
+                // progress until it does, so the caller is left to wait before retrying.
+                StepResult::OriginAdvanceErr(PipelineErrorKind::Temporary(PipelineError::Eof)) => {
+                    debug!(
+                        target: "pipeline",
+                        "Data source exhausted at L1 origin #{} before #{}", origin.number, target
+                    );
+                    return Err(PipelineError::Eof.temp());
+                }
+                StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => return Err(e),
+            }
+        }
+    }
 }
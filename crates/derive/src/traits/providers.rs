@@ -2,12 +2,13 @@
 
 use crate::errors::PipelineErrorKind;
 use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
-use alloy_consensus::{Header, Receipt, TxEnvelope};
-use alloy_primitives::B256;
+use alloy_consensus::{Header, Receipt, Transaction, TxEip4844Variant, TxEnvelope};
+use alloy_primitives::{Address, BloomInput, B256};
 use async_trait::async_trait;
 use core::fmt::Display;
+use op_alloy_consensus::OpBlock;
 use op_alloy_genesis::{RollupConfig, SystemConfig};
-use op_alloy_protocol::{BatchValidationProvider, BlockInfo};
+use op_alloy_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo};
 
 /// Describes the functionality of a data source that can provide information from the blockchain.
 #[async_trait]
@@ -31,6 +32,49 @@ pub trait ChainProvider {
         &mut self,
         hash: B256,
     ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error>;
+
+    /// Returns whether the L1 block with the given hash may carry derivation data: a transaction
+    /// sent to `batch_inbox`, or a log emitted by the `system_config` contract. A `false` result
+    /// is exact, so the block can be skipped without missing batcher data or a system config
+    /// update.
+    ///
+    /// By default, the logs bloom of the header is checked for the system config contract and the
+    /// transactions of the block for the batch inbox, which avoids fetching the receipts.
+    /// Providers backed by a remote node may override this with a cheaper log query.
+    async fn block_has_batcher_tx(
+        &mut self,
+        hash: B256,
+        batch_inbox: Address,
+        system_config: Address,
+    ) -> Result<bool, Self::Error>
+    where
+        Self: Send,
+    {
+        let header = self.header_by_hash(hash).await?;
+        if header.logs_bloom.contains_input(BloomInput::Raw(system_config.as_slice())) {
+            return Ok(true);
+        }
+        let (_, txs) = self.block_info_and_transactions_by_hash(hash).await?;
+        Ok(txs.iter().any(|tx| {
+            let to = match tx {
+                TxEnvelope::Legacy(tx) => tx.tx().to(),
+                TxEnvelope::Eip2930(tx) => tx.tx().to(),
+                TxEnvelope::Eip1559(tx) => tx.tx().to(),
+                TxEnvelope::Eip4844(tx) => match tx.tx() {
+                    TxEip4844Variant::TxEip4844(tx) => tx.to(),
+                    TxEip4844Variant::TxEip4844WithSidecar(tx) => tx.tx().to(),
+                },
+                _ => None,
+            };
+            to == Some(batch_inbox)
+        }))
+    }
+
+    /// Evicts any cached responses requested by an L1 block number above `number`, which may
+    /// have been reorged out. Called by the L1 traversal stage when the pipeline is reset.
+    ///
+    /// By default, providers do not cache responses and this does nothing.
+    fn invalidate_l1_above(&mut self, _number: u64) {}
 }
 
 /// Describes the functionality of a data source that fetches safe blocks.
@@ -45,6 +89,69 @@ pub trait L2ChainProvider: BatchValidationProviderDerive {
         number: u64,
         rollup_config: Arc<RollupConfig>,
     ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error>;
+
+    /// Returns the [L2BlockInfo]s of the blocks from `start` to `end`, inclusive, in order.
+    ///
+    /// By default, the blocks are fetched one at a time. Providers backed by a remote node should
+    /// override this to fetch the range in a single batched request.
+    async fn block_info_by_range(
+        &mut self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<L2BlockInfo>, <Self as BatchValidationProvider>::Error>
+    where
+        Self: Send,
+    {
+        let mut blocks = Vec::new();
+        for number in start..=end {
+            blocks.push(self.l2_block_info_by_number(number).await?);
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the [OpBlock]s from `start` to `end`, inclusive, in order.
+    ///
+    /// By default, the blocks are fetched one at a time. Providers backed by a remote node should
+    /// override this to fetch the range in a single batched request.
+    async fn blocks_by_range(
+        &mut self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<OpBlock>, <Self as BatchValidationProvider>::Error>
+    where
+        Self: Send,
+    {
+        let mut blocks = Vec::new();
+        for number in start..=end {
+            blocks.push(self.block_by_number(number).await?);
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the [SystemConfig]s of the L2 blocks from `start` to `end`, inclusive, in order.
+    ///
+    /// By default, the system configs are fetched one at a time.
+    async fn system_configs_by_range(
+        &mut self,
+        start: u64,
+        end: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<Vec<SystemConfig>, <Self as L2ChainProvider>::Error>
+    where
+        Self: Send,
+    {
+        let mut configs = Vec::new();
+        for number in start..=end {
+            configs.push(self.system_config_by_number(number, rollup_config.clone()).await?);
+        }
+        Ok(configs)
+    }
+
+    /// Evicts any cached responses requested by an L2 block number above `number`, which may
+    /// have been reorged out. Called by the pipeline when it is reset.
+    ///
+    /// By default, providers do not cache responses and this does nothing.
+    fn invalidate_l2_above(&mut self, _number: u64) {}
 }
 
 /// A super-trait for [BatchValidationProvider] that binds `Self::Error` to have a conversion into
@@ -59,3 +166,57 @@ where
     <T as BatchValidationProvider>::Error: Into<PipelineErrorKind>,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestL2ChainProvider, TestProviderError};
+    use alloc::vec;
+    use alloy_consensus::BlockBody;
+
+    fn block_info(number: u64) -> L2BlockInfo {
+        L2BlockInfo { block_info: BlockInfo { number, ..Default::default() }, ..Default::default() }
+    }
+
+    fn block(number: u64) -> OpBlock {
+        let body = BlockBody { transactions: Vec::new(), ommers: Vec::new(), withdrawals: None };
+        OpBlock { header: Header { number, ..Default::default() }, body }
+    }
+
+    #[tokio::test]
+    async fn test_block_info_by_range() {
+        let mut provider = TestL2ChainProvider::default();
+        provider.blocks = vec![block_info(3), block_info(1), block_info(2)];
+        let blocks = provider.block_info_by_range(1, 3).await.unwrap();
+        assert_eq!(blocks, vec![block_info(1), block_info(2), block_info(3)]);
+        assert_eq!(provider.block_info_by_range(2, 2).await.unwrap(), vec![block_info(2)]);
+        assert!(provider.block_info_by_range(3, 2).await.unwrap().is_empty());
+
+        let err = provider.block_info_by_range(2, 4).await.unwrap_err();
+        assert!(matches!(err, TestProviderError::BlockNotFound(4)));
+    }
+
+    #[tokio::test]
+    async fn test_blocks_by_range() {
+        let mut provider = TestL2ChainProvider::default();
+        provider.op_blocks = vec![block(5), block(6)];
+        assert_eq!(provider.blocks_by_range(5, 6).await.unwrap(), vec![block(5), block(6)]);
+
+        let err = provider.blocks_by_range(4, 6).await.unwrap_err();
+        assert!(matches!(err, TestProviderError::L2BlockNotFound(4)));
+    }
+
+    #[tokio::test]
+    async fn test_system_configs_by_range() {
+        let mut provider = TestL2ChainProvider::default();
+        let config = |gas_limit| SystemConfig { gas_limit, ..Default::default() };
+        provider.system_configs.insert(1, config(1));
+        provider.system_configs.insert(2, config(2));
+        let cfg = Arc::new(RollupConfig::default());
+        let configs = provider.system_configs_by_range(1, 2, cfg.clone()).await.unwrap();
+        assert_eq!(configs, vec![config(1), config(2)]);
+
+        let err = provider.system_configs_by_range(1, 3, cfg).await.unwrap_err();
+        assert!(matches!(err, TestProviderError::SystemConfigNotFound(3)));
+    }
+}
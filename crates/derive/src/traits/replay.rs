@@ -0,0 +1,16 @@
+//! Contains the [ReplaySink] trait for recording provider traffic.
+
+use crate::types::ReplayEntry;
+use core::fmt::Debug;
+
+/// An append-only destination for [ReplayEntry]s recorded by a [RecordingChainProvider].
+///
+/// The sink is shared between all clones of the recording provider, so implementations are
+/// responsible for their own synchronization. A `std` implementation would typically append each
+/// entry to a file as a JSON line.
+///
+/// [RecordingChainProvider]: crate::sources::RecordingChainProvider
+pub trait ReplaySink: Debug + Send + Sync {
+    /// Appends a single [ReplayEntry] to the log.
+    fn append(&self, entry: ReplayEntry);
+}
@@ -0,0 +1,32 @@
+//! Contains the [RetrySleep] and [TransientError] traits used by the [RetryProvider].
+//!
+//! [RetryProvider]: crate::sources::RetryProvider
+
+use alloc::boxed::Box;
+use async_trait::async_trait;
+use core::{fmt::Debug, time::Duration};
+
+/// Waits out the delay between two attempts of a [RetryProvider].
+///
+/// `kona-derive` has no runtime, so the embedder supplies the timer. A `std` implementation
+/// would typically call `tokio::time::sleep`, while a fault proof program, which has no notion of
+/// time, can return immediately.
+///
+/// [RetryProvider]: crate::sources::RetryProvider
+#[async_trait]
+pub trait RetrySleep: Debug + Send {
+    /// Waits for the given duration.
+    async fn sleep(&mut self, duration: Duration);
+}
+
+/// Classifies provider errors as transient or deterministic for the [RetryProvider].
+///
+/// Transient errors, such as a dropped connection or a rate-limited request, may succeed when
+/// the request is repeated. Deterministic errors, such as a block that does not exist yet, are
+/// surfaced to the pipeline immediately.
+///
+/// [RetryProvider]: crate::sources::RetryProvider
+pub trait TransientError {
+    /// Returns whether repeating the failed request may succeed.
+    fn is_transient(&self) -> bool;
+}
@@ -0,0 +1,26 @@
+//! Contains the [SequencerPolicy] trait for adjusting the derived payload attributes.
+
+use crate::types::SequencerOverrides;
+use core::fmt::Debug;
+
+/// Adjusts the payload attributes built by the [AttributesBuilder] for sequencing.
+///
+/// Verifiers derive attributes with `no_tx_pool` set and the gas limit of the system config,
+/// while a sequencer building unsafe blocks from the same code path may need to include
+/// transactions from its pool, or override the gas limit. The policy is only handed the
+/// [SequencerOverrides], so it cannot alter the consensus-critical fields of the attributes,
+/// such as the timestamp, the randomness or the transactions.
+///
+/// [AttributesBuilder]: crate::traits::AttributesBuilder
+pub trait SequencerPolicy: Debug + Send + Sync {
+    /// Adjusts the overridable fields of the attributes.
+    ///
+    /// By default, the fields are left as derived, which is the verifier behavior.
+    fn apply(&self, _overrides: &mut SequencerOverrides) {}
+}
+
+/// A [SequencerPolicy] implementation that leaves the derived attributes unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifierPolicy;
+
+impl SequencerPolicy for VerifierPolicy {}
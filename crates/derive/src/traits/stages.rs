@@ -1,22 +1,35 @@
 //! This module contains common traits for stages within the derivation pipeline.
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use async_trait::async_trait;
 use op_alloy_protocol::BlockInfo;
 
-use crate::types::{PipelineResult, Signal};
+use crate::types::{PipelineResult, PipelineStatus, Signal, SignalReceipt};
 
 /// Providers a way for the pipeline to accept a signal from the driver.
 #[async_trait]
 pub trait SignalReceiver {
-    /// Receives a signal from the driver.
-    async fn signal(&mut self, signal: Signal) -> PipelineResult<()>;
+    /// Receives a signal from the driver, and returns a [SignalReceipt] of the work discarded by
+    /// this stage and the stages below it.
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<SignalReceipt>;
 }
 
 /// Provides a method for accessing the pipeline's current L1 origin.
 pub trait OriginProvider {
     /// Returns the optional L1 [BlockInfo] origin.
     fn origin(&self) -> Option<BlockInfo>;
+
+    /// Appends the name and L1 origin of every stage, from the bottom of the pipeline up to and
+    /// including this one, to `origins`. Intended for diagnosing stalled origin advancement.
+    ///
+    /// Every stage must push its own entry after forwarding to the stage below, so the list is
+    /// never truncated.
+    fn collect_stage_origins(&self, origins: &mut Vec<(&'static str, Option<BlockInfo>)>);
+
+    /// Adds the channels and batches buffered by this stage and the stages below it to `status`.
+    ///
+    /// Stages that do not override this method contribute nothing, and hide the stages below.
+    fn collect_status(&self, _status: &mut PipelineStatus) {}
 }
 
 /// Defines a trait for advancing the L1 origin of the pipeline.
@@ -25,4 +38,21 @@ pub trait OriginAdvancer {
     /// Advances the internal state of the lowest stage to the next l1 origin.
     /// This method is the equivalent of the reference implementation `advance_l1_block`.
     async fn advance_origin(&mut self) -> PipelineResult<()>;
+
+    /// Advances the internal state of the lowest stage by at least one L1 block, skipping over
+    /// L1 blocks without derivation data up to the `target` block. The origin stops at `target`,
+    /// or at the first L1 block that may carry batcher data or a system config update, whichever
+    /// comes first. Once the origin reaches the `target` block number, its hash must match the
+    /// `target` hash, or a [crate::errors::ResetError::ReorgDetected] is returned.
+    ///
+    /// The stages do not see the skipped L1 blocks as origins, so `target` must not be past the
+    /// L1 origin of the L2 safe head: every later L1 block is an epoch the batch stages need.
+    ///
+    /// Stages that do not override this method advance a single origin.
+    async fn advance_origin_to(&mut self, _target: BlockInfo) -> PipelineResult<()>
+    where
+        Self: Send,
+    {
+        self.advance_origin().await
+    }
 }
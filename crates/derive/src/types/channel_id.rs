@@ -0,0 +1,151 @@
+//! Contains the [ChannelId] type.
+
+use alloy_primitives::hex;
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// The length of a [ChannelId] in bytes.
+pub const CHANNEL_ID_LENGTH: usize = 16;
+
+/// The identifier of a channel, shared by all of its frames.
+///
+/// Channel ids are displayed in a short `0x1234…cdef` form, or as the full `0x`-prefixed hex with
+/// the alternate flag (`{:#}`). Log lines use the full form, so that they can be correlated with
+/// batcher logs. Channel ids are parsed from, and serialize as, the full hex string, so that they
+/// can be used as map keys.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChannelId(pub [u8; CHANNEL_ID_LENGTH]);
+
+impl ChannelId {
+    /// Creates a new [ChannelId] from its raw bytes.
+    pub const fn new(id: [u8; CHANNEL_ID_LENGTH]) -> Self {
+        Self(id)
+    }
+
+    /// Returns the raw bytes of the [ChannelId].
+    pub const fn as_bytes(&self) -> &[u8; CHANNEL_ID_LENGTH] {
+        &self.0
+    }
+}
+
+impl From<[u8; CHANNEL_ID_LENGTH]> for ChannelId {
+    fn from(id: [u8; CHANNEL_ID_LENGTH]) -> Self {
+        Self(id)
+    }
+}
+
+impl From<ChannelId> for [u8; CHANNEL_ID_LENGTH] {
+    fn from(id: ChannelId) -> Self {
+        id.0
+    }
+}
+
+impl Display for ChannelId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; 2 * CHANNEL_ID_LENGTH];
+        hex::encode_to_slice(self.0, &mut buf).map_err(|_| fmt::Error)?;
+        let hex = core::str::from_utf8(&buf).map_err(|_| fmt::Error)?;
+        if f.alternate() {
+            write!(f, "0x{hex}")
+        } else {
+            write!(f, "0x{}…{}", &hex[..4], &hex[hex.len() - 4..])
+        }
+    }
+}
+
+impl fmt::Debug for ChannelId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChannelId({self:#})")
+    }
+}
+
+impl FromStr for ChannelId {
+    type Err = hex::FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut id = [0u8; CHANNEL_ID_LENGTH];
+        hex::decode_to_slice(s.strip_prefix("0x").unwrap_or(s), &mut id)?;
+        Ok(Self(id))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ChannelId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{self:#}"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ChannelId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ChannelIdVisitor;
+
+        impl serde::de::Visitor<'_> for ChannelIdVisitor {
+            type Value = ChannelId;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a hex encoded channel id")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ChannelIdVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_channel_id_display_round_trip() {
+        let mut bytes = [0xAB; CHANNEL_ID_LENGTH];
+        bytes[0] = 0x01;
+        bytes[CHANNEL_ID_LENGTH - 1] = 0xEF;
+        assert_eq!(ChannelId::new(bytes).to_string(), "0x01ab…abef");
+
+        let id = ChannelId::new([0xAB; CHANNEL_ID_LENGTH]);
+        let s = alloc::format!("{id:#}");
+        assert_eq!(s, "0xabababababababababababababababab");
+        assert_eq!(s.parse::<ChannelId>().unwrap(), id);
+        assert_eq!("abababababababababababababababab".parse::<ChannelId>().unwrap(), id);
+        assert_eq!(alloc::format!("{id:?}"), "ChannelId(0xabababababababababababababababab)");
+    }
+
+    #[test]
+    fn test_channel_id_parse_errors() {
+        assert!("0xabab".parse::<ChannelId>().is_err());
+        assert!("0xzzabababababababababababababababab".parse::<ChannelId>().is_err());
+    }
+
+    #[test]
+    fn test_channel_id_ordering() {
+        let mut ids = [2, 0, 1].map(|b| ChannelId::new([b; CHANNEL_ID_LENGTH]));
+        ids.sort();
+        assert_eq!(ids, [0, 1, 2].map(|b| ChannelId::new([b; CHANNEL_ID_LENGTH])));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_channel_id_serde_map_key() {
+        use alloc::collections::BTreeMap;
+
+        let mut channels = BTreeMap::new();
+        channels.insert(ChannelId::new([1; 16]), 10u64);
+        channels.insert(ChannelId::new([2; 16]), 20u64);
+        let json = serde_json::to_string(&channels).unwrap();
+        assert_eq!(
+            json,
+            r#"{"0x01010101010101010101010101010101":10,"0x02020202020202020202020202020202":20}"#
+        );
+        let decoded: BTreeMap<ChannelId, u64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, channels);
+    }
+}
@@ -0,0 +1,251 @@
+//! Contains [ChannelOut], the batcher-side inverse of the channel stages.
+
+use crate::{
+    decode::encode_batch,
+    errors::ChannelOutError,
+    stages::{CHANNEL_VERSION_BROTLI, FRAME_OVERHEAD_LEN},
+    types::ChannelId,
+};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::Bytes;
+use alloy_rlp::Encodable;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use op_alloy_protocol::{Batch, Frame};
+
+/// The zlib compression level used by [ChannelOut].
+const ZLIB_COMPRESSION_LEVEL: u8 = 9;
+
+/// The maximum length of an uncompressed brotli meta-block that [ChannelOut] emits.
+const BROTLI_META_BLOCK_LEN: usize = 1 << 16;
+
+/// The compression algorithm of a channel built with a [ChannelOut].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, derive_more::Display)]
+pub enum CompressionAlgo {
+    /// Zlib compression, accepted on all hardforks.
+    #[default]
+    #[display("zlib")]
+    Zlib,
+    /// Brotli compression, accepted from Fjord onwards.
+    #[display("brotli")]
+    Brotli,
+}
+
+/// Builds a channel from batches, as the batcher does, and splits it into frames.
+///
+/// This is the inverse of the [ChannelBank] or [ChannelAssembler] and the [ChannelReader]: the
+/// batches are RLP encoded with their batch type prefix, compressed, and split into frames. It
+/// mirrors op-node's `ChannelOut`, and is intended for fixture generators and fuzzing.
+///
+/// Brotli channels are written as uncompressed brotli meta-blocks, which every brotli decoder
+/// accepts, since kona only depends on brotli for decompression.
+///
+/// [ChannelBank]: crate::stages::ChannelBank
+/// [ChannelAssembler]: crate::stages::ChannelAssembler
+/// [ChannelReader]: crate::stages::ChannelReader
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelOut {
+    /// The id of the channel.
+    pub id: ChannelId,
+    /// The compression algorithm of the channel.
+    pub algo: CompressionAlgo,
+    /// The RLP stream of the encoded batches added so far.
+    rlp: Vec<u8>,
+}
+
+impl ChannelOut {
+    /// Creates a new, empty [ChannelOut].
+    pub const fn new(id: ChannelId, algo: CompressionAlgo) -> Self {
+        Self { id, algo, rlp: Vec::new() }
+    }
+
+    /// Adds a batch to the channel.
+    pub fn add_batch(&mut self, batch: Batch) -> Result<(), ChannelOutError> {
+        let mut data = Vec::new();
+        encode_batch(&batch, &mut data)?;
+        Bytes::from(data).encode(&mut self.rlp);
+        Ok(())
+    }
+
+    /// Returns the length of the uncompressed RLP stream of the batches added so far.
+    pub fn input_bytes(&self) -> usize {
+        self.rlp.len()
+    }
+
+    /// Returns the length of the compressed channel data, which is split into frames by
+    /// [ChannelOut::output_frames].
+    ///
+    /// The channel is compressed on every call.
+    pub fn ready_bytes(&self) -> usize {
+        self.compressed().len()
+    }
+
+    /// Returns the compressed channel data.
+    pub fn compressed(&self) -> Vec<u8> {
+        match self.algo {
+            CompressionAlgo::Zlib => compress_to_vec_zlib(&self.rlp, ZLIB_COMPRESSION_LEVEL),
+            CompressionAlgo::Brotli => {
+                let mut data = vec![CHANNEL_VERSION_BROTLI];
+                encode_brotli_uncompressed(&self.rlp, &mut data);
+                data
+            }
+        }
+    }
+
+    /// Splits the compressed channel data into frames whose encoding, including the frame
+    /// overhead, is at most `max_frame_size` bytes. The last frame closes the channel.
+    pub fn output_frames(&self, max_frame_size: usize) -> Result<Vec<Frame>, ChannelOutError> {
+        if max_frame_size <= FRAME_OVERHEAD_LEN {
+            return Err(ChannelOutError::FrameSizeTooSmall(max_frame_size));
+        }
+        let compressed = self.compressed();
+        let chunks = compressed.chunks(max_frame_size - FRAME_OVERHEAD_LEN);
+        if chunks.len() > u16::MAX as usize + 1 {
+            return Err(ChannelOutError::TooManyFrames);
+        }
+
+        let count = chunks.len();
+        Ok(chunks
+            .enumerate()
+            .map(|(number, chunk)| Frame {
+                id: self.id.into(),
+                number: number as u16,
+                data: chunk.to_vec(),
+                is_last: number == count - 1,
+            })
+            .collect())
+    }
+}
+
+/// Encodes `data` as a brotli stream of uncompressed meta-blocks with a 16-bit window, appending
+/// it to `out`.
+fn encode_brotli_uncompressed(data: &[u8], out: &mut Vec<u8>) {
+    // The stream header is a single 0 bit for WBITS = 16, so the first meta-block header is
+    // shifted by one bit.
+    let mut shift = 1;
+    for block in data.chunks(BROTLI_META_BLOCK_LEN) {
+        // ISLAST = 0, MNIBBLES = 4, MLEN - 1 and ISUNCOMPRESSED = 1, padded to a byte.
+        let header = (((block.len() as u32 - 1) << 3) | (1 << 19)) << shift;
+        out.extend_from_slice(&header.to_le_bytes()[..3]);
+        out.extend_from_slice(block);
+        shift = 0;
+    }
+    // A last, empty meta-block: ISLAST = 1 and ISLASTEMPTY = 1.
+    out.push(0b11 << shift);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        decode::{assemble_single_channel, decode_batches},
+        errors::PipelineError,
+        stages::{decompress_brotli, BatchStreamProvider, ChannelBank, ChannelReader},
+        test_utils::TestNextFrameProvider,
+        types::DerivationLimits,
+    };
+    use alloc::sync::Arc;
+    use alloy_consensus::{Signed, TxEip1559, TxEnvelope};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Signature, B256};
+    use op_alloy_genesis::RollupConfig;
+    use op_alloy_protocol::{SingleBatch, SpanBatch};
+
+    /// Returns three consecutive batches, each with a signed transaction, so that they can also
+    /// be encoded in a span batch.
+    fn singles() -> Vec<SingleBatch> {
+        (1..=3)
+            .map(|n| {
+                let input = vec![0x02; 100 * n as usize].into();
+                let tx = TxEip1559 { chain_id: 10, nonce: n, input, ..Default::default() };
+                let signed = Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO);
+                let tx = TxEnvelope::Eip1559(signed).encoded_2718().into();
+                SingleBatch { timestamp: 2 * n, transactions: vec![tx], ..Default::default() }
+            })
+            .collect()
+    }
+
+    fn channel_out(algo: CompressionAlgo) -> ChannelOut {
+        let mut channel_out = ChannelOut::new(ChannelId::new([0xAA; 16]), algo);
+        singles().into_iter().for_each(|b| channel_out.add_batch(Batch::Single(b)).unwrap());
+        channel_out
+    }
+
+    #[tokio::test]
+    async fn test_channel_out_round_trip_through_stages() {
+        let cfg = Arc::new(RollupConfig { fjord_time: Some(0), ..Default::default() });
+        for algo in [CompressionAlgo::Zlib, CompressionAlgo::Brotli] {
+            let frames = channel_out(algo).output_frames(64).unwrap();
+            assert!(frames.len() > 1, "{algo} channel fits in one frame");
+
+            // The mock frame provider pops frames from the back.
+            let data = frames.into_iter().rev().map(Ok).collect();
+            let bank = ChannelBank::new(cfg.clone(), TestNextFrameProvider::new(data));
+            let mut reader = ChannelReader::new(bank, cfg.clone());
+            let mut batches = Vec::new();
+            loop {
+                match reader.next_batch().await {
+                    Ok(Batch::Single(batch)) => batches.push(batch),
+                    Ok(Batch::Span(_)) => panic!("unexpected span batch"),
+                    Err(e) if e == PipelineError::NotEnoughData.temp() => {}
+                    Err(e) if e == PipelineError::Eof.temp() => break,
+                    Err(e) => panic!("unexpected error: {e}"),
+                }
+            }
+            assert_eq!(batches, singles(), "{algo} channel");
+        }
+    }
+
+    #[test]
+    fn test_channel_out_span_batch() {
+        let cfg = RollupConfig {
+            block_time: 2,
+            fjord_time: Some(0),
+            l2_chain_id: 10,
+            ..Default::default()
+        };
+        let mut span = SpanBatch { chain_id: cfg.l2_chain_id, ..Default::default() };
+        for (seq_num, batch) in singles().into_iter().enumerate() {
+            span.append_singular_batch(batch, seq_num as u64).unwrap();
+        }
+        let mut channel_out = ChannelOut::new(ChannelId::default(), CompressionAlgo::Brotli);
+        channel_out.add_batch(Batch::Span(span)).unwrap();
+
+        let frames = channel_out.output_frames(1_000).unwrap();
+        let channel = assemble_single_channel(frames).unwrap();
+        assert_eq!(channel.len(), channel_out.ready_bytes());
+        let batches = decode_batches(&channel, &DerivationLimits::SPEC, &cfg, 0).unwrap();
+        let [Batch::Span(decoded)] = batches.as_slice() else {
+            panic!("expected a single span batch");
+        };
+        let timestamps = decoded.batches.iter().map(|b| b.timestamp).collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_channel_out_frame_sizes() {
+        let channel_out = channel_out(CompressionAlgo::Zlib);
+        let max_frame_size = FRAME_OVERHEAD_LEN + 10;
+        let frames = channel_out.output_frames(max_frame_size).unwrap();
+        assert_eq!(frames.len(), channel_out.ready_bytes().div_ceil(10));
+        assert!(frames.iter().all(|f| f.encode().len() <= max_frame_size));
+        assert!(frames.iter().enumerate().all(|(i, f)| f.number as usize == i));
+        assert_eq!(frames.iter().filter(|f| f.is_last).count(), 1);
+        assert!(frames[frames.len() - 1].is_last);
+
+        assert_eq!(
+            channel_out.output_frames(FRAME_OVERHEAD_LEN),
+            Err(ChannelOutError::FrameSizeTooSmall(FRAME_OVERHEAD_LEN))
+        );
+    }
+
+    #[test]
+    fn test_brotli_uncompressed_meta_blocks() {
+        for len in [0, 1, BROTLI_META_BLOCK_LEN, BROTLI_META_BLOCK_LEN + 1] {
+            let data = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let mut encoded = Vec::new();
+            encode_brotli_uncompressed(&data, &mut encoded);
+            let decoded = decompress_brotli(&encoded, len + 1).unwrap();
+            assert_eq!(decoded, data, "{len} bytes");
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Contains [BlocklistDepositFilter], a [DepositFilter] that drops blocklisted deposits.
+
+use crate::traits::DepositFilter;
+use alloc::collections::BTreeSet;
+use alloy_primitives::{Address, B256};
+use op_alloy_consensus::TxDeposit;
+
+/// A [DepositFilter] that drops deposits by source hash or by sender.
+///
+/// Source hashes identify individual `TransactionDeposited` events, while blocklisted senders
+/// drop every deposit from an address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlocklistDepositFilter {
+    /// The source hashes of the deposits to drop.
+    pub source_hashes: BTreeSet<B256>,
+    /// The senders whose deposits are dropped.
+    pub senders: BTreeSet<Address>,
+}
+
+impl BlocklistDepositFilter {
+    /// Creates a new, empty [BlocklistDepositFilter].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops the deposit with the given source hash.
+    pub fn with_source_hash(mut self, source_hash: B256) -> Self {
+        self.source_hashes.insert(source_hash);
+        self
+    }
+
+    /// Drops every deposit sent by the given address.
+    pub fn with_sender(mut self, sender: Address) -> Self {
+        self.senders.insert(sender);
+        self
+    }
+}
+
+impl DepositFilter for BlocklistDepositFilter {
+    fn filter(&self, deposit: TxDeposit) -> Option<TxDeposit> {
+        let blocked = self.source_hashes.contains(&deposit.source_hash) ||
+            self.senders.contains(&deposit.from);
+        (!blocked).then_some(deposit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocklist_deposit_filter() {
+        let deposit = |source_hash, from| TxDeposit { source_hash, from, ..Default::default() };
+        let filter = BlocklistDepositFilter::new()
+            .with_source_hash(B256::with_last_byte(1))
+            .with_sender(Address::with_last_byte(2));
+
+        assert_eq!(filter.filter(deposit(B256::with_last_byte(1), Address::ZERO)), None);
+        assert_eq!(filter.filter(deposit(B256::ZERO, Address::with_last_byte(2))), None);
+        let allowed = deposit(B256::with_last_byte(3), Address::with_last_byte(3));
+        assert_eq!(filter.filter(allowed.clone()), Some(allowed));
+    }
+}
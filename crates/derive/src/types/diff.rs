@@ -0,0 +1,120 @@
+//! Contains [AttributesDiff], a field-by-field comparison of payload attributes.
+
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+/// A field-by-field comparison of expected and derived [OpPayloadAttributes], for reporting
+/// mismatches against reference payloads.
+///
+/// Each differing field holds the `(expected, actual)` pair, and is [None] if the field matches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributesDiff {
+    /// The timestamps, if they differ.
+    pub timestamp: Option<(u64, u64)>,
+    /// The gas limits, if they differ.
+    pub gas_limit: Option<(Option<u64>, Option<u64>)>,
+    /// The transaction counts, if they differ.
+    pub tx_count: Option<(usize, usize)>,
+    /// The index of the first transaction that differs, or that is missing from either side.
+    pub first_differing_tx: Option<usize>,
+    /// Whether any other field differs, e.g. the `prev_randao` or the EIP-1559 parameters.
+    pub other_fields_differ: bool,
+}
+
+impl AttributesDiff {
+    /// Compares the `expected` attributes against the `actual` derived attributes.
+    pub fn between(expected: &OpPayloadAttributes, actual: &OpPayloadAttributes) -> Self {
+        let (expected_payload, actual_payload) =
+            (&expected.payload_attributes, &actual.payload_attributes);
+        let timestamp = (expected_payload.timestamp, actual_payload.timestamp);
+        let gas_limit = (expected.gas_limit, actual.gas_limit);
+
+        let expected_txs = expected.transactions.as_deref().unwrap_or_default();
+        let actual_txs = actual.transactions.as_deref().unwrap_or_default();
+        let tx_count = (expected_txs.len(), actual_txs.len());
+        let first_differing_tx =
+            (0..tx_count.0.max(tx_count.1)).find(|&i| expected_txs.get(i) != actual_txs.get(i));
+
+        let other_fields_differ = expected_payload.prev_randao != actual_payload.prev_randao ||
+            expected_payload.suggested_fee_recipient != actual_payload.suggested_fee_recipient ||
+            expected_payload.withdrawals != actual_payload.withdrawals ||
+            expected_payload.parent_beacon_block_root != actual_payload.parent_beacon_block_root ||
+            expected.no_tx_pool != actual.no_tx_pool ||
+            expected.eip_1559_params != actual.eip_1559_params;
+
+        Self {
+            timestamp: (timestamp.0 != timestamp.1).then_some(timestamp),
+            gas_limit: (gas_limit.0 != gas_limit.1).then_some(gas_limit),
+            tx_count: (tx_count.0 != tx_count.1).then_some(tx_count),
+            first_differing_tx,
+            other_fields_differ,
+        }
+    }
+
+    /// Returns whether the compared attributes are equal.
+    pub fn is_match(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::{Bytes, B256};
+    use alloy_rpc_types_engine::PayloadAttributes;
+
+    fn attributes(txs: &[&'static [u8]]) -> OpPayloadAttributes {
+        OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: 0,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Default::default(),
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: Some(txs.iter().map(|tx| Bytes::from_static(tx)).collect()),
+            no_tx_pool: Some(true),
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+        }
+    }
+
+    #[test]
+    fn test_attributes_diff_match() {
+        let expected = attributes(&[&[0x7E, 0x01], &[0x02, 0xAA]]);
+        assert!(AttributesDiff::between(&expected, &expected.clone()).is_match());
+    }
+
+    #[test]
+    fn test_attributes_diff_fields() {
+        let expected = attributes(&[&[0x7E, 0x01], &[0x02, 0xAA], &[0x02, 0xBB]]);
+        let mut actual = attributes(&[&[0x7E, 0x01], &[0x02, 0xAB]]);
+        actual.payload_attributes.timestamp = 2;
+        actual.gas_limit = None;
+        actual.payload_attributes.prev_randao = B256::repeat_byte(0x01);
+
+        let diff = AttributesDiff::between(&expected, &actual);
+        assert_eq!(
+            diff,
+            AttributesDiff {
+                timestamp: Some((0, 2)),
+                gas_limit: Some((Some(30_000_000), None)),
+                tx_count: Some((3, 2)),
+                first_differing_tx: Some(1),
+                other_fields_differ: true,
+            }
+        );
+        assert!(!diff.is_match());
+    }
+
+    #[test]
+    fn test_attributes_diff_missing_trailing_tx() {
+        let expected = attributes(&[&[0x7E, 0x01]]);
+        let actual = OpPayloadAttributes { transactions: Some(vec![]), ..expected.clone() };
+        let diff = AttributesDiff::between(&expected, &actual);
+        assert_eq!(diff.tx_count, Some((1, 0)));
+        assert_eq!(diff.first_differing_tx, Some(0));
+        assert!(!diff.other_fields_differ);
+    }
+}
@@ -0,0 +1,115 @@
+//! Contains the [EpochCache], a shared cache of recently used L1 epoch headers.
+
+use alloc::{collections::VecDeque, sync::Arc};
+use alloy_consensus::Header;
+use alloy_eips::BlockNumHash;
+use spin::Mutex;
+
+/// The default number of epochs retained by the [EpochCache].
+pub const DEFAULT_EPOCH_CACHE_SIZE: usize = 4;
+
+/// A bounded cache of L1 epoch headers, shared between pipeline components.
+///
+/// Every L2 block of an epoch needs the epoch's L1 header, so a span of L2 blocks would otherwise
+/// fetch the same header once per block. Entries are keyed by epoch number and hash, so a
+/// reorged epoch is never served from the cache. Clones share the same underlying cache, and
+/// the [BatchProvider] invalidates it when the pipeline is reset.
+///
+/// [BatchProvider]: crate::stages::BatchProvider
+#[derive(Debug, Clone)]
+pub struct EpochCache {
+    /// The cached epochs, oldest first.
+    entries: Arc<Mutex<VecDeque<(BlockNumHash, Header)>>>,
+    /// The maximum number of cached epochs.
+    capacity: usize,
+}
+
+impl Default for EpochCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_EPOCH_CACHE_SIZE)
+    }
+}
+
+impl EpochCache {
+    /// Creates a new [EpochCache] that retains at most `capacity` epochs.
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))), capacity }
+    }
+
+    /// Returns the cached header of the given epoch.
+    pub fn get(&self, epoch: BlockNumHash) -> Option<Header> {
+        self.entries.lock().iter().find(|(e, _)| *e == epoch).map(|(_, header)| header.clone())
+    }
+
+    /// Inserts the header of the given epoch, evicting the oldest epoch if the cache is full.
+    pub fn insert(&self, epoch: BlockNumHash, header: Header) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock();
+        entries.retain(|(e, _)| e.number != epoch.number);
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back((epoch, header));
+    }
+
+    /// Removes all cached epochs.
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+
+    /// Returns the number of cached epochs.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Returns whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn epoch(number: u64) -> BlockNumHash {
+        BlockNumHash { number, hash: B256::with_last_byte(number as u8) }
+    }
+
+    #[test]
+    fn test_epoch_cache_bounded() {
+        let cache = EpochCache::new(2);
+        for number in 0..3 {
+            cache.insert(epoch(number), Header { number, ..Default::default() });
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(epoch(0)).is_none());
+        assert_eq!(cache.get(epoch(2)).unwrap().number, 2);
+    }
+
+    #[test]
+    fn test_epoch_cache_keyed_by_hash() {
+        let cache = EpochCache::default();
+        cache.insert(epoch(1), Header::default());
+        let reorged = BlockNumHash { number: 1, hash: B256::with_last_byte(0xFF) };
+        assert!(cache.get(reorged).is_none());
+
+        // Inserting the reorged epoch replaces the stale entry.
+        cache.insert(reorged, Header::default());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(epoch(1)).is_none());
+    }
+
+    #[test]
+    fn test_epoch_cache_shared_clear() {
+        let cache = EpochCache::default();
+        let shared = cache.clone();
+        cache.insert(epoch(1), Header::default());
+        assert!(shared.get(epoch(1)).is_some());
+        shared.clear();
+        assert!(cache.is_empty());
+    }
+}
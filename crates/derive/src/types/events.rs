@@ -0,0 +1,55 @@
+//! Events emitted by the derivation pipeline to an [EventSink].
+//!
+//! [EventSink]: crate::traits::EventSink
+
+use op_alloy_protocol::{BlockInfo, L2BlockInfo};
+
+/// The L1 origin of the pipeline was advanced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OriginAdvancedEvent {
+    /// The new L1 origin of the pipeline.
+    pub origin: Option<BlockInfo>,
+}
+
+/// Payload attributes were derived and queued for the consumer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttributesDerivedEvent {
+    /// The L2 parent block of the derived attributes.
+    pub parent: L2BlockInfo,
+    /// The timestamp of the derived attributes.
+    pub timestamp: u64,
+    /// The L1 origin that the attributes were derived from.
+    pub derived_from: Option<BlockInfo>,
+}
+
+/// The pipeline was reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetEvent {
+    /// The L2 safe head the pipeline was reset to.
+    pub l2_safe_head: L2BlockInfo,
+    /// The L1 origin the pipeline was reset to.
+    pub l1_origin: BlockInfo,
+    /// Whether the reset was a Holocene activation reset.
+    pub activation: bool,
+}
+
+/// The channel currently being derived from was flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelFlushedEvent {
+    /// The L1 origin of the pipeline at the time of the flush.
+    pub origin: Option<BlockInfo>,
+}
+
+/// An event emitted by the derivation pipeline once the corresponding state change has been
+/// committed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineEvent {
+    /// See [OriginAdvancedEvent].
+    OriginAdvanced(OriginAdvancedEvent),
+    /// See [AttributesDerivedEvent].
+    AttributesDerived(AttributesDerivedEvent),
+    /// See [ResetEvent].
+    Reset(ResetEvent),
+    /// See [ChannelFlushedEvent].
+    ChannelFlushed(ChannelFlushedEvent),
+}
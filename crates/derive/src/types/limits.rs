@@ -0,0 +1,128 @@
+//! Contains the [DerivationLimits], the resource bounds enforced by the derivation pipeline.
+
+use op_alloy_genesis::{
+    RollupConfig, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD,
+};
+use op_alloy_protocol::MAX_SPAN_BATCH_ELEMENTS;
+
+/// The maximum size of a channel bank.
+pub const MAX_CHANNEL_BANK_SIZE: usize = 100_000_000;
+
+/// The maximum size of a channel bank after the Fjord Hardfork.
+pub const FJORD_MAX_CHANNEL_BANK_SIZE: usize = 1_000_000_000;
+
+/// The maximum length of the data in a single frame.
+pub const MAX_FRAME_LEN: usize = 1_000_000;
+
+/// The resource bounds enforced by the derivation pipeline.
+///
+/// The [Default] values are the spec values. Hardfork-dependent limits are resolved against the
+/// [RollupConfig] and the L1 origin timestamp through the accessor methods. Embedders of
+/// non-standard chains can override individual limits through the [PipelineBuilder].
+///
+/// [PipelineBuilder]: crate::pipeline::PipelineBuilder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerivationLimits {
+    /// The maximum size of the channel bank before Fjord.
+    pub channel_bank_size: usize,
+    /// The maximum size of the channel bank after Fjord.
+    pub fjord_channel_bank_size: usize,
+    /// The maximum number of decompressed RLP bytes in a channel before Fjord.
+    pub rlp_bytes_per_channel: u64,
+    /// The maximum number of decompressed RLP bytes in a channel after Fjord.
+    pub fjord_rlp_bytes_per_channel: u64,
+    /// The maximum length of the data in a single frame.
+    pub frame_len: usize,
+    /// The maximum number of elements in a span batch.
+    pub span_batch_elements: u64,
+    /// The maximum number of batches buffered by the [BatchQueue] before Holocene, including
+    /// future batches waiting for a gap to be filled. The spec does not bound the queue, so this
+    /// is [None] by default. Setting a bound evicts the batch furthest in the future once the
+    /// queue is full, which diverges from the spec if the evicted batch would have been used.
+    ///
+    /// [BatchQueue]: crate::stages::BatchQueue
+    pub buffered_batches: Option<usize>,
+}
+
+impl Default for DerivationLimits {
+    fn default() -> Self {
+        Self::SPEC
+    }
+}
+
+impl DerivationLimits {
+    /// The spec values of all limits.
+    pub const SPEC: Self = Self {
+        channel_bank_size: MAX_CHANNEL_BANK_SIZE,
+        fjord_channel_bank_size: FJORD_MAX_CHANNEL_BANK_SIZE,
+        rlp_bytes_per_channel: MAX_RLP_BYTES_PER_CHANNEL_BEDROCK,
+        fjord_rlp_bytes_per_channel: MAX_RLP_BYTES_PER_CHANNEL_FJORD,
+        frame_len: MAX_FRAME_LEN,
+        span_batch_elements: MAX_SPAN_BATCH_ELEMENTS,
+        buffered_batches: None,
+    };
+
+    /// Returns the maximum channel bank size at the given L1 origin timestamp.
+    pub fn max_channel_bank_size(&self, cfg: &RollupConfig, timestamp: u64) -> usize {
+        if cfg.is_fjord_active(timestamp) {
+            self.fjord_channel_bank_size
+        } else {
+            self.channel_bank_size
+        }
+    }
+
+    /// Returns the maximum number of RLP bytes per channel at the given L1 origin timestamp.
+    pub fn max_rlp_bytes_per_channel(&self, cfg: &RollupConfig, timestamp: u64) -> usize {
+        if cfg.is_fjord_active(timestamp) {
+            self.fjord_rlp_bytes_per_channel as usize
+        } else {
+            self.rlp_bytes_per_channel as usize
+        }
+    }
+
+    /// Returns whether any limit differs from the spec defaults.
+    pub fn is_overridden(&self) -> bool {
+        *self != Self::SPEC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_limits_match_spec() {
+        let limits = DerivationLimits::default();
+        assert_eq!(limits.channel_bank_size, 100_000_000);
+        assert_eq!(limits.fjord_channel_bank_size, 1_000_000_000);
+        assert_eq!(limits.rlp_bytes_per_channel, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK);
+        assert_eq!(limits.fjord_rlp_bytes_per_channel, MAX_RLP_BYTES_PER_CHANNEL_FJORD);
+        assert_eq!(limits.frame_len, 1_000_000);
+        assert_eq!(limits.span_batch_elements, MAX_SPAN_BATCH_ELEMENTS);
+        assert_eq!(limits.buffered_batches, None);
+        assert!(!limits.is_overridden());
+    }
+
+    #[test]
+    fn test_limits_resolve_by_hardfork() {
+        let cfg = RollupConfig { fjord_time: Some(10), ..Default::default() };
+        let limits = DerivationLimits::default();
+        assert_eq!(limits.max_channel_bank_size(&cfg, 9), MAX_CHANNEL_BANK_SIZE);
+        assert_eq!(limits.max_channel_bank_size(&cfg, 10), FJORD_MAX_CHANNEL_BANK_SIZE);
+        assert_eq!(
+            limits.max_rlp_bytes_per_channel(&cfg, 9),
+            MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize
+        );
+        assert_eq!(
+            limits.max_rlp_bytes_per_channel(&cfg, 10),
+            MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize
+        );
+    }
+
+    #[test]
+    fn test_limits_override() {
+        let limits = DerivationLimits { channel_bank_size: 1, ..Default::default() };
+        assert!(limits.is_overridden());
+        assert_eq!(limits.max_channel_bank_size(&RollupConfig::default(), 0), 1);
+    }
+}
@@ -0,0 +1,184 @@
+//! Contains the types recorded through [DerivationMetrics], and [CountingMetrics], an
+//! implementation that aggregates them in memory.
+//!
+//! [DerivationMetrics]: crate::traits::DerivationMetrics
+
+use crate::traits::DerivationMetrics;
+use alloc::sync::Arc;
+use op_alloy_protocol::BatchValidity;
+use spin::Mutex;
+
+/// The reason a channel was closed by the [ChannelBank] or [ChannelAssembler].
+///
+/// [ChannelBank]: crate::stages::ChannelBank
+/// [ChannelAssembler]: crate::stages::ChannelAssembler
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum ChannelCloseReason {
+    /// The channel received all of its frames and was read.
+    #[display("ready")]
+    Ready,
+    /// The channel was not completed within the channel timeout.
+    #[display("timed out")]
+    TimedOut,
+    /// The channel was evicted to keep the channel bank below its maximum size.
+    #[display("evicted")]
+    Evicted,
+    /// The channel exceeded the maximum RLP bytes per channel.
+    #[display("oversized")]
+    Oversized,
+    /// The incomplete channel was replaced by a new channel.
+    #[display("superseded")]
+    Superseded,
+}
+
+/// A snapshot of the metrics aggregated by [CountingMetrics].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetricsSummary {
+    /// The number of frames parsed from batcher transactions.
+    pub frames: u64,
+    /// The total length of the data of all parsed frames, in bytes.
+    pub frame_bytes: u64,
+    /// The number of channels opened.
+    pub channels_opened: u64,
+    /// The number of channels read once complete.
+    pub channels_ready: u64,
+    /// The number of channels closed without being read.
+    pub channels_dropped: u64,
+    /// The number of channels that timed out, included in `channels_dropped`.
+    pub channels_timed_out: u64,
+    /// The number of accepted batches.
+    pub batches_accepted: u64,
+    /// The number of dropped batches, including future batches dropped after Holocene.
+    pub batches_dropped: u64,
+    /// The number of batches discarded as outdated.
+    pub batches_past: u64,
+    /// The number of prepared payload attributes.
+    pub attributes_prepared: u64,
+    /// The last recorded distance of the pipeline origin ahead of the L2 safe head's L1 origin, in
+    /// L1 blocks.
+    pub safe_origin_distance: u64,
+    /// The highest recorded distance of the pipeline origin ahead of the L2 safe head's L1
+    /// origin, in L1 blocks.
+    pub max_safe_origin_distance: u64,
+    /// The last recorded lag of the pipeline origin behind the L1 head, in L1 blocks.
+    pub l1_head_lag: u64,
+}
+
+/// A [DerivationMetrics] implementation that aggregates all metrics into a [MetricsSummary].
+///
+/// Clones share the same counters, so one handle can be given to the pipeline while another is
+/// kept to read the [MetricsSummary], e.g. at the end of a run.
+#[derive(Debug, Clone, Default)]
+pub struct CountingMetrics {
+    summary: Arc<Mutex<MetricsSummary>>,
+}
+
+impl CountingMetrics {
+    /// Creates a new [CountingMetrics] with all counters at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a snapshot of the aggregated metrics.
+    pub fn summary(&self) -> MetricsSummary {
+        *self.summary.lock()
+    }
+
+    /// Resets all counters to zero.
+    pub fn clear(&self) {
+        *self.summary.lock() = MetricsSummary::default();
+    }
+}
+
+impl DerivationMetrics for CountingMetrics {
+    fn record_frame(&self, len: usize) {
+        let mut summary = self.summary.lock();
+        summary.frames += 1;
+        summary.frame_bytes += len as u64;
+    }
+
+    fn record_channel_opened(&self) {
+        self.summary.lock().channels_opened += 1;
+    }
+
+    fn record_channel_closed(&self, reason: ChannelCloseReason) {
+        let mut summary = self.summary.lock();
+        match reason {
+            ChannelCloseReason::Ready => summary.channels_ready += 1,
+            ChannelCloseReason::TimedOut => {
+                summary.channels_timed_out += 1;
+                summary.channels_dropped += 1;
+            }
+            _ => summary.channels_dropped += 1,
+        }
+    }
+
+    fn record_batch(&self, validity: BatchValidity) {
+        let mut summary = self.summary.lock();
+        match validity {
+            BatchValidity::Accept => summary.batches_accepted += 1,
+            BatchValidity::Past => summary.batches_past += 1,
+            BatchValidity::Drop | BatchValidity::Future => summary.batches_dropped += 1,
+            BatchValidity::Undecided => {}
+        }
+    }
+
+    fn record_attributes_prepared(&self) {
+        self.summary.lock().attributes_prepared += 1;
+    }
+
+    fn record_safe_origin_distance(&self, distance: u64) {
+        let mut summary = self.summary.lock();
+        summary.safe_origin_distance = distance;
+        summary.max_safe_origin_distance = summary.max_safe_origin_distance.max(distance);
+    }
+
+    fn record_l1_head_lag(&self, lag: u64) {
+        self.summary.lock().l1_head_lag = lag;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting_metrics_aggregates() {
+        let metrics = CountingMetrics::new();
+        let handle = metrics.clone();
+        handle.record_frame(10);
+        handle.record_frame(5);
+        handle.record_channel_opened();
+        handle.record_channel_closed(ChannelCloseReason::Ready);
+        handle.record_channel_closed(ChannelCloseReason::TimedOut);
+        handle.record_channel_closed(ChannelCloseReason::Evicted);
+        handle.record_batch(BatchValidity::Accept);
+        handle.record_batch(BatchValidity::Drop);
+        handle.record_batch(BatchValidity::Future);
+        handle.record_batch(BatchValidity::Past);
+        handle.record_attributes_prepared();
+        handle.record_safe_origin_distance(4);
+        handle.record_safe_origin_distance(1);
+        handle.record_l1_head_lag(7);
+
+        let expected = MetricsSummary {
+            frames: 2,
+            frame_bytes: 15,
+            channels_opened: 1,
+            channels_ready: 1,
+            channels_dropped: 2,
+            channels_timed_out: 1,
+            batches_accepted: 1,
+            batches_dropped: 2,
+            batches_past: 1,
+            attributes_prepared: 1,
+            safe_origin_distance: 1,
+            max_safe_origin_distance: 4,
+            l1_head_lag: 7,
+        };
+        assert_eq!(metrics.summary(), expected);
+
+        metrics.clear();
+        assert_eq!(handle.summary(), MetricsSummary::default());
+    }
+}
@@ -4,4 +4,55 @@ mod results;
 pub use results::{PipelineResult, StepResult};
 
 mod signals;
-pub use signals::{ActivationSignal, ResetSignal, Signal};
+pub use signals::{ActivationSignal, ResetSignal, Signal, SignalReceipt};
+
+mod events;
+pub use events::{
+    AttributesDerivedEvent, ChannelFlushedEvent, OriginAdvancedEvent, PipelineEvent, ResetEvent,
+};
+
+mod limits;
+pub use limits::{
+    DerivationLimits, FJORD_MAX_CHANNEL_BANK_SIZE, MAX_CHANNEL_BANK_SIZE, MAX_FRAME_LEN,
+};
+
+mod epoch_cache;
+pub use epoch_cache::{EpochCache, DEFAULT_EPOCH_CACHE_SIZE};
+
+mod signer_cache;
+pub use signer_cache::{SignerCache, DEFAULT_SIGNER_CACHE_SIZE};
+
+mod channel_id;
+pub use channel_id::{ChannelId, CHANNEL_ID_LENGTH};
+
+mod stats;
+pub use stats::AttributesStats;
+
+mod replay;
+pub use replay::{ChainRequest, ChainResponse, ReplayEntry};
+
+mod retry;
+pub use retry::{
+    RetryPolicy, DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_BASE_DELAY, DEFAULT_RETRY_JITTER,
+};
+
+mod metrics;
+pub use metrics::{ChannelCloseReason, CountingMetrics, MetricsSummary};
+
+mod snapshot;
+pub use snapshot::{PreparedAttributes, PreparedEntry};
+
+mod diff;
+pub use diff::AttributesDiff;
+
+mod channel_out;
+pub use channel_out::{ChannelOut, CompressionAlgo};
+
+mod deposits;
+pub use deposits::BlocklistDepositFilter;
+
+mod sequencer;
+pub use sequencer::SequencerOverrides;
+
+mod status;
+pub use status::PipelineStatus;
@@ -0,0 +1,105 @@
+//! Request and response types recorded in a replay log.
+//!
+//! A replay log captures every [ChainProvider], [L2ChainProvider] and [BlobProvider] request
+//! made during a derivation run along with the response that was served, so the run can be
+//! reproduced later without access to the original RPC.
+//!
+//! [ChainProvider]: crate::traits::ChainProvider
+//! [L2ChainProvider]: crate::traits::L2ChainProvider
+//! [BlobProvider]: crate::traits::BlobProvider
+
+use alloc::{boxed::Box, vec::Vec};
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::{Bytes, B256};
+use op_alloy_genesis::SystemConfig;
+use op_alloy_protocol::{BlockInfo, L2BlockInfo};
+
+/// A request made to a provider of the derivation pipeline.
+#[derive(derive_more::Display, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChainRequest {
+    /// A `header_by_hash` request.
+    #[display("header_by_hash({_0})")]
+    HeaderByHash(B256),
+    /// A `block_info_by_number` request.
+    #[display("block_info_by_number({_0})")]
+    BlockInfoByNumber(u64),
+    /// A `receipts_by_hash` request.
+    #[display("receipts_by_hash({_0})")]
+    ReceiptsByHash(B256),
+    /// A `block_info_and_transactions_by_hash` request.
+    #[display("block_info_and_transactions_by_hash({_0})")]
+    BlockInfoAndTransactionsByHash(B256),
+    /// An `l2_block_info_by_number` request.
+    #[display("l2_block_info_by_number({_0})")]
+    L2BlockInfoByNumber(u64),
+    /// A `block_by_number` request for an L2 block.
+    #[display("block_by_number({_0})")]
+    L2BlockByNumber(u64),
+    /// A `system_config_by_number` request.
+    #[display("system_config_by_number({_0})")]
+    SystemConfigByNumber(u64),
+    /// A request for the blob with the given versioned hash, as part of a `get_blobs` request.
+    #[display("blob_by_hash({_0})")]
+    BlobByHash(B256),
+}
+
+/// A successful response served by a provider of the derivation pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChainResponse {
+    /// The response to a [ChainRequest::HeaderByHash] request.
+    Header(Box<Header>),
+    /// The response to a [ChainRequest::BlockInfoByNumber] request.
+    BlockInfo(BlockInfo),
+    /// The response to a [ChainRequest::ReceiptsByHash] request.
+    Receipts(Vec<Receipt>),
+    /// The response to a [ChainRequest::BlockInfoAndTransactionsByHash] request.
+    BlockInfoAndTransactions(BlockInfo, Vec<TxEnvelope>),
+    /// The response to a [ChainRequest::L2BlockInfoByNumber] request.
+    L2BlockInfo(L2BlockInfo),
+    /// The RLP encoding of the response to a [ChainRequest::L2BlockByNumber] request, as the
+    /// [OpBlock] itself is not serializable.
+    ///
+    /// [OpBlock]: op_alloy_consensus::OpBlock
+    L2Block(Bytes),
+    /// The response to a [ChainRequest::SystemConfigByNumber] request.
+    SystemConfig(SystemConfig),
+    /// The response to a [ChainRequest::BlobByHash] request.
+    Blob(Box<Blob>),
+}
+
+/// A single request/response pair in a replay log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayEntry {
+    /// The request made to the provider.
+    pub request: ChainRequest,
+    /// The response served by the provider.
+    pub response: ChainResponse,
+}
+
+impl ReplayEntry {
+    /// Creates a new [ReplayEntry].
+    pub const fn new(request: ChainRequest, response: ChainResponse) -> Self {
+        Self { request, response }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_entry_json_roundtrip() {
+        let block = BlockInfo { number: 1, timestamp: 12, ..Default::default() };
+        let entry = ReplayEntry::new(
+            ChainRequest::BlockInfoByNumber(1),
+            ChainResponse::BlockInfoAndTransactions(block, Vec::new()),
+        );
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(serde_json::from_str::<ReplayEntry>(&line).unwrap(), entry);
+    }
+}
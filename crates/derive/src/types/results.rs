@@ -6,14 +6,191 @@ use crate::errors::PipelineErrorKind;
 pub type PipelineResult<T> = Result<T, PipelineErrorKind>;
 
 /// A pipeline error.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(derive_more::Display, Debug, PartialEq, Eq)]
 pub enum StepResult {
     /// Attributes were successfully prepared.
+    #[display("Prepared attributes")]
     PreparedAttributes,
     /// Origin was advanced.
+    #[display("Advanced origin")]
     AdvancedOrigin,
     /// Origin advance failed.
+    #[display("Origin advance failed: {_0}")]
     OriginAdvanceErr(PipelineErrorKind),
     /// Step failed.
+    #[display("Step failed: {_0}")]
     StepFailed(PipelineErrorKind),
+    /// The step was rejected because the cursor is behind a cursor that was already stepped on.
+    /// The stages were not touched.
+    /// The first argument is the cursor's L2 block number, and the second argument is the highest
+    /// L2 block number stepped on.
+    #[display("Stale cursor: L2 block #{_0} is behind #{_1}")]
+    StaleCursor(u64, u64),
+    /// The step was rejected because the queue of prepared attributes is at capacity. The stages
+    /// were not touched; stepping resumes once the consumer pops prepared attributes.
+    #[display("Prepared attributes queue is full")]
+    PreparedQueueFull,
+    /// Origin was advanced, but more consecutive origins than the configured stall threshold were
+    /// advanced past without preparing attributes, e.g. because the batch inbox is misconfigured.
+    /// The argument is the number of origins advanced since attributes were last prepared.
+    /// Stepping may continue as usual.
+    #[display("Stalled: no attributes prepared for {_0} origins")]
+    Stalled(u64),
+    /// Attributes were derived again after a restart, but were already prepared from a snapshot
+    /// restored with `DerivationPipeline::restore_prepared`, so nothing was added to the prepared
+    /// attributes queue.
+    #[display("Skipped restored attributes")]
+    SkippedRestoredAttributes,
+}
+
+impl StepResult {
+    /// Returns `true` if the step made progress, either by preparing attributes, by skipping
+    /// restored attributes, or by advancing the origin.
+    pub const fn is_progress(&self) -> bool {
+        matches!(
+            self,
+            Self::PreparedAttributes |
+                Self::AdvancedOrigin |
+                Self::Stalled(_) |
+                Self::SkippedRestoredAttributes
+        )
+    }
+
+    /// Returns `true` if the step resulted in an error.
+    pub const fn is_err(&self) -> bool {
+        matches!(self, Self::OriginAdvanceErr(_) | Self::StepFailed(_))
+    }
+
+    /// Returns a reference to the inner [PipelineErrorKind], if the step failed.
+    pub const fn error(&self) -> Option<&PipelineErrorKind> {
+        match self {
+            Self::OriginAdvanceErr(e) | Self::StepFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Consumes the [StepResult], returning the inner [PipelineErrorKind] if the step failed.
+    pub fn into_error(self) -> Option<PipelineErrorKind> {
+        match self {
+            Self::OriginAdvanceErr(e) | Self::StepFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Converts the [StepResult] into a [PipelineResult], mapping failures to `Err` and everything
+    /// else, including a [StepResult::StaleCursor], to `Ok`.
+    pub fn into_result(self) -> PipelineResult<Self> {
+        match self {
+            Self::OriginAdvanceErr(e) | Self::StepFailed(e) => Err(e),
+            progress => Ok(progress),
+        }
+    }
+}
+
+impl From<StepResult> for PipelineResult<()> {
+    fn from(result: StepResult) -> Self {
+        result.into_result().map(|_| ())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StepResult {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use alloc::string::ToString;
+        use serde::ser::SerializeStruct;
+
+        let kind = match self {
+            Self::PreparedAttributes => "prepared_attributes",
+            Self::AdvancedOrigin => "advanced_origin",
+            Self::OriginAdvanceErr(_) => "origin_advance_err",
+            Self::StepFailed(_) => "step_failed",
+            Self::StaleCursor(_, _) => "stale_cursor",
+            Self::PreparedQueueFull => "prepared_queue_full",
+            Self::Stalled(_) => "stalled",
+            Self::SkippedRestoredAttributes => "skipped_restored_attributes",
+        };
+        let mut state = serializer.serialize_struct("StepResult", 2)?;
+        state.serialize_field("result", kind)?;
+        state.serialize_field("error", &self.error().map(|e| e.to_string()))?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{PipelineError, ResetError};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_step_result_display() {
+        assert_eq!(StepResult::PreparedAttributes.to_string(), "Prepared attributes");
+        assert_eq!(StepResult::AdvancedOrigin.to_string(), "Advanced origin");
+        assert_eq!(
+            StepResult::OriginAdvanceErr(PipelineError::Eof.temp()).to_string(),
+            "Origin advance failed: Temporary error: EOF"
+        );
+        assert_eq!(
+            StepResult::StepFailed(PipelineError::MissingOrigin.crit()).to_string(),
+            "Step failed: Critical error: Missing L1 origin from previous stage"
+        );
+        assert_eq!(
+            StepResult::StepFailed(ResetError::HoloceneActivation.reset()).to_string(),
+            "Step failed: Pipeline reset: Holocene activation reset"
+        );
+    }
+
+    #[test]
+    fn test_step_result_helpers() {
+        assert!(StepResult::PreparedAttributes.is_progress());
+        assert!(StepResult::AdvancedOrigin.is_progress());
+        assert_eq!(StepResult::AdvancedOrigin.into_error(), None);
+
+        let failed = StepResult::StepFailed(PipelineError::Eof.temp());
+        assert!(failed.is_err());
+        assert_eq!(failed.error(), Some(&PipelineError::Eof.temp()));
+        assert_eq!(failed.into_error(), Some(PipelineError::Eof.temp()));
+
+        let res: PipelineResult<()> =
+            StepResult::OriginAdvanceErr(PipelineError::Eof.temp()).into();
+        assert_eq!(res, Err(PipelineError::Eof.temp()));
+        let res: PipelineResult<()> = StepResult::PreparedAttributes.into();
+        assert_eq!(res, Ok(()));
+
+        let stale = StepResult::StaleCursor(1, 2);
+        assert!(!stale.is_progress());
+        assert!(!stale.is_err());
+        assert_eq!(stale.error(), None);
+        assert_eq!(stale.to_string(), "Stale cursor: L2 block #1 is behind #2");
+
+        let full = StepResult::PreparedQueueFull;
+        assert!(!full.is_progress());
+        assert!(!full.is_err());
+        assert_eq!(full.to_string(), "Prepared attributes queue is full");
+
+        let stalled = StepResult::Stalled(4);
+        assert!(stalled.is_progress());
+        assert!(!stalled.is_err());
+        assert_eq!(stalled.to_string(), "Stalled: no attributes prepared for 4 origins");
+
+        let skipped = StepResult::SkippedRestoredAttributes;
+        assert!(skipped.is_progress());
+        assert!(!skipped.is_err());
+        assert_eq!(skipped.to_string(), "Skipped restored attributes");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_step_result_serialize() {
+        let json = serde_json::to_string(&StepResult::AdvancedOrigin).unwrap();
+        assert_eq!(json, r#"{"result":"advanced_origin","error":null}"#);
+
+        let json =
+            serde_json::to_string(&StepResult::StepFailed(PipelineError::MissingOrigin.crit()))
+                .unwrap();
+        assert_eq!(
+            json,
+            r#"{"result":"step_failed","error":"Critical error: Missing L1 origin from previous stage"}"#
+        );
+    }
 }
@@ -0,0 +1,100 @@
+//! Contains the [RetryPolicy] used by the [RetryProvider].
+//!
+//! [RetryProvider]: crate::sources::RetryProvider
+
+use core::time::Duration;
+
+/// The default maximum number of attempts per request, including the first one.
+pub const DEFAULT_RETRY_ATTEMPTS: u32 = 5;
+
+/// The default delay before the first retry.
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// The default maximum jitter added to every retry delay.
+pub const DEFAULT_RETRY_JITTER: Duration = Duration::from_millis(50);
+
+/// Configures how the [RetryProvider] retries transient provider errors.
+///
+/// The delay before retry `n` (starting at 1) is `base_delay * 2^(n - 1)`, plus a jitter of at
+/// most `max_jitter`.
+///
+/// [RetryProvider]: crate::sources::RetryProvider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts per request, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The maximum jitter added to every retry delay.
+    pub max_jitter: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RETRY_ATTEMPTS,
+            base_delay: DEFAULT_RETRY_BASE_DELAY,
+            max_jitter: DEFAULT_RETRY_JITTER,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub const NONE: Self =
+        Self { max_attempts: 1, base_delay: Duration::ZERO, max_jitter: Duration::ZERO };
+
+    /// Creates a new [RetryPolicy] without jitter.
+    pub const fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self { max_attempts, base_delay, max_jitter: Duration::ZERO }
+    }
+
+    /// Sets the maximum jitter added to every retry delay.
+    pub const fn with_jitter(mut self, max_jitter: Duration) -> Self {
+        self.max_jitter = max_jitter;
+        self
+    }
+
+    /// Returns the delay before the given retry, starting at 1. `entropy` selects the jitter
+    /// within `[0, max_jitter]`.
+    pub const fn delay(&self, retry: u32, entropy: u64) -> Duration {
+        let factor = match 1u32.checked_shl(retry.saturating_sub(1)) {
+            Some(factor) => factor,
+            None => u32::MAX,
+        };
+        let backoff = self.base_delay.saturating_mul(factor);
+        let range = (self.max_jitter.as_nanos() as u64).saturating_add(1);
+        let jitter = Duration::from_nanos(entropy % range);
+        backoff.saturating_add(jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_policy_exponential_delay() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100));
+        assert_eq!(policy.delay(1, 7), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, 7), Duration::from_millis(200));
+        assert_eq!(policy.delay(4, 7), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn test_retry_policy_jitter_bounded() {
+        let policy =
+            RetryPolicy::new(5, Duration::from_millis(100)).with_jitter(Duration::from_millis(10));
+        for entropy in [0, 1, 10_000_000, u64::MAX] {
+            let delay = policy.delay(1, entropy);
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay <= Duration::from_millis(110));
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_delay_saturates() {
+        let policy = RetryPolicy::new(u32::MAX, Duration::from_secs(1));
+        assert_eq!(policy.delay(u32::MAX, 0), Duration::from_secs(1).saturating_mul(u32::MAX));
+    }
+}
@@ -0,0 +1,18 @@
+//! Contains [SequencerOverrides], the fields of payload attributes a [SequencerPolicy] may change.
+//!
+//! [SequencerPolicy]: crate::traits::SequencerPolicy
+
+use alloy_primitives::Address;
+
+/// The fields of the derived payload attributes that a [SequencerPolicy] may override.
+///
+/// [SequencerPolicy]: crate::traits::SequencerPolicy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequencerOverrides {
+    /// Whether the execution engine must not include transactions from its pool.
+    pub no_tx_pool: bool,
+    /// The gas limit of the block.
+    pub gas_limit: u64,
+    /// The recipient of the block's priority fees.
+    pub suggested_fee_recipient: Address,
+}
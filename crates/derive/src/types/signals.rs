@@ -17,6 +17,10 @@ pub enum Signal {
     Activation(ActivationSignal),
     /// Flush the currently active channel.
     FlushChannel,
+    /// Advance the pipeline to the next L1 origin without waiting for the current one to be
+    /// exhausted, e.g. when the driver knows no more data will appear in it. Unlike a reset, the
+    /// stages keep their state.
+    AdvanceOrigin,
 }
 
 impl Signal {
@@ -26,10 +30,22 @@ impl Signal {
             Self::Reset(reset) => reset.with_system_config(system_config).signal(),
             Self::Activation(activation) => activation.with_system_config(system_config).signal(),
             Self::FlushChannel => Self::FlushChannel,
+            Self::AdvanceOrigin => Self::AdvanceOrigin,
         }
     }
 }
 
+/// A summary of the work discarded by the pipeline while handling a [Signal].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SignalReceipt {
+    /// The number of prepared attributes that were discarded.
+    pub prepared_discarded: usize,
+    /// Whether the stages were reset to a new L1 origin.
+    pub stages_reset: bool,
+    /// The number of channels dropped, including a partially read channel.
+    pub flushed_channels: usize,
+}
+
 /// A pipeline reset signal.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct ResetSignal {
@@ -0,0 +1,182 @@
+//! Contains the [SignerCache], a shared cache of recovered batcher transaction signers.
+
+use alloc::{collections::VecDeque, sync::Arc};
+use alloy_consensus::TxEnvelope;
+use alloy_primitives::{map::HashMap, Address, Signature, B256};
+use spin::Mutex;
+
+/// The default number of signers retained by the [SignerCache].
+pub const DEFAULT_SIGNER_CACHE_SIZE: usize = 1_024;
+
+/// A bounded cache of recovered transaction signers, keyed by signature hash and signature.
+///
+/// The data sources recover the signer of every batch inbox transaction, and a reset replays the
+/// same L1 blocks, so the same transactions would otherwise be recovered again. The key is
+/// computed locally from the transaction contents rather than taken from the provider-supplied
+/// transaction hash, and fully determines the recovered signer, so the mapping never needs to be
+/// invalidated. Clones share the same underlying cache.
+#[derive(Debug, Clone)]
+pub struct SignerCache {
+    /// The cached signers and the hit and miss counters.
+    inner: Arc<Mutex<SignerCacheInner>>,
+    /// The maximum number of cached signers.
+    capacity: usize,
+}
+
+/// The state of a [SignerCache].
+#[derive(Debug, Default)]
+struct SignerCacheInner {
+    /// The recovered signers by signature hash and signature. Failed recoveries are cached as
+    /// [None].
+    signers: HashMap<(B256, Signature), Option<Address>>,
+    /// The cached keys, oldest first.
+    order: VecDeque<(B256, Signature)>,
+    /// The number of lookups served from the cache.
+    hits: u64,
+    /// The number of lookups that required a recovery.
+    misses: u64,
+}
+
+impl Default for SignerCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIGNER_CACHE_SIZE)
+    }
+}
+
+impl SignerCache {
+    /// Creates a new [SignerCache] that retains at most `capacity` signers.
+    pub fn new(capacity: usize) -> Self {
+        Self { inner: Arc::new(Mutex::new(SignerCacheInner::default())), capacity }
+    }
+
+    /// Returns the signer of the transaction, recovering it if it is not cached.
+    pub fn recover_signer(&self, tx: &TxEnvelope) -> Option<Address> {
+        let signature = match tx {
+            TxEnvelope::Legacy(tx) => tx.signature(),
+            TxEnvelope::Eip2930(tx) => tx.signature(),
+            TxEnvelope::Eip1559(tx) => tx.signature(),
+            TxEnvelope::Eip4844(tx) => tx.signature(),
+            TxEnvelope::Eip7702(tx) => tx.signature(),
+            _ => return tx.recover_signer().ok(),
+        };
+        self.get_or_recover(tx.signature_hash(), *signature, || tx.recover_signer().ok())
+    }
+
+    /// Returns the cached signer for the given signature hash and signature, or computes it with
+    /// `recover` and caches the result. `recover` must recover the signer of exactly this
+    /// signature hash and signature.
+    pub fn get_or_recover(
+        &self,
+        signature_hash: B256,
+        signature: Signature,
+        recover: impl FnOnce() -> Option<Address>,
+    ) -> Option<Address> {
+        let key = (signature_hash, signature);
+        {
+            let mut inner = self.inner.lock();
+            if let Some(signer) = inner.signers.get(&key).copied() {
+                inner.hits += 1;
+                return signer;
+            }
+            inner.misses += 1;
+        }
+
+        // Recover without holding the lock, as recovery is expensive.
+        let signer = recover();
+        if self.capacity == 0 {
+            return signer;
+        }
+        let mut inner = self.inner.lock();
+        if inner.signers.insert(key, signer).is_none() {
+            inner.order.push_back(key);
+            if inner.order.len() > self.capacity {
+                if let Some(evicted) = inner.order.pop_front() {
+                    inner.signers.remove(&evicted);
+                }
+            }
+        }
+        signer
+    }
+
+    /// Returns the number of lookups served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.inner.lock().hits
+    }
+
+    /// Returns the number of lookups that required a recovery.
+    pub fn misses(&self) -> u64 {
+        self.inner.lock().misses
+    }
+
+    /// Returns the number of cached signers.
+    pub fn len(&self) -> usize {
+        self.inner.lock().order.len()
+    }
+
+    /// Returns whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().order.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxEip1559};
+    use alloy_primitives::{Bytes, TxKind};
+
+    fn key(i: u8) -> (B256, Signature) {
+        (B256::with_last_byte(i), Signature::test_signature())
+    }
+
+    #[test]
+    fn test_signer_cache_hits() {
+        let cache = SignerCache::default();
+        let signer = Some(Address::with_last_byte(1));
+        let (hash, sig) = key(1);
+        assert_eq!(cache.get_or_recover(hash, sig, || signer), signer);
+        assert_eq!(cache.get_or_recover(hash, sig, || unreachable!()), signer);
+        let (hash, sig) = key(2);
+        assert_eq!(cache.get_or_recover(hash, sig, || None), None);
+        assert_eq!(cache.get_or_recover(hash, sig, || unreachable!()), None);
+        assert_eq!((cache.hits(), cache.misses()), (2, 2));
+    }
+
+    #[test]
+    fn test_signer_cache_bounded() {
+        let cache = SignerCache::new(2);
+        for i in 1..=3 {
+            let (hash, sig) = key(i);
+            cache.get_or_recover(hash, sig, || Some(Address::with_last_byte(i)));
+        }
+        assert_eq!(cache.len(), 2);
+
+        // The oldest signer was evicted.
+        let mut recovered = false;
+        let (hash, sig) = key(1);
+        cache.get_or_recover(hash, sig, || {
+            recovered = true;
+            None
+        });
+        assert!(recovered);
+    }
+
+    #[test]
+    fn test_signer_cache_ignores_tx_hash() {
+        let tx = |input: &'static [u8]| {
+            let tx = TxEip1559 {
+                to: TxKind::Call(Address::ZERO),
+                input: Bytes::from_static(input),
+                ..Default::default()
+            };
+            // Both transactions claim the same, unverified, transaction hash.
+            TxEnvelope::Eip1559(Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO))
+        };
+        let (first, second) = (tx(&[1]), tx(&[2]));
+        let cache = SignerCache::default();
+        assert_eq!(cache.recover_signer(&first), first.recover_signer().ok());
+        assert_eq!(cache.recover_signer(&second), second.recover_signer().ok());
+        assert_ne!(cache.recover_signer(&first), cache.recover_signer(&second));
+        assert_eq!((cache.hits(), cache.misses()), (2, 2));
+    }
+}
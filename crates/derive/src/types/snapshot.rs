@@ -0,0 +1,74 @@
+//! Contains [PreparedAttributes] and [PreparedEntry], its serializable form.
+
+use op_alloy_protocol::{BlockInfo, L2BlockInfo};
+use op_alloy_rpc_types_engine::{OpAttributesWithParent, OpPayloadAttributes};
+
+/// A prepared [OpAttributesWithParent] in the [DerivationPipeline]'s queue, along with the L1
+/// origin it was derived from.
+///
+/// [DerivationPipeline]: crate::pipeline::DerivationPipeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedAttributes {
+    /// The prepared attributes.
+    pub attributes: OpAttributesWithParent,
+    /// The L1 origin the attributes were derived from, if known.
+    pub derived_from: Option<BlockInfo>,
+}
+
+impl PreparedAttributes {
+    /// Creates a new [PreparedAttributes] from the prepared attributes and their L1 origin.
+    pub const fn new(attributes: OpAttributesWithParent, derived_from: Option<BlockInfo>) -> Self {
+        Self { attributes, derived_from }
+    }
+}
+
+/// The serializable form of [PreparedAttributes].
+///
+/// Snapshots of the prepared queue, taken with [DerivationPipeline::snapshot_prepared], can be
+/// persisted and restored with [DerivationPipeline::restore_prepared] so that a restarted node
+/// does not have to re-derive them.
+///
+/// [DerivationPipeline]: crate::pipeline::DerivationPipeline
+/// [DerivationPipeline::snapshot_prepared]: crate::pipeline::DerivationPipeline::snapshot_prepared
+/// [DerivationPipeline::restore_prepared]: crate::pipeline::DerivationPipeline::restore_prepared
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreparedEntry {
+    /// The payload attributes.
+    pub attributes: OpPayloadAttributes,
+    /// The L2 parent block of the attributes.
+    pub parent: L2BlockInfo,
+    /// Whether the attributes are the last in their span batch.
+    pub is_last_in_span: bool,
+    /// The L1 origin the attributes were derived from.
+    pub derived_from: Option<BlockInfo>,
+}
+
+impl PreparedEntry {
+    /// Creates a new [PreparedEntry] from the prepared attributes and their L1 origin.
+    pub fn new(attributes: OpAttributesWithParent, derived_from: Option<BlockInfo>) -> Self {
+        Self {
+            attributes: attributes.attributes,
+            parent: attributes.parent,
+            is_last_in_span: attributes.is_last_in_span,
+            derived_from,
+        }
+    }
+}
+
+impl From<PreparedAttributes> for PreparedEntry {
+    fn from(prepared: PreparedAttributes) -> Self {
+        Self::new(prepared.attributes, prepared.derived_from)
+    }
+}
+
+impl From<PreparedEntry> for PreparedAttributes {
+    fn from(entry: PreparedEntry) -> Self {
+        let attributes = OpAttributesWithParent {
+            attributes: entry.attributes,
+            parent: entry.parent,
+            is_last_in_span: entry.is_last_in_span,
+        };
+        Self::new(attributes, entry.derived_from)
+    }
+}
@@ -0,0 +1,69 @@
+//! Contains [AttributesStats], a lightweight summary of the transactions in payload attributes.
+
+use alloy_primitives::Bytes;
+use op_alloy_consensus::OpTxType;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+/// A summary of the transactions carried by derived payload attributes.
+///
+/// Transactions are classified by their first byte only, so computing the summary never decodes
+/// a transaction. Empty transactions are counted as user transactions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttributesStats {
+    /// The number of deposit transactions.
+    pub deposits: usize,
+    /// The number of non-deposit transactions.
+    pub user_transactions: usize,
+    /// The total length of all encoded transactions, in bytes.
+    pub total_bytes: usize,
+}
+
+impl AttributesStats {
+    /// Computes the [AttributesStats] of a list of encoded transactions.
+    pub fn from_transactions(txs: &[Bytes]) -> Self {
+        txs.iter().fold(Self::default(), |mut stats, tx| {
+            if tx.first() == Some(&(OpTxType::Deposit as u8)) {
+                stats.deposits += 1;
+            } else {
+                stats.user_transactions += 1;
+            }
+            stats.total_bytes += tx.len();
+            stats
+        })
+    }
+
+    /// Computes the [AttributesStats] of the given [OpPayloadAttributes].
+    pub fn from_attributes(attributes: &OpPayloadAttributes) -> Self {
+        attributes.transactions.as_deref().map(Self::from_transactions).unwrap_or_default()
+    }
+
+    /// Returns the total number of transactions.
+    pub const fn transactions(&self) -> usize {
+        self.deposits + self.user_transactions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_stats_mixed_transactions() {
+        let txs = vec![
+            Bytes::from(vec![OpTxType::Deposit as u8, 0x01, 0x02]),
+            Bytes::from(vec![0x02, 0x01]),
+            Bytes::from(vec![OpTxType::Deposit as u8]),
+            Bytes::from(vec![0xf8, 0x01, 0x02, 0x03]),
+            Bytes::default(),
+        ];
+        let stats = AttributesStats::from_transactions(&txs);
+        assert_eq!(stats, AttributesStats { deposits: 2, user_transactions: 3, total_bytes: 10 });
+        assert_eq!(stats.transactions(), 5);
+    }
+
+    #[test]
+    fn test_stats_no_transactions() {
+        assert_eq!(AttributesStats::from_transactions(&[]), AttributesStats::default());
+    }
+}
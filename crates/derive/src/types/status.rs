@@ -0,0 +1,29 @@
+//! Contains [PipelineStatus], a point-in-time summary of the derivation pipeline's state.
+
+use alloc::vec::Vec;
+use op_alloy_protocol::{BlockInfo, L2BlockInfo};
+
+/// A snapshot of the derivation pipeline's state, returned by [Pipeline::status].
+///
+/// The status is assembled from lightweight accessors on every stage, so taking it never
+/// touches the providers. It is intended for logging when derivation fails or stalls.
+///
+/// [Pipeline::status]: crate::traits::Pipeline::status
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PipelineStatus {
+    /// The L1 origin of the pipeline.
+    pub origin: Option<BlockInfo>,
+    /// The L2 parent of the most recently prepared attributes, if any are prepared.
+    pub last_prepared_cursor: Option<L2BlockInfo>,
+    /// The number of prepared attributes.
+    pub prepared_len: usize,
+    /// The name and L1 origin of every stage, from the bottom of the pipeline up.
+    pub stage_origins: Vec<(&'static str, Option<BlockInfo>)>,
+    /// The number of channels buffered by the channel stages.
+    pub channels: usize,
+    /// The total size of the frames buffered by the channel stages.
+    pub channel_bytes: usize,
+    /// The number of batches buffered by the batch stages.
+    pub buffered_batches: usize,
+}
@@ -13,7 +13,7 @@ use kona_derive::{
 use op_alloy_consensus::{OpBlock, OpTxEnvelope, OpTxType};
 use op_alloy_genesis::RollupConfig;
 use op_alloy_protocol::L2BlockInfo;
-use op_alloy_rpc_types_engine::OpAttributesWithParent;
+use op_alloy_rpc_types_engine::{OpAttributesWithParent, OpPayloadAttributes};
 use tracing::{error, info, warn};
 
 use crate::{
@@ -123,11 +123,7 @@ where
                         self.pipeline.signal(Signal::FlushChannel).await?;
 
                         // Strip out all transactions that are not deposits.
-                        attributes.transactions = attributes.transactions.map(|txs| {
-                            txs.into_iter()
-                                .filter(|tx| (!tx.is_empty() && tx[0] == OpTxType::Deposit as u8))
-                                .collect::<Vec<_>>()
-                        });
+                        strip_non_deposits(&mut attributes);
 
                         // Retry the execution.
                         executor =
@@ -179,3 +175,13 @@ where
         }
     }
 }
+
+/// Strips all transactions that are not deposits from the given attributes, turning them into a
+/// deposit-only block.
+pub(crate) fn strip_non_deposits(attributes: &mut OpPayloadAttributes) {
+    attributes.transactions = attributes.transactions.take().map(|txs| {
+        txs.into_iter()
+            .filter(|tx| !tx.is_empty() && tx[0] == OpTxType::Deposit as u8)
+            .collect::<Vec<_>>()
+    });
+}
@@ -84,6 +84,18 @@ impl PipelineCursor {
         self.tips.insert(origin.number, l2_tip_block);
     }
 
+    /// Returns the L2 tip and L1 origin to restart the pipeline from on a reset.
+    ///
+    /// The pipeline must restart far enough back that any channel which may still contribute to
+    /// the next safe head is read again from its first frame, so this is the latest tip derived
+    /// from an L1 origin at least a channel timeout behind the current origin. Returns [None] if
+    /// no such tip is cached, as the pipeline cannot be safely restarted from the cache.
+    pub fn reset_target(&self) -> Option<(&TipCursor, BlockInfo)> {
+        let channel_start = self.origin.number.checked_sub(self.channel_timeout)?;
+        let (number, tip) = self.tips.range(..=channel_start).next_back()?;
+        Some((tip, self.origin_infos[number]))
+    }
+
     /// When the L1 undergoes a reorg, we need to reset the cursor to the fork block minus
     /// the channel timeout, because an L2 block might have started to be derived at the
     /// beginning of the channel.
@@ -111,3 +123,58 @@ impl PipelineCursor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Sealable;
+
+    fn tip(number: u64) -> TipCursor {
+        let l2_safe_head = L2BlockInfo {
+            block_info: BlockInfo { number, timestamp: number * 2, ..Default::default() },
+            ..Default::default()
+        };
+        let header = Header { number, ..Default::default() }.seal_slow();
+        TipCursor::new(l2_safe_head, header, B256::with_last_byte(number as u8))
+    }
+
+    fn origin(number: u64) -> BlockInfo {
+        BlockInfo { number, timestamp: number * 12, ..Default::default() }
+    }
+
+    /// Returns a cursor with a channel timeout of 2, advanced through 24 L2 blocks at six L2
+    /// blocks per L1 block.
+    fn advanced_cursor() -> PipelineCursor {
+        let mut cursor = PipelineCursor::new(2, origin(0));
+        cursor.advance(origin(0), tip(0));
+        for number in 1..=24 {
+            cursor.advance(origin(number / 6), tip(number));
+        }
+        cursor
+    }
+
+    #[test]
+    fn test_pipeline_cursor_reset_target() {
+        let cursor = advanced_cursor();
+        assert_eq!(cursor.origin(), origin(4));
+
+        // The latest tip derived from L1 origin #2 or earlier.
+        let (target, target_origin) = cursor.reset_target().unwrap();
+        assert_eq!(target.l2_safe_head.block_info.number, 17);
+        assert_eq!(target_origin, origin(2));
+    }
+
+    #[test]
+    fn test_pipeline_cursor_reset_target_missing() {
+        // The chain is younger than a channel timeout.
+        let mut cursor = PipelineCursor::new(2, origin(0));
+        cursor.advance(origin(1), tip(1));
+        assert!(cursor.reset_target().is_none());
+
+        // No cached tip is a channel timeout behind the origin.
+        let mut cursor = PipelineCursor::new(2, origin(5));
+        cursor.advance(origin(5), tip(1));
+        cursor.advance(origin(6), tip(2));
+        assert!(cursor.reset_target().is_none());
+    }
+}
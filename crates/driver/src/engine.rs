@@ -0,0 +1,370 @@
+//! Contains a driver that inserts derived attributes into an execution engine.
+
+use alloc::{boxed::Box, string::String};
+use async_trait::async_trait;
+use core::fmt::Debug;
+use kona_derive::{
+    errors::{PipelineError, PipelineErrorKind},
+    traits::{Pipeline, SignalReceiver},
+    types::Signal,
+};
+use op_alloy_protocol::{BlockInfo, L2BlockInfo};
+use op_alloy_rpc_types_engine::OpAttributesWithParent;
+use tracing::{error, info, warn};
+
+use crate::{core::strip_non_deposits, DriverError, DriverPipeline, DriverResult};
+
+/// An error returned by an [EngineConsumer] when inserting attributes.
+#[derive(derive_more::Display, Clone, Debug, PartialEq, Eq)]
+pub enum EngineError {
+    /// The engine rejected the payload built from the attributes as invalid.
+    #[display("Invalid payload: {_0}")]
+    InvalidPayload(String),
+    /// The engine could not be reached or failed for a reason unrelated to the payload.
+    #[display("Engine unavailable: {_0}")]
+    Unavailable(String),
+}
+
+impl core::error::Error for EngineError {}
+
+/// Consumes [OpAttributesWithParent] produced by the derivation pipeline, building and
+/// inserting the resulting block into an execution engine.
+#[async_trait]
+pub trait EngineConsumer {
+    /// Builds a payload from the given attributes, inserts it into the engine, and updates the
+    /// engine's forkchoice so that the new block becomes the safe head.
+    ///
+    /// ## Returns
+    /// - `Ok(L2BlockInfo)` - The [L2BlockInfo] of the inserted block.
+    /// - `Err(EngineError::InvalidPayload)` - The engine rejected the payload. The caller is
+    ///   expected to handle this by flushing the current channel and retrying with a deposit-only
+    ///   payload once Holocene is active, or by dropping the attributes before Holocene.
+    /// - `Err(EngineError::Unavailable)` - The engine failed for a reason unrelated to the payload.
+    async fn insert_attributes(
+        &mut self,
+        attrs: OpAttributesWithParent,
+    ) -> Result<L2BlockInfo, EngineError>;
+}
+
+/// Tracks the L2 safe head along with the L1 origin that it was derived from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SafeHeadTracker {
+    /// The current L2 safe head.
+    l2_safe_head: L2BlockInfo,
+    /// The L1 origin of the pipeline at the time the safe head was last advanced.
+    derived_from: Option<BlockInfo>,
+}
+
+impl SafeHeadTracker {
+    /// Creates a new [SafeHeadTracker] starting at the given L2 safe head.
+    pub const fn new(l2_safe_head: L2BlockInfo) -> Self {
+        Self { l2_safe_head, derived_from: None }
+    }
+
+    /// Returns the current L2 safe head.
+    pub const fn l2_safe_head(&self) -> &L2BlockInfo {
+        &self.l2_safe_head
+    }
+
+    /// Returns the L1 origin that the current safe head was derived from, if known.
+    pub const fn derived_from(&self) -> Option<BlockInfo> {
+        self.derived_from
+    }
+
+    /// Advances the safe head to the given block, derived from the given L1 origin.
+    pub fn advance(&mut self, l2_safe_head: L2BlockInfo, derived_from: Option<BlockInfo>) {
+        self.l2_safe_head = l2_safe_head;
+        self.derived_from = derived_from;
+    }
+}
+
+/// A driver that produces payloads from a [DriverPipeline], inserts them into an
+/// [EngineConsumer], and keeps the [SafeHeadTracker] up to date.
+#[derive(Debug)]
+pub struct DerivationDriver<E, DP, P>
+where
+    E: EngineConsumer + Send + Sync + Debug,
+    DP: DriverPipeline<P> + Send + Sync + Debug,
+    P: Pipeline + SignalReceiver + Send + Sync + Debug,
+{
+    /// Marker for the pipeline.
+    _marker: core::marker::PhantomData<P>,
+    /// A pipeline abstraction.
+    pub pipeline: DP,
+    /// The safe head tracker.
+    pub safe_head: SafeHeadTracker,
+    /// The engine consuming the derived attributes.
+    pub engine: E,
+}
+
+impl<E, DP, P> DerivationDriver<E, DP, P>
+where
+    E: EngineConsumer + Send + Sync + Debug,
+    DP: DriverPipeline<P> + Send + Sync + Debug,
+    P: Pipeline + SignalReceiver + Send + Sync + Debug,
+{
+    /// Creates a new [DerivationDriver].
+    pub const fn new(pipeline: DP, safe_head: SafeHeadTracker, engine: E) -> Self {
+        Self { _marker: core::marker::PhantomData, pipeline, safe_head, engine }
+    }
+
+    /// Drives the pipeline until the L2 safe head reaches the `target` block number.
+    ///
+    /// Returns the final safe head. If the pipeline's data source is exhausted before the target
+    /// is reached, the current safe head is returned.
+    pub async fn advance_to(&mut self, target: u64) -> DriverResult<L2BlockInfo, EngineError> {
+        while self.safe_head.l2_safe_head().block_info.number < target {
+            match self.step().await {
+                Ok(_) => {}
+                Err(DriverError::Pipeline(PipelineErrorKind::Critical(
+                    PipelineError::EndOfSource,
+                ))) => {
+                    warn!(target: "driver", "Exhausted data source; halting at current safe head");
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(*self.safe_head.l2_safe_head())
+    }
+
+    /// Produces the next payload and inserts it into the engine.
+    ///
+    /// ## Returns
+    /// - `Ok(Some(L2BlockInfo))` - A new block was inserted and is now the safe head.
+    /// - `Ok(None)` - The payload was invalid and dropped before Holocene.
+    /// - `Err(_)` - A critical pipeline error or an engine error occurred.
+    pub async fn step(&mut self) -> DriverResult<Option<L2BlockInfo>, EngineError> {
+        let mut attrs = self.pipeline.produce_payload(*self.safe_head.l2_safe_head()).await?;
+        let derived_from = self.pipeline.derived_from();
+
+        let timestamp = attrs.attributes.payload_attributes.timestamp;
+        let info = match self.engine.insert_attributes(attrs.clone()).await {
+            Ok(info) => info,
+            Err(EngineError::InvalidPayload(reason)) => {
+                if !self.pipeline.rollup_config().is_holocene_active(timestamp) {
+                    warn!(target: "driver", "Dropping invalid payload: {reason}");
+                    return Ok(None);
+                }
+
+                warn!(target: "driver", "Invalid payload ({reason}); flushing channel and retrying deposit-only block");
+                self.pipeline.signal(Signal::FlushChannel).await?;
+
+                strip_non_deposits(&mut attrs.attributes);
+                self.engine.insert_attributes(attrs).await.map_err(|e| {
+                    error!(target: "driver", "Critical - failed to insert deposit-only block: {e}");
+                    DriverError::Executor(e)
+                })?
+            }
+            Err(e) => return Err(DriverError::Executor(e)),
+        };
+
+        info!(target: "driver", "Inserted L2 block #{}", info.block_info.number);
+        self.safe_head.advance(info, derived_from);
+        Ok(Some(info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, sync::Arc, vec, vec::Vec};
+    use alloy_primitives::Bytes;
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use kona_derive::{
+        errors::ResetError,
+        pipeline::DerivationPipeline,
+        test_utils::{TestL2ChainProvider, TestNextAttributes},
+    };
+    use op_alloy_consensus::OpTxType;
+    use op_alloy_genesis::{RollupConfig, SystemConfig};
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    type TestPipeline = DerivationPipeline<TestNextAttributes, TestL2ChainProvider>;
+
+    impl DriverPipeline<TestPipeline> for TestPipeline {
+        fn flush(&self) {}
+    }
+
+    /// A mock [EngineConsumer] that "executes" attributes by assigning them sequential block
+    /// numbers.
+    #[derive(Debug, Default, Clone)]
+    struct TestEngine {
+        /// The attributes that were successfully inserted, in order.
+        inserted: Vec<OpAttributesWithParent>,
+        /// Timestamps of payloads to reject as invalid. Each entry rejects a single insertion.
+        invalid: Vec<u64>,
+        /// If set, every insertion fails with [EngineError::Unavailable].
+        unavailable: bool,
+    }
+
+    #[async_trait]
+    impl EngineConsumer for TestEngine {
+        async fn insert_attributes(
+            &mut self,
+            attrs: OpAttributesWithParent,
+        ) -> Result<L2BlockInfo, EngineError> {
+            if self.unavailable {
+                return Err(EngineError::Unavailable("engine offline".to_string()));
+            }
+            let timestamp = attrs.attributes.payload_attributes.timestamp;
+            if let Some(i) = self.invalid.iter().position(|t| *t == timestamp) {
+                self.invalid.remove(i);
+                return Err(EngineError::InvalidPayload("rejected by test engine".to_string()));
+            }
+            self.inserted.push(attrs);
+            Ok(L2BlockInfo {
+                block_info: BlockInfo {
+                    number: self.inserted.len() as u64,
+                    timestamp,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        }
+    }
+
+    fn attributes(timestamp: u64, transactions: Vec<Bytes>) -> OpAttributesWithParent {
+        OpAttributesWithParent {
+            attributes: OpPayloadAttributes {
+                payload_attributes: PayloadAttributes {
+                    timestamp,
+                    prev_randao: Default::default(),
+                    suggested_fee_recipient: Default::default(),
+                    withdrawals: None,
+                    parent_beacon_block_root: None,
+                },
+                transactions: Some(transactions),
+                no_tx_pool: Some(true),
+                gas_limit: None,
+                eip_1559_params: None,
+            },
+            parent: Default::default(),
+            is_last_in_span: false,
+        }
+    }
+
+    fn deposit() -> Bytes {
+        Bytes::from(vec![OpTxType::Deposit as u8, 0x01])
+    }
+
+    fn user_tx() -> Bytes {
+        Bytes::from(vec![0x02, 0x01])
+    }
+
+    fn new_test_driver(
+        cfg: RollupConfig,
+        attrs: Vec<OpAttributesWithParent>,
+        engine: TestEngine,
+    ) -> DerivationDriver<TestEngine, TestPipeline, TestPipeline> {
+        let mut l2_chain_provider = TestL2ChainProvider::default();
+        l2_chain_provider.system_configs.insert(0, SystemConfig::default());
+        let attributes = TestNextAttributes::with_queue(attrs);
+        let pipeline = DerivationPipeline::new(attributes, Arc::new(cfg), l2_chain_provider);
+        DerivationDriver::new(pipeline, SafeHeadTracker::default(), engine)
+    }
+
+    #[test]
+    fn test_safe_head_tracker_advance() {
+        let mut tracker = SafeHeadTracker::default();
+        assert_eq!(tracker.derived_from(), None);
+
+        let head = L2BlockInfo {
+            block_info: BlockInfo { number: 1, ..Default::default() },
+            ..Default::default()
+        };
+        let origin = BlockInfo { number: 10, ..Default::default() };
+        tracker.advance(head, Some(origin));
+        assert_eq!(tracker.l2_safe_head(), &head);
+        assert_eq!(tracker.derived_from(), Some(origin));
+    }
+
+    #[tokio::test]
+    async fn test_engine_driver_inserts_blocks() {
+        let attrs = vec![
+            attributes(2, vec![deposit(), user_tx()]),
+            attributes(4, vec![deposit()]),
+            attributes(6, vec![deposit(), user_tx()]),
+        ];
+        let mut driver = new_test_driver(RollupConfig::default(), attrs, TestEngine::default());
+
+        let head = driver.advance_to(3).await.unwrap();
+        assert_eq!(head.block_info.number, 3);
+        assert_eq!(driver.engine.inserted.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_engine_driver_retries_invalid_payload_deposit_only_post_holocene() {
+        let cfg = RollupConfig { holocene_time: Some(0), ..Default::default() };
+        let attrs = vec![
+            attributes(2, vec![deposit(), user_tx()]),
+            attributes(4, vec![deposit(), user_tx()]),
+            attributes(6, vec![deposit(), user_tx()]),
+        ];
+        let engine = TestEngine { invalid: vec![4], ..Default::default() };
+        let mut driver = new_test_driver(cfg, attrs, engine);
+
+        let head = driver.advance_to(3).await.unwrap();
+        assert_eq!(head.block_info.number, 3);
+        assert_eq!(driver.pipeline.attributes.flushed, 1);
+
+        // The rejected payload was retried with only its deposits.
+        let retried = &driver.engine.inserted[1];
+        assert_eq!(retried.attributes.payload_attributes.timestamp, 4);
+        assert_eq!(retried.attributes.transactions, Some(vec![deposit()]));
+    }
+
+    #[tokio::test]
+    async fn test_engine_driver_drops_invalid_payload_pre_holocene() {
+        let attrs = vec![
+            attributes(2, vec![deposit(), user_tx()]),
+            attributes(4, vec![deposit(), user_tx()]),
+            attributes(6, vec![deposit(), user_tx()]),
+        ];
+        let engine = TestEngine { invalid: vec![4], ..Default::default() };
+        let mut driver = new_test_driver(RollupConfig::default(), attrs, engine);
+
+        let head = driver.advance_to(2).await.unwrap();
+        assert_eq!(head.block_info.number, 2);
+        assert_eq!(driver.pipeline.attributes.flushed, 0);
+        let timestamps = driver
+            .engine
+            .inserted
+            .iter()
+            .map(|a| a.attributes.payload_attributes.timestamp)
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![2, 6]);
+    }
+
+    #[tokio::test]
+    async fn test_engine_driver_engine_unavailable() {
+        let attrs = vec![attributes(2, vec![deposit()])];
+        let engine = TestEngine { unavailable: true, ..Default::default() };
+        let mut driver = new_test_driver(RollupConfig::default(), attrs, engine);
+
+        let err = driver.advance_to(1).await.unwrap_err();
+        assert!(matches!(err, DriverError::Executor(EngineError::Unavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_engine_driver_resets_on_reset_error() {
+        let attrs = vec![attributes(2, vec![deposit()])];
+        let mut driver = new_test_driver(RollupConfig::default(), attrs, TestEngine::default());
+        driver.pipeline.attributes.step_error = Some(ResetError::HoloceneActivation.reset());
+
+        let head = driver.advance_to(1).await.unwrap();
+        assert_eq!(head.block_info.number, 1);
+        assert_eq!(driver.pipeline.attributes.activations, 1);
+    }
+
+    #[tokio::test]
+    async fn test_engine_driver_halts_at_end_of_source() {
+        let attrs = vec![attributes(2, vec![deposit()])];
+        let mut driver = new_test_driver(RollupConfig::default(), attrs, TestEngine::default());
+        driver.advance_to(1).await.unwrap();
+        driver.pipeline.attributes.step_error = Some(PipelineError::EndOfSource.crit());
+
+        let head = driver.advance_to(5).await.unwrap();
+        assert_eq!(head.block_info.number, 1);
+    }
+}
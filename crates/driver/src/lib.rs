@@ -21,6 +21,9 @@ pub use executor::{Executor, ExecutorConstructor};
 mod core;
 pub use core::Driver;
 
+mod engine;
+pub use engine::{DerivationDriver, EngineConsumer, EngineError, SafeHeadTracker};
+
 mod cursor;
 pub use cursor::PipelineCursor;
 
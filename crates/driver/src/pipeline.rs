@@ -33,14 +33,36 @@ where
         // first attributes are produced. All batches at and before the safe head will be
         // dropped, so the first payload will always be the disputed one.
         loop {
-            match self.step(l2_safe_head).await {
-                StepResult::PreparedAttributes => {
-                    info!(target: "client_derivation_driver", "Stepped derivation pipeline")
+            let result = self.step(l2_safe_head).await;
+            if let StepResult::Stalled(origins) = result {
+                warn!(
+                    target: "client_derivation_driver",
+                    "Derivation stalled: no attributes prepared for {} L1 origins", origins
+                );
+            }
+            // Temporary failures are routine while waiting for data, so the status is only
+            // logged for failures that reset or halt the pipeline.
+            if matches!(
+                result,
+                StepResult::StepFailed(
+                    PipelineErrorKind::Reset(_) | PipelineErrorKind::Critical(_)
+                )
+            ) {
+                warn!(target: "client_derivation_driver", "Pipeline status: {:?}", self.status());
+            }
+            // A stale cursor means the pipeline is ahead of the safe head it was stepped on, so it
+            // is reset back to the safe head like any other reset error.
+            let error = match result {
+                StepResult::StaleCursor(stale, highest) => {
+                    Some(ResetError::StaleCursor(stale, highest).reset())
                 }
-                StepResult::AdvancedOrigin => {
-                    info!(target: "client_derivation_driver", "Advanced origin")
+                result => result.into_error(),
+            };
+            match error {
+                None => {
+                    info!(target: "client_derivation_driver", "Stepped derivation pipeline")
                 }
-                StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => {
+                Some(e) => {
                     warn!(target: "client_derivation_driver", "Failed to step derivation pipeline: {:?}", e);
 
                     // Break the loop unless the error signifies that there is not enough data to